@@ -1,7 +1,11 @@
 use std::fmt::{self, Display, Formatter};
-use std::path::PathBuf;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
-use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use clap::builder::ValueParser;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use typst_syntax::PackageSpec;
 
 /// The Typst compiler.
 #[derive(Debug, Clone, Parser)]
@@ -29,6 +33,9 @@ pub enum Command {
     #[command(visible_alias = "w")]
     Watch(CompileCommand),
 
+    /// Processes an input file to extract provided metadata
+    Query(QueryCommand),
+
     /// Lists all discovered fonts in system and custom font paths
     Fonts(FontsCommand),
 }
@@ -36,13 +43,188 @@ pub enum Command {
 /// Compiles the input file into a PDF file
 #[derive(Debug, Clone, Parser)]
 pub struct CompileCommand {
-    /// Path to input Typst file
-    pub input: PathBuf,
+    /// Shared arguments.
+    #[clap(flatten)]
+    pub common: SharedArgs,
 
-    /// Path to output PDF file or PNG file(s)
+    /// Path to output PDF file or PNG/SVG file(s), or `-` to write to stdout.
+    /// For multiple pages, `{n}` is replaced by the zero-padded page number,
+    /// `{p}` by the page number, and `{t}` by the total number of pages
     pub output: Option<PathBuf>,
 
-    /// Configures the project root
+    /// The format of the output file, inferred from the extension by default
+    #[arg(long = "format", short = 'f')]
+    pub format: Option<OutputFormat>,
+
+    /// Another input file to compile in the same invocation. Its output is
+    /// written next to it unless the documents are merged (can be repeated)
+    #[arg(long = "extra-input", value_name = "INPUT")]
+    pub extra_inputs: Vec<PathBuf>,
+
+    /// Concatenates the documents of all inputs into the output file instead
+    /// of writing one output per input
+    #[arg(long = "merge")]
+    pub merge: bool,
+
+    /// Continues the page labels of merged documents instead of restarting
+    /// them for each input
+    #[arg(long = "continuous-page-labels", requires = "merge")]
+    pub continuous_page_labels: bool,
+
+    /// Which pages to export, e.g. `1,4-10` or `5-`. Exports all pages by
+    /// default
+    #[arg(
+        long = "pages",
+        value_name = "PAGES",
+        value_delimiter = ',',
+        value_parser = ValueParser::new(parse_page_range),
+    )]
+    pub pages: Vec<RangeInclusive<usize>>,
+
+    /// Opens the output file using the default viewer after compilation
+    #[arg(long = "open")]
+    pub open: Option<Option<String>>,
+
+    /// The PPI (pixels per inch) to use for PNG export
+    #[arg(
+        long = "ppi",
+        visible_alias = "pixels-per-inch",
+        default_value_t = 144.0,
+        value_parser = ValueParser::new(parse_ppi),
+    )]
+    pub ppi: f32,
+
+    /// Renders PNG pages on a transparent instead of a white background
+    #[arg(long = "transparent")]
+    pub transparent: bool,
+
+    /// Encrypts the PDF and requires this password to open it
+    #[arg(long = "user-password", env = "TYPST_USER_PASSWORD", value_name = "PASSWORD")]
+    pub user_password: Option<String>,
+
+    /// Encrypts the PDF and requires this password to change its permissions
+    #[arg(
+        long = "owner-password",
+        env = "TYPST_OWNER_PASSWORD",
+        value_name = "PASSWORD"
+    )]
+    pub owner_password: Option<String>,
+
+    /// Encrypts the PDF and forbids printing it
+    #[arg(long = "no-print")]
+    pub no_print: bool,
+
+    /// Encrypts the PDF and forbids copying text and graphics out of it
+    #[arg(long = "no-copy")]
+    pub no_copy: bool,
+
+    /// Writes text and shape colors in the PDF as device CMYK for print.
+    /// Colors that aren't already CMYK are converted without taking a color
+    /// profile into account and images keep their color spaces
+    #[arg(long = "cmyk")]
+    pub cmyk: bool,
+
+    /// Embeds this CMYK output ICC profile as the PDF's output intent. Colors
+    /// are not converted through it (implies `--cmyk`)
+    #[arg(long = "icc-profile", value_name = "FILE")]
+    pub icc_profile: Option<PathBuf>,
+
+    /// Makes full black overprint other inks in CMYK output (implies `--cmyk`)
+    #[arg(long = "overprint-black")]
+    pub overprint_black: bool,
+
+    /// Warns about content in the PDF that is not accessible to assistive
+    /// technology, like images without alternative descriptions
+    #[arg(long = "accessible")]
+    pub accessible: bool,
+
+    /// Embeds fonts fully instead of only the used glyphs
+    #[arg(long = "no-subset")]
+    pub no_subset: bool,
+
+    /// Converts TrueType fonts to CFF when embedding them into the PDF
+    #[arg(long = "force-cff")]
+    pub force_cff: bool,
+
+    /// Does not embed fonts of this family, e.g. because of their license
+    #[arg(long = "no-embed", value_name = "FAMILY")]
+    pub no_embed: Vec<String>,
+
+    /// Downsamples images in the PDF to this resolution at the largest size
+    /// they are shown at
+    #[arg(
+        long = "image-dpi",
+        value_name = "DPI",
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub image_dpi: Option<u32>,
+
+    /// The quality from 1 to 100 with which JPEG images in the PDF are encoded
+    #[arg(
+        long = "jpeg-quality",
+        value_name = "QUALITY",
+        default_value_t = 75,
+        value_parser = clap::value_parser!(u8).range(1..=100),
+    )]
+    pub jpeg_quality: u8,
+
+    /// How strongly to compress the PDF, from 0 (not at all) to 10 (smallest)
+    #[arg(
+        long = "compression-level",
+        value_name = "LEVEL",
+        default_value_t = 6,
+        value_parser = clap::value_parser!(u8).range(0..=10),
+    )]
+    pub compression_level: u8,
+
+    /// Aborts the compilation if it uses more than this much memory, e.g.
+    /// `512M` or `2G`. While watching, the compilation instead fails once it
+    /// is done and the watcher keeps running
+    #[arg(
+        long = "memory-limit",
+        value_name = "SIZE",
+        value_parser = ValueParser::new(parse_size),
+    )]
+    pub memory_limit: Option<usize>,
+
+    /// How many recompilations cached results are kept for without being
+    /// used while watching
+    #[arg(long = "cache-max-age", value_name = "COUNT", default_value_t = 10)]
+    pub cache_max_age: usize,
+
+    /// Clears the cache between recompilations while watching if more than
+    /// this much memory is in use, e.g. `512M` or `2G`
+    #[arg(
+        long = "cache-max-memory",
+        value_name = "SIZE",
+        value_parser = ValueParser::new(parse_size),
+    )]
+    pub cache_max_memory: Option<usize>,
+
+    /// Writes a map from regions of the pages to source locations, so that
+    /// viewers and editors can jump between the output and the source
+    #[arg(long = "source-map", value_name = "OUTPUT_JSON")]
+    pub source_map: Option<Option<PathBuf>>,
+
+    /// Produces a flamegraph of the compilation process
+    #[arg(long = "flamegraph", value_name = "OUTPUT_SVG")]
+    pub flamegraph: Option<Option<PathBuf>>,
+
+    /// Reports how long the phases of the compilation and the slowest
+    /// functions and pages took, and writes all timings as a trace in the
+    /// Chrome trace event format
+    #[arg(long = "timings", value_name = "OUTPUT_JSON")]
+    pub timings: Option<Option<PathBuf>>,
+}
+
+/// Arguments shared by all commands that compile a document.
+#[derive(Debug, Clone, Args)]
+pub struct SharedArgs {
+    /// Path to input Typst file, or `-` to read from stdin
+    pub input: PathBuf,
+
+    /// Configures the project root (defaults to the directory of the input
+    /// file, or the working directory when reading from stdin)
     #[clap(long = "root", env = "TYPST_ROOT", value_name = "DIR")]
     pub root: Option<PathBuf>,
 
@@ -55,15 +237,17 @@ pub struct CompileCommand {
     )]
     pub font_paths: Vec<PathBuf>,
 
-    /// Opens the output file using the default viewer after compilation
-    #[arg(long = "open")]
-    pub open: Option<Option<String>>,
-
-    /// The PPI (pixels per inch) to use for PNG export
-    #[arg(long = "ppi", default_value_t = 144.0)]
-    pub ppi: f32,
+    /// Adds a string key-value pair, visible through `sys.inputs`
+    #[clap(
+        long = "input",
+        value_name = "key=value",
+        action = ArgAction::Append,
+        value_parser = ValueParser::new(parse_input_pair),
+    )]
+    pub inputs: Vec<(String, String)>,
 
-    /// In which format to emit diagnostics
+    /// In which format to emit diagnostics. The `json` format prints one
+    /// object per line with the file, byte range, severity, message, and hints
     #[clap(
         long,
         default_value_t = DiagnosticFormat::Human,
@@ -71,17 +255,243 @@ pub struct CompileCommand {
     )]
     pub diagnostic_format: DiagnosticFormat,
 
-    /// Produces a flamegraph of the compilation process
-    #[arg(long = "flamegraph", value_name = "OUTPUT_SVG")]
-    pub flamegraph: Option<Option<PathBuf>>,
+    /// Suppresses warnings of this kind, or all of them for `all`
+    #[clap(long = "suppress-warnings", value_name = "KIND", value_delimiter = ',')]
+    pub suppress_warnings: Vec<String>,
+
+    /// The date of the document as a Unix timestamp or an ISO 8601 date,
+    /// used as the current date and as the creation date of PDFs so that
+    /// builds are reproducible
+    #[clap(
+        long = "creation-date",
+        env = "SOURCE_DATE_EPOCH",
+        value_name = "DATE",
+        value_parser = ValueParser::new(parse_creation_date),
+    )]
+    pub creation_date: Option<DateTime<Utc>>,
+
+    /// Disables downloading of packages, only using those that are available
+    /// locally
+    #[arg(long = "offline", env = "TYPST_OFFLINE")]
+    pub offline: bool,
+
+    /// Custom path to the cache of downloaded packages
+    #[clap(
+        long = "package-cache-path",
+        env = "TYPST_PACKAGE_CACHE_PATH",
+        value_name = "DIR"
+    )]
+    pub package_cache_path: Option<PathBuf>,
+
+    /// Custom path to local packages, which are used instead of downloaded
+    /// ones
+    #[clap(long = "package-path", env = "TYPST_PACKAGE_PATH", value_name = "DIR")]
+    pub package_path: Option<PathBuf>,
+
+    /// Resolves a package to a local directory, e.g. for developing it:
+    /// `@preview/mypkg:0.1.0=../mypkg` (can be repeated)
+    #[clap(
+        long = "package",
+        value_name = "SPEC=DIR",
+        value_parser = ValueParser::new(parse_package_override),
+    )]
+    pub package_overrides: Vec<(PackageSpec, PathBuf)>,
+}
+
+/// Parses key/value pairs split by the first equal sign.
+fn parse_input_pair(raw: &str) -> Result<(String, String), String> {
+    let (key, val) = raw
+        .split_once('=')
+        .ok_or("input must be a key and a value separated by an equal sign")?;
+    let key = key.trim().to_owned();
+    if key.is_empty() {
+        return Err("the key was missing or empty".to_owned());
+    }
+    Ok((key, val.trim().to_owned()))
+}
+
+/// Parses a package specification and the directory it resolves to, split by
+/// the first equal sign.
+fn parse_package_override(raw: &str) -> Result<(PackageSpec, PathBuf), String> {
+    let (spec, dir) = raw
+        .split_once('=')
+        .ok_or("expected a package and a directory separated by an equal sign")?;
+    let spec = spec.trim().parse::<PackageSpec>().map_err(|err| err.to_string())?;
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("the directory was missing or empty".to_owned());
+    }
+    Ok((spec, dir.into()))
+}
+
+/// Parses a positive resolution in pixels per inch.
+fn parse_ppi(raw: &str) -> Result<f32, String> {
+    match raw.trim().parse::<f32>() {
+        Ok(ppi) if ppi.is_finite() && ppi > 0.0 => Ok(ppi),
+        Ok(_) => Err("the resolution must be positive".to_owned()),
+        Err(_) => Err(format!("invalid resolution: {raw}")),
+    }
+}
+
+/// Parses a page number or a range of page numbers, where either bound may be
+/// omitted.
+fn parse_page_range(raw: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |number: &str, default: usize| {
+        let number = number.trim();
+        if number.is_empty() {
+            return Ok(default);
+        }
+        match number.parse::<usize>() {
+            Ok(0) => Err("page numbers start at 1".to_owned()),
+            Ok(number) => Ok(number),
+            Err(_) => Err(format!("invalid page number: {number}")),
+        }
+    };
+
+    let range = match raw.split_once('-') {
+        Some((start, end)) => parse(start, 1)?..=parse(end, usize::MAX)?,
+        None if raw.trim().is_empty() => return Err("the page range is empty".to_owned()),
+        None => {
+            let page = parse(raw, 1)?;
+            page..=page
+        }
+    };
+
+    if range.is_empty() {
+        return Err(format!("the page range {raw} is empty"));
+    }
+
+    Ok(range)
+}
+
+/// Parses a number of bytes with an optional binary unit suffix.
+fn parse_size(raw: &str) -> Result<usize, String> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+    let number: usize =
+        number.parse().map_err(|_| format!("invalid memory size: {raw}"))?;
+    let factor: usize = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        _ => return Err(format!("unknown memory unit: {unit}")),
+    };
+    number
+        .checked_mul(factor)
+        .ok_or_else(|| "the memory size is too large".to_owned())
+}
+
+/// Parses a Unix timestamp or an ISO 8601 date, with an optional time.
+fn parse_creation_date(raw: &str) -> Result<DateTime<Utc>, String> {
+    let raw = raw.trim();
+    if let Ok(timestamp) = raw.parse::<i64>() {
+        return Utc
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .ok_or_else(|| "the timestamp is out of range".to_owned());
+    }
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(Utc.from_utc_datetime(&datetime));
+    }
+
+    if let Some(datetime) = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        return Ok(Utc.from_utc_datetime(&datetime));
+    }
+
+    Err("expected a Unix timestamp or an ISO 8601 date".to_owned())
+}
+
+impl SharedArgs {
+    /// Whether the main source is read from stdin.
+    pub fn reads_stdin(&self) -> bool {
+        self.input == Path::new("-")
+    }
 }
 
 impl CompileCommand {
     /// The output path.
+    ///
+    /// Defaults to stdout when reading from stdin.
     pub fn output(&self) -> PathBuf {
-        self.output
-            .clone()
-            .unwrap_or_else(|| self.input.with_extension("pdf"))
+        self.output.clone().unwrap_or_else(|| {
+            if self.common.reads_stdin() {
+                return "-".into();
+            }
+            let ext = self.format.unwrap_or(OutputFormat::Pdf).to_string();
+            self.common.input.with_extension(ext)
+        })
+    }
+
+    /// Whether the page with the given number, starting at 1, is exported.
+    pub fn exports_page(&self, number: usize) -> bool {
+        self.pages.is_empty() || self.pages.iter().any(|range| range.contains(&number))
+    }
+
+    /// Whether the output is written to stdout.
+    pub fn writes_stdout(&self) -> bool {
+        self.output() == Path::new("-")
+    }
+
+    /// The output format.
+    pub fn output_format(&self) -> OutputFormat {
+        self.format.unwrap_or_else(|| {
+            let output = self.output();
+            match output.extension() {
+                Some(ext) if ext.eq_ignore_ascii_case("png") => OutputFormat::Png,
+                Some(ext) if ext.eq_ignore_ascii_case("svg") => OutputFormat::Svg,
+                Some(ext) if ext.eq_ignore_ascii_case("epub") => OutputFormat::Epub,
+                _ => OutputFormat::Pdf,
+            }
+        })
+    }
+}
+
+/// Processes an input file to extract provided metadata
+#[derive(Debug, Clone, Parser)]
+pub struct QueryCommand {
+    /// Shared arguments.
+    #[clap(flatten)]
+    pub common: SharedArgs,
+
+    /// Defines which elements to retrieve, e.g. `heading` or `<todo>`
+    pub selector: String,
+
+    /// Extracts just one field from all retrieved elements
+    #[arg(long = "field")]
+    pub field: Option<String>,
+
+    /// Expects and retrieves exactly one element
+    #[arg(long = "one")]
+    pub one: bool,
+
+    /// The format to serialize in
+    #[arg(long = "format", default_value_t = SerializationFormat::Json)]
+    pub format: SerializationFormat,
+}
+
+/// Which format to use for serialized output.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum SerializationFormat {
+    Json,
+    Yaml,
+}
+
+impl Display for SerializationFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
     }
 }
 
@@ -100,6 +510,29 @@ pub struct FontsCommand {
     /// Also lists style variants of each font family
     #[arg(long)]
     pub variants: bool,
+
+    /// Also lists the OpenType features, variable axes, named instances, and
+    /// Unicode coverage of each variant (implies `--variants`)
+    #[arg(long)]
+    pub info: bool,
+}
+
+/// Which format to use for the generated output file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
+pub enum OutputFormat {
+    Pdf,
+    Png,
+    Svg,
+    Epub,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
 }
 
 /// Which format to use for diagnostics.
@@ -107,6 +540,7 @@ pub struct FontsCommand {
 pub enum DiagnosticFormat {
     Human,
     Short,
+    Json,
 }
 
 impl Display for DiagnosticFormat {
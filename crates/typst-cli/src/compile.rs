@@ -1,31 +1,178 @@
 use std::fs;
+use std::io::{self, Write};
+use std::ops::Range;
 use std::path::Path;
 
+use chrono::{Datelike, Timelike};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term::{self, termcolor};
+use serde::Serialize;
 use termcolor::{ColorChoice, StandardStream};
 use typst::diag::{bail, Severity, SourceDiagnostic, StrResult};
-use typst::doc::Document;
-use typst::eval::{eco_format, Tracer};
-use typst::geom::Color;
+use typst::doc::{Destination, Document, Frame, FrameItem, Meta};
+use typst::eval::{eco_format, Datetime, Tracer};
+use typst::export::{
+    PdfEncryption, PdfFontOptions, PdfOptions, PdfOutputIntent, PdfSizeOptions,
+};
+use typst::geom::{Color, RgbaColor};
+use typst::model::Introspector;
 use typst::syntax::{FileId, Source};
 use typst::World;
 
-use crate::args::{CompileCommand, DiagnosticFormat};
+use crate::args::{CompileCommand, DiagnosticFormat, OutputFormat, SharedArgs};
 use crate::watch::Status;
 use crate::world::SystemWorld;
-use crate::{color_stream, set_failed};
+use crate::{color_stream, memory, print_error, set_failed};
 
 type CodespanResult<T> = Result<T, CodespanError>;
 type CodespanError = codespan_reporting::files::Error;
 
 /// Execute a compilation command.
 pub fn compile(mut command: CompileCommand) -> StrResult<()> {
-    let mut world = SystemWorld::new(&command)?;
-    compile_once(&mut world, &mut command, false)?;
+    if !command.extra_inputs.is_empty() && command.common.reads_stdin() {
+        bail!("cannot compile extra inputs when reading from stdin");
+    }
+
+    memory::setup(&command);
+    let mut world = SystemWorld::new(&command.common)?;
+    if command.extra_inputs.is_empty() {
+        compile_once(&mut world, &mut command, false)?;
+    } else if command.merge {
+        compile_merged(&mut world, &mut command)?;
+    } else {
+        compile_each(&mut world, &command)?;
+    }
+    Ok(())
+}
+
+/// Compile the main input and all extra inputs into separate outputs, sharing
+/// the fonts and caches between them.
+fn compile_each(world: &mut SystemWorld, command: &CompileCommand) -> StrResult<()> {
+    // The main input is compiled with the given settings.
+    let mut main = command.clone();
+    main.extra_inputs.clear();
+    compile_once(world, &mut main, false)?;
+
+    // The other inputs write their output and source map next to the input.
+    for input in &command.extra_inputs {
+        world.set_main(input)?;
+        let mut single = main.clone();
+        single.common.input = input.clone();
+        single.output = None;
+        if let Some(source_map) = &mut single.source_map {
+            *source_map = None;
+        }
+        compile_once(world, &mut single, false)?;
+    }
+
     Ok(())
 }
 
+/// Compile the main input and all extra inputs and concatenate them into a
+/// single output.
+fn compile_merged(
+    world: &mut SystemWorld,
+    command: &mut CompileCommand,
+) -> StrResult<()> {
+    let inputs: Vec<_> = std::iter::once(command.common.input.clone())
+        .chain(command.extra_inputs.iter().cloned())
+        .collect();
+
+    let mut documents = vec![];
+    let mut warnings = vec![];
+    let mut duration = std::time::Duration::ZERO;
+    let mut failed = false;
+    for input in &inputs {
+        tracing::info!("Compiling {}", input.display());
+        world.set_main(input)?;
+        match compile_document(world, command, false)? {
+            Some(compiled) => {
+                documents.push(compiled.document);
+                warnings.extend(compiled.warnings);
+                duration += compiled.duration;
+            }
+            None => failed = true,
+        }
+    }
+
+    if failed {
+        return Ok(());
+    }
+
+    let document = merge_documents(documents, command.continuous_page_labels);
+    export_document(world, command, &document, warnings, duration, false)
+}
+
+/// Concatenate the pages of multiple documents.
+///
+/// The title and author are taken from the first document that has them.
+/// Links into the documents are resolved against their own document first, so
+/// that they still point to the right page after merging.
+fn merge_documents(documents: Vec<Document>, continuous_labels: bool) -> Document {
+    let mut merged = Document::default();
+    let mut last_label = 0;
+    for (index, document) in documents.into_iter().enumerate() {
+        let introspector = Introspector::new(&document.pages);
+        let offset = merged.pages.len();
+        let start = if continuous_labels { last_label } else { 0 };
+        merged.title = merged.title.or(document.title);
+        if merged.author.is_empty() {
+            merged.author = document.author;
+        }
+
+        for mut page in document.pages {
+            let label = relocate(&mut page, &introspector, index, offset, start);
+            last_label = label.unwrap_or(last_label + 1);
+            merged.pages.push(page);
+        }
+    }
+    merged
+}
+
+/// Prepare a frame for being moved into a merged document.
+///
+/// Shifts the page numbers of links and page labels and makes the locations
+/// of elements unique across documents. Returns the page label's number, if
+/// the frame has one.
+fn relocate(
+    frame: &mut Frame,
+    introspector: &Introspector,
+    index: usize,
+    offset: usize,
+    start: usize,
+) -> Option<usize> {
+    let mut number = None;
+    for (_, item) in frame.items_mut() {
+        match item {
+            FrameItem::Group(group) => {
+                let inner =
+                    relocate(&mut group.frame, introspector, index, offset, start);
+                number = number.or(inner);
+            }
+            FrameItem::Meta(Meta::Link(dest), _) => {
+                let mut position = match dest {
+                    Destination::Position(position) => *position,
+                    Destination::Location(location) => introspector.position(*location),
+                    Destination::Url(_) => continue,
+                };
+                position.page = position.page.saturating_add(offset);
+                *dest = Destination::Position(position);
+            }
+            FrameItem::Meta(Meta::Elem(content), _) => {
+                if let Some(location) = content.location() {
+                    content.replace_location(location.in_document(index));
+                }
+            }
+            FrameItem::Meta(Meta::PdfPageLabel(label), _) => {
+                label.number = label.number.saturating_add(start);
+                number = Some(label.number.get());
+            }
+            _ => {}
+        }
+    }
+    number
+}
+
 /// Compile a single time.
 ///
 /// Returns whether it compiled without errors.
@@ -35,6 +182,31 @@ pub fn compile_once(
     command: &mut CompileCommand,
     watching: bool,
 ) -> StrResult<()> {
+    if let Some(compiled) = compile_document(world, command, watching)? {
+        let Compiled { document, warnings, duration } = compiled;
+        export_document(world, command, &document, warnings, duration, watching)?;
+    }
+    Ok(())
+}
+
+/// A successfully compiled document.
+struct Compiled {
+    document: Document,
+    /// The warnings of the compilation that weren't suppressed.
+    warnings: Vec<SourceDiagnostic>,
+    /// How long the compilation took.
+    duration: std::time::Duration,
+}
+
+/// Compile the main file of the world.
+///
+/// If the compilation fails or exceeds the memory limit, the errors are
+/// printed and `None` is returned.
+fn compile_document(
+    world: &mut SystemWorld,
+    command: &CompileCommand,
+    watching: bool,
+) -> StrResult<Option<Compiled>> {
     tracing::info!("Starting compilation");
 
     let start = std::time::Instant::now();
@@ -48,32 +220,47 @@ pub fn compile_once(
 
     let mut tracer = Tracer::default();
 
+    // Outside of watch mode, a compilation that exceeds the memory limit is
+    // aborted right away. Otherwise, it is reported once it is done.
+    memory::reset_peak();
+    let format = command.common.diagnostic_format;
+    let guard = command
+        .memory_limit
+        .filter(|_| !watching)
+        .map(|limit| memory::abort_above(limit, format));
     let result = typst::compile(world, &mut tracer);
     let duration = start.elapsed();
+    drop(guard);
 
-    let warnings = tracer.warnings();
-
-    match result {
-        // Export the PDF / PNG.
-        Ok(document) => {
-            export(&document, command)?;
+    if let Some(limit) = command.memory_limit {
+        let peak = memory::peak();
+        if peak > limit {
+            // Drop all memoized results so that the memory is given back.
+            comemo::evict(0);
+            set_failed();
+            tracing::info!("Compilation exceeded the memory limit");
 
-            tracing::info!("Compilation succeeded in {duration:?}");
             if watching {
-                if warnings.is_empty() {
-                    Status::Success(duration).print(command).unwrap();
-                } else {
-                    Status::PartialSuccess(duration).print(command).unwrap();
-                }
+                Status::Error.print(command).unwrap();
             }
 
-            print_diagnostics(world, &[], &warnings, command.diagnostic_format)
-                .map_err(|_| "failed to print diagnostics")?;
-
-            if let Some(open) = command.open.take() {
-                open_file(open.as_deref(), &command.output())?;
-            }
+            print_error(
+                &eco_format!(
+                    "compilation exceeded the memory limit of {} (used {})",
+                    memory::format_size(limit),
+                    memory::format_size(peak),
+                ),
+                command.common.diagnostic_format,
+            )
+            .map_err(|_| "failed to print error")?;
+            return Ok(None);
         }
+    }
+
+    let warnings = filter_warnings(tracer.warnings(), &command.common);
+
+    match result {
+        Ok(document) => Ok(Some(Compiled { document, warnings, duration })),
 
         // Print diagnostics.
         Err(errors) => {
@@ -84,58 +271,333 @@ pub fn compile_once(
                 Status::Error.print(command).unwrap();
             }
 
-            print_diagnostics(world, &errors, &warnings, command.diagnostic_format)
-                .map_err(|_| "failed to print diagnostics")?;
+            print_diagnostics(
+                world,
+                &errors,
+                &warnings,
+                command.common.diagnostic_format,
+            )
+            .map_err(|_| "failed to print diagnostics")?;
+            Ok(None)
+        }
+    }
+}
+
+/// Export a compiled document, write its source map, and print the warnings
+/// of its compilation and export.
+fn export_document(
+    world: &SystemWorld,
+    command: &mut CompileCommand,
+    document: &Document,
+    mut warnings: Vec<SourceDiagnostic>,
+    duration: std::time::Duration,
+    watching: bool,
+) -> StrResult<()> {
+    // Export the PDF / PNG / SVG.
+    let export_warnings = export(document, command)?;
+    warnings.extend(filter_warnings(export_warnings, &command.common));
+    if let Some(path) = &command.source_map {
+        let path = match path {
+            Some(path) => path.clone(),
+            None if command.writes_stdout() => {
+                bail!("the source map needs a path when writing to stdout")
+            }
+            None => command.output().with_extension("map.json"),
+        };
+        write_source_map(world, document, &path)?;
+    }
+
+    tracing::info!("Compilation succeeded in {duration:?}");
+    if watching {
+        if warnings.is_empty() {
+            Status::Success(duration).print(command).unwrap();
+        } else {
+            Status::PartialSuccess(duration).print(command).unwrap();
+        }
+    }
+
+    print_diagnostics(world, &[], &warnings, command.common.diagnostic_format)
+        .map_err(|_| "failed to print diagnostics")?;
+
+    if let Some(open) = command.open.take() {
+        if command.writes_stdout() {
+            bail!("cannot open output that was written to stdout");
         }
+        open_file(open.as_deref(), &command.output())?;
     }
 
     Ok(())
 }
 
 /// Export into the target format.
-fn export(document: &Document, command: &CompileCommand) -> StrResult<()> {
-    match command.output().extension() {
-        Some(ext) if ext.eq_ignore_ascii_case("png") => export_png(document, command),
-        _ => export_pdf(document, command),
+///
+/// Returns warnings about content that could not be exported faithfully.
+#[tracing::instrument(skip_all)]
+fn export(
+    document: &Document,
+    command: &CompileCommand,
+) -> StrResult<Vec<SourceDiagnostic>> {
+    if !(1..=document.pages.len()).any(|number| command.exports_page(number)) {
+        bail!("none of the selected pages exist in the document");
+    }
+
+    match command.output_format() {
+        OutputFormat::Pdf => export_pdf(document, command),
+        OutputFormat::Png => export_image(document, command, ImageExportFormat::Png),
+        OutputFormat::Svg => export_image(document, command, ImageExportFormat::Svg),
+        OutputFormat::Epub => export_epub(document, command).map(|()| vec![]),
     }
 }
 
 /// Export to a PDF.
-fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
+fn export_pdf(
+    document: &Document,
+    command: &CompileCommand,
+) -> StrResult<Vec<SourceDiagnostic>> {
+    let output = command.output();
+    let options = PdfOptions {
+        encryption: pdf_encryption(command),
+        output_intent: pdf_output_intent(command)?,
+        fonts: PdfFontOptions {
+            subset: !command.no_subset,
+            force_cff: command.force_cff,
+            exclude: command
+                .no_embed
+                .iter()
+                .map(|family| family.as_str().into())
+                .collect(),
+        },
+        size: PdfSizeOptions {
+            image_dpi: command.image_dpi,
+            jpeg_quality: command.jpeg_quality,
+            compression_level: command.compression_level,
+        },
+        pages: (!command.pages.is_empty()).then(|| command.pages.clone()),
+        accessible: command.accessible,
+        creation_date: command.common.creation_date.and_then(|date| {
+            Datetime::from_ymd_hms(
+                date.year(),
+                date.month().try_into().ok()?,
+                date.day().try_into().ok()?,
+                date.hour().try_into().ok()?,
+                date.minute().try_into().ok()?,
+                date.second().try_into().ok()?,
+            )
+        }),
+    };
+    let (buffer, warnings) = typst::export::pdf_with_options(document, &options);
+    write_output(&output, &buffer).map_err(|_| "failed to write PDF file")?;
+    Ok(warnings)
+}
+
+/// A region of a page and the source location that produced it.
+#[derive(Serialize)]
+struct SourceMapEntry {
+    /// The page number, starting at 1.
+    page: usize,
+    /// The coordinates of the region's top left corner in points, from the
+    /// top left of the page.
+    x: f64,
+    y: f64,
+    /// The region's size in points.
+    width: f64,
+    height: f64,
+    /// The path of the source file.
+    file: String,
+    /// The line and column in the source file, starting at 1.
+    line: usize,
+    column: usize,
+}
+
+/// Write a sidecar file that maps regions of the pages to source locations.
+fn write_source_map(
+    world: &SystemWorld,
+    document: &Document,
+    path: &Path,
+) -> StrResult<()> {
+    let entries: Vec<_> = typst::ide::source_regions(&document.pages)
+        .into_iter()
+        .filter_map(|region| {
+            let id = region.span.0.id();
+            let source = world.source(id).ok()?;
+            let offset = region.offset(&source)?;
+            let file = match world.path(id) {
+                Some(path) => path.display().to_string(),
+                None => id.to_string(),
+            };
+            Some(SourceMapEntry {
+                page: region.position.page.get(),
+                x: region.position.point.x.to_pt(),
+                y: region.position.point.y.to_pt(),
+                width: region.size.x.to_pt(),
+                height: region.size.y.to_pt(),
+                file,
+                line: source.byte_to_line(offset)? + 1,
+                column: source.byte_to_column(offset)? + 1,
+            })
+        })
+        .collect();
+
+    let json = serde_json::to_string(&entries).map_err(|err| eco_format!("{err}"))?;
+    fs::write(path, json).map_err(|_| "failed to write source map")?;
+    Ok(())
+}
+
+/// The encryption settings for PDF export, if any of them were given.
+fn pdf_encryption(command: &CompileCommand) -> Option<PdfEncryption> {
+    if command.user_password.is_none()
+        && command.owner_password.is_none()
+        && !command.no_print
+        && !command.no_copy
+    {
+        return None;
+    }
+
+    Some(PdfEncryption {
+        user_password: command.user_password.as_deref().unwrap_or_default().into(),
+        owner_password: command.owner_password.as_deref().unwrap_or_default().into(),
+        allow_print: !command.no_print,
+        allow_copy: !command.no_copy,
+    })
+}
+
+/// The print color settings for PDF export, if CMYK output was requested.
+fn pdf_output_intent(command: &CompileCommand) -> StrResult<Option<PdfOutputIntent>> {
+    if !command.cmyk && command.icc_profile.is_none() && !command.overprint_black {
+        return Ok(None);
+    }
+
+    let mut intent = PdfOutputIntent {
+        overprint_black: command.overprint_black,
+        ..Default::default()
+    };
+
+    if let Some(path) = &command.icc_profile {
+        let data = fs::read(path).map_err(|_| "failed to read ICC profile")?;
+        if !is_cmyk_output_profile(&data) {
+            bail!("ICC profile is not a CMYK output profile");
+        }
+        intent.profile = Some(data.into());
+        if let Some(stem) = path.file_stem() {
+            intent.condition = stem.to_string_lossy().into();
+        }
+    }
+
+    Ok(Some(intent))
+}
+
+/// Whether the data is an ICC profile for a CMYK output device, judging by
+/// its device class and color space in the header.
+fn is_cmyk_output_profile(data: &[u8]) -> bool {
+    data.len() >= 128 && &data[12..16] == b"prtr" && &data[16..20] == b"CMYK"
+}
+
+/// Export to an EPUB.
+fn export_epub(document: &Document, command: &CompileCommand) -> StrResult<()> {
     let output = command.output();
-    let buffer = typst::export::pdf(document);
-    fs::write(output, buffer).map_err(|_| "failed to write PDF file")?;
+    let document = Document {
+        pages: document
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| command.exports_page(i + 1))
+            .map(|(_, frame)| frame.clone())
+            .collect(),
+        ..document.clone()
+    };
+    let buffer = typst::export::epub(&document);
+    write_output(&output, &buffer).map_err(|_| "failed to write EPUB file")?;
     Ok(())
 }
 
-/// Export to one or multiple PNGs.
-fn export_png(document: &Document, command: &CompileCommand) -> StrResult<()> {
-    // Determine whether we have a `{n}` numbering.
+/// An image format to export in.
+enum ImageExportFormat {
+    Png,
+    Svg,
+}
+
+/// Export to one or multiple PNGs or SVGs.
+fn export_image(
+    document: &Document,
+    command: &CompileCommand,
+    fmt: ImageExportFormat,
+) -> StrResult<Vec<SourceDiagnostic>> {
+    // Determine whether we have a `{n}` or `{p}` numbering.
     let output = command.output();
     let string = output.to_str().unwrap_or_default();
-    let numbered = string.contains("{n}");
-    if !numbered && document.pages.len() > 1 {
-        bail!("cannot export multiple PNGs without `{{n}}` in output path");
+    let numbered = string.contains("{n}") || string.contains("{p}");
+    let exported = (1..=document.pages.len())
+        .filter(|&number| command.exports_page(number))
+        .count();
+    if !numbered && exported > 1 {
+        let name = match fmt {
+            ImageExportFormat::Png => "PNGs",
+            ImageExportFormat::Svg => "SVGs",
+        };
+        if command.writes_stdout() {
+            bail!("cannot export multiple {name} to stdout");
+        }
+        bail!("cannot export multiple {name} without `{{n}}` or `{{p}}` in output path");
     }
 
     // Find a number width that accommodates all pages. For instance, the
     // first page should be numbered "001" if there are between 100 and
     // 999 pages.
-    let width = 1 + document.pages.len().checked_ilog10().unwrap_or(0) as usize;
+    let total = document.pages.len();
+    let width = 1 + total.checked_ilog10().unwrap_or(0) as usize;
     let mut storage;
+    let mut warnings = vec![];
 
     for (i, frame) in document.pages.iter().enumerate() {
-        let pixmap = typst::export::render(frame, command.ppi / 72.0, Color::WHITE);
+        if !command.exports_page(i + 1) {
+            continue;
+        }
+
         let path = if numbered {
-            storage = string.replace("{n}", &format!("{:0width$}", i + 1));
+            storage = string
+                .replace("{n}", &format!("{:0width$}", i + 1))
+                .replace("{p}", &(i + 1).to_string())
+                .replace("{t}", &total.to_string());
             Path::new(&storage)
         } else {
             output.as_path()
         };
-        pixmap.save_png(path).map_err(|_| "failed to write PNG file")?;
+        match fmt {
+            ImageExportFormat::Png => {
+                let fill = if command.transparent {
+                    RgbaColor::new(0, 0, 0, 0).into()
+                } else {
+                    Color::WHITE
+                };
+                let pixmap = typst::export::render(frame, command.ppi / 72.0, fill);
+                let buffer = pixmap.encode_png().map_err(|_| "failed to encode PNG")?;
+                write_output(path, &buffer).map_err(|_| "failed to write PNG file")?;
+            }
+            ImageExportFormat::Svg => {
+                let (svg, svg_warnings) = typst::export::svg(frame);
+                write_output(path, svg.as_bytes())
+                    .map_err(|_| "failed to write SVG file")?;
+                for warning in svg_warnings {
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            }
+        }
     }
 
-    Ok(())
+    Ok(warnings)
+}
+
+/// Write an exported file, or to stdout if the path is `-`.
+fn write_output(path: &Path, data: &[u8]) -> io::Result<()> {
+    if path == Path::new("-") {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(data)?;
+        stdout.flush()
+    } else {
+        fs::write(path, data)
+    }
 }
 
 /// Opens the given file using:
@@ -151,8 +613,29 @@ fn open_file(open: Option<&str>, path: &Path) -> StrResult<()> {
     Ok(())
 }
 
+/// Remove the warnings that were suppressed on the command line.
+pub fn filter_warnings(
+    warnings: impl IntoIterator<Item = SourceDiagnostic>,
+    args: &SharedArgs,
+) -> Vec<SourceDiagnostic> {
+    let suppressed = &args.suppress_warnings;
+    if suppressed.iter().any(|kind| kind == "all") {
+        return vec![];
+    }
+
+    warnings
+        .into_iter()
+        .filter(|warning| {
+            warning
+                .kind
+                .as_ref()
+                .map_or(true, |kind| !suppressed.iter().any(|s| s == kind.as_str()))
+        })
+        .collect()
+}
+
 /// Print diagnostic messages to the terminal.
-fn print_diagnostics(
+pub fn print_diagnostics(
     world: &SystemWorld,
     errors: &[SourceDiagnostic],
     warnings: &[SourceDiagnostic],
@@ -161,6 +644,9 @@ fn print_diagnostics(
     let mut w = match diagnostic_format {
         DiagnosticFormat::Human => color_stream(),
         DiagnosticFormat::Short => StandardStream::stderr(ColorChoice::Never),
+        DiagnosticFormat::Json => {
+            return Ok(print_json_diagnostics(world, errors, warnings)?);
+        }
     };
 
     let mut config = term::Config { tab_width: 2, ..Default::default() };
@@ -169,7 +655,7 @@ fn print_diagnostics(
     }
 
     for diagnostic in warnings.iter().chain(errors.iter()) {
-        let diag = match diagnostic.severity {
+        let mut diag = match diagnostic.severity {
             Severity::Error => Diagnostic::error(),
             Severity::Warning => Diagnostic::warning(),
         }
@@ -181,10 +667,15 @@ fn print_diagnostics(
                 .map(|e| (eco_format!("hint: {e}")).into())
                 .collect(),
         )
-        .with_labels(vec![Label::primary(
-            diagnostic.span.id(),
-            world.range(diagnostic.span),
-        )]);
+        .with_labels(if diagnostic.span.is_detached() {
+            vec![]
+        } else {
+            vec![Label::primary(diagnostic.span.id(), world.range(diagnostic.span))]
+        });
+
+        if let Some(kind) = &diagnostic.kind {
+            diag = diag.with_code(kind.as_str());
+        }
 
         term::emit(&mut w, &config, world, &diag)?;
 
@@ -202,6 +693,62 @@ fn print_diagnostics(
     Ok(())
 }
 
+/// Print diagnostic messages as JSON lines to stderr.
+fn print_json_diagnostics(
+    world: &SystemWorld,
+    errors: &[SourceDiagnostic],
+    warnings: &[SourceDiagnostic],
+) -> io::Result<()> {
+    let mut w = io::stderr().lock();
+    for diagnostic in warnings.iter().chain(errors.iter()) {
+        let span = diagnostic.span;
+        let json = JsonDiagnostic {
+            severity: match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            file: (!span.is_detached()).then(|| match world.path(span.id()) {
+                Some(path) => path.display().to_string(),
+                None => span.id().to_string(),
+            }),
+            range: (!span.is_detached()).then(|| world.range(span)),
+            message: &diagnostic.message,
+            hints: diagnostic.hints.iter().map(|hint| hint.as_str()).collect(),
+            kind: diagnostic.kind.as_deref(),
+        };
+        serde_json::to_writer(&mut w, &json)?;
+        writeln!(w)?;
+    }
+    w.flush()
+}
+
+/// Print an application-level error as a JSON line to stderr.
+pub fn print_json_error(msg: &str) -> io::Result<()> {
+    let json = JsonDiagnostic {
+        severity: "error",
+        file: None,
+        range: None,
+        message: msg,
+        hints: vec![],
+        kind: None,
+    };
+    let mut w = io::stderr().lock();
+    serde_json::to_writer(&mut w, &json)?;
+    writeln!(w)?;
+    w.flush()
+}
+
+/// A diagnostic in the JSON format.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: &'static str,
+    file: Option<String>,
+    range: Option<Range<usize>>,
+    message: &'a str,
+    hints: Vec<&'a str>,
+    kind: Option<&'a str>,
+}
+
 impl<'a> codespan_reporting::files::Files<'a> for SystemWorld {
     type FileId = FileId;
     type Name = FileId;
@@ -4,8 +4,9 @@ use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
 use memmap2::Mmap;
+use ttf_parser::{Fixed, LazyArray16, Tag, VariationAxis};
 use typst::diag::StrResult;
-use typst::font::{Font, FontBook, FontInfo, FontVariant};
+use typst::font::{Coverage, Font, FontBook, FontInfo, FontVariant};
 use typst::util::Bytes;
 use walkdir::WalkDir;
 
@@ -16,12 +17,18 @@ pub fn fonts(command: FontsCommand) -> StrResult<()> {
     let mut searcher = FontSearcher::new();
     searcher.search(&command.font_paths);
 
-    for (name, infos) in searcher.book.families() {
+    for (name, _) in searcher.book.families() {
         println!("{name}");
-        if command.variants {
-            for info in infos {
+        if command.variants || command.info {
+            for id in searcher.book.select_family(&name.to_lowercase()) {
+                let Some(info) = searcher.book.info(id) else { continue };
                 let FontVariant { style, weight, stretch } = info.variant;
                 println!("- Style: {style:?}, Weight: {weight:?}, Stretch: {stretch:?}");
+                if command.info {
+                    if let Some(font) = searcher.fonts[id].get() {
+                        print_info(&font);
+                    }
+                }
             }
         }
     }
@@ -29,6 +36,112 @@ pub fn fonts(command: FontsCommand) -> StrResult<()> {
     Ok(())
 }
 
+/// Print the OpenType features, variable axes, named instances, and coverage
+/// of a font.
+fn print_info(font: &Font) {
+    let ttf = font.ttf();
+    let tables = ttf.tables();
+
+    let mut features: Vec<String> = [tables.gsub, tables.gpos]
+        .into_iter()
+        .flatten()
+        .flat_map(|table| table.features)
+        .map(|feature| feature.tag.to_string())
+        .collect();
+    features.sort();
+    features.dedup();
+    if !features.is_empty() {
+        println!("  Features: {}", features.join(", "));
+    }
+
+    let axes: Vec<_> = ttf.variation_axes().into_iter().collect();
+    if !axes.is_empty() {
+        let list: Vec<String> = axes
+            .iter()
+            .map(|axis| {
+                format!(
+                    "{} {}..{}..{}",
+                    axis.tag, axis.min_value, axis.def_value, axis.max_value
+                )
+            })
+            .collect();
+        println!("  Axes: {}", list.join(", "));
+    }
+
+    let instances = named_instances(font, &axes);
+    if !instances.is_empty() {
+        println!("  Instances: {}", instances.join(", "));
+    }
+
+    let ranges = codepoint_ranges(&font.info().coverage);
+    let count: u32 = ranges.iter().map(|(start, end)| end - start + 1).sum();
+    let shown: Vec<String> = ranges
+        .iter()
+        .take(6)
+        .map(|&(start, end)| {
+            if start == end {
+                format!("U+{start:04X}")
+            } else {
+                format!("U+{start:04X}-U+{end:04X}")
+            }
+        })
+        .collect();
+    let more = ranges.len().saturating_sub(shown.len());
+    let suffix =
+        if more > 0 { format!(", and {more} more ranges") } else { String::new() };
+    println!("  Coverage: {count} codepoints ({}{suffix})", shown.join(", "));
+}
+
+/// Read the names and coordinates of the named instances of a variable font.
+///
+/// ttf-parser only exposes the axes of the `fvar` table, so the instance
+/// records that follow them are read here.
+fn named_instances(font: &Font, axes: &[VariationAxis]) -> Vec<String> {
+    let Some(data) = font.ttf().raw_face().table(Tag::from_bytes(b"fvar")) else {
+        return vec![];
+    };
+
+    // The header consists of the version, the offset to the axes, a reserved
+    // field, the axis count and size, and the instance count and size.
+    let header = LazyArray16::<u16>::new(data.get(..16).unwrap_or_default());
+    let (Some(offset), Some(axis_size), Some(count), Some(size)) =
+        (header.get(2), header.get(5), header.get(6), header.get(7))
+    else {
+        return vec![];
+    };
+
+    let start = usize::from(offset) + axes.len() * usize::from(axis_size);
+    (0..usize::from(count))
+        .filter_map(|i| {
+            // Each record starts with the name id and flags, followed by the
+            // coordinates on all axes.
+            let at = start + i * usize::from(size);
+            let record = data.get(at..at + usize::from(size))?;
+            let name_id = LazyArray16::<u16>::new(record).get(0)?;
+            let name = font.find_name(name_id).unwrap_or_else(|| "?".into());
+            let coords = LazyArray16::<Fixed>::new(record.get(4..)?);
+            let list: Vec<String> = axes
+                .iter()
+                .zip(coords)
+                .map(|(axis, Fixed(value))| format!("{}={value}", axis.tag))
+                .collect();
+            Some(format!("{name} ({})", list.join(" ")))
+        })
+        .collect()
+}
+
+/// Group the codepoints covered by a font into inclusive ranges.
+fn codepoint_ranges(coverage: &Coverage) -> Vec<(u32, u32)> {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+    for c in coverage.iter() {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == c => *end = c,
+            _ => ranges.push((c, c)),
+        }
+    }
+    ranges
+}
+
 /// Searches for fonts.
 pub struct FontSearcher {
     /// Metadata about all discovered fonts.
@@ -1,7 +1,10 @@
 mod args;
 mod compile;
 mod fonts;
+mod memory;
 mod package;
+mod query;
+mod timings;
 mod tracing;
 mod watch;
 mod world;
@@ -13,9 +16,13 @@ use std::process::ExitCode;
 
 use clap::Parser;
 use codespan_reporting::term::{self, termcolor};
-use termcolor::{ColorChoice, WriteColor};
+use termcolor::{ColorChoice, StandardStream, WriteColor};
 
-use crate::args::{CliArguments, Command};
+use crate::args::{CliArguments, Command, DiagnosticFormat};
+
+/// Keeps track of how much memory is in use.
+#[global_allocator]
+static ALLOCATOR: crate::memory::CountingAllocator = crate::memory::CountingAllocator;
 
 thread_local! {
     /// The CLI's exit code.
@@ -33,15 +40,24 @@ fn main() -> ExitCode {
         }
     };
 
+    let format = match &arguments.command {
+        Command::Compile(command) | Command::Watch(command) => {
+            command.common.diagnostic_format
+        }
+        Command::Query(command) => command.common.diagnostic_format,
+        Command::Fonts(_) => DiagnosticFormat::Human,
+    };
+
     let res = match arguments.command {
         Command::Compile(command) => crate::compile::compile(command),
         Command::Watch(command) => crate::watch::watch(command),
+        Command::Query(command) => crate::query::query(command),
         Command::Fonts(command) => crate::fonts::fonts(command),
     };
 
     if let Err(msg) = res {
         set_failed();
-        print_error(&msg).expect("failed to print error");
+        print_error(&msg, format).expect("failed to print error");
     }
 
     EXIT.with(|cell| cell.get())
@@ -53,8 +69,12 @@ fn set_failed() {
 }
 
 /// Print an application-level error (independent from a source file).
-fn print_error(msg: &str) -> io::Result<()> {
-    let mut w = color_stream();
+fn print_error(msg: &str, format: DiagnosticFormat) -> io::Result<()> {
+    let mut w = match format {
+        DiagnosticFormat::Human => color_stream(),
+        DiagnosticFormat::Short => StandardStream::stderr(ColorChoice::Never),
+        DiagnosticFormat::Json => return crate::compile::print_json_error(msg),
+    };
     let styles = term::Styles::default();
 
     w.set_color(&styles.header_error)?;
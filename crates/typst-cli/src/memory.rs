@@ -0,0 +1,161 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+use typst::eval::eco_format;
+
+use crate::args::{CompileCommand, DiagnosticFormat};
+use crate::print_error;
+
+/// Whether allocations are counted. This is only enabled if one of the memory
+/// options needs it, so that other runs don't pay for the bookkeeping.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The number of bytes that are currently allocated. This can become negative
+/// when memory that was allocated before counting started is freed.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+
+/// The highest number of bytes allocated at once since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The memory limit of the running compilation and the format to report
+/// exceeding it in, if the process should be aborted when it is exceeded.
+static LIMIT: Mutex<Option<(usize, DiagnosticFormat)>> = Mutex::new(None);
+
+/// How often the memory use is checked against the limit.
+const INTERVAL: Duration = Duration::from_millis(10);
+
+/// The system allocator, keeping track of how much memory is in use if
+/// counting is enabled.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new
+    }
+}
+
+/// Record an allocation of `size` bytes.
+fn grow(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        let size = size as isize;
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current.max(0) as usize, Ordering::Relaxed);
+    }
+}
+
+/// Record a deallocation of `size` bytes.
+fn shrink(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        CURRENT.fetch_sub(size as isize, Ordering::Relaxed);
+    }
+}
+
+/// Start counting allocations if one of the command's memory options needs
+/// it.
+pub fn setup(command: &CompileCommand) {
+    if command.memory_limit.is_some() || command.cache_max_memory.is_some() {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The number of bytes that are currently allocated.
+pub fn current() -> usize {
+    CURRENT.load(Ordering::Relaxed).max(0) as usize
+}
+
+/// The highest number of bytes that were allocated at once since the last
+/// call to [`reset_peak`].
+pub fn peak() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// Start measuring the peak from the current usage.
+pub fn reset_peak() {
+    PEAK.store(current(), Ordering::Relaxed);
+}
+
+/// Abort the process if more than `limit` bytes are in use before the
+/// returned guard is dropped. The error is printed in the given `format`.
+///
+/// The memory use is checked periodically from another thread, since the
+/// compilation itself can't be interrupted. This is only meant for one-off
+/// compilations: While watching, the limit is instead checked against the
+/// peak usage once the compilation is done, so that the watcher keeps running.
+pub fn abort_above(limit: usize, format: DiagnosticFormat) -> LimitGuard {
+    static WATCHDOG: Once = Once::new();
+    WATCHDOG.call_once(|| {
+        std::thread::spawn(|| loop {
+            std::thread::sleep(INTERVAL);
+            let Some((limit, format)) = *LIMIT.lock().unwrap() else { continue };
+            if current() > limit {
+                print_error(
+                    &eco_format!(
+                        "compilation exceeded the memory limit of {}",
+                        format_size(limit),
+                    ),
+                    format,
+                )
+                .ok();
+                std::process::exit(1);
+            }
+        });
+    });
+
+    *LIMIT.lock().unwrap() = Some((limit, format));
+    LimitGuard
+}
+
+/// Lifts the memory limit when dropped.
+pub struct LimitGuard;
+
+impl Drop for LimitGuard {
+    fn drop(&mut self) {
+        *LIMIT.lock().unwrap() = None;
+    }
+}
+
+/// Evict memoized results between compilations.
+///
+/// Results that were not used for `max_age` compilations are always evicted.
+/// If more than `max` bytes are still in use afterwards, the cache is cleared
+/// completely.
+pub fn evict(max_age: usize, max: Option<usize>) {
+    comemo::evict(max_age);
+    if max.is_some_and(|max| current() > max) {
+        tracing::info!("Cache exceeds memory limit, clearing it");
+        comemo::evict(0);
+    }
+}
+
+/// Format a number of bytes in mebibytes.
+pub fn format_size(bytes: usize) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
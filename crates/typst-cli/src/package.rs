@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -8,33 +9,74 @@ use typst::diag::{PackageError, PackageResult};
 use typst::syntax::PackageSpec;
 
 use super::color_stream;
+use crate::args::SharedArgs;
 
-/// Make a package available in the on-disk cache.
-pub fn prepare_package(spec: &PackageSpec) -> PackageResult<PathBuf> {
-    let subdir =
-        format!("typst/packages/{}/{}-{}", spec.namespace, spec.name, spec.version);
+/// Where packages are looked up and whether they may be downloaded.
+pub struct PackageStorage {
+    /// Packages that are resolved to a local directory, bypassing the other
+    /// directories.
+    overrides: HashMap<PackageSpec, PathBuf>,
+    /// The directory with locally installed packages.
+    data_dir: Option<PathBuf>,
+    /// The directory in which downloaded packages are cached.
+    cache_dir: Option<PathBuf>,
+    /// Whether downloading packages is forbidden.
+    offline: bool,
+}
 
-    if let Some(data_dir) = dirs::data_dir() {
-        let dir = data_dir.join(&subdir);
-        if dir.exists() {
-            return Ok(dir);
+impl PackageStorage {
+    /// Create the package storage for a compilation.
+    pub fn new(command: &SharedArgs) -> Self {
+        Self {
+            overrides: command.package_overrides.iter().cloned().collect(),
+            data_dir: command
+                .package_path
+                .clone()
+                .or_else(|| dirs::data_dir().map(|dir| dir.join("typst/packages"))),
+            cache_dir: command
+                .package_cache_path
+                .clone()
+                .or_else(|| dirs::cache_dir().map(|dir| dir.join("typst/packages"))),
+            offline: command.offline,
         }
     }
 
-    if let Some(cache_dir) = dirs::cache_dir() {
-        let dir = cache_dir.join(&subdir);
+    /// Make a package available in the on-disk cache.
+    pub fn prepare_package(&self, spec: &PackageSpec) -> PackageResult<PathBuf> {
+        if let Some(dir) = self.overrides.get(spec) {
+            return dir.canonicalize().map_err(|_| PackageError::NotFound(spec.clone()));
+        }
+
+        let subdir = format!("{}/{}-{}", spec.namespace, spec.name, spec.version);
 
-        // Download from network if it doesn't exist yet.
-        if spec.namespace == "preview" && !dir.exists() {
-            download_package(spec, &dir)?;
+        if let Some(data_dir) = &self.data_dir {
+            let dir = data_dir.join(&subdir);
+            if dir.exists() {
+                return Ok(dir);
+            }
         }
 
-        if dir.exists() {
-            return Ok(dir);
+        if let Some(cache_dir) = &self.cache_dir {
+            let dir = cache_dir.join(&subdir);
+            if dir.exists() {
+                return Ok(dir);
+            }
+
+            // Download from network if it doesn't exist yet.
+            if spec.namespace == "preview" {
+                if self.offline {
+                    return Err(PackageError::Offline(spec.clone()));
+                }
+
+                download_package(spec, &dir)?;
+                if dir.exists() {
+                    return Ok(dir);
+                }
+            }
         }
-    }
 
-    Err(PackageError::NotFound(spec.clone()))
+        Err(PackageError::NotFound(spec.clone()))
+    }
 }
 
 /// Download a package over the network.
@@ -0,0 +1,109 @@
+use comemo::Track;
+use serde::Serialize;
+use typst::diag::{bail, StrResult};
+use typst::doc::Document;
+use typst::eval::{eco_format, eval_string, EvalMode, IntoValue, Scope, Tracer, Value};
+use typst::model::{Introspector, LocatableSelector};
+use typst::syntax::Span;
+use typst::World;
+
+use crate::args::{QueryCommand, SerializationFormat};
+use crate::compile::{filter_warnings, print_diagnostics};
+use crate::set_failed;
+use crate::world::SystemWorld;
+
+/// Execute a query command.
+pub fn query(command: QueryCommand) -> StrResult<()> {
+    let mut world = SystemWorld::new(&command.common)?;
+    tracing::info!("Starting querying");
+
+    // Reset everything and ensure that the main file is present.
+    world.reset();
+    world.source(world.main()).map_err(|err| err.to_string())?;
+
+    let mut tracer = Tracer::default();
+    let result = typst::compile(&world, &mut tracer);
+    let warnings = filter_warnings(tracer.warnings(), &command.common);
+
+    match result {
+        // Retrieve and print query results.
+        Ok(document) => {
+            let data = retrieve(&world, &command, &document)?;
+            let serialized = format(data, &command)?;
+            println!("{serialized}");
+            print_diagnostics(&world, &[], &warnings, command.common.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
+        }
+
+        // Print diagnostics.
+        Err(errors) => {
+            set_failed();
+            print_diagnostics(
+                &world,
+                &errors,
+                &warnings,
+                command.common.diagnostic_format,
+            )
+            .map_err(|_| "failed to print diagnostics")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Retrieve the values matched by the selector.
+fn retrieve(
+    world: &dyn World,
+    command: &QueryCommand,
+    document: &Document,
+) -> StrResult<Vec<Value>> {
+    let selector = eval_string(
+        world.track(),
+        &command.selector,
+        Span::detached(),
+        EvalMode::Code,
+        Scope::default(),
+    )
+    .map_err(|errors| {
+        let mut message = eco_format!("failed to evaluate selector");
+        for (i, error) in errors.into_iter().enumerate() {
+            message.push_str(if i == 0 { ": " } else { ", " });
+            message.push_str(&error.message);
+        }
+        message
+    })?
+    .cast::<LocatableSelector>()?;
+
+    Ok(Introspector::new(&document.pages)
+        .query(&selector.0)
+        .iter()
+        .filter_map(|elem| match &command.field {
+            Some(field) => elem.field(field),
+            None => Some((**elem).clone().into_value()),
+        })
+        .collect())
+}
+
+/// Serialize the query results in the requested format.
+fn format(values: Vec<Value>, command: &QueryCommand) -> StrResult<String> {
+    if command.one {
+        let [value] = values.as_slice() else {
+            bail!("expected exactly one element, found {}", values.len());
+        };
+        serialize(value, command.format)
+    } else {
+        serialize(&values, command.format)
+    }
+}
+
+/// Serialize data to the output format.
+fn serialize(data: &impl Serialize, format: SerializationFormat) -> StrResult<String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::to_string_pretty(data).map_err(|e| eco_format!("{e}"))
+        }
+        SerializationFormat::Yaml => {
+            serde_yaml::to_string(data).map_err(|e| eco_format!("{e}"))
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// The phases of a compilation and the spans that measure them.
+const PHASES: &[(&str, &str)] =
+    &[("parse", "parse"), ("eval", "eval"), ("layout", "typeset"), ("export", "export")];
+
+/// How many of the slowest functions and pages to report.
+const HOTTEST: usize = 10;
+
+/// A tracing layer that measures how long each span takes.
+pub struct TimingsLayer {
+    start: Instant,
+    timings: Arc<Mutex<Timings>>,
+}
+
+impl TimingsLayer {
+    /// Create a new layer and a guard that reports its timings when
+    /// dropped.
+    pub fn new(output: PathBuf) -> (Self, TimingsGuard) {
+        let timings = Arc::new(Mutex::new(Timings::default()));
+        let layer = Self { start: Instant::now(), timings: timings.clone() };
+        (layer, TimingsGuard { timings, output })
+    }
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = PageVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(page), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(Page(page));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut()
+                .replace(Entered { at: Instant::now(), children: Duration::ZERO });
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let Some(entered) = span.extensions_mut().remove::<Entered>() else { return };
+        let duration = entered.at.elapsed();
+
+        if let Some(parent) = span.parent() {
+            if let Some(parent) = parent.extensions_mut().get_mut::<Entered>() {
+                parent.children += duration;
+            }
+        }
+
+        // Only count the outermost span for recursive calls into the total.
+        let name = span.name();
+        let outermost = span.scope().skip(1).all(|ancestor| ancestor.name() != name);
+        let page = span.extensions().get::<Page>().map(|page| page.0);
+
+        let mut timings = self.timings.lock().unwrap();
+        timings.events.push(TraceEvent {
+            name,
+            ph: "X",
+            ts: (entered.at - self.start).as_micros() as u64,
+            dur: duration.as_micros() as u64,
+            pid: 1,
+            tid: 1,
+        });
+
+        let stats = timings.stats.entry(name).or_default();
+        stats.calls += 1;
+        stats.own += duration.saturating_sub(entered.children);
+        if outermost {
+            stats.total += duration;
+        }
+
+        if let Some(page) = page {
+            timings.pages.push((page, duration));
+        }
+    }
+}
+
+/// Reports the timings when dropped.
+pub struct TimingsGuard {
+    timings: Arc<Mutex<Timings>>,
+    output: PathBuf,
+}
+
+impl TimingsGuard {
+    fn finish(&mut self) -> io::Result<()> {
+        let mut timings = self.timings.lock().unwrap();
+
+        eprintln!("Phases:");
+        for &(phase, name) in PHASES {
+            let total = timings.stats.get(name).map_or(Duration::ZERO, |s| s.total);
+            eprintln!("  {phase:<8} {}", format_duration(total));
+        }
+
+        let mut hottest: Vec<_> = timings.stats.iter().collect();
+        hottest.sort_by_key(|(_, stats)| Reverse(stats.own));
+        eprintln!("Slowest functions (by own time):");
+        for (name, stats) in hottest.into_iter().take(HOTTEST) {
+            eprintln!(
+                "  {:>10}  {name} ({} calls)",
+                format_duration(stats.own),
+                stats.calls
+            );
+        }
+
+        if !timings.pages.is_empty() {
+            timings.pages.sort_by_key(|&(_, duration)| Reverse(duration));
+            eprintln!("Slowest pages:");
+            for (page, duration) in timings.pages.iter().take(HOTTEST) {
+                eprintln!("  {:>10}  page {page}", format_duration(*duration));
+            }
+        }
+
+        let writer = BufWriter::new(File::create(&self.output)?);
+        let trace = Trace { trace_events: &timings.events };
+        serde_json::to_writer(writer, &trace)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+}
+
+impl Drop for TimingsGuard {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            if let Err(e) = self.finish() {
+                eprintln!("failed to write timings: {e}");
+            }
+        }
+    }
+}
+
+/// All timings recorded so far.
+#[derive(Default)]
+struct Timings {
+    events: Vec<TraceEvent>,
+    stats: HashMap<&'static str, Stats>,
+    pages: Vec<(u64, Duration)>,
+}
+
+/// Accumulated timings of all spans with the same name.
+#[derive(Default)]
+struct Stats {
+    calls: usize,
+    /// The time spent in the outermost spans, including their children.
+    total: Duration,
+    /// The time spent in the spans themselves, excluding their children.
+    own: Duration,
+}
+
+/// Stored in the extensions of a span while it is entered.
+struct Entered {
+    at: Instant,
+    children: Duration,
+}
+
+/// The number of the first page laid out by a span.
+struct Page(u64);
+
+/// Extracts the `page` field of a span.
+struct PageVisitor(Option<u64>);
+
+impl Visit for PageVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "page" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
+
+/// A trace in the Chrome trace event format.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trace<'a> {
+    trace_events: &'a [TraceEvent],
+}
+
+/// A complete event in the Chrome trace event format.
+#[derive(Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    ph: &'static str,
+    ts: u64,
+    dur: u64,
+    pid: u32,
+    tid: u32,
+}
+
+/// Format a duration in milliseconds.
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
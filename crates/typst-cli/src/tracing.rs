@@ -10,23 +10,33 @@ use tracing_subscriber::fmt;
 use tracing_subscriber::prelude::*;
 
 use crate::args::{CliArguments, Command};
+use crate::timings::{TimingsGuard, TimingsLayer};
 
 /// Initializes the tracing system and returns a guard that will flush the
-/// flamegraph to disk when dropped.
+/// flamegraph and the timings to disk when dropped.
 pub fn setup_tracing(args: &CliArguments) -> io::Result<Option<impl Drop>> {
-    let flamegraph = match &args.command {
-        Command::Compile(command) => command.flamegraph.as_ref(),
+    let (flamegraph, timings) = match &args.command {
+        Command::Compile(command) => {
+            (command.flamegraph.as_ref(), command.timings.as_ref())
+        }
         Command::Watch(command) if command.flamegraph.is_some() => {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 "cannot use --flamegraph with watch command",
             ));
         }
-        _ => None,
+        Command::Watch(command) if command.timings.is_some() => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot use --timings with watch command",
+            ));
+        }
+        _ => (None, None),
     };
 
-    // Short circuit if we don't need to initialize flamegraph or debugging.
-    if flamegraph.is_none() && args.verbosity == 0 {
+    // Short circuit if we don't need to initialize flamegraph, timings, or
+    // debugging.
+    if flamegraph.is_none() && timings.is_none() && args.verbosity == 0 {
         tracing_subscriber::fmt()
             .without_time()
             .with_max_level(level_filter(args))
@@ -41,12 +51,25 @@ pub fn setup_tracing(args: &CliArguments) -> io::Result<Option<impl Drop>> {
     // Error layer for building backtraces
     let error_layer = ErrorLayer::default();
 
+    // Build the timings layer.
+    let (timings_layer, timings_guard) = match timings {
+        Some(path) => {
+            let output = path.clone().unwrap_or_else(|| "timings.json".into());
+            let (layer, guard) = TimingsLayer::new(output);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
     // Build the registry.
-    let registry = tracing_subscriber::registry().with(fmt_layer).with(error_layer);
+    let registry = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(error_layer)
+        .with(timings_layer);
 
     let Some(path) = flamegraph else {
         registry.init();
-        return Ok(None);
+        return Ok(Some(TracingGuard { flame: None, timings: timings_guard }));
     };
 
     // Create a temporary file to store the flamegraph data.
@@ -70,9 +93,12 @@ pub fn setup_tracing(args: &CliArguments) -> io::Result<Option<impl Drop>> {
     );
 
     Ok(Some(TracingGuard {
-        flush_guard: Some(flush_guard),
-        temp_file,
-        output_svg: path.clone().unwrap_or_else(|| "flamegraph.svg".into()),
+        flame: Some(FlameGuard {
+            flush_guard: Some(flush_guard),
+            temp_file,
+            output_svg: path.clone().unwrap_or_else(|| "flamegraph.svg".into()),
+        }),
+        timings: timings_guard,
     }))
 }
 
@@ -87,14 +113,27 @@ fn level_filter(args: &CliArguments) -> LevelFilter {
     }
 }
 
-/// Will flush the flamegraph to disk when dropped.
+/// Will flush the flamegraph and the timings to disk when dropped.
 struct TracingGuard {
+    flame: Option<FlameGuard>,
+    timings: Option<TimingsGuard>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        // Flush the flamegraph before reporting the timings.
+        drop(self.flame.take());
+        drop(self.timings.take());
+    }
+}
+
+/// Will flush the flamegraph to disk when dropped.
+struct FlameGuard {
     flush_guard: Option<FlushGuard<BufWriter<File>>>,
     temp_file: File,
     output_svg: PathBuf,
 }
-
-impl TracingGuard {
+impl FlameGuard {
     fn finish(&mut self) -> io::Result<()> {
         if self.flush_guard.is_none() {
             return Ok(());
@@ -125,7 +164,7 @@ impl TracingGuard {
     }
 }
 
-impl Drop for TracingGuard {
+impl Drop for FlameGuard {
     fn drop(&mut self) {
         if !std::thread::panicking() {
             if let Err(e) = self.finish() {
@@ -6,7 +6,7 @@ use codespan_reporting::term::{self, termcolor};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use same_file::is_same_file;
 use termcolor::WriteColor;
-use typst::diag::StrResult;
+use typst::diag::{bail, StrResult};
 use typst::eval::eco_format;
 
 use crate::args::CompileCommand;
@@ -16,8 +16,17 @@ use crate::world::SystemWorld;
 
 /// Execute a watching compilation command.
 pub fn watch(mut command: CompileCommand) -> StrResult<()> {
+    if command.common.reads_stdin() {
+        bail!("cannot watch input from stdin");
+    }
+
+    if !command.extra_inputs.is_empty() {
+        bail!("cannot watch multiple inputs");
+    }
+
     // Create the world that serves sources, files, and fonts.
-    let mut world = SystemWorld::new(&command)?;
+    crate::memory::setup(&command);
+    let mut world = SystemWorld::new(&command.common)?;
 
     // Perform initial compilation.
     compile_once(&mut world, &mut command, true)?;
@@ -28,13 +37,14 @@ pub fn watch(mut command: CompileCommand) -> StrResult<()> {
         .map_err(|_| "failed to setup file watching")?;
 
     // Watch all the files that are used by the input file and its dependencies.
-    watch_dependencies(&mut world, &mut watcher, HashSet::new())?;
+    let mut dependencies = HashSet::new();
+    let mut watched = HashSet::new();
+    watch_dependencies(&mut world, &mut watcher, &mut dependencies, &mut watched)?;
 
     // Handle events.
     let timeout = std::time::Duration::from_millis(100);
     let output = command.output();
     loop {
-        let mut removed = HashSet::new();
         let mut recompile = false;
         for event in rx
             .recv()
@@ -52,50 +62,55 @@ pub fn watch(mut command: CompileCommand) -> StrResult<()> {
                 notify::EventKind::Remove(notify::event::RemoveKind::File)
             ) {
                 let path = &event.paths[0];
-                removed.insert(path.clone());
+                watched.remove(path);
 
                 // Remove the watch in case it still exists.
                 watcher.unwatch(path).ok();
             }
 
-            recompile |= is_event_relevant(&event, &output);
+            recompile |= is_event_relevant(&event, &output, &dependencies);
         }
 
         if recompile {
-            // Retrieve the dependencies of the last compilation.
-            let previous: HashSet<PathBuf> = world
-                .dependencies()
-                .filter(|path| !removed.contains(*path))
-                .map(ToOwned::to_owned)
-                .collect();
-
             // Recompile.
             compile_once(&mut world, &mut command, true)?;
-            comemo::evict(10);
+            crate::memory::evict(command.cache_max_age, command.cache_max_memory);
 
             // Adjust the watching.
-            watch_dependencies(&mut world, &mut watcher, previous)?;
+            watch_dependencies(
+                &mut world,
+                &mut watcher,
+                &mut dependencies,
+                &mut watched,
+            )?;
         }
     }
 }
 
 /// Adjust the file watching. Watches all new dependencies and unwatches
-/// all `previous` dependencies that are not relevant anymore.
+/// all `watched` paths that are not relevant anymore.
+///
+/// Also updates `dependencies` to the files the last compilation accessed
+/// and `watched` to the paths that are watched now.
 #[tracing::instrument(skip_all)]
 fn watch_dependencies(
     world: &mut SystemWorld,
     watcher: &mut dyn Watcher,
-    mut previous: HashSet<PathBuf>,
+    dependencies: &mut HashSet<PathBuf>,
+    watched: &mut HashSet<PathBuf>,
 ) -> StrResult<()> {
+    *dependencies = world.dependencies().map(canonicalize).collect();
+
     // Watch new paths that weren't watched yet.
-    for path in world.dependencies() {
-        let watched = previous.remove(path);
-        if path.exists() && !watched {
+    let mut previous = std::mem::take(watched);
+    for path in watch_targets(world) {
+        if !previous.remove(&path) {
             tracing::info!("Watching {}", path.display());
             watcher
-                .watch(path, RecursiveMode::NonRecursive)
+                .watch(&path, RecursiveMode::NonRecursive)
                 .map_err(|_| eco_format!("failed to watch {path:?}"))?;
         }
+        watched.insert(path);
     }
 
     // Unwatch old paths that don't need to be watched anymore.
@@ -107,8 +122,36 @@ fn watch_dependencies(
     Ok(())
 }
 
+/// The paths to watch for the dependencies of the last compilation.
+///
+/// Files that don't exist are watched through their closest existing
+/// ancestor directory, so that their creation is noticed.
+fn watch_targets(world: &mut SystemWorld) -> HashSet<PathBuf> {
+    world
+        .dependencies()
+        .filter_map(|path| path.ancestors().find(|path| path.exists()))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Resolve symlinks in a path, so that event paths and dependencies can be
+/// compared. The part of the path that doesn't exist (anymore) is kept as is.
+fn canonicalize(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(canonical) = ancestor.canonicalize() {
+            let rest = path.strip_prefix(ancestor).unwrap_or(Path::new(""));
+            return canonical.join(rest);
+        }
+    }
+    path.to_owned()
+}
+
 /// Whether a watch event is relevant for compilation.
-fn is_event_relevant(event: &notify::Event, output: &Path) -> bool {
+fn is_event_relevant(
+    event: &notify::Event,
+    output: &Path,
+    dependencies: &HashSet<PathBuf>,
+) -> bool {
     // Never recompile because the output file changed.
     if event
         .paths
@@ -118,6 +161,17 @@ fn is_event_relevant(event: &notify::Event, output: &Path) -> bool {
         return false;
     }
 
+    // Ignore changes to other files in watched directories. Changes to a
+    // directory on the way to a dependency are relevant, as it may be the
+    // creation of a directory that a missing file will be created in.
+    if !event.paths.is_empty()
+        && !event.paths.iter().map(|path| canonicalize(path)).any(|path| {
+            dependencies.iter().any(|dependency| dependency.starts_with(&path))
+        })
+    {
+        return false;
+    }
+
     match &event.kind {
         notify::EventKind::Any => true,
         notify::EventKind::Access(_) => false,
@@ -159,7 +213,7 @@ impl Status {
         w.set_color(&color)?;
         write!(w, "watching")?;
         w.reset()?;
-        writeln!(w, " {}", command.input.display())?;
+        writeln!(w, " {}", command.common.input.display())?;
 
         w.set_color(&color)?;
         write!(w, "writing to")?;
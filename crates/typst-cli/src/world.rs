@@ -1,7 +1,8 @@
 use std::cell::{OnceCell, RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::hash::Hash;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 
 use chrono::Datelike;
@@ -9,15 +10,18 @@ use comemo::Prehashed;
 use same_file::Handle;
 use siphasher::sip128::{Hasher128, SipHasher13};
 use typst::diag::{FileError, FileResult, StrResult};
-use typst::eval::{eco_format, Datetime, Library};
+use typst::eval::{eco_format, Datetime, Dict, IntoValue, Library};
 use typst::font::{Font, FontBook};
 use typst::syntax::{FileId, Source};
 use typst::util::{Bytes, PathExt};
 use typst::World;
 
-use crate::args::CompileCommand;
+use crate::args::SharedArgs;
 use crate::fonts::{FontSearcher, FontSlot};
-use crate::package::prepare_package;
+use crate::package::PackageStorage;
+
+/// The virtual path of a main source file that was read from stdin.
+const STDIN_PATH: &str = "/<stdin>";
 
 /// A world that provides access to the operating system.
 pub struct SystemWorld {
@@ -25,18 +29,28 @@ pub struct SystemWorld {
     root: PathBuf,
     /// The input path.
     main: FileId,
+    /// The main source if it was read from stdin.
+    stdin: Option<Source>,
     /// Typst's standard library.
     library: Prehashed<Library>,
     /// Metadata about discovered fonts.
     book: Prehashed<FontBook>,
     /// Locations of and storage for lazily loaded fonts.
     fonts: Vec<FontSlot>,
+    /// Where packages are looked up and downloaded to.
+    packages: PackageStorage,
     /// Maps package-path combinations to canonical hashes. All package-path
     /// combinations that point to the same file are mapped to the same hash. To
     /// be used in conjunction with `paths`.
     hashes: RefCell<HashMap<FileId, FileResult<PathHash>>>,
     /// Maps canonical path hashes to source files and buffers.
     paths: RefCell<HashMap<PathHash, PathSlot>>,
+    /// All system paths the compilation tried to access, including those
+    /// that don't exist (yet).
+    accessed: RefCell<HashSet<PathBuf>>,
+    /// The fixed date of the document, if any. Used instead of the current
+    /// date so that the output is reproducible.
+    creation_date: Option<chrono::DateTime<chrono::Utc>>,
     /// The current date if requested. This is stored here to ensure it is
     /// always the same within one compilation. Reset between compilations.
     today: OnceCell<Option<Datetime>>,
@@ -44,41 +58,65 @@ pub struct SystemWorld {
 
 impl SystemWorld {
     /// Create a new system world.
-    pub fn new(command: &CompileCommand) -> StrResult<Self> {
+    pub fn new(command: &SharedArgs) -> StrResult<Self> {
         let mut searcher = FontSearcher::new();
         searcher.search(&command.font_paths);
 
+        // Read the main source from stdin if requested.
+        let stdin = if command.reads_stdin() {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|_| "failed to read from stdin")?;
+            Some(text)
+        } else {
+            None
+        };
+
         // Resolve the system-global input path.
-        let system_input = command.input.canonicalize().map_err(|_| {
-            eco_format!("input file not found (searched at {})", command.input.display())
-        })?;
+        let system_input = match &stdin {
+            Some(_) => None,
+            None => Some(canonicalize_input(&command.input)?),
+        };
 
         // Resolve the system-global root directory.
         let root = {
             let path = command
                 .root
                 .as_deref()
-                .or_else(|| system_input.parent())
+                .or_else(|| system_input.as_deref()?.parent())
                 .unwrap_or(Path::new("."));
             path.canonicalize().map_err(|_| {
                 eco_format!("root directory not found (searched at {})", path.display())
             })?
         };
 
-        // Resolve the input path within the project.
-        let project_input = system_input
-            .strip_prefix(&root)
-            .map(|path| Path::new("/").join(path))
-            .map_err(|_| "input file must be contained in project root")?;
+        // Resolve the input path within the project. The source read from
+        // stdin gets a virtual path directly in the root.
+        let main = match &system_input {
+            Some(system_input) => project_file(&root, system_input)?,
+            None => FileId::new(None, Path::new(STDIN_PATH)),
+        };
+        let stdin = stdin.map(|text| Source::new(main, text));
+
+        let inputs: Dict = command
+            .inputs
+            .iter()
+            .map(|(k, v)| (k.as_str().into(), v.as_str().into_value()))
+            .collect();
 
         Ok(Self {
             root,
-            main: FileId::new(None, &project_input),
-            library: Prehashed::new(typst_library::build()),
+            main,
+            stdin,
+            library: Prehashed::new(typst_library::build_with_inputs(inputs)),
             book: Prehashed::new(searcher.book),
             fonts: searcher.fonts,
+            packages: PackageStorage::new(command),
             hashes: RefCell::default(),
             paths: RefCell::default(),
+            accessed: RefCell::default(),
+            creation_date: command.creation_date,
             today: OnceCell::new(),
         })
     }
@@ -88,18 +126,39 @@ impl SystemWorld {
         self.main
     }
 
+    /// Compile another file of the project as the main source file.
+    ///
+    /// The file must be contained in the same root as the original input.
+    pub fn set_main(&mut self, path: &Path) -> StrResult<()> {
+        let system_input = canonicalize_input(path)?;
+        self.main = project_file(&self.root, &system_input)?;
+        Ok(())
+    }
+
     /// Return all paths the last compilation depended on.
+    ///
+    /// This includes files that the compilation failed to access, so that a
+    /// recompilation can be triggered once they are created.
     pub fn dependencies(&mut self) -> impl Iterator<Item = &Path> {
-        self.paths.get_mut().values().map(|slot| slot.system_path.as_path())
+        self.accessed.get_mut().iter().map(PathBuf::as_path)
     }
 
     /// Reset the compilation state in preparation of a new compilation.
     pub fn reset(&mut self) {
         self.hashes.borrow_mut().clear();
         self.paths.borrow_mut().clear();
+        self.accessed.borrow_mut().clear();
         self.today.take();
     }
 
+    /// The path of a file on the system, if it exists.
+    pub fn path(&self, id: FileId) -> Option<PathBuf> {
+        if self.stdin.as_ref().map(Source::id) == Some(id) {
+            return None;
+        }
+        self.slot(id).ok().map(|slot| slot.system_path.clone())
+    }
+
     /// Lookup a source file by id.
     #[track_caller]
     pub fn lookup(&self, id: FileId) -> Source {
@@ -121,10 +180,16 @@ impl World for SystemWorld {
     }
 
     fn source(&self, id: FileId) -> FileResult<Source> {
+        if let Some(source) = self.stdin.as_ref().filter(|source| source.id() == id) {
+            return Ok(source.clone());
+        }
         self.slot(id)?.source()
     }
 
     fn file(&self, id: FileId) -> FileResult<Bytes> {
+        if let Some(source) = self.stdin.as_ref().filter(|source| source.id() == id) {
+            return Ok(Bytes::from(source.text().as_bytes().to_vec()));
+        }
         self.slot(id)?.file()
     }
 
@@ -134,9 +199,15 @@ impl World for SystemWorld {
 
     fn today(&self, offset: Option<i64>) -> Option<Datetime> {
         *self.today.get_or_init(|| {
-            let naive = match offset {
-                None => chrono::Local::now().naive_local(),
-                Some(o) => (chrono::Utc::now() + chrono::Duration::hours(o)).naive_utc(),
+            let naive = match (self.creation_date, offset) {
+                // A fixed date is interpreted as UTC so that it doesn't depend
+                // on the timezone of the machine.
+                (Some(date), None) => date.naive_utc(),
+                (None, None) => chrono::Local::now().naive_local(),
+                (date, Some(o)) => {
+                    let now = date.unwrap_or_else(chrono::Utc::now);
+                    (now + chrono::Duration::hours(o)).naive_utc()
+                }
             };
 
             Datetime::from_ymd(
@@ -161,7 +232,7 @@ impl SystemWorld {
                 // Determine the root path relative to which the file path
                 // will be resolved.
                 let root = match id.package() {
-                    Some(spec) => prepare_package(spec)?,
+                    Some(spec) => self.packages.prepare_package(spec)?,
                     None => self.root.clone(),
                 };
 
@@ -169,6 +240,7 @@ impl SystemWorld {
                 // access. Note: It can still escape via symlinks.
                 system_path =
                     root.join_rooted(id.path()).ok_or(FileError::AccessDenied)?;
+                self.accessed.borrow_mut().insert(system_path.clone());
 
                 PathHash::new(&system_path)
             })
@@ -219,6 +291,21 @@ impl PathSlot {
     }
 }
 
+/// Resolve the system-global path of an input file.
+fn canonicalize_input(path: &Path) -> StrResult<PathBuf> {
+    path.canonicalize()
+        .map_err(|_| eco_format!("input file not found (searched at {})", path.display()))
+}
+
+/// The id of an input file within the project.
+fn project_file(root: &Path, system_input: &Path) -> StrResult<FileId> {
+    let project_input = system_input
+        .strip_prefix(root)
+        .map(|path| Path::new("/").join(path))
+        .map_err(|_| "input file must be contained in project root")?;
+    Ok(FileId::new(None, &project_input))
+}
+
 /// A hash that is the same for all paths pointing to the same entity.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct PathHash(u128);
@@ -40,11 +40,13 @@ static FONTS: Lazy<(Prehashed<FontBook>, Vec<Font>)> = Lazy::new(|| {
 
 static LIBRARY: Lazy<Prehashed<Library>> = Lazy::new(|| {
     let mut lib = typst_library::build();
-    // Hack for documenting the `mix` function in the color module.
+    // Hack for documenting the functions in the color module.
     // Will be superseded by proper associated functions.
-    lib.global
-        .scope_mut()
-        .define("mix", typst_library::compute::mix_func());
+    let scope = lib.global.scope_mut();
+    scope.define("mix", typst_library::compute::mix_func());
+    scope.define("hsl", typst_library::compute::hsl_func());
+    scope.define("hsv", typst_library::compute::hsv_func());
+    scope.define("oklab", typst_library::compute::oklab_func());
     lib.styles
         .set(PageElem::set_width(Smart::Custom(Abs::pt(240.0).into())));
     lib.styles.set(PageElem::set_height(Smart::Auto));
@@ -158,6 +160,10 @@ fn reference_pages(resolver: &dyn Resolver) -> PageModel {
         category_page(resolver, "symbols"),
         category_page(resolver, "foundations").with_part("Compute"),
         category_page(resolver, "calculate"),
+        category_page(resolver, "linalg"),
+        category_page(resolver, "random"),
+        category_page(resolver, "crypto"),
+        category_page(resolver, "encoding"),
         category_page(resolver, "construct"),
         category_page(resolver, "data-loading"),
     ];
@@ -232,16 +238,17 @@ fn category_page(resolver: &dyn Resolver, category: &str) -> PageModel {
     let mut children = vec![];
     let mut items = vec![];
 
-    let focus = match category {
-        "math" => &LIBRARY.math,
-        "calculate" => module(&LIBRARY.global, "calc").unwrap(),
-        _ => &LIBRARY.global,
-    };
-
-    let parents: &[&str] = match category {
-        "math" => &[],
-        "calculate" => &["calc"],
-        _ => &[],
+    let focuses: Vec<(&Module, &[&str])> = match category {
+        "math" => vec![(&LIBRARY.math, &[])],
+        "calculate" => vec![(module(&LIBRARY.global, "calc").unwrap(), &["calc"])],
+        "linalg" => vec![(module(&LIBRARY.global, "linalg").unwrap(), &["linalg"])],
+        "random" => vec![(module(&LIBRARY.global, "random").unwrap(), &["random"])],
+        "crypto" => vec![(module(&LIBRARY.global, "crypto").unwrap(), &["crypto"])],
+        "encoding" => vec![
+            (module(&LIBRARY.global, "base64").unwrap(), &["base64"]),
+            (module(&LIBRARY.global, "hex").unwrap(), &["hex"]),
+        ],
+        _ => vec![(&LIBRARY.global, &[])],
     };
 
     let grouped = match category {
@@ -250,30 +257,32 @@ fn category_page(resolver: &dyn Resolver, category: &str) -> PageModel {
     };
 
     // Add functions.
-    for (_, value) in focus.scope().iter() {
-        let Value::Func(func) = value else { continue };
-        let Some(info) = func.info() else { continue };
-        if info.category != category {
-            continue;
-        }
+    for &(focus, parents) in &focuses {
+        for (_, value) in focus.scope().iter() {
+            let Value::Func(func) = value else { continue };
+            let Some(info) = func.info() else { continue };
+            if info.category != category {
+                continue;
+            }
 
-        // Skip grouped functions.
-        if grouped
-            .iter()
-            .flat_map(|group| &group.functions)
-            .any(|f| f == info.name)
-        {
-            continue;
-        }
+            // Skip grouped functions.
+            if grouped
+                .iter()
+                .flat_map(|group| &group.functions)
+                .any(|f| f == info.name)
+            {
+                continue;
+            }
 
-        let subpage = function_page(resolver, &route, func, info, parents);
-        items.push(CategoryItem {
-            name: info.name.into(),
-            route: subpage.route.clone(),
-            oneliner: oneliner(info.docs).into(),
-            code: true,
-        });
-        children.push(subpage);
+            let subpage = function_page(resolver, &route, func, info, parents);
+            items.push(CategoryItem {
+                name: info.name.into(),
+                route: subpage.route.clone(),
+                oneliner: oneliner(info.docs).into(),
+                code: true,
+            });
+            children.push(subpage);
+        }
     }
 
     // Add grouped functions.
@@ -286,7 +295,7 @@ fn category_page(resolver: &dyn Resolver, category: &str) -> PageModel {
         }];
 
         for name in &group.functions {
-            let value = focus.get(name).unwrap();
+            let value = focuses[0].0.get(name).unwrap();
             let Value::Func(func) = value else { panic!("not a function") };
             let info = func.info().unwrap();
             let func = func_model(resolver, func, info, &[], info.name);
@@ -954,6 +963,8 @@ const TYPE_ORDER: &[&str] = &[
     "boolean",
     "integer",
     "float",
+    "decimal",
+    "complex",
     "length",
     "angle",
     "ratio",
@@ -961,7 +972,9 @@ const TYPE_ORDER: &[&str] = &[
     "fraction",
     "color",
     "datetime",
+    "duration",
     "string",
+    "bytes",
     "regex",
     "label",
     "content",
@@ -970,11 +983,13 @@ const TYPE_ORDER: &[&str] = &[
     "function",
     "arguments",
     "location",
+    "styles",
     "dir",
     "alignment",
     "2d alignment",
     "selector",
     "stroke",
+    "plugin",
 ];
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use std::cmp;
 use std::cmp::Ordering;
 use std::ops::{Div, Rem};
 
-use typst::eval::{Module, Scope};
+use typst::eval::{Complex, Module, Scope};
 
 use crate::prelude::*;
 
@@ -44,10 +44,20 @@ pub fn module() -> Module {
     scope.define("odd", odd_func());
     scope.define("rem", rem_func());
     scope.define("quo", quo_func());
+    scope.define("bit-and", bit_and_func());
+    scope.define("bit-or", bit_or_func());
+    scope.define("bit-xor", bit_xor_func());
+    scope.define("bit-not", bit_not_func());
+    scope.define("bit-lshift", bit_lshift_func());
+    scope.define("bit-rshift", bit_rshift_func());
+    scope.define("complex", complex_func());
+    scope.define("polar", polar_func());
+    scope.define("roots", roots_func());
     scope.define("inf", f64::INFINITY);
     scope.define("nan", f64::NAN);
     scope.define("pi", std::f64::consts::PI);
     scope.define("e", std::f64::consts::E);
+    scope.define("i", Complex::I);
     Module::new("calc").with_scope(scope)
 }
 
@@ -57,7 +67,8 @@ pub fn module() -> Module {
 /// ```example
 /// #calc.abs(-5) \
 /// #calc.abs(5pt - 2cm) \
-/// #calc.abs(2fr)
+/// #calc.abs(2fr) \
+/// #calc.abs(calc.complex(3, 4))
 /// ```
 ///
 /// Display: Absolute
@@ -82,6 +93,7 @@ cast! {
     v: Angle => Self(Value::Angle(v.abs())),
     v: Ratio => Self(Value::Ratio(v.abs())),
     v: Fr => Self(Value::Fraction(v.abs())),
+    v: Complex => Self(v.abs().into_value()),
 }
 
 /// Raises a value to some exponent.
@@ -958,6 +970,282 @@ pub fn quo(
     Ok(floor(dividend.apply2(divisor.v, Div::div, Div::div)))
 }
 
+/// Calculates the bitwise AND of integers.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-and(0b1100, 0b1010) \
+/// #str(calc.bit-and(0xdead, 0xff), base: 16)
+/// ```
+///
+/// Display: Bitwise AND
+/// Category: calculate
+#[func]
+pub fn bit_and(
+    /// The integers to combine.
+    #[variadic]
+    values: Vec<i64>,
+) -> i64 {
+    values.into_iter().fold(-1, |acc, v| acc & v)
+}
+
+/// Calculates the bitwise OR of integers.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-or(0b1100, 0b1010)
+/// ```
+///
+/// Display: Bitwise OR
+/// Category: calculate
+#[func]
+pub fn bit_or(
+    /// The integers to combine.
+    #[variadic]
+    values: Vec<i64>,
+) -> i64 {
+    values.into_iter().fold(0, |acc, v| acc | v)
+}
+
+/// Calculates the bitwise exclusive OR of integers.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-xor(0b1100, 0b1010)
+/// ```
+///
+/// Display: Bitwise XOR
+/// Category: calculate
+#[func]
+pub fn bit_xor(
+    /// The integers to combine.
+    #[variadic]
+    values: Vec<i64>,
+) -> i64 {
+    values.into_iter().fold(0, |acc, v| acc ^ v)
+}
+
+/// Inverts all bits of an integer.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-not(0) \
+/// #calc.bit-not(-6)
+/// ```
+///
+/// Display: Bitwise NOT
+/// Category: calculate
+#[func]
+pub fn bit_not(
+    /// The integer whose bits to invert.
+    value: i64,
+) -> i64 {
+    !value
+}
+
+/// Shifts the bits of an integer to the left.
+///
+/// Bits that are shifted beyond the 64 bits of an integer are discarded.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-lshift(1, 4) \
+/// #str(calc.bit-lshift(0b101, 2), base: 2)
+/// ```
+///
+/// Display: Left Shift
+/// Category: calculate
+#[func]
+pub fn bit_lshift(
+    /// The integer to shift.
+    value: i64,
+    /// The number of bits to shift by.
+    shift: Spanned<i64>,
+) -> SourceResult<i64> {
+    if !(0..64).contains(&shift.v) {
+        bail!(shift.span, "shift amount must be between 0 and 63");
+    }
+    Ok(value << shift.v)
+}
+
+/// Shifts the bits of an integer to the right.
+///
+/// The shift is arithmetic, that is, the sign of the integer is preserved.
+///
+/// ## Example { #example }
+/// ```example
+/// #calc.bit-rshift(256, 4) \
+/// #calc.bit-rshift(-16, 2)
+/// ```
+///
+/// Display: Right Shift
+/// Category: calculate
+#[func]
+pub fn bit_rshift(
+    /// The integer to shift.
+    value: i64,
+    /// The number of bits to shift by.
+    shift: Spanned<i64>,
+) -> SourceResult<i64> {
+    if !(0..64).contains(&shift.v) {
+        bail!(shift.span, "shift amount must be between 0 and 63");
+    }
+    Ok(value >> shift.v)
+}
+
+/// Creates a complex number from its real and imaginary part.
+///
+/// Complex numbers support arithmetic with each other and with integers and
+/// floats. You can also build them from the imaginary unit `calc.i`.
+///
+/// ## Example { #example }
+/// ```example
+/// #let z = calc.complex(3, 4)
+/// #z \
+/// #(z * calc.i) \
+/// #z.abs() \
+/// #(1 / z).display(digits: 2)
+/// ```
+///
+/// Display: Complex
+/// Category: calculate
+#[func]
+pub fn complex(
+    /// The real part.
+    re: Num,
+    /// The imaginary part.
+    #[default(Num::Int(0))]
+    im: Num,
+) -> Complex {
+    Complex::new(re.float(), im.float())
+}
+
+/// Creates a complex number from its magnitude and angle.
+///
+/// ## Example { #example }
+/// ```example
+/// #let z = calc.polar(2, 90deg)
+/// #z.display(digits: 1) \
+/// #z.display(polar: true)
+/// ```
+///
+/// Display: Polar
+/// Category: calculate
+#[func]
+pub fn polar(
+    /// The magnitude of the number.
+    radius: Num,
+    /// The angle between the positive real axis and the number.
+    angle: Angle,
+) -> Complex {
+    Complex::from_polar(radius.float(), angle)
+}
+
+/// Calculates the roots of a polynomial.
+///
+/// The coefficients are given from the highest to the lowest power. The roots
+/// are returned as [complex numbers]($type/complex), sorted by their real and
+/// then by their imaginary part.
+///
+/// ## Example { #example }
+/// ```example
+/// // x² - 3x + 2
+/// #calc.roots(1, -3, 2).map(z => z.re()) \
+/// // x² + 1
+/// #calc.roots(1, 0, 1)
+/// ```
+///
+/// Display: Roots
+/// Category: calculate
+#[func]
+pub fn roots(
+    /// The coefficients of the polynomial.
+    #[variadic]
+    coefficients: Vec<Num>,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Array> {
+    let coefficients: Vec<f64> = coefficients
+        .into_iter()
+        .map(Num::float)
+        .skip_while(|&c| c == 0.0)
+        .collect();
+
+    if coefficients.len() < 2 {
+        bail!(span, "polynomial must have a degree of at least one");
+    }
+
+    if coefficients.iter().any(|c| !c.is_finite()) {
+        bail!(span, "coefficients must be finite");
+    }
+
+    Ok(polynomial_roots(&coefficients)
+        .into_iter()
+        .map(IntoValue::into_value)
+        .collect())
+}
+
+/// Find the roots of a polynomial with finite coefficients, given from the
+/// highest to the lowest power, whose leading coefficient is not zero.
+pub(crate) fn polynomial_roots(coefficients: &[f64]) -> Vec<Complex> {
+    // Normalize the polynomial so that its leading coefficient is one.
+    let leading = coefficients[0];
+    let coefficients: Vec<f64> = coefficients.iter().map(|c| c / leading).collect();
+
+    let eval = |z: Complex| {
+        coefficients
+            .iter()
+            .fold(Complex::default(), |acc, &c| acc * z + Complex::from(c))
+    };
+
+    // Find all roots at once with the Durand-Kerner method.
+    let degree = coefficients.len() - 1;
+    let seed = Complex::new(0.4, 0.9);
+    let mut roots: Vec<Complex> =
+        std::iter::successors(Some(Complex::from(1.0)), |&z| Some(z * seed))
+            .take(degree)
+            .collect();
+
+    for _ in 0..1000 {
+        let mut change = 0.0f64;
+        for i in 0..degree {
+            let z = roots[i];
+            let denominator = roots
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Complex::from(1.0), |acc, (_, &other)| acc * (z - other));
+            let delta = eval(z) / denominator;
+            if delta.re.is_finite() && delta.im.is_finite() {
+                roots[i] = z - delta;
+                change = change.max(delta.abs());
+            }
+        }
+
+        if change < 1e-14 {
+            break;
+        }
+    }
+
+    // Remove the noise of the iteration from real and imaginary roots.
+    let clean = |v: f64, scale: f64| if v.abs() < 1e-7 * scale { 0.0 } else { v };
+    let mut roots: Vec<Complex> = roots
+        .into_iter()
+        .map(|z| {
+            let scale = z.abs().max(1.0);
+            Complex::new(clean(z.re, scale), clean(z.im, scale))
+        })
+        .collect();
+
+    roots.sort_by(|a, b| {
+        a.re.partial_cmp(&b.re)
+            .unwrap_or(Ordering::Equal)
+            .then(a.im.partial_cmp(&b.im).unwrap_or(Ordering::Equal))
+    });
+
+    roots
+}
+
 /// A value which can be passed to functions that work with integers and floats.
 #[derive(Debug, Copy, Clone)]
 pub enum Num {
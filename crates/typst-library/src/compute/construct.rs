@@ -3,7 +3,8 @@ use std::str::FromStr;
 
 use time::{Month, PrimitiveDateTime};
 
-use typst::eval::{Datetime, Module, Regex};
+use typst::eval::{Datetime, Decimal, Duration, Module, Regex};
+use typst::util::Bytes;
 
 use crate::prelude::*;
 
@@ -11,14 +12,16 @@ use crate::prelude::*;
 ///
 /// - Booleans are converted to `0` or `1`.
 /// - Floats are floored to the next 64-bit integer.
-/// - Strings are parsed in base 10.
+/// - Strings are parsed in base 10. This can be overridden with the optional
+///   `base` parameter.
 ///
 /// ## Example { #example }
 /// ```example
 /// #int(false) \
 /// #int(true) \
 /// #int(2.7) \
-/// #{ int("27") + int("4") }
+/// #{ int("27") + int("4") } \
+/// #int("ff", base: 16)
 /// ```
 ///
 /// Display: Integer
@@ -26,20 +29,51 @@ use crate::prelude::*;
 #[func]
 pub fn int(
     /// The value that should be converted to an integer.
-    value: ToInt,
-) -> i64 {
-    value.0
+    value: Spanned<ToInt>,
+    /// The base (radix) in which to parse a string, between 2 and 36.
+    ///
+    /// ```example
+    /// #int("1010", base: 2) \
+    /// #int("-7F", base: 16) \
+    /// #int("z", base: 36)
+    /// ```
+    #[named]
+    #[default(Spanned::new(10, Span::detached()))]
+    base: Spanned<i64>,
+) -> SourceResult<i64> {
+    let Spanned { v: value, span } = value;
+    if base.v < 2 || base.v > 36 {
+        bail!(base.span, "base must be between 2 and 36");
+    }
+
+    Ok(match value {
+        ToInt::Int(n) => {
+            if base.v != 10 {
+                bail!(base.span, "base is only supported for strings");
+            }
+            n
+        }
+        ToInt::Str(s) => i64::from_str_radix(&s, base.v as u32)
+            .map_err(|_| eco_format!("invalid integer: {}", s))
+            .at(span)?,
+    })
 }
 
 /// A value that can be cast to an integer.
-pub struct ToInt(i64);
+pub enum ToInt {
+    /// A value that was already converted to an integer.
+    Int(i64),
+    /// A string that is yet to be parsed.
+    Str(EcoString),
+}
 
 cast! {
     ToInt,
-    v: bool => Self(v as i64),
-    v: i64 => Self(v),
-    v: f64 => Self(v as i64),
-    v: EcoString => Self(v.parse().map_err(|_| eco_format!("invalid integer: {}", v))?),
+    v: bool => Self::Int(v as i64),
+    v: i64 => Self::Int(v),
+    v: f64 => Self::Int(v as i64),
+    v: Decimal => Self::Int(v.to_int()?),
+    v: EcoString => Self::Str(v),
 }
 
 /// Converts a value to a float.
@@ -79,6 +113,7 @@ cast! {
     v: i64 => Self(v as f64),
     v: f64 => Self(v),
     v: Ratio => Self(v.get()),
+    v: Decimal => Self(v.to_float()),
     v: EcoString => Self(v.parse().map_err(|_| eco_format!("invalid float: {}", v))?),
 }
 
@@ -97,17 +132,17 @@ cast! {
 pub fn luma(
     /// The gray component.
     gray: Component,
+    /// The alpha component.
+    #[default(Component(255))]
+    alpha: Component,
 ) -> Color {
-    LumaColor::new(gray.0).into()
+    LumaColor::new(gray.0).with_alpha(alpha.0).into()
 }
 
 /// Creates an RGB(A) color.
 ///
-/// The color is specified in the sRGB color space.
-///
-/// _Note:_ While you can specify transparent colors and Typst's preview will
-/// render them correctly, the PDF export does not handle them properly at the
-/// moment. This will be fixed in the future.
+/// The color is specified in the sRGB color space. Like all colors, it can be
+/// made transparent through its alpha component.
 ///
 /// ## Example { #example }
 /// ```example
@@ -169,6 +204,7 @@ pub struct Component(u8);
 
 cast! {
     Component,
+    self => Value::Int(self.0 as i64),
     v: i64 => match v {
         0 ..= 255 => Self(v as u8),
         _ => bail!("number must be between 0 and 255"),
@@ -183,8 +219,8 @@ cast! {
 /// Creates a new datetime.
 ///
 /// You can specify the [datetime]($type/datetime) using a year, month, day,
-/// hour, minute, and second. You can also get the current date with
-/// [`datetime.today`]($func/datetime.today).
+/// hour, minute, and second or parse it from an ISO 8601 string. You can also
+/// get the current date with [`datetime.today`]($func/datetime.today).
 ///
 /// ## Example
 /// ```example
@@ -197,7 +233,9 @@ cast! {
 /// #date.display() \
 /// #date.display(
 ///   "[day].[month].[year]"
-/// )
+/// ) \
+/// #datetime("2012-08-03T14:30:00")
+///   .display("[hour]:[minute]")
 /// ```
 ///
 /// ## Format
@@ -208,6 +246,9 @@ cast! {
 /// * If you specify all of year, month, day, hour, minute and second, Typst
 ///   will store a full datetime.
 ///
+/// The same applies to parsed strings: `{"2023-02-01"}` results in a date,
+/// `{"15:38:57"}` in a time and `{"2023-02-01T15:38:57"}` in a full datetime.
+///
 /// Depending on how it is stored, the [`display`]($type/datetime.display)
 /// method will choose a different formatting by default.
 ///
@@ -219,6 +260,19 @@ cast! {
     scope
 )]
 pub fn datetime(
+    /// An ISO 8601 string to parse the datetime from.
+    ///
+    /// Accepts dates of the form `YYYY-MM-DD`, times of the form
+    /// `HH:MM:SS` (seconds are optional) and combinations of both separated
+    /// by a `T` or a space. If this string is given, the individual
+    /// components should not be given.
+    ///
+    /// ```example
+    /// #datetime("2023-05-17").display() \
+    /// #datetime("08:15").display()
+    /// ```
+    #[default]
+    iso: Option<EcoString>,
     /// The year of the datetime.
     #[named]
     year: Option<YearComponent>,
@@ -238,6 +292,19 @@ pub fn datetime(
     #[named]
     second: Option<SecondComponent>,
 ) -> StrResult<Datetime> {
+    if let Some(iso) = iso {
+        if year.is_some()
+            || month.is_some()
+            || day.is_some()
+            || hour.is_some()
+            || minute.is_some()
+            || second.is_some()
+        {
+            bail!("cannot combine an ISO string with individual components");
+        }
+        return Datetime::from_iso(&iso);
+    }
+
     let time = match (hour, minute, second) {
         (Some(hour), Some(minute), Some(second)) => {
             match time::Time::from_hms(hour.0, minute.0, second.0) {
@@ -338,6 +405,90 @@ pub fn datetime_today(
         .ok_or("unable to get the current date")?)
 }
 
+/// Creates a new duration.
+///
+/// A [duration]($type/duration) is a span of time. You can create a duration
+/// from weeks, days, hours, minutes, and seconds or get one by subtracting two
+/// [datetimes]($type/datetime) of the same kind. Durations can be added to or
+/// subtracted from datetimes.
+///
+/// ## Example { #example }
+/// ```example
+/// #let start = datetime(year: 2023, month: 8, day: 1)
+/// #let end = datetime(year: 2023, month: 8, day: 15)
+/// #let span = end - start
+///
+/// #span.days() days \
+/// #(start + duration(weeks: 1)).display() \
+/// #duration(hours: 1, minutes: 30).display()
+/// ```
+///
+/// Display: Duration
+/// Category: construct
+#[func]
+pub fn duration(
+    /// The number of weeks.
+    #[named]
+    #[default(0)]
+    weeks: i64,
+    /// The number of days.
+    #[named]
+    #[default(0)]
+    days: i64,
+    /// The number of hours.
+    #[named]
+    #[default(0)]
+    hours: i64,
+    /// The number of minutes.
+    #[named]
+    #[default(0)]
+    minutes: i64,
+    /// The number of seconds.
+    #[named]
+    #[default(0)]
+    seconds: i64,
+) -> StrResult<Duration> {
+    Duration::from_components(weeks, days, hours, minutes, seconds)
+}
+
+/// Creates a decimal number.
+///
+/// A [decimal]($type/decimal) represents numbers like `0.1` exactly and
+/// performs exact arithmetic with other decimals and integers. This makes it
+/// the right choice for monetary amounts, where the rounding errors of
+/// [floats]($type/float) are unacceptable. For this reason, decimals are best
+/// created from strings. Floats are converted through their shortest
+/// representation.
+///
+/// ## Example { #example }
+/// ```example
+/// #(0.1 + 0.2) \
+/// #(decimal("0.1") + decimal("0.2")) \
+/// #(decimal("19.99") * 3) \
+/// #(decimal("10") / 3).round(2)
+/// ```
+///
+/// Display: Decimal
+/// Category: construct
+#[func]
+pub fn decimal(
+    /// The value that should be converted to a decimal.
+    value: ToDecimal,
+) -> Decimal {
+    value.0
+}
+
+/// A value that can be cast to a decimal.
+pub struct ToDecimal(Decimal);
+
+cast! {
+    ToDecimal,
+    v: i64 => Self(Decimal::from_int(v)),
+    v: f64 => Self(Decimal::from_float(v)?),
+    v: Decimal => Self(v),
+    v: EcoString => Self(Decimal::parse(&v)?),
+}
+
 /// Creates a CMYK color.
 ///
 /// This is useful if you want to target a specific printer. The conversion
@@ -363,17 +514,24 @@ pub fn cmyk(
     yellow: RatioComponent,
     /// The key component.
     key: RatioComponent,
+    /// The alpha component.
+    #[default(Component(255))]
+    alpha: Component,
 ) -> Color {
-    CmykColor::new(cyan.0, magenta.0, yellow.0, key.0).into()
+    CmykColor::new(cyan.0, magenta.0, yellow.0, key.0)
+        .with_alpha(alpha.0)
+        .into()
 }
 
 /// A component that must be a ratio.
-pub struct RatioComponent(u8);
+///
+/// Holds both the 8-bit quantized and the exact value.
+pub struct RatioComponent(u8, f64);
 
 cast! {
     RatioComponent,
     v: Ratio => if (0.0 ..= 1.0).contains(&v.get()) {
-        Self((v.get() * 255.0).round() as u8)
+        Self((v.get() * 255.0).round() as u8, v.get())
     } else {
         bail!("ratio must be between 0% and 100%");
     },
@@ -383,9 +541,100 @@ cast! {
 pub fn color_module() -> Module {
     let mut scope = Scope::new();
     scope.define("mix", mix_func());
+    scope.define("hsl", hsl_func());
+    scope.define("hsv", hsv_func());
+    scope.define("oklab", oklab_func());
     Module::new("color").with_scope(scope)
 }
 
+/// Creates a color from hue, saturation and lightness.
+///
+/// The color is converted to sRGB, so it can be used anywhere an
+/// [`rgb`]($func/rgb) color can.
+///
+/// ## Example
+/// ```example
+/// #for hue in range(0, 360, step: 60) {
+///   box(square(fill: color.hsl(hue * 1deg, 80%, 50%)))
+/// }
+/// ```
+///
+/// Display: HSL
+/// Category: construct
+#[func]
+pub fn hsl(
+    /// The hue angle.
+    hue: Angle,
+    /// The saturation component.
+    saturation: RatioComponent,
+    /// The lightness component.
+    lightness: RatioComponent,
+    /// The alpha component.
+    #[default(Component(255))]
+    alpha: Component,
+) -> Color {
+    RgbaColor::from_hsl(hue, saturation.1, lightness.1, alpha.0).into()
+}
+
+/// Creates a color from hue, saturation and value.
+///
+/// The color is converted to sRGB, so it can be used anywhere an
+/// [`rgb`]($func/rgb) color can.
+///
+/// ## Example
+/// ```example
+/// #for value in range(20, 101, step: 20) {
+///   box(square(fill: color.hsv(200deg, 70%, value * 1%)))
+/// }
+/// ```
+///
+/// Display: HSV
+/// Category: construct
+#[func]
+pub fn hsv(
+    /// The hue angle.
+    hue: Angle,
+    /// The saturation component.
+    saturation: RatioComponent,
+    /// The value component.
+    value: RatioComponent,
+    /// The alpha component.
+    #[default(Component(255))]
+    alpha: Component,
+) -> Color {
+    RgbaColor::from_hsv(hue, saturation.1, value.1, alpha.0).into()
+}
+
+/// Creates a color in the perceptual Oklab color space.
+///
+/// Equal steps in lightness look equally large, which makes this space well
+/// suited for defining palettes. The color is converted to sRGB, so it can be
+/// used anywhere an [`rgb`]($func/rgb) color can.
+///
+/// ## Example
+/// ```example
+/// #for l in range(30, 91, step: 15) {
+///   box(square(fill: color.oklab(l * 1%, 0.1, -0.1)))
+/// }
+/// ```
+///
+/// Display: Oklab
+/// Category: construct
+#[func]
+pub fn oklab(
+    /// The perceived lightness.
+    lightness: RatioComponent,
+    /// The green-red component, typically between `{-0.4}` and `{0.4}`.
+    a: f64,
+    /// The blue-yellow component, typically between `{-0.4}` and `{0.4}`.
+    b: f64,
+    /// The alpha component.
+    #[default(Component(255))]
+    alpha: Component,
+) -> Color {
+    RgbaColor::from_oklab(lightness.1, a, b, alpha.0).into()
+}
+
 /// Create a color by mixing two or more colors.
 ///
 /// ## Example
@@ -484,8 +733,13 @@ cast! {
 ///
 /// - Integers are formatted in base 10. This can be overridden with the
 ///   optional `base` parameter.
-/// - Floats are formatted in base 10 and never in exponential notation.
+/// - Floats are formatted in base 10 and never in exponential notation unless
+///   `scientific` is set.
 /// - From labels the name is extracted.
+/// - Bytes are decoded as UTF-8.
+///
+/// Numbers can additionally be formatted with a fixed number of decimal
+/// places, a thousands separator, and a custom decimal separator.
 ///
 /// If you wish to convert from and to Unicode code points, see
 /// [`str.to-unicode`]($func/str.to-unicode) and
@@ -497,7 +751,9 @@ cast! {
 /// #str(4000, base: 16) \
 /// #str(2.7) \
 /// #str(1e8) \
-/// #str(<intro>)
+/// #str(<intro>) \
+/// #str(1234567.891, digits: 2, thousands: " ", decimal: ",") \
+/// #str(0.000123, scientific: true)
 /// ```
 ///
 /// Display: String
@@ -510,24 +766,90 @@ cast! {
 )]
 pub fn str(
     /// The value that should be converted to a string.
-    value: ToStr,
+    value: Spanned<ToStr>,
     /// The base (radix) to display integers in, between 2 and 36.
     #[named]
     #[default(Spanned::new(10, Span::detached()))]
     base: Spanned<i64>,
+    /// The number of digits to display after the decimal point. Numbers are
+    /// rounded or padded with zeros as necessary.
+    #[named]
+    #[default]
+    digits: Option<usize>,
+    /// A separator to insert between groups of three digits in the integer
+    /// part of a number.
+    #[named]
+    #[default]
+    thousands: Option<EcoString>,
+    /// The separator between the integer and the fractional part of a number.
+    #[named]
+    #[default]
+    decimal: Option<EcoString>,
+    /// Whether to display a number in scientific notation.
+    #[named]
+    #[default(false)]
+    scientific: bool,
 ) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let format = NumberFormat { digits, thousands, decimal, scientific };
     Ok(match value {
         ToStr::Str(s) => {
             if base.v != 10 {
                 bail!(base.span, "base is only supported for integers");
             }
+            if !format.is_plain() {
+                bail!(span, "number formatting is only supported for numbers");
+            }
             s
         }
         ToStr::Int(n) => {
             if base.v < 2 || base.v > 36 {
                 bail!(base.span, "base must be between 2 and 36");
             }
-            int_to_base(n, base.v).into()
+            if base.v != 10 {
+                if !format.is_plain() {
+                    bail!(base.span, "number formatting is only supported in base 10");
+                }
+                int_to_base(n, base.v).into()
+            } else if format.scientific {
+                let v = n as f64;
+                let text = match format.digits {
+                    None => eco_format!("{v:e}"),
+                    Some(digits) => eco_format!("{v:.digits$e}"),
+                };
+                format.apply(&text).into()
+            } else {
+                let mut text = eco_format!("{n}");
+                if let Some(digits) = format.digits.filter(|&d| d > 0) {
+                    text.push('.');
+                    text.push_str(&"0".repeat(digits));
+                }
+                format.apply(&text).into()
+            }
+        }
+        ToStr::Float(v) => {
+            if base.v != 10 {
+                bail!(base.span, "base is only supported for integers");
+            }
+            let text = match (format.scientific, format.digits) {
+                (false, None) => eco_format!("{v}"),
+                (false, Some(digits)) => eco_format!("{v:.digits$}"),
+                (true, None) => eco_format!("{v:e}"),
+                (true, Some(digits)) => eco_format!("{v:.digits$e}"),
+            };
+            format.apply(&text).into()
+        }
+        ToStr::Decimal(v) => {
+            if base.v != 10 {
+                bail!(base.span, "base is only supported for integers");
+            }
+            let text = match (format.scientific, format.digits) {
+                (false, None) => eco_format!("{v}"),
+                (false, Some(digits)) => eco_format!("{}", v.rescale(digits as u32)),
+                (true, None) => v.scientific(),
+                (true, Some(digits)) => eco_format!("{:.digits$e}", v.to_float()),
+            };
+            format.apply(&text).into()
         }
     })
 }
@@ -538,16 +860,125 @@ pub enum ToStr {
     Str(Str),
     /// An integer about to be formatted in a given base.
     Int(i64),
+    /// A float about to be formatted.
+    Float(f64),
+    /// A decimal about to be formatted.
+    Decimal(Decimal),
 }
 
 cast! {
     ToStr,
     v: i64 => Self::Int(v),
-    v: f64 => Self::Str(format_str!("{}", v)),
+    v: f64 => Self::Float(v),
+    v: Decimal => Self::Decimal(v),
     v: Label => Self::Str(v.0.into()),
+    v: Bytes => Self::Str(
+        std::str::from_utf8(&v)
+            .map_err(|_| "bytes are not valid utf-8")?
+            .into()
+    ),
     v: Str => Self::Str(v),
 }
 
+/// How to format a number in base 10.
+struct NumberFormat {
+    /// The number of digits after the decimal point.
+    digits: Option<usize>,
+    /// The separator between groups of three integer digits.
+    thousands: Option<EcoString>,
+    /// The decimal separator.
+    decimal: Option<EcoString>,
+    /// Whether to use scientific notation.
+    scientific: bool,
+}
+
+impl NumberFormat {
+    /// Whether no formatting option was given.
+    fn is_plain(&self) -> bool {
+        self.digits.is_none()
+            && self.thousands.is_none()
+            && self.decimal.is_none()
+            && !self.scientific
+    }
+
+    /// Apply the separators to a number formatted by Rust.
+    fn apply(&self, text: &str) -> EcoString {
+        let (sign, rest) = match text.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", text),
+        };
+
+        let split = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let (int, tail) = rest.split_at(split);
+
+        let mut out = EcoString::from(sign);
+        match &self.thousands {
+            Some(separator) if int.len() > 3 => {
+                for (i, c) in int.chars().enumerate() {
+                    if i > 0 && (int.len() - i) % 3 == 0 {
+                        out.push_str(separator);
+                    }
+                    out.push(c);
+                }
+            }
+            _ => out.push_str(int),
+        }
+
+        match (&self.decimal, tail.strip_prefix('.')) {
+            (Some(decimal), Some(fraction)) => {
+                out.push_str(decimal);
+                out.push_str(fraction);
+            }
+            _ => out.push_str(tail),
+        }
+
+        out
+    }
+}
+
+/// Converts a value to bytes.
+///
+/// - Strings are encoded in UTF-8.
+/// - Arrays of integers between `{0}` and `{255}` are converted directly.
+///
+/// Bytes are mostly useful to pass data to and from
+/// [plugins]($func/plugin).
+///
+/// ## Example { #example }
+/// ```example
+/// #bytes("Hello 😃") \
+/// #bytes((123, 160, 22, 0))
+/// ```
+///
+/// Display: Bytes
+/// Category: construct
+#[func]
+pub fn bytes(
+    /// The value that should be converted to bytes.
+    value: ToBytes,
+) -> Bytes {
+    value.0
+}
+
+/// A value that can be cast to bytes.
+pub struct ToBytes(Bytes);
+
+cast! {
+    ToBytes,
+    v: Str => Self(v.as_bytes().into()),
+    v: Array => Self(
+        v.into_iter()
+            .map(|item| match item {
+                Value::Int(byte @ 0..=255) => Ok(byte as u8),
+                Value::Int(_) => bail!("number must be between 0 and 255"),
+                v => bail!("expected integer, found {}", v.type_name()),
+            })
+            .collect::<StrResult<Vec<u8>>>()?
+            .into()
+    ),
+    v: Bytes => Self(v),
+}
+
 /// Format an integer in a base.
 fn int_to_base(mut n: i64, base: i64) -> EcoString {
     if n == 0 {
@@ -633,6 +1064,61 @@ cast! {
     },
 }
 
+/// Converts a value to a dictionary.
+///
+/// - Dictionaries are returned as-is.
+/// - Arrays must consist of pairs of a string key and a value, like the ones
+///   returned by the [`pairs`]($type/dictionary.pairs) method. Later pairs
+///   override earlier ones with the same key.
+///
+/// ## Example { #example }
+/// ```example
+/// #let prices = (apple: 1.5, pear: 2)
+/// #let doubled = prices
+///   .pairs()
+///   .map(((fruit, price)) => (fruit, price * 2))
+/// #dictionary.from-pairs(doubled)
+/// ```
+///
+/// Display: Dictionary
+/// Category: construct
+#[func]
+#[scope(
+    scope.define("from-pairs", dictionary_from_pairs_func());
+    scope
+)]
+pub fn dictionary(
+    /// The value that should be converted to a dictionary.
+    value: ToDict,
+) -> Dict {
+    value.0
+}
+
+/// A value that can be cast to a dictionary.
+pub struct ToDict(Dict);
+
+cast! {
+    ToDict,
+    v: Dict => Self(v),
+    v: Array => Self(Dict::from_pairs(v)?),
+}
+
+/// Creates a dictionary from an array of key-value pairs.
+///
+/// ```example
+/// #dictionary.from-pairs((("a", 1), ("b", 2)))
+/// ```
+///
+/// Display: Dictionary From Pairs
+/// Category: construct
+#[func]
+pub fn dictionary_from_pairs(
+    /// The pairs of a string key and a value.
+    pairs: Array,
+) -> StrResult<Dict> {
+    Dict::from_pairs(pairs)
+}
+
 /// Creates a label from a string.
 ///
 /// Inserting a label into content attaches it to the closest previous element
@@ -0,0 +1,78 @@
+//! Hashing of strings and bytes.
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use typst::eval::{Module, Scope};
+
+use super::encoding::hex_encode;
+use super::Readable;
+use crate::prelude::*;
+
+/// A module with hash functions.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("sha256", sha256_func());
+    scope.define("md5", md5_func());
+    scope.define("crc32", crc32_func());
+    Module::new("crypto").with_scope(scope)
+}
+
+/// Calculates the SHA-256 hash of a string or bytes.
+///
+/// The hash is returned as a lowercase hexadecimal string.
+///
+/// ## Example { #example }
+/// ```example
+/// #crypto.sha256("Typst")
+/// ```
+///
+/// Display: SHA-256
+/// Category: crypto
+#[func]
+pub fn sha256(
+    /// The data to hash. Strings are hashed in their UTF-8 encoding.
+    data: Readable,
+) -> Str {
+    hex_encode(Readable(Sha256::digest(&data.0).as_slice().into()))
+}
+
+/// Calculates the MD5 hash of a string or bytes.
+///
+/// The hash is returned as a lowercase hexadecimal string. MD5 is not
+/// cryptographically secure, but still useful for fingerprints and cache
+/// keys.
+///
+/// ## Example { #example }
+/// ```example
+/// #crypto.md5("Typst")
+/// ```
+///
+/// Display: MD5
+/// Category: crypto
+#[func]
+pub fn md5(
+    /// The data to hash. Strings are hashed in their UTF-8 encoding.
+    data: Readable,
+) -> Str {
+    hex_encode(Readable(Md5::digest(&data.0).as_slice().into()))
+}
+
+/// Calculates the CRC-32 checksum of a string or bytes.
+///
+/// The checksum is returned as an integer.
+///
+/// ## Example { #example }
+/// ```example
+/// #crypto.crc32("Typst") \
+/// #str(crypto.crc32("Typst"), base: 16)
+/// ```
+///
+/// Display: CRC-32
+/// Category: crypto
+#[func]
+pub fn crc32(
+    /// The data to checksum. Strings are hashed in their UTF-8 encoding.
+    data: Readable,
+) -> i64 {
+    crc32fast::hash(&data.0).into()
+}
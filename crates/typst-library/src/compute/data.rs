@@ -1,5 +1,6 @@
 use typst::diag::{format_xml_like_error, FileError};
-use typst::eval::Datetime;
+use typst::eval::{Datetime, Plugin};
+use typst::util::Bytes;
 
 use crate::prelude::*;
 
@@ -33,12 +34,60 @@ pub fn read(
     Ok(text.into())
 }
 
+/// A value that can be read as raw bytes: A string or bytes.
+pub struct Readable(pub Bytes);
+
+cast! {
+    Readable,
+    v: Str => Self(v.as_bytes().into()),
+    v: Bytes => Self(v),
+}
+
+/// Loads a WebAssembly plugin.
+///
+/// Plugins extend Typst with functions written in any language that compiles
+/// to WebAssembly. The functions exported by the plugin can be called as
+/// methods on the loaded plugin. They take any number of
+/// [bytes]($type/bytes) as arguments and return bytes. Plugins run in a
+/// sandbox and have no access to the file system or network.
+///
+/// A plugin must export its memory and follow a minimal protocol: Each
+/// function receives the lengths of its arguments as 32-bit integers. To get
+/// the argument data, it calls the imported function
+/// `typst_env.wasm_minimal_protocol_write_args_to_buffer` with a pointer to a
+/// buffer that can fit all arguments. To return data, it calls
+/// `typst_env.wasm_minimal_protocol_send_result_to_host` with a pointer and a
+/// length. The function itself returns `0` on success and `1` if the returned
+/// data is an error message.
+///
+/// ## Example { #example }
+/// ```example
+/// #let hello = plugin("hello.wasm")
+/// #str(hello.hello())
+/// ```
+///
+/// Display: Plugin
+/// Category: data-loading
+#[func]
+pub fn plugin(
+    /// Path to a WebAssembly file.
+    path: Spanned<EcoString>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> SourceResult<Plugin> {
+    let Spanned { v: path, span } = path;
+    let id = vm.location().join(&path).at(span)?;
+    let data = vm.world().file(id).at(span)?;
+    Plugin::new(data).at(span)
+}
+
 /// Reads structured data from a CSV file.
 ///
 /// The CSV file will be read and parsed into a 2-dimensional array of strings:
 /// Each row in the CSV file will be represented as an array of strings, and all
 /// rows will be collected into a single array. Header rows will not be
-/// stripped.
+/// stripped unless `header` is set to `{true}`, in which case each row is
+/// returned as a dictionary keyed by the column names from the first row.
 ///
 /// ## Example { #example }
 /// ```example
@@ -54,6 +103,10 @@ pub fn read(
 /// Display: CSV
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("encode", csv_encode_func());
+    scope
+)]
 pub fn csv(
     /// Path to a CSV file.
     path: Spanned<EcoString>,
@@ -62,6 +115,37 @@ pub fn csv(
     #[named]
     #[default]
     delimiter: Delimiter,
+    /// Whether the first row of the CSV file contains the column names. If
+    /// `{true}`, the first row is not returned as data. Instead, each other
+    /// row is returned as a dictionary mapping from column names to values.
+    ///
+    /// ```example
+    /// #let animals = csv("zoo.csv", header: true)
+    /// #for animal in animals [
+    ///   - #animal.Name is a #animal.Species
+    /// ]
+    /// ```
+    #[named]
+    #[default(false)]
+    header: bool,
+    /// Types to convert the columns to. Can either be an array with one type
+    /// per column or, if `header` is `{true}`, a dictionary mapping from
+    /// column names to types. Columns without a type remain strings.
+    ///
+    /// The supported types are `{"string"}`, `{"integer"}`, `{"float"}`, and
+    /// `{"boolean"}`. Empty fields in converted columns become `{none}`.
+    ///
+    /// ```example
+    /// #let planets = csv(
+    ///   "planets.csv",
+    ///   header: true,
+    ///   types: (moons: "integer"),
+    /// )
+    /// #planets.map(p => p.moons).sum() moons
+    /// ```
+    #[named]
+    #[default]
+    types: ColumnTypes,
     /// The virtual machine.
     vm: &mut Vm,
 ) -> SourceResult<Array> {
@@ -69,11 +153,16 @@ pub fn csv(
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
 
+    if !header && matches!(types, ColumnTypes::ByName(_)) {
+        bail!(span, "column types can only be given by name if the file has a header");
+    }
+
     let mut builder = csv::ReaderBuilder::new();
     builder.has_headers(false);
     builder.delimiter(delimiter.0 as u8);
 
     let mut reader = builder.from_reader(data.as_slice());
+    let mut names: Option<Vec<Str>> = None;
     let mut array = Array::new();
 
     for (line, result) in reader.records().enumerate() {
@@ -82,13 +171,128 @@ pub fn csv(
         // https://github.com/BurntSushi/rust-csv/issues/184
         let line = line + 1; // Counting lines from 1
         let row = result.map_err(|err| format_csv_error(err, line)).at(span)?;
-        let sub = row.into_iter().map(|field| field.into_value()).collect();
-        array.push(Value::Array(sub))
+
+        if header && names.is_none() {
+            names = Some(row.iter().map(Str::from).collect());
+            continue;
+        }
+
+        let mut fields = Vec::with_capacity(row.len());
+        for (i, field) in row.iter().enumerate() {
+            let name = names.as_ref().and_then(|names| names.get(i));
+            let value = match types.get(i, name) {
+                Some(ty) => ty.convert(field, line).at(span)?,
+                None => field.into_value(),
+            };
+            fields.push(value);
+        }
+
+        array.push(match &names {
+            Some(names) => Value::Dict(names.iter().cloned().zip(fields).collect()),
+            None => Value::Array(fields.into_iter().collect()),
+        });
     }
 
     Ok(array)
 }
 
+/// Types to convert CSV columns to.
+#[derive(Default)]
+pub enum ColumnTypes {
+    /// No conversion at all.
+    #[default]
+    None,
+    /// One type per column index.
+    ByIndex(Vec<ColumnType>),
+    /// Types for the columns with the given names.
+    ByName(Vec<(Str, ColumnType)>),
+}
+
+impl ColumnTypes {
+    /// The type for the column with the given index and name.
+    fn get(&self, index: usize, name: Option<&Str>) -> Option<ColumnType> {
+        match self {
+            Self::None => None,
+            Self::ByIndex(types) => types.get(index).copied(),
+            Self::ByName(types) => types
+                .iter()
+                .find(|(column, _)| Some(column) == name)
+                .map(|&(_, ty)| ty),
+        }
+    }
+}
+
+cast! {
+    ColumnTypes,
+    self => match self {
+        Self::None => Value::None,
+        Self::ByIndex(types) => {
+            types.into_iter().map(IntoValue::into_value).collect::<Array>().into_value()
+        }
+        Self::ByName(types) => types
+            .into_iter()
+            .map(|(name, ty)| (name, ty.into_value()))
+            .collect::<Dict>()
+            .into_value(),
+    },
+    v: Array => Self::ByIndex(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+    v: Dict => Self::ByName(
+        v.into_iter()
+            .map(|(name, ty)| Ok((name, ty.cast()?)))
+            .collect::<StrResult<_>>()?
+    ),
+}
+
+/// A type to convert a CSV column to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ColumnType {
+    /// Keep the field as a string.
+    #[string("string")]
+    Str,
+    /// Parse the field as an integer.
+    #[string("integer")]
+    Int,
+    /// Parse the field as a float.
+    #[string("float")]
+    Float,
+    /// Parse the field as a boolean.
+    #[string("boolean")]
+    Bool,
+}
+
+impl ColumnType {
+    /// Convert a field to this type.
+    fn convert(self, field: &str, line: usize) -> StrResult<Value> {
+        let trimmed = field.trim();
+        if trimmed.is_empty() && !matches!(self, Self::Str) {
+            return Ok(Value::None);
+        }
+
+        let converted = match self {
+            Self::Str => Some(field.into_value()),
+            Self::Int => trimmed.parse::<i64>().ok().map(Value::Int),
+            Self::Float => trimmed.parse::<f64>().ok().map(Value::Float),
+            Self::Bool => match trimmed {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+        };
+
+        converted.ok_or_else(|| {
+            let name = match self {
+                Self::Str => "string",
+                Self::Int => "integer",
+                Self::Float => "float",
+                Self::Bool => "boolean",
+            };
+            eco_format!(
+                "failed to parse csv file: cannot convert {field:?} to {name} in line {line}"
+            )
+        })
+    }
+}
+
 /// The delimiter to use when parsing CSV files.
 pub struct Delimiter(char);
 
@@ -129,6 +333,108 @@ fn format_csv_error(error: csv::Error, line: usize) -> EcoString {
     }
 }
 
+/// Encodes structured data into a CSV string.
+///
+/// The data must be an array of rows. Each row is either an array of fields or
+/// a dictionary. If the rows are dictionaries, the keys of the first row are
+/// written as a header and the other rows are expected to have the same keys.
+///
+/// ## Example { #example }
+/// ```example
+/// #csv.encode((
+///   ("Name", "Weight"),
+///   ("Debby", 1900),
+///   ("Fluffy", 115),
+/// ))
+/// ```
+///
+/// Display: Encode CSV
+/// Category: data-loading
+#[func]
+pub fn csv_encode(
+    /// The rows to encode.
+    value: Spanned<Array>,
+    /// The delimiter that separates columns in the CSV string.
+    /// Must be a single ASCII character.
+    #[named]
+    #[default]
+    delimiter: Delimiter,
+) -> SourceResult<Str> {
+    let Spanned { v: rows, span } = value;
+
+    let mut builder = csv::WriterBuilder::new();
+    builder.delimiter(delimiter.0 as u8);
+    let mut writer = builder.from_writer(vec![]);
+    let mut header: Option<Vec<Str>> = None;
+
+    for row in rows {
+        let fields: Vec<Value> = match row {
+            Value::Array(fields) => fields.into_iter().collect(),
+            Value::Dict(dict) => {
+                if header.is_none() {
+                    let keys: Vec<Str> =
+                        dict.iter().map(|(key, _)| key.clone()).collect();
+                    write_csv_record(&mut writer, &keys).at(span)?;
+                    header = Some(keys);
+                }
+
+                let keys = header.as_ref().unwrap();
+                keys.iter()
+                    .map(|key| dict.at(key, None).cloned())
+                    .collect::<StrResult<_>>()
+                    .at(span)?
+            }
+            v => bail!(
+                span,
+                "expected array or dictionary as row, found {}",
+                v.type_name()
+            ),
+        };
+
+        let fields = fields
+            .into_iter()
+            .map(csv_field)
+            .collect::<StrResult<Vec<_>>>()
+            .at(span)?;
+        write_csv_record(&mut writer, &fields).at(span)?;
+    }
+
+    let data = writer
+        .into_inner()
+        .map_err(|_| "failed to encode value as csv")
+        .at(span)?;
+    Ok(std::str::from_utf8(&data)
+        .map_err(|_| "failed to encode value as csv")
+        .at(span)?
+        .into())
+}
+
+/// Write a single record with a CSV writer.
+fn write_csv_record<T: AsRef<str>>(
+    writer: &mut csv::Writer<Vec<u8>>,
+    fields: &[T],
+) -> StrResult<()> {
+    writer
+        .write_record(fields.iter().map(|field| field.as_ref()))
+        .map_err(|err| eco_format!("failed to encode value as csv: {err}"))
+}
+
+/// Convert a Typst value to a CSV field.
+fn csv_field(value: Value) -> StrResult<EcoString> {
+    Ok(match value {
+        Value::None => EcoString::new(),
+        Value::Bool(v) => eco_format!("{v}"),
+        Value::Int(v) => eco_format!("{v}"),
+        Value::Float(v) => eco_format!("{v}"),
+        Value::Str(v) => v.into(),
+        Value::Symbol(v) => eco_format!("{}", v.get()),
+        Value::Dyn(v) if v.is::<Datetime>() => {
+            v.downcast::<Datetime>().unwrap().display(None, None, None)?
+        }
+        v => bail!("cannot encode {} as csv", v.type_name()),
+    })
+}
+
 /// Reads structured data from a JSON file.
 ///
 /// The file must contain a valid JSON object or array. JSON objects will be
@@ -170,6 +476,10 @@ fn format_csv_error(error: csv::Error, line: usize) -> EcoString {
 /// Display: JSON
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("encode", json_encode_func());
+    scope
+)]
 pub fn json(
     /// Path to a JSON file.
     path: Spanned<EcoString>,
@@ -211,6 +521,71 @@ fn format_json_error(error: serde_json::Error) -> EcoString {
     eco_format!("failed to parse json file: syntax error in line {}", error.line())
 }
 
+/// Encodes structured data into a JSON string.
+///
+/// Dictionaries become JSON objects and arrays become JSON arrays. Strings,
+/// numbers, and booleans are converted into their JSON equivalents, `{none}`
+/// becomes `null`, and datetimes are written as strings.
+///
+/// ## Example { #example }
+/// ```example
+/// #json.encode((name: "Debby", weight: 1900))
+/// ```
+///
+/// Display: Encode JSON
+/// Category: data-loading
+#[func]
+pub fn json_encode(
+    /// The value to encode.
+    value: Spanned<Value>,
+    /// Whether to pretty print the JSON with newlines and indentation.
+    #[named]
+    #[default(true)]
+    pretty: bool,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let value = value_to_json(value).at(span)?;
+    let text = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+
+    Ok(text
+        .map_err(|err| eco_format!("failed to encode value as json: {err}"))
+        .at(span)?
+        .into())
+}
+
+/// Convert a Typst value to a JSON value.
+fn value_to_json(value: Value) -> StrResult<serde_json::Value> {
+    Ok(match value {
+        Value::None => serde_json::Value::Null,
+        Value::Bool(v) => v.into(),
+        Value::Int(v) => v.into(),
+        Value::Float(v) => serde_json::Number::from_f64(v)
+            .ok_or("cannot encode non-finite float as json")?
+            .into(),
+        Value::Str(v) => String::from(v).into(),
+        Value::Symbol(v) => v.get().to_string().into(),
+        Value::Array(v) => serde_json::Value::Array(
+            v.into_iter().map(value_to_json).collect::<StrResult<_>>()?,
+        ),
+        Value::Dict(v) => serde_json::Value::Object(
+            v.into_iter()
+                .map(|(key, value)| Ok((key.into(), value_to_json(value)?)))
+                .collect::<StrResult<_>>()?,
+        ),
+        Value::Dyn(v) if v.is::<Datetime>() => v
+            .downcast::<Datetime>()
+            .unwrap()
+            .display(None, None, None)?
+            .to_string()
+            .into(),
+        v => bail!("cannot encode {} as json", v.type_name()),
+    })
+}
+
 /// Reads structured data from a TOML file.
 ///
 /// The file must contain a valid TOML table. TOML tables will be
@@ -236,6 +611,10 @@ fn format_json_error(error: serde_json::Error) -> EcoString {
 /// Display: TOML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("encode", toml_encode_func());
+    scope
+)]
 pub fn toml(
     /// Path to a TOML file.
     path: Spanned<EcoString>,
@@ -304,6 +683,80 @@ fn format_toml_error(error: toml::de::Error) -> EcoString {
     }
 }
 
+/// Encodes structured data into a TOML string.
+///
+/// The value must be a dictionary, which becomes the top-level table of the
+/// TOML document. Datetimes are written as TOML datetimes. Since TOML has no
+/// equivalent of `{none}`, it cannot be encoded.
+///
+/// ## Example { #example }
+/// ```example
+/// #raw(toml.encode((
+///   title: "Zoo",
+///   animals: ("Debby", "Fluffy"),
+/// )), lang: "toml")
+/// ```
+///
+/// Display: Encode TOML
+/// Category: data-loading
+#[func]
+pub fn toml_encode(
+    /// The dictionary to encode.
+    value: Spanned<Dict>,
+    /// Whether to pretty print arrays over multiple lines.
+    #[named]
+    #[default(true)]
+    pretty: bool,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let value = value_to_toml(Value::Dict(value)).at(span)?;
+    let text =
+        if pretty { toml::to_string_pretty(&value) } else { toml::to_string(&value) };
+    Ok(text
+        .map_err(|err| eco_format!("failed to encode value as toml: {err}"))
+        .at(span)?
+        .into())
+}
+
+/// Convert a Typst value to a TOML value.
+fn value_to_toml(value: Value) -> StrResult<toml::Value> {
+    Ok(match value {
+        Value::Bool(v) => v.into(),
+        Value::Int(v) => v.into(),
+        Value::Float(v) => v.into(),
+        Value::Str(v) => String::from(v).into(),
+        Value::Symbol(v) => v.get().to_string().into(),
+        Value::Array(v) => toml::Value::Array(
+            v.into_iter().map(value_to_toml).collect::<StrResult<_>>()?,
+        ),
+        Value::Dict(v) => toml::Value::Table(
+            v.into_iter()
+                .map(|(key, value)| Ok((key.into(), value_to_toml(value)?)))
+                .collect::<StrResult<_>>()?,
+        ),
+        Value::Dyn(v) if v.is::<Datetime>() => {
+            let datetime = v.downcast::<Datetime>().unwrap();
+            let date = match (datetime.year(), datetime.month(), datetime.day()) {
+                (Some(year), Some(month), Some(day)) => Some(toml::value::Date {
+                    year: u16::try_from(year)
+                        .map_err(|_| "cannot encode this year as toml")?,
+                    month,
+                    day,
+                }),
+                _ => None,
+            };
+            let time = match (datetime.hour(), datetime.minute(), datetime.second()) {
+                (Some(hour), Some(minute), Some(second)) => {
+                    Some(toml::value::Time { hour, minute, second, nanosecond: 0 })
+                }
+                _ => None,
+            };
+            toml::Value::Datetime(toml::value::Datetime { date, time, offset: None })
+        }
+        v => bail!("cannot encode {} as toml", v.type_name()),
+    })
+}
+
 /// Reads structured data from a YAML file.
 ///
 /// The file must contain a valid YAML object or array. YAML mappings will be
@@ -345,6 +798,10 @@ fn format_toml_error(error: toml::de::Error) -> EcoString {
 /// Display: YAML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("encode", yaml_encode_func());
+    scope
+)]
 pub fn yaml(
     /// Path to a YAML file.
     path: Spanned<EcoString>,
@@ -396,8 +853,67 @@ fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
     eco_format!("failed to parse yaml file: {}", error.to_string().trim())
 }
 
+/// Encodes structured data into a YAML string.
+///
+/// Dictionaries become YAML mappings and arrays become YAML sequences.
+/// Strings, numbers, and booleans are converted into their YAML equivalents,
+/// `{none}` becomes `null`, and datetimes are written as strings.
+///
+/// ## Example { #example }
+/// ```example
+/// #raw(yaml.encode((
+///   name: "Debby",
+///   species: "Rhinoceros",
+/// )), lang: "yaml")
+/// ```
+///
+/// Display: Encode YAML
+/// Category: data-loading
+#[func]
+pub fn yaml_encode(
+    /// The value to encode.
+    value: Spanned<Value>,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let value = value_to_yaml(value).at(span)?;
+    let text = serde_yaml::to_string(&value)
+        .map_err(|err| eco_format!("failed to encode value as yaml: {err}"))
+        .at(span)?;
+    Ok(text.strip_prefix("---\n").unwrap_or(&text).into())
+}
+
+/// Convert a Typst value to a YAML value.
+fn value_to_yaml(value: Value) -> StrResult<serde_yaml::Value> {
+    Ok(match value {
+        Value::None => serde_yaml::Value::Null,
+        Value::Bool(v) => v.into(),
+        Value::Int(v) => v.into(),
+        Value::Float(v) => v.into(),
+        Value::Str(v) => String::from(v).into(),
+        Value::Symbol(v) => v.get().to_string().into(),
+        Value::Array(v) => serde_yaml::Value::Sequence(
+            v.into_iter().map(value_to_yaml).collect::<StrResult<_>>()?,
+        ),
+        Value::Dict(v) => serde_yaml::Value::Mapping(
+            v.into_iter()
+                .map(|(key, value)| Ok((String::from(key).into(), value_to_yaml(value)?)))
+                .collect::<StrResult<_>>()?,
+        ),
+        Value::Dyn(v) if v.is::<Datetime>() => v
+            .downcast::<Datetime>()
+            .unwrap()
+            .display(None, None, None)?
+            .to_string()
+            .into(),
+        v => bail!("cannot encode {} as yaml", v.type_name()),
+    })
+}
+
 /// Reads structured data from an XML file.
 ///
+/// To find elements in the parsed structure without walking through it by
+/// hand, you can use [`xml.select`]($func/xml.select).
+///
 /// The XML file is parsed into an array of dictionaries and strings. XML nodes
 /// can be elements or strings. Elements are represented as dictionaries with
 /// the the following keys:
@@ -448,6 +964,10 @@ fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
 /// Display: XML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("select", xml_select_func());
+    scope
+)]
 pub fn xml(
     /// Path to an XML file.
     path: Spanned<EcoString>,
@@ -490,3 +1010,86 @@ fn convert_xml(node: roxmltree::Node) -> Value {
 fn format_xml_error(error: roxmltree::Error) -> EcoString {
     format_xml_like_error("xml file", error)
 }
+
+/// Selects elements from parsed XML data by their path.
+///
+/// The path consists of tag names separated by slashes. Starting from the
+/// given nodes, each segment selects the child elements with that tag name.
+/// A `*` segment matches elements with any tag name. If the last segment
+/// starts with an `@`, the values of the attribute with that name are
+/// selected instead of the elements themselves.
+///
+/// ## Example { #example }
+/// ```example
+/// #let data = xml("example.xml")
+/// #for title in xml.select(data, "news/article/title") [
+///   - #title.children.first()
+/// ]
+/// ```
+///
+/// Display: Select XML
+/// Category: data-loading
+#[func]
+pub fn xml_select(
+    /// The nodes to start from. This can either be the data returned by
+    /// [`xml`]($func/xml) or a single element.
+    nodes: XmlNodes,
+    /// The path of the elements or attribute to select.
+    path: EcoString,
+) -> StrResult<Array> {
+    let mut current = nodes.0;
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty()).peekable();
+    while let Some(segment) = segments.next() {
+        if let Some(attr) = segment.strip_prefix('@') {
+            if segments.peek().is_some() {
+                bail!("attribute selector must be the last segment of the path");
+            }
+
+            return Ok(current
+                .iter()
+                .filter_map(|node| xml_field(node, "attrs"))
+                .filter_map(|attrs| match attrs {
+                    Value::Dict(attrs) => attrs.at(attr, None).ok().cloned(),
+                    _ => None,
+                })
+                .collect());
+        }
+
+        current = current
+            .iter()
+            .filter_map(|node| xml_field(node, "children"))
+            .filter_map(|children| match children {
+                Value::Array(children) => Some(children.iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter(|child| match xml_field(child, "tag") {
+                Some(Value::Str(tag)) => segment == "*" || tag.as_str() == segment,
+                _ => false,
+            })
+            .cloned()
+            .collect();
+    }
+
+    Ok(current)
+}
+
+/// The elements to start an XML selection from.
+///
+/// The data returned by `xml` is wrapped into a virtual root element such that
+/// the first segment of the path selects among its top-level elements.
+pub struct XmlNodes(Array);
+
+cast! {
+    XmlNodes,
+    v: Array => Self(array![dict! { "children" => v }]),
+    v: Dict => Self(array![v]),
+}
+
+/// Access a field of an XML element.
+fn xml_field<'a>(node: &'a Value, key: &str) -> Option<&'a Value> {
+    match node {
+        Value::Dict(dict) => dict.at(key, None).ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,137 @@
+//! Encoding and decoding of bytes as text.
+
+use base64::Engine;
+use typst::eval::{Module, Scope};
+use typst::util::Bytes;
+
+use super::Readable;
+use crate::prelude::*;
+
+/// A module with functions for Base64 encoding.
+pub fn base64_module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("encode", base64_encode_func());
+    scope.define("decode", base64_decode_func());
+    Module::new("base64").with_scope(scope)
+}
+
+/// A module with functions for hexadecimal encoding.
+pub fn hex_module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("encode", hex_encode_func());
+    scope.define("decode", hex_decode_func());
+    Module::new("hex").with_scope(scope)
+}
+
+/// Encodes a string or bytes in Base64.
+///
+/// ## Example { #example }
+/// ```example
+/// #base64.encode("Typst") \
+/// #base64.encode(bytes((0, 255)))
+/// ```
+///
+/// Display: Base64 Encode
+/// Category: encoding
+#[func]
+pub fn base64_encode(
+    /// The data to encode. Strings are encoded in UTF-8 first.
+    data: Readable,
+    /// Whether to use the URL- and filename-safe alphabet, which uses `-` and
+    /// `_` instead of `+` and `/`.
+    #[named]
+    #[default(false)]
+    url_safe: bool,
+) -> Str {
+    base64_engine(url_safe).encode(data.0.as_slice()).into()
+}
+
+/// Decodes Base64 into bytes.
+///
+/// Padding with `=` is optional.
+///
+/// ## Example { #example }
+/// ```example
+/// #str(base64.decode("VHlwc3Q="))
+/// ```
+///
+/// Display: Base64 Decode
+/// Category: encoding
+#[func]
+pub fn base64_decode(
+    /// The Base64 string to decode.
+    text: Str,
+    /// Whether the string uses the URL- and filename-safe alphabet.
+    #[named]
+    #[default(false)]
+    url_safe: bool,
+) -> StrResult<Bytes> {
+    base64_engine(url_safe)
+        .decode(text.as_str())
+        .map(Bytes::from)
+        .map_err(|err| eco_format!("failed to decode base64 ({err})"))
+}
+
+/// The Base64 engine for the given alphabet.
+fn base64_engine(url_safe: bool) -> base64::engine::GeneralPurpose {
+    let config = base64::engine::GeneralPurposeConfig::new()
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent);
+    let alphabet =
+        if url_safe { &base64::alphabet::URL_SAFE } else { &base64::alphabet::STANDARD };
+    base64::engine::GeneralPurpose::new(alphabet, config)
+}
+
+/// Encodes a string or bytes as lowercase hexadecimal digits.
+///
+/// ## Example { #example }
+/// ```example
+/// #hex.encode("Typst") \
+/// #hex.encode(bytes((0, 255)))
+/// ```
+///
+/// Display: Hex Encode
+/// Category: encoding
+#[func]
+pub fn hex_encode(
+    /// The data to encode. Strings are encoded in UTF-8 first.
+    data: Readable,
+) -> Str {
+    let mut out = EcoString::with_capacity(2 * data.0.len());
+    for byte in data.0.iter() {
+        out.push_str(&eco_format!("{byte:02x}"));
+    }
+    out.into()
+}
+
+/// Decodes hexadecimal digits into bytes.
+///
+/// Both lowercase and uppercase digits are accepted.
+///
+/// ## Example { #example }
+/// ```example
+/// #str(hex.decode("5479707374"))
+/// ```
+///
+/// Display: Hex Decode
+/// Category: encoding
+#[func]
+pub fn hex_decode(
+    /// The hexadecimal string to decode.
+    text: Str,
+) -> StrResult<Bytes> {
+    if text.len() % 2 != 0 {
+        bail!("hexadecimal string must have an even number of digits");
+    }
+
+    let digit = |c: u8| {
+        (c as char)
+            .to_digit(16)
+            .ok_or_else(|| eco_format!("invalid hexadecimal digit: {:?}", c as char))
+    };
+
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? * 16 + digit(pair[1])?) as u8))
+        .collect::<StrResult<Vec<u8>>>()
+        .map(Bytes::from)
+}
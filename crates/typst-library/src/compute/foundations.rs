@@ -4,7 +4,10 @@ use crate::prelude::*;
 
 /// Determines the type of a value.
 ///
-/// Returns the name of the value's type.
+/// Returns the name of the value's type as a string. Compare it with another
+/// type name like `{"integer"}` rather than with a function like `int`. To
+/// check against several types or against an element function, use
+/// [`type.is`]($func/type.is).
 ///
 /// ## Example { #example }
 /// ```example
@@ -13,12 +16,17 @@ use crate::prelude::*;
 /// #type("hello") \
 /// #type(none) \
 /// #type([Hi]) \
-/// #type(x => x + 1)
+/// #type(x => x + 1) \
+/// #(type(12) == "integer")
 /// ```
 ///
 /// Display: Type
 /// Category: foundations
 #[func]
+#[scope(
+    scope.define("is", type_is_func());
+    scope
+)]
 pub fn type_(
     /// The value whose type's to determine.
     value: Value,
@@ -26,6 +34,90 @@ pub fn type_(
     value.type_name().into()
 }
 
+/// Checks whether a value is of one of the given types.
+///
+/// Types are given by their name, as returned by [`type`]($func/type).
+/// Additionally, an element function matches content of that element, so that
+/// generic template functions can distinguish, for instance, headings from
+/// other content.
+///
+/// ## Example { #example }
+/// ```example
+/// #type.is(1, "integer", "float") \
+/// #type.is("a", "content") \
+/// #type.is([= Title], heading) \
+/// #type.is([Text], heading)
+/// ```
+///
+/// Display: Type Check
+/// Category: foundations
+#[func]
+pub fn type_is(
+    /// The value whose type to check.
+    value: Value,
+    /// The types to check against.
+    #[variadic]
+    types: Vec<TypeSpec>,
+) -> bool {
+    types.iter().any(|spec| match spec {
+        TypeSpec::Name(name) => value.type_name() == name.as_str(),
+        TypeSpec::Elem(elem) => {
+            matches!(&value, Value::Content(content) if content.func() == *elem)
+        }
+    })
+}
+
+/// A type to check a value against.
+pub enum TypeSpec {
+    /// A type by its name.
+    Name(Str),
+    /// An element function, matching content of that element.
+    Elem(ElemFunc),
+}
+
+cast! {
+    TypeSpec,
+    v: Str => Self::Name(v),
+    v: ElemFunc => Self::Elem(v),
+}
+
+/// Returns the fields of content or of an element function.
+///
+/// For content, returns a dictionary of the fields that are set on it, just
+/// like the content's [`fields`]($type/content.fields) method. For an element
+/// function, returns an array with the names of all fields the element
+/// supports.
+///
+/// ## Example { #example }
+/// ```example
+/// #fields([= Title]) \
+/// #fields(heading)
+/// ```
+///
+/// Display: Fields
+/// Category: foundations
+#[func]
+pub fn fields(
+    /// The content or element function to inspect.
+    value: Spanned<Value>,
+) -> SourceResult<Value> {
+    Ok(match value.v {
+        Value::Content(content) => content.dict().into_value(),
+        Value::Func(func) if func.element().is_some() => func
+            .info()
+            .map(|info| info.params.iter().map(|param| param.name.into_value()))
+            .into_iter()
+            .flatten()
+            .collect::<Array>()
+            .into_value(),
+        v => bail!(
+            value.span,
+            "expected content or element function, found {}",
+            v.type_name()
+        ),
+    })
+}
+
 /// Returns the string representation of a value.
 ///
 /// When inserted into content, most values are displayed as this representation
@@ -82,6 +174,68 @@ pub fn panic(
     Err(msg)
 }
 
+/// Emits a warning without failing.
+///
+/// The warning is shown in the compiler output alongside the location of the
+/// call, but compilation continues as usual. This is useful, for instance, to
+/// alert users of a template that they are using a deprecated option.
+///
+/// ## Example { #example }
+/// The code below produces the warning `the "color" option is deprecated`.
+/// ```typ
+/// #let note(body, color: none) = {
+///   if color != none {
+///     warn("the \"color\" option is deprecated")
+///   }
+///   block(body)
+/// }
+/// ```
+///
+/// Display: Warn
+/// Category: foundations
+#[func]
+pub fn warn(
+    /// The warning message.
+    message: EcoString,
+    /// The virtual machine.
+    vm: &mut Vm,
+    /// The callsite span.
+    span: Span,
+) -> NoneValue {
+    vm.vt.tracer.warn(warning!(span, "{}", message));
+    NoneValue
+}
+
+/// Suppresses warnings within a piece of content.
+///
+/// Warnings that the compiler or the [`warn`]($func/warn) function produce
+/// for code within the body are not shown. Each warning of the compiler has a
+/// kind, which is shown alongside it.
+///
+/// ## Example { #example }
+/// ```example
+/// #suppress-warnings("empty-strong")[
+///   This has no text within **stars.
+/// ]
+/// ```
+///
+/// Display: Suppress Warnings
+/// Category: foundations
+#[func]
+pub fn suppress_warnings(
+    /// The kinds of warnings to suppress, e.g. `{"deprecated"}`. If none are
+    /// given, all warnings are suppressed.
+    #[variadic]
+    kinds: Vec<EcoString>,
+    /// The content within which to suppress warnings.
+    body: Spanned<Content>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Content {
+    vm.vt.tracer.suppress(body.span, kinds.into_iter().collect());
+    body.v
+}
+
 /// Ensures that a condition is fulfilled.
 ///
 /// Fails with an error if the condition is not fulfilled. Does not
@@ -190,6 +344,57 @@ pub fn assert_ne(
     Ok(NoneValue)
 }
 
+/// Calls a function and catches any error it produces.
+///
+/// Returns a dictionary whose `ok` field indicates whether the call succeeded.
+/// On success, the `value` field holds the function's return value. On
+/// failure, the `error` field holds the message of the first error instead,
+/// while the `errors` field holds all errors as dictionaries with a `message`
+/// and an array of `hints`. This lets templates degrade gracefully, for
+/// instance when an optional data file is missing.
+///
+/// ## Example { #example }
+/// ```example
+/// #let parsed = catch(() => int("twelve"))
+/// #if parsed.ok [
+///   The number is #parsed.value.
+/// ] else [
+///   Not a number: #parsed.error
+/// ]
+///
+/// #let (value: v = 0) = catch(() => int("12"))
+/// The value is #v.
+/// ```
+///
+/// Display: Catch
+/// Category: foundations
+#[func]
+pub fn catch(
+    /// The function to call. It is called without any arguments.
+    body: Func,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Dict {
+    let args = Args::new::<Value>(body.span(), []);
+    match body.call_vm(vm, args) {
+        Ok(value) => dict! { "ok" => true, "value" => value },
+        Err(errors) => {
+            let message =
+                errors.first().map(|error| error.message.clone()).unwrap_or_default();
+            let errors: Array = errors
+                .iter()
+                .map(|error| {
+                    let hints: Array =
+                        error.hints.iter().cloned().map(IntoValue::into_value).collect();
+                    dict! { "message" => error.message.clone(), "hints" => hints }
+                        .into_value()
+                })
+                .collect();
+            dict! { "ok" => false, "error" => message, "errors" => errors }
+        }
+    }
+}
+
 /// Evaluates a string as Typst code.
 ///
 /// This function should only be used as a last resort.
@@ -245,3 +450,51 @@ pub fn eval(
     }
     typst::eval::eval_string(vm.world(), &text, span, mode, scope)
 }
+
+/// Calls the first case whose parameter pattern matches a value.
+///
+/// Each case is a function taking a single argument. Cases are tried in
+/// order and the first one whose
+/// [destructuring pattern]($scripting/#bindings) fits the structure of the
+/// value is called with it. A case with a plain parameter like `x` or `_`
+/// matches any value and can thus act as a fallback.
+///
+/// ## Example { #example }
+/// ```example
+/// #let describe(shape) = match(
+///   shape,
+///   ((radius: r)) => [Circle with radius #r],
+///   ((width: w, height: h = w)) => [Rectangle of #w × #h],
+///   ((x, y)) => [Point at (#x, #y)],
+///   _ => [Unknown shape],
+/// )
+///
+/// #describe((radius: 2cm)) \
+/// #describe((width: 3cm)) \
+/// #describe((1, 2)) \
+/// #describe(none)
+/// ```
+///
+/// Display: Match
+/// Category: foundations
+#[func]
+pub fn match_(
+    /// The value to match.
+    value: Value,
+    /// The cases to try, in order.
+    #[variadic]
+    cases: Vec<Func>,
+    /// The virtual machine.
+    vm: &mut Vm,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Value> {
+    for case in cases {
+        if case.accepts(&value) {
+            let args = Args::new(case.span(), [value]);
+            return case.call_vm(vm, args);
+        }
+    }
+
+    bail!(span, "no case matches {}", value.type_name())
+}
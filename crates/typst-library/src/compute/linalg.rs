@@ -0,0 +1,446 @@
+//! Linear algebra on matrices and vectors.
+
+use typst::eval::{Module, Scope};
+
+use super::calc::polynomial_roots;
+use crate::prelude::*;
+
+/// A module with functions for matrices and vectors.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("identity", identity_func());
+    scope.define("transpose", transpose_func());
+    scope.define("dot", dot_func());
+    scope.define("mul", mul_func());
+    scope.define("det", det_func());
+    scope.define("inv", inv_func());
+    scope.define("solve", solve_func());
+    scope.define("eigenvalues", eigenvalues_func());
+    Module::new("linalg").with_scope(scope)
+}
+
+/// Creates an identity matrix.
+///
+/// ## Example { #example }
+/// ```example
+/// $ #math.mat(..linalg.identity(3)) $
+/// ```
+///
+/// Display: Identity
+/// Category: linalg
+#[func]
+pub fn identity(
+    /// The number of rows and columns.
+    size: Spanned<usize>,
+) -> SourceResult<Array> {
+    if size.v == 0 {
+        bail!(size.span, "size must be positive");
+    }
+    let mut matrix = Matrix::zeros(size.v, size.v);
+    for i in 0..size.v {
+        matrix[(i, i)] = 1.0;
+    }
+    Ok(matrix.into_array())
+}
+
+/// Transposes a matrix.
+///
+/// ## Example { #example }
+/// ```example
+/// #linalg.transpose(((1, 2, 3), (4, 5, 6)))
+/// ```
+///
+/// Display: Transpose
+/// Category: linalg
+#[func]
+pub fn transpose(
+    /// The matrix to transpose.
+    matrix: Matrix,
+) -> Array {
+    let mut transposed = Matrix::zeros(matrix.cols, matrix.rows);
+    for i in 0..matrix.rows {
+        for j in 0..matrix.cols {
+            transposed[(j, i)] = matrix[(i, j)];
+        }
+    }
+    transposed.into_array()
+}
+
+/// Calculates the dot product of two vectors.
+///
+/// ## Example { #example }
+/// ```example
+/// #linalg.dot((1, 2, 3), (4, 5, 6))
+/// ```
+///
+/// Display: Dot Product
+/// Category: linalg
+#[func]
+pub fn dot(
+    /// The first vector.
+    a: Vector,
+    /// The second vector.
+    b: Vector,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<f64> {
+    if a.0.len() != b.0.len() {
+        bail!(span, "vectors must have the same length");
+    }
+    Ok(a.0.iter().zip(&b.0).map(|(x, y)| x * y).sum())
+}
+
+/// Multiplies a matrix with another matrix or with a vector.
+///
+/// Multiplying with a vector yields a vector, multiplying with a matrix yields
+/// a matrix.
+///
+/// ## Example { #example }
+/// ```example
+/// #let a = ((1, 2), (3, 4))
+/// $ #math.mat(..linalg.mul(a, a)) $
+/// #linalg.mul(a, (1, 1))
+/// ```
+///
+/// Display: Multiply
+/// Category: linalg
+#[func]
+pub fn mul(
+    /// The matrix on the left-hand side.
+    a: Matrix,
+    /// The matrix or vector on the right-hand side.
+    b: Operand,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Array> {
+    let (b, is_vector) = match b {
+        Operand::Matrix(matrix) => (matrix, false),
+        Operand::Vector(vector) => (Matrix::column(vector), true),
+    };
+
+    if a.cols != b.rows {
+        bail!(
+            span,
+            "cannot multiply a {}×{} matrix with a {}×{} {}",
+            a.rows,
+            a.cols,
+            b.rows,
+            b.cols,
+            if is_vector { "vector" } else { "matrix" },
+        );
+    }
+
+    let mut product = Matrix::zeros(a.rows, b.cols);
+    for i in 0..a.rows {
+        for j in 0..b.cols {
+            product[(i, j)] = (0..a.cols).map(|k| a[(i, k)] * b[(k, j)]).sum();
+        }
+    }
+
+    Ok(if is_vector {
+        product.data.into_iter().map(IntoValue::into_value).collect()
+    } else {
+        product.into_array()
+    })
+}
+
+/// Calculates the determinant of a square matrix.
+///
+/// ## Example { #example }
+/// ```example
+/// #linalg.det(((1, 2), (3, 4)))
+/// ```
+///
+/// Display: Determinant
+/// Category: linalg
+#[func]
+pub fn det(
+    /// The matrix whose determinant to calculate.
+    matrix: Spanned<Matrix>,
+) -> SourceResult<f64> {
+    let Spanned { v: matrix, span } = matrix;
+    matrix.check_square().at(span)?;
+    Ok(matrix.lu().map_or(0.0, |(lu, _, sign)| {
+        (0..lu.rows).map(|i| lu[(i, i)]).product::<f64>() * sign
+    }))
+}
+
+/// Calculates the inverse of a square matrix.
+///
+/// ## Example { #example }
+/// ```example
+/// #let inv = linalg.inv(((4, 7), (2, 6)))
+/// $ #math.mat(..inv.map(row => row.map(x => calc.round(x, digits: 2)))) $
+/// ```
+///
+/// Display: Inverse
+/// Category: linalg
+#[func]
+pub fn inv(
+    /// The matrix to invert.
+    matrix: Spanned<Matrix>,
+) -> SourceResult<Array> {
+    let Spanned { v: matrix, span } = matrix;
+    matrix.check_square().at(span)?;
+    let n = matrix.rows;
+    let mut inverse = Matrix::zeros(n, n);
+    for j in 0..n {
+        let mut unit = vec![0.0; n];
+        unit[j] = 1.0;
+        let column = matrix.solve(unit).at(span)?;
+        for (i, v) in column.into_iter().enumerate() {
+            inverse[(i, j)] = v;
+        }
+    }
+    Ok(inverse.into_array())
+}
+
+/// Solves the linear system `a x = b` for the vector `x`.
+///
+/// ## Example { #example }
+/// ```example
+/// // 2x + y = 5 and x - y = 1
+/// #linalg.solve(((2, 1), (1, -1)), (5, 1))
+/// ```
+///
+/// Display: Solve
+/// Category: linalg
+#[func]
+pub fn solve(
+    /// The square matrix of coefficients.
+    a: Spanned<Matrix>,
+    /// The vector on the right-hand side.
+    b: Spanned<Vector>,
+) -> SourceResult<Array> {
+    a.v.check_square().at(a.span)?;
+    if b.v.0.len() != a.v.rows {
+        bail!(b.span, "vector must have as many entries as the matrix has rows");
+    }
+    let x = a.v.solve(b.v.0).at(a.span)?;
+    Ok(x.into_iter().map(IntoValue::into_value).collect())
+}
+
+/// Calculates the eigenvalues of a square matrix.
+///
+/// The eigenvalues are the roots of the matrix's characteristic polynomial.
+/// Real eigenvalues are returned as floats and others as
+/// [complex numbers]($type/complex). The eigenvalues are sorted by their real
+/// and then by their imaginary part. This function is intended for small
+/// matrices, for larger ones the results become imprecise.
+///
+/// ## Example { #example }
+/// ```example
+/// #linalg.eigenvalues(((2, 0), (0, 3))) \
+/// #linalg.eigenvalues(((0, -1), (1, 0)))
+/// ```
+///
+/// Display: Eigenvalues
+/// Category: linalg
+#[func]
+pub fn eigenvalues(
+    /// The matrix whose eigenvalues to calculate.
+    matrix: Spanned<Matrix>,
+) -> SourceResult<Array> {
+    let Spanned { v: matrix, span } = matrix;
+    matrix.check_square().at(span)?;
+    Ok(polynomial_roots(&matrix.characteristic_polynomial())
+        .into_iter()
+        .map(|z| if z.im == 0.0 { z.re.into_value() } else { z.into_value() })
+        .collect())
+}
+
+/// A matrix of floats, stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    /// A matrix of the given size filled with zeros.
+    fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    /// A matrix with a single column.
+    fn column(vector: Vector) -> Self {
+        Self { rows: vector.0.len(), cols: 1, data: vector.0 }
+    }
+
+    /// Ensure that the matrix is square.
+    fn check_square(&self) -> StrResult<()> {
+        if self.rows != self.cols {
+            bail!("expected square matrix, found {}×{} matrix", self.rows, self.cols);
+        }
+        Ok(())
+    }
+
+    /// Compute the LU decomposition with partial pivoting of a square matrix.
+    ///
+    /// Returns the combined factors, the row permutation, and the sign of the
+    /// permutation, or `None` if the matrix is singular.
+    fn lu(&self) -> Option<(Self, Vec<usize>, f64)> {
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+
+        // Pivots are compared relative to the largest entry so that the
+        // singularity check doesn't depend on the scale of the matrix.
+        let scale = self.data.iter().fold(0.0, |max: f64, x| max.max(x.abs()));
+        let tolerance = scale * n as f64 * f64::EPSILON;
+
+        for k in 0..n {
+            let pivot =
+                (k..n).max_by(|&a, &b| lu[(a, k)].abs().total_cmp(&lu[(b, k)].abs()))?;
+
+            if lu[(pivot, k)].abs() <= tolerance {
+                return None;
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    lu.data.swap(k * n + j, pivot * n + j);
+                }
+                perm.swap(k, pivot);
+                sign = -sign;
+            }
+
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    lu[(i, j)] -= factor * lu[(k, j)];
+                }
+            }
+        }
+
+        Some((lu, perm, sign))
+    }
+
+    /// Solve the linear system with this square matrix and the given vector.
+    fn solve(&self, b: Vec<f64>) -> StrResult<Vec<f64>> {
+        let n = self.rows;
+        let (lu, perm, _) = self.lu().ok_or("matrix is singular")?;
+
+        // Forward substitution with the unit lower triangular factor.
+        let mut x: Vec<f64> = perm.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|j| lu[(i, j)] * x[j]).sum();
+            x[i] -= sum;
+        }
+
+        // Backward substitution with the upper triangular factor.
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|j| lu[(i, j)] * x[j]).sum();
+            x[i] = (x[i] - sum) / lu[(i, i)];
+        }
+
+        Ok(x)
+    }
+
+    /// Compute the coefficients of the characteristic polynomial of a square
+    /// matrix with the Faddeev-LeVerrier algorithm, from the highest to the
+    /// lowest power.
+    fn characteristic_polynomial(&self) -> Vec<f64> {
+        let n = self.rows;
+        let mut coefficients = vec![1.0];
+        let mut m = Self::zeros(n, n);
+        for k in 1..=n {
+            // M_k = A M_{k-1} + c_{n-k+1} I
+            let previous = coefficients[k - 1];
+            let mut next = Self::zeros(n, n);
+            for i in 0..n {
+                for j in 0..n {
+                    next[(i, j)] = (0..n).map(|l| self[(i, l)] * m[(l, j)]).sum();
+                }
+                next[(i, i)] += previous;
+            }
+            m = next;
+
+            // c_{n-k} = -tr(A M_k) / k
+            let trace: f64 = (0..n)
+                .map(|i| (0..n).map(|l| self[(i, l)] * m[(l, i)]).sum::<f64>())
+                .sum();
+            coefficients.push(-trace / k as f64);
+        }
+        coefficients
+    }
+
+    /// Convert the matrix into an array of rows.
+    fn into_array(self) -> Array {
+        self.data
+            .chunks(self.cols)
+            .map(|row| row.iter().copied().map(IntoValue::into_value).collect::<Array>())
+            .map(IntoValue::into_value)
+            .collect()
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+cast! {
+    Matrix,
+    array: Array => {
+        let rows = array
+            .into_iter()
+            .map(|row| row.cast::<Vector>().map(|row| row.0))
+            .collect::<StrResult<Vec<_>>>()?;
+        let cols = rows.first().map_or(0, Vec::len);
+        if cols == 0 {
+            bail!("matrix must not be empty");
+        }
+        if rows.iter().any(|row| row.len() != cols) {
+            bail!("matrix rows must all have the same length");
+        }
+        Self { rows: rows.len(), cols, data: rows.concat() }
+    },
+}
+
+/// A vector of floats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector(Vec<f64>);
+
+cast! {
+    Vector,
+    array: Array => Self(
+        array
+            .into_iter()
+            .map(|v| match v {
+                Value::Int(v) => Ok(v as f64),
+                Value::Float(v) => Ok(v),
+                v => Err(eco_format!("expected number, found {}", v.type_name())),
+            })
+            .collect::<StrResult<_>>()?,
+    ),
+}
+
+/// The right-hand side of a multiplication.
+pub enum Operand {
+    Matrix(Matrix),
+    Vector(Vector),
+}
+
+cast! {
+    Operand,
+    array: Array => {
+        if array.iter().any(|v| matches!(v, Value::Array(_))) {
+            Self::Matrix(array.into_value().cast()?)
+        } else {
+            Self::Vector(array.into_value().cast()?)
+        }
+    },
+}
@@ -2,8 +2,13 @@
 
 pub mod calc;
 mod construct;
+pub mod crypto;
 mod data;
+pub mod encoding;
 mod foundations;
+pub mod linalg;
+pub mod random;
+pub mod sys;
 
 pub use self::construct::*;
 pub use self::data::*;
@@ -15,9 +20,14 @@ use crate::prelude::*;
 pub(super) fn define(global: &mut Scope) {
     global.define("type", type_func());
     global.define("repr", repr_func());
+    global.define("fields", fields_func());
     global.define("panic", panic_func());
     global.define("assert", assert_func());
+    global.define("warn", warn_func());
+    global.define("suppress-warnings", suppress_warnings_func());
+    global.define("catch", catch_func());
     global.define("eval", eval_func());
+    global.define("match", match_func());
     global.define("int", int_func());
     global.define("float", float_func());
     global.define("luma", luma_func());
@@ -25,16 +35,26 @@ pub(super) fn define(global: &mut Scope) {
     global.define("cmyk", cmyk_func());
     global.define("color", color_module());
     global.define("datetime", datetime_func());
+    global.define("duration", duration_func());
+    global.define("decimal", decimal_func());
     global.define("symbol", symbol_func());
     global.define("str", str_func());
+    global.define("bytes", bytes_func());
+    global.define("dictionary", dictionary_func());
     global.define("label", label_func());
     global.define("regex", regex_func());
     global.define("range", range_func());
     global.define("read", read_func());
+    global.define("plugin", plugin_func());
     global.define("csv", csv_func());
     global.define("json", json_func());
     global.define("toml", toml_func());
     global.define("yaml", yaml_func());
     global.define("xml", xml_func());
     global.define("calc", calc::module());
+    global.define("linalg", linalg::module());
+    global.define("random", random::module());
+    global.define("crypto", crypto::module());
+    global.define("base64", encoding::base64_module());
+    global.define("hex", encoding::hex_module());
 }
@@ -0,0 +1,45 @@
+//! Seeded generation of pseudo-random numbers.
+
+use typst::eval::{Module, Rng, Scope};
+
+use crate::prelude::*;
+
+/// A module with functions for pseudo-random numbers.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("new", new_func());
+    Module::new("random").with_scope(scope)
+}
+
+/// Creates a new seeded pseudo-random number generator.
+///
+/// The numbers produced by a generator only depend on its seed, so they are
+/// the same in each compilation. Generators are advanced by calling their
+/// methods, which is why they must be stored in a variable:
+///
+/// - `int(low, high)`: A random integer that is at least `low` and smaller
+///   than `high`.
+/// - `float()`: A random float that is at least `{0.0}` and smaller than
+///   `{1.0}`.
+/// - `shuffle(array)`: A copy of the array with its items in random order.
+/// - `choice(array)`: A random item of a non-empty array.
+///
+/// ## Example { #example }
+/// ```example
+/// #let rng = random.new(seed: 42)
+/// #rng.int(1, 7) \
+/// #rng.float() \
+/// #rng.shuffle((1, 2, 3, 4, 5))
+/// ```
+///
+/// Display: New
+/// Category: random
+#[func]
+pub fn new(
+    /// The seed of the generator.
+    #[named]
+    #[default(0)]
+    seed: i64,
+) -> Rng {
+    Rng::new(seed)
+}
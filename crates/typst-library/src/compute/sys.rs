@@ -0,0 +1,10 @@
+//! System-related things.
+
+use typst::eval::{Dict, Module, Scope};
+
+/// A module with information about the compilation environment.
+pub fn module(inputs: Dict) -> Module {
+    let mut scope = Scope::new();
+    scope.define("inputs", inputs);
+    Module::new("sys").with_scope(scope)
+}
@@ -215,6 +215,7 @@ impl Layout for EnumElem {
         };
 
         let mut cells = vec![];
+        let mut structures = vec![];
         let mut number = self.start(styles);
         let mut parents = self.parents(styles);
         let full = self.full(styles);
@@ -252,6 +253,12 @@ impl Layout for EnumElem {
             cells.push(resolved);
             cells.push(Content::empty());
             cells.push(item.body().styled(Self::set_parents(Parent(number))));
+            structures.extend([
+                None,
+                Some(Structure::ListLabel),
+                None,
+                Some(Structure::ListBody),
+            ]);
             number = number.saturating_add(1);
         }
 
@@ -266,9 +273,15 @@ impl Layout for EnumElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .with_structures(structures);
 
-        Ok(layouter.layout(vt)?.fragment)
+        let mut fragment = layouter.layout(vt)?.fragment;
+        for frame in &mut fragment {
+            frame.meta_iter([Meta::Structure(Structure::List)]);
+        }
+
+        Ok(fragment)
     }
 }
 
@@ -235,11 +235,17 @@ impl<'a> FlowLayouter<'a> {
             }
         }
 
-        for (i, frame) in lines.into_iter().enumerate() {
+        for (i, mut frame) in lines.into_iter().enumerate() {
             if i > 0 {
                 self.layout_item(vt, FlowItem::Absolute(leading, true))?;
             }
 
+            if !frame.is_empty() {
+                let structure =
+                    if i == 0 { Structure::Paragraph } else { Structure::Line };
+                frame.meta_iter([Meta::Structure(structure)]);
+            }
+
             self.layout_item(
                 vt,
                 FlowItem::Frame { frame, aligns, sticky: false, movable: true },
@@ -140,6 +140,8 @@ cast! {
 pub struct GridLayouter<'a> {
     /// The grid cells.
     cells: &'a [Content],
+    /// The structural roles of the grid cells, by cell index.
+    structures: Vec<Option<Structure>>,
     /// Whether this is an RTL grid.
     is_rtl: bool,
     /// Whether this grid has gutters.
@@ -263,6 +265,7 @@ impl<'a> GridLayouter<'a> {
 
         Self {
             cells,
+            structures: vec![],
             is_rtl,
             has_gutter,
             rows,
@@ -278,6 +281,12 @@ impl<'a> GridLayouter<'a> {
         }
     }
 
+    /// Tag the frames of the cells with structural roles, given by cell index.
+    pub fn with_structures(mut self, structures: Vec<Option<Structure>>) -> Self {
+        self.structures = structures;
+        self
+    }
+
     /// Determines the columns sizes and then layouts the grid row-by-row.
     pub fn layout(mut self, vt: &mut Vt) -> SourceResult<GridLayout> {
         self.measure_columns(vt)?;
@@ -576,7 +585,8 @@ impl<'a> GridLayouter<'a> {
                 if self.rows[y] == Sizing::Auto {
                     pod.full = self.regions.full;
                 }
-                let frame = cell.layout(vt, self.styles, pod)?.into_frame();
+                let mut frame = cell.layout(vt, self.styles, pod)?.into_frame();
+                self.tag(&mut frame, x, y);
                 output.push_frame(pos, frame);
             }
 
@@ -613,7 +623,8 @@ impl<'a> GridLayouter<'a> {
 
                 // Push the layouted frames into the individual output frames.
                 let fragment = cell.layout(vt, self.styles, pod)?;
-                for (output, frame) in outputs.iter_mut().zip(fragment) {
+                for (output, mut frame) in outputs.iter_mut().zip(fragment) {
+                    self.tag(&mut frame, x, y);
                     output.push_frame(pos, frame);
                 }
             }
@@ -679,11 +690,30 @@ impl<'a> GridLayouter<'a> {
         Ok(())
     }
 
+    /// Attach the structural role of the cell in column `x` and row `y` to
+    /// its frame.
+    fn tag(&self, frame: &mut Frame, x: usize, y: usize) {
+        let structure = self.index(x, y).and_then(|i| self.structures.get(i));
+        if let Some(&Some(structure)) = structure {
+            if !frame.is_empty() {
+                frame.meta_iter([Meta::Structure(structure)]);
+            }
+        }
+    }
+
     /// Get the content of the cell in column `x` and row `y`.
     ///
     /// Returns `None` if it's a gutter cell.
     #[track_caller]
-    fn cell(&self, mut x: usize, y: usize) -> Option<&'a Content> {
+    fn cell(&self, x: usize, y: usize) -> Option<&'a Content> {
+        self.index(x, y).and_then(|i| self.cells.get(i))
+    }
+
+    /// Get the index of the cell in column `x` and row `y`.
+    ///
+    /// Returns `None` if it's a gutter cell.
+    #[track_caller]
+    fn index(&self, mut x: usize, y: usize) -> Option<usize> {
         assert!(x < self.cols.len());
         assert!(y < self.rows.len());
 
@@ -696,13 +726,13 @@ impl<'a> GridLayouter<'a> {
             // Even columns and rows are children, odd ones are gutter.
             if x % 2 == 0 && y % 2 == 0 {
                 let c = 1 + self.cols.len() / 2;
-                self.cells.get((y / 2) * c + x / 2)
+                Some((y / 2) * c + x / 2)
             } else {
                 None
             }
         } else {
             let c = self.cols.len();
-            self.cells.get(y * c + x)
+            Some(y * c + x)
         }
     }
 }
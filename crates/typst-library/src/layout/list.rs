@@ -143,11 +143,18 @@ impl Layout for ListElem {
             .aligned(Align::LEFT_TOP.into());
 
         let mut cells = vec![];
+        let mut structures = vec![];
         for item in self.children() {
             cells.push(Content::empty());
             cells.push(marker.clone());
             cells.push(Content::empty());
             cells.push(item.body().styled(Self::set_depth(Depth)));
+            structures.extend([
+                None,
+                Some(Structure::ListLabel),
+                None,
+                Some(Structure::ListBody),
+            ]);
         }
 
         let layouter = GridLayouter::new(
@@ -161,9 +168,15 @@ impl Layout for ListElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .with_structures(structures);
 
-        Ok(layouter.layout(vt)?.fragment)
+        let mut fragment = layouter.layout(vt)?.fragment;
+        for frame in &mut fragment {
+            frame.meta_iter([Meta::Structure(Structure::List)]);
+        }
+
+        Ok(fragment)
     }
 }
 
@@ -3,10 +3,11 @@ use crate::prelude::*;
 /// Measures the layouted size of content.
 ///
 /// The `measure` function lets you determine the layouted size of content.
-/// Note that an infinite space is assumed, therefore the measured height/width
-/// may not necessarily match the final height/width of the measured content.
-/// If you want to measure in the current layout dimensions, you can combined
-/// `measure` and [`layout`]($func/layout).
+/// By default, an infinite space is assumed, therefore the measured
+/// height/width may not necessarily match the final height/width of the
+/// measured content. You can restrict the available space with the `width` and
+/// `height` arguments. If you want to measure in the current layout
+/// dimensions, you can combined `measure` and [`layout`]($func/layout).
 ///
 /// # Example { #example }
 /// The same content can have a different size depending on the styles that
@@ -34,6 +35,18 @@ use crate::prelude::*;
 /// #thing[Welcome]
 /// ```
 ///
+/// Alternatively, you can pass a function instead of styles. In this case,
+/// `measure` returns content that measures the body with the styles active
+/// where the content is placed and then displays the result of calling the
+/// function with the measured size.
+///
+/// ```example
+/// #let body = [Hello!]
+/// #measure(body, size => [#size.width])
+/// #set text(14pt)
+/// #measure(body, size => [#size.width])
+/// ```
+///
 /// The measure function returns a dictionary with the entries `width` and
 /// `height`, both of type [`length`]($type/length).
 ///
@@ -43,14 +56,104 @@ use crate::prelude::*;
 pub fn measure(
     /// The content whose size to measure.
     content: Content,
-    /// The styles with which to layout the content.
-    styles: Styles,
+    /// The styles with which to layout the content or a function that receives
+    /// the measured size.
+    styles: MeasureWith,
+    /// The width available to the content.
+    ///
+    /// ```example
+    /// #let body = lorem(20)
+    /// #style(styles => {
+    ///   let size = measure(body, styles, width: 4cm)
+    ///   [The text is #size.height high.]
+    /// })
+    /// ```
+    #[named]
+    #[default]
+    width: Smart<Length>,
+    /// The height available to the content.
+    #[named]
+    #[default]
+    height: Smart<Length>,
     /// The virtual machine.
     vm: &mut Vm,
+) -> SourceResult<Value> {
+    match styles {
+        MeasureWith::Styles(styles) => {
+            let styles = StyleChain::new(&styles);
+            measure_size(&mut vm.vt, &content, styles, width, height)
+                .map(IntoValue::into_value)
+        }
+        MeasureWith::Func(func) => Ok(MeasureElem::new(content, func)
+            .with_width(width)
+            .with_height(height)
+            .pack()
+            .into_value()),
+    }
+}
+
+/// How to measure content.
+pub enum MeasureWith {
+    /// Measure with the given styles.
+    Styles(Styles),
+    /// Measure with the styles of the call site and pass the size to the
+    /// function.
+    Func(Func),
+}
+
+cast! {
+    MeasureWith,
+    v: Styles => Self::Styles(v),
+    v: Func => Self::Func(v),
+}
+
+/// Executes a `measure` call with the styles of the call site.
+///
+/// Display: Measure
+/// Category: special
+#[element(Show)]
+struct MeasureElem {
+    /// The content whose size to measure.
+    #[required]
+    body: Content,
+
+    /// The function to call with the measured size.
+    #[required]
+    func: Func,
+
+    /// The width available to the content.
+    width: Smart<Length>,
+
+    /// The height available to the content.
+    height: Smart<Length>,
+}
+
+impl Show for MeasureElem {
+    #[tracing::instrument(name = "MeasureElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let size = measure_size(
+            vt,
+            &self.body(),
+            styles,
+            self.width(styles),
+            self.height(styles),
+        )?;
+        Ok(self.func().call_vt(vt, [size])?.display())
+    }
+}
+
+/// Measure content in the given styles and available space.
+fn measure_size(
+    vt: &mut Vt,
+    content: &Content,
+    styles: StyleChain,
+    width: Smart<Length>,
+    height: Smart<Length>,
 ) -> SourceResult<Dict> {
-    let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
-    let styles = StyleChain::new(&styles);
-    let frame = content.measure(&mut vm.vt, styles, pod)?.into_frame();
+    let available =
+        Axes::new(width, height).map(|v| v.map_or(Abs::inf(), |v| v.resolve(styles)));
+    let pod = Regions::one(available, Axes::splat(false));
+    let frame = content.measure(vt, styles, pod)?.into_frame();
     let Size { x, y } = frame.size();
     Ok(dict! { "width" => x, "height" => y })
 }
@@ -1,6 +1,9 @@
 use std::ptr;
 use std::str::FromStr;
 
+use typst::eval::Tracer;
+use typst::model::DelayedErrors;
+
 use super::{AlignElem, ColumnsElem};
 use crate::meta::{Counter, CounterKey, Numbering};
 use crate::prelude::*;
@@ -302,8 +305,96 @@ impl PageElem {
     /// while we post-process the pages in this function. This function returns
     /// a fragment consisting of multiple frames, one per output page of this
     /// page run.
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(
+        name = "PageElem::layout",
+        skip_all,
+        fields(page = number.get())
+    )]
     pub fn layout(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        number: NonZeroUsize,
+    ) -> SourceResult<Fragment> {
+        // Page runs are cached as a whole, so that a change in one run of pages
+        // doesn't require laying out the unchanged runs before it again. The
+        // layout only depends on the parity of the first page's number, and
+        // only for two-sided margins and cleared pages. To keep a run cached
+        // when pages are inserted before it, the cache is keyed on the first
+        // page number with the same parity instead of the actual number.
+        #[allow(clippy::too_many_arguments)]
+        #[comemo::memoize]
+        fn cached(
+            page: &PageElem,
+            world: Tracked<dyn World + '_>,
+            introspector: Tracked<Introspector>,
+            locator: Tracked<Locator>,
+            delayed: TrackedMut<DelayedErrors>,
+            tracer: TrackedMut<Tracer>,
+            styles: StyleChain,
+            number: NonZeroUsize,
+        ) -> SourceResult<Fragment> {
+            let mut locator = Locator::chained(locator);
+            let mut vt = Vt {
+                world,
+                introspector,
+                locator: &mut locator,
+                delayed,
+                tracer,
+            };
+            page.layout_impl(&mut vt, styles, number)
+        }
+
+        let parity_matters = self.margin(styles).two_sided.unwrap_or(false)
+            || self.clear_to(styles).is_some();
+        let key = if parity_matters && number.get() % 2 == 0 {
+            NonZeroUsize::new(2).unwrap()
+        } else {
+            NonZeroUsize::ONE
+        };
+
+        let mut fragment = cached(
+            self,
+            vt.world,
+            vt.introspector,
+            vt.locator.track(),
+            TrackedMut::reborrow_mut(&mut vt.delayed),
+            TrackedMut::reborrow_mut(&mut vt.tracer),
+            styles,
+            key,
+        )?;
+
+        vt.locator.visit_frames(&fragment);
+
+        // Tell PDF viewers how the pages are numbered. This depends on the
+        // actual page numbers, so it is done after the cached layout.
+        let numbering = self.numbering(styles);
+        let mut number = number;
+        for frame in fragment.iter_mut() {
+            let page_number = Counter::new(CounterKey::Page).at_page(vt, number)?.first();
+            let page_number = NonZeroUsize::new(page_number).unwrap_or(NonZeroUsize::ONE);
+            let label = match &numbering {
+                Some(Numbering::Pattern(pattern)) => pattern.pdf_page_label(page_number),
+                Some(Numbering::Func(_)) => PdfPageLabel {
+                    prefix: None,
+                    style: Some(PdfPageLabelStyle::Arabic),
+                    number: page_number,
+                },
+                None => PdfPageLabel { prefix: None, style: None, number: page_number },
+            };
+            frame.push(
+                Point::zero(),
+                FrameItem::Meta(Meta::PdfPageLabel(label), Size::zero()),
+            );
+            number = number.saturating_add(1);
+        }
+
+        Ok(fragment)
+    }
+
+    /// Layout the page run without caching. Only the parity of `number` is
+    /// relevant here, the page labels are added by the caller.
+    fn layout_impl(
         &self,
         vt: &mut Vt,
         styles: StyleChain,
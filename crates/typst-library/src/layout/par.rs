@@ -8,6 +8,7 @@ use typst::eval::Tracer;
 use typst::model::DelayedErrors;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::{BoxElem, HElem, Sizing, Spacing};
 use crate::layout::AlignElem;
@@ -107,6 +108,12 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// Whether a word that doesn't fit into a line on its own may be broken
+    /// at any character. Used for raw blocks that wrap long lines.
+    #[internal]
+    #[default(false)]
+    pub emergency: bool,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -416,11 +423,16 @@ impl SpanMapper {
 
     /// Determine the span at the given byte offset.
     ///
-    /// May return a detached span.
+    /// An offset at the boundary of two segments belongs to the second one,
+    /// while the end of the text still belongs to the last segment. May
+    /// return a detached span.
     pub fn span_at(&self, offset: usize) -> (Span, u16) {
         let mut cursor = 0;
-        for &(len, span) in &self.0 {
-            if (cursor..=cursor + len).contains(&offset) {
+        for (i, &(len, span)) in self.0.iter().enumerate() {
+            let last = i + 1 == self.0.len();
+            if (cursor..cursor + len).contains(&offset)
+                || (last && offset == cursor + len)
+            {
                 return (span, u16::try_from(offset - cursor).unwrap_or(0));
             }
             cursor += len;
@@ -822,6 +834,11 @@ fn shared_get<T: PartialEq>(
 
 /// Find suitable linebreaks.
 fn linebreak<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
+    // Emergency breaks are only supported by the simple algorithm.
+    if ParElem::emergency_in(p.styles) {
+        return linebreak_simple(vt, p, width);
+    }
+
     let linebreaks = ParElem::linebreaks_in(p.styles).unwrap_or_else(|| {
         if ParElem::justify_in(p.styles) {
             Linebreaks::Optimized
@@ -840,6 +857,7 @@ fn linebreak<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
 /// lines greedily, always taking the longest possible line. This may lead to
 /// very unbalanced line, but is fast and simple.
 fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
+    let emergency = ParElem::emergency_in(p.styles);
     let mut lines = vec![];
     let mut start = 0;
     let mut last = None;
@@ -859,6 +877,18 @@ fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line
             }
         }
 
+        // If even the rebuilt line doesn't fit and breaking anywhere is
+        // allowed, break it up at the last fitting characters.
+        if emergency && !width.fits(attempt.width) {
+            while let Some((emergency_line, split)) =
+                linebreak_emergency(vt, p, start..end, width)
+            {
+                lines.push(emergency_line);
+                start = split;
+            }
+            attempt = line(vt, p, start..end, mandatory, hyphen);
+        }
+
         // Finish the current line if there is a mandatory line break (i.e.
         // due to "\n") or if the line doesn't fit horizontally already
         // since then no shorter line will be possible.
@@ -878,6 +908,34 @@ fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line
     lines
 }
 
+/// Break off the longest start of a range that fits into the width at a
+/// grapheme boundary. Returns the line and where the rest of the range starts,
+/// or `None` if the whole range fits or can't be broken up.
+fn linebreak_emergency<'a>(
+    vt: &Vt,
+    p: &'a Preparation<'a>,
+    range: Range,
+    width: Abs,
+) -> Option<(Line<'a>, usize)> {
+    if width.fits(line(vt, p, range.clone(), false, false).width) {
+        return None;
+    }
+
+    // Keep at least one grapheme on the line so that we make progress.
+    let mut fitting = None;
+    let text = &p.bidi.text[range.clone()];
+    for (offset, _) in text.grapheme_indices(true).skip(1) {
+        let end = range.start + offset;
+        let attempt = line(vt, p, range.start..end, false, false);
+        if fitting.is_some() && !width.fits(attempt.width) {
+            break;
+        }
+        fitting = Some((attempt, end));
+    }
+
+    fitting
+}
+
 /// Perform line breaking in optimized Knuth-Plass style. Here, we use more
 /// context to determine the line breaks than in the simple first-fit style. For
 /// example, we might choose to cut a line short even though there is still a
@@ -1575,3 +1633,23 @@ fn overhang(c: char) -> f64 {
         _ => 0.0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typst::syntax::FileId;
+
+    #[test]
+    fn test_span_at() {
+        let id = FileId::detached();
+        let (a, b) = (Span::new(id, 2), Span::new(id, 3));
+        let mut spans = SpanMapper::new();
+        spans.push(3, a);
+        spans.push(2, b);
+        assert_eq!(spans.span_at(0), (a, 0));
+        assert_eq!(spans.span_at(2), (a, 2));
+        assert_eq!(spans.span_at(3), (b, 0));
+        assert_eq!(spans.span_at(5), (b, 2));
+        assert_eq!(spans.span_at(6), (Span::detached(), 0));
+    }
+}
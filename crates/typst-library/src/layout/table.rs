@@ -167,6 +167,11 @@ impl Layout for TableElem {
             &cells,
             regions,
             styles,
+        )
+        .with_structures(
+            (0..cells.len())
+                .map(|i| Some(Structure::TableCell(i / cols)))
+                .collect(),
         );
 
         // Measure the columns and layout the grid row-by-row.
@@ -219,6 +224,8 @@ impl Layout for TableElem {
                 }
                 dx += col;
             }
+
+            frame.meta_iter([Meta::Structure(Structure::Table)]);
         }
 
         Ok(layout.fragment)
@@ -15,7 +15,7 @@ pub mod text;
 pub mod visualize;
 
 use typst::diag::At;
-use typst::eval::{LangItems, Library, Module, Scope};
+use typst::eval::{Dict, LangItems, Library, Module, Scope};
 use typst::geom::Smart;
 use typst::model::{Element, Styles};
 
@@ -23,14 +23,20 @@ use self::layout::LayoutRoot;
 
 /// Construct the standard library.
 pub fn build() -> Library {
+    build_with_inputs(Dict::new())
+}
+
+/// Construct the standard library with inputs that are available to
+/// documents as `sys.inputs`.
+pub fn build_with_inputs(inputs: Dict) -> Library {
     let math = math::module();
-    let global = global(math.clone());
+    let global = global(math.clone(), inputs);
     Library { global, math, styles: styles(), items: items() }
 }
 
 /// Construct the module with global definitions.
 #[tracing::instrument(skip_all)]
-fn global(math: Module) -> Module {
+fn global(math: Module, inputs: Dict) -> Module {
     let mut global = Scope::deduplicating();
 
     // Categories.
@@ -41,6 +47,7 @@ fn global(math: Module) -> Module {
     compute::define(&mut global);
     symbols::define(&mut global);
     global.define("math", math);
+    global.define("sys", compute::sys::module(inputs));
 
     Module::new("global").with_scope(global)
 }
@@ -11,6 +11,37 @@ use crate::layout::PageElem;
 use crate::math::EquationElem;
 use crate::prelude::*;
 
+/// A location, or a selector whose first match determines the location.
+pub enum Anchor {
+    /// A specific location.
+    Location(Location),
+    /// A selector for an element.
+    Selector(LocatableSelector),
+}
+
+impl Anchor {
+    /// Determine the location this anchor refers to.
+    pub fn resolve(&self, introspector: Tracked<Introspector>) -> StrResult<Location> {
+        match self {
+            Self::Location(location) => Ok(*location),
+            Self::Selector(LocatableSelector(Selector::Label(label))) => introspector
+                .query_label(label)?
+                .location()
+                .ok_or_else(|| eco_format!("label `{label:?}` is not locatable")),
+            Self::Selector(LocatableSelector(selector)) => introspector
+                .query_first(selector)
+                .and_then(|elem| elem.location())
+                .ok_or_else(|| eco_format!("selector does not match any element")),
+        }
+    }
+}
+
+cast! {
+    Anchor,
+    v: Location => Self::Location(v),
+    v: LocatableSelector => Self::Selector(v),
+}
+
 /// Counts through pages, elements, and more.
 ///
 /// With the counter function, you can access and modify counters for pages,
@@ -255,10 +286,24 @@ use crate::prelude::*;
 /// Gets the value of the counter at the given location. Always returns an
 /// array of integers, even if the counter has just one number.
 ///
-/// - location: location (positional, required)
+/// ```example
+/// #set heading(numbering: "1.")
+///
+/// = Introduction
+/// = Background <background>
+/// = Analysis
+///
+/// #locate(loc => [
+///   Background is chapter
+///   #counter(heading).at(<background>).first().
+/// ])
+/// ```
+///
+/// - location: location or label or selector (positional, required)
 ///   The location at which the counter value should be retrieved. A suitable
 ///   location can be retrieved from [`locate`]($func/locate) or
-///   [`query`]($func/query).
+///   [`query`]($func/query). When given a label or selector, the counter value
+///   at its first match in the document is retrieved.
 ///
 /// - returns: array
 ///
@@ -266,13 +311,27 @@ use crate::prelude::*;
 /// Gets the value of the counter at the end of the document. Always returns an
 /// array of integers, even if the counter has just one number.
 ///
-/// - location: location (positional, required)
-///   Can be any location. Why is it required then? Typst has to evaluate parts
-///   of your code multiple times to determine all counter values. By only
-///   allowing this method within [`locate`]($func/locate) calls, the amount of
-///   code that can depend on the method's result is reduced. If you could call
-///   `final` directly at the top level of a module, the evaluation of the whole
-///   module and its exports could depend on the counter's value.
+/// ```example
+/// #set page(
+///   height: 60pt,
+///   footer: locate(loc => [
+///     Page #counter(page).at(loc).first()
+///     of #counter(page).final().first()
+///   ]),
+/// )
+///
+/// First
+/// #pagebreak()
+/// Second
+/// ```
+///
+/// - location: location (positional)
+///   Can be any location. The argument is optional and only accepted for
+///   backwards compatibility. Note that the final value is only known once the
+///   document has been laid out. Therefore, this method should be called
+///   within [`locate`]($func/locate) calls or other content that is laid out,
+///   like a page footer. This way, the amount of code that can depend on the
+///   method's result is reduced.
 ///
 /// - returns: array
 ///
@@ -326,8 +385,20 @@ impl Counter {
                 ))
                 .into_value(),
             "update" => self.update(args.expect("value or function")?).into_value(),
-            "at" => self.at(&mut vm.vt, args.expect("location")?)?.into_value(),
-            "final" => self.final_(&mut vm.vt, args.expect("location")?)?.into_value(),
+            "at" => {
+                let Spanned { v: anchor, span } =
+                    args.expect::<Spanned<Anchor>>("location")?;
+                vm.vt
+                    .delayed(|vt| {
+                        let location = anchor.resolve(vt.introspector).at(span)?;
+                        self.at(vt, location)
+                    })
+                    .into_value()
+            }
+            "final" => {
+                args.eat::<Location>()?;
+                self.final_(&mut vm.vt)?.into_value()
+            }
             _ => bail!(span, "type counter has no method `{}`", method),
         };
         args.finish()?;
@@ -352,8 +423,24 @@ impl Counter {
         Ok(state)
     }
 
+    /// Get the value of the state at the end of the given page.
+    pub fn at_page(&self, vt: &mut Vt, page: NonZeroUsize) -> SourceResult<CounterState> {
+        let sequence = self.sequence(vt)?;
+        let (mut state, stop) = sequence
+            .iter()
+            .rev()
+            .find(|(_, stop)| *stop <= page)
+            .unwrap_or(&sequence[0])
+            .clone();
+        if self.is_page() {
+            let delta = page.get().saturating_sub(stop.get());
+            state.step(NonZeroUsize::ONE, delta);
+        }
+        Ok(state)
+    }
+
     /// Get the value of the state at the final location.
-    pub fn final_(&self, vt: &mut Vt, _: Location) -> SourceResult<CounterState> {
+    pub fn final_(&self, vt: &mut Vt) -> SourceResult<CounterState> {
         let sequence = self.sequence(vt)?;
         let (mut state, page) = sequence.last().unwrap().clone();
         if self.is_page() {
@@ -559,7 +646,7 @@ pub trait Count {
 }
 
 /// Counts through elements with different levels.
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
 pub struct CounterState(pub SmallVec<[usize; 3]>);
 
 impl CounterState {
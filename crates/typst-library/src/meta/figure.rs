@@ -1,7 +1,8 @@
 use std::str::FromStr;
 
 use super::{
-    Count, Counter, CounterKey, CounterUpdate, LocalName, Numbering, NumberingPattern,
+    local_name_in, Count, Counter, CounterKey, CounterUpdate, LocalName, Numbering,
+    NumberingPattern,
 };
 use crate::layout::{BlockElem, PlaceElem, VElem};
 use crate::meta::{Outlinable, Refable, Supplement};
@@ -108,6 +109,22 @@ pub struct FigureElem {
     /// The figure's caption.
     pub caption: Option<Content>,
 
+    /// A text describing the figure for readers who cannot see it.
+    ///
+    /// It is exported as the figure's alternative description. If it isn't
+    /// given, the description of the first image in the figure or the
+    /// caption is used instead.
+    ///
+    /// ```example
+    /// #figure(
+    ///   alt: "A bar chart with three rising bars",
+    ///   caption: [Growth over three years],
+    ///   stack(dir: ltr, spacing: 4pt,
+    ///     ..(1cm, 1.5cm, 2cm).map(h => rect(width: 8pt, height: h))),
+    /// )
+    /// ```
+    pub alt: Option<EcoString>,
+
     /// The caption's position. Either `{top}` or `{bottom}`.
     ///
     /// ```example
@@ -227,12 +244,9 @@ impl Synthesize for FigureElem {
                 let name = match &kind {
                     FigureKind::Elem(func) => {
                         let empty = Content::new(*func);
-                        empty.with::<dyn LocalName>().map(|c| {
-                            TextElem::packed(c.local_name(
-                                TextElem::lang_in(styles),
-                                TextElem::region_in(styles),
-                            ))
-                        })
+                        empty
+                            .with::<dyn LocalName>()
+                            .map(|c| TextElem::packed(local_name_in(*func, c, styles)))
                     }
                     FigureKind::Name(_) => None,
                 };
@@ -0,0 +1,42 @@
+use crate::prelude::*;
+
+/// Exposes a value to the query system without producing visible content.
+///
+/// This element can be retrieved with the [`query`]($func/query) function and
+/// from the command line with `typst query`. Its purpose is to expose an
+/// arbitrary value to the introspection system. To identify a metadata value
+/// among others, you can attach a [`label`]($type/label) to it and query for
+/// that label.
+///
+/// ## Example { #example }
+/// ```example
+/// // Put metadata somewhere.
+/// #metadata("This is a note") <note>
+///
+/// // And find it from anywhere else.
+/// #locate(loc => {
+///   query(<note>, loc).first().value
+/// })
+/// ```
+///
+/// Display: Metadata
+/// Category: meta
+#[element(Behave, Show, Locatable)]
+pub struct MetadataElem {
+    /// The value to embed into the document.
+    #[required]
+    pub value: Value,
+}
+
+impl Show for MetadataElem {
+    #[tracing::instrument(name = "MetadataElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+impl Behave for MetadataElem {
+    fn behaviour(&self) -> Behaviour {
+        Behaviour::Ignorant
+    }
+}
@@ -8,8 +8,10 @@ mod figure;
 mod footnote;
 mod heading;
 mod link;
+mod metadata;
 mod numbering;
 mod outline;
+pub mod pdf;
 mod query;
 mod reference;
 mod state;
@@ -22,6 +24,7 @@ pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
 pub use self::link::*;
+pub use self::metadata::*;
 pub use self::numbering::*;
 pub use self::outline::*;
 pub use self::query::*;
@@ -50,6 +53,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define("state", state_func());
     global.define("query", query_func());
     global.define("selector", selector_func());
+    global.define("metadata", MetadataElem::func());
+    global.define("pdf", pdf::module());
 }
 
 /// The named with which an element is referenced.
@@ -58,7 +63,25 @@ pub trait LocalName {
     fn local_name(&self, lang: Lang, region: Option<Region>) -> &'static str;
 
     /// Resolve the local name with a style chain.
-    fn local_name_in(&self, styles: StyleChain) -> &'static str {
-        self.local_name(TextElem::lang_in(styles), TextElem::region_in(styles))
+    fn local_name_in(&self, styles: StyleChain) -> EcoString
+    where
+        Self: Element,
+    {
+        local_name_in(Self::func(), self, styles)
+    }
+}
+
+/// Resolve the local name of an element with a style chain, preferring the
+/// names set through the text's `names` over the built-in ones.
+pub fn local_name_in(
+    func: ElemFunc,
+    elem: &dyn LocalName,
+    styles: StyleChain,
+) -> EcoString {
+    let lang = TextElem::lang_in(styles);
+    let region = TextElem::region_in(styles);
+    match TextElem::names_in(styles).get(func.name(), lang, region) {
+        Some(name) => name.into(),
+        None => elem.local_name(lang, region).into(),
     }
 }
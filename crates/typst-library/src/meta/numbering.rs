@@ -195,6 +195,29 @@ impl NumberingPattern {
     pub fn pieces(&self) -> usize {
         self.pieces.len()
     }
+
+    /// Convert the first counting symbol of the pattern into a PDF page
+    /// label for the given number.
+    ///
+    /// PDF viewers only support a few numbering styles. For other kinds of
+    /// numbering, the label only consists of the prefix.
+    pub fn pdf_page_label(&self, number: NonZeroUsize) -> PdfPageLabel {
+        let Some((prefix, kind, case)) = self.pieces.first() else {
+            return PdfPageLabel { prefix: None, style: None, number };
+        };
+
+        let style = match (kind, case) {
+            (NumberingKind::Arabic, _) => Some(PdfPageLabelStyle::Arabic),
+            (NumberingKind::Roman, Case::Lower) => Some(PdfPageLabelStyle::LowerRoman),
+            (NumberingKind::Roman, Case::Upper) => Some(PdfPageLabelStyle::UpperRoman),
+            (NumberingKind::Letter, Case::Lower) => Some(PdfPageLabelStyle::LowerAlpha),
+            (NumberingKind::Letter, Case::Upper) => Some(PdfPageLabelStyle::UpperAlpha),
+            _ => None,
+        };
+
+        let prefix = (!prefix.is_empty()).then(|| prefix.clone());
+        PdfPageLabel { prefix, style, number }
+    }
 }
 
 impl FromStr for NumberingPattern {
@@ -190,14 +190,9 @@ impl Show for OutlineElem {
         let mut seq = vec![ParbreakElem::new().pack()];
         // Build the outline title.
         if let Some(title) = self.title(styles) {
-            let title =
-                title.unwrap_or_else(|| {
-                    TextElem::packed(self.local_name(
-                        TextElem::lang_in(styles),
-                        TextElem::region_in(styles),
-                    ))
-                    .spanned(self.span())
-                });
+            let title = title.unwrap_or_else(|| {
+                TextElem::packed(self.local_name_in(styles)).spanned(self.span())
+            });
 
             seq.push(HeadingElem::new(title).with_level(NonZeroUsize::ONE).pack());
         }
@@ -315,6 +310,17 @@ impl OutlineIndent {
         seq: &mut Vec<Content>,
         span: Span,
     ) -> SourceResult<()> {
+        if let Some(Smart::Custom(OutlineIndent::Bool(_))) = indent {
+            vt.tracer.warn(
+                warning!(span, "passing a boolean to `indent` is deprecated")
+                    .with_hint(
+                        "use `auto` instead of `true` and `none` instead of `false`"
+                            .into(),
+                    )
+                    .with_kind("deprecated"),
+            );
+        }
+
         match indent {
             // 'none' | 'false' => no indenting
             None | Some(Smart::Custom(OutlineIndent::Bool(false))) => {}
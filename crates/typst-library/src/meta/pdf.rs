@@ -0,0 +1,224 @@
+//! PDF-specific functionality.
+
+use typst::eval::Module;
+use typst::util::Bytes;
+
+use crate::layout::BoxElem;
+use crate::prelude::*;
+use crate::visualize::RectElem;
+
+/// A module with functions that only affect PDF export.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("embed-file", EmbedElem::func());
+    scope.define("text-field", TextFieldElem::func());
+    scope.define("checkbox", CheckboxElem::func());
+    scope.define("signature", SignatureElem::func());
+    Module::new("pdf").with_scope(scope)
+}
+
+/// Attaches a file to the exported PDF.
+///
+/// The file is embedded into the PDF as an attachment that readers can
+/// extract from the PDF viewer. This is useful to ship the data a report was
+/// generated from alongside the report itself. The element doesn't produce
+/// any visible content, and formats other than PDF ignore it.
+///
+/// ## Example { #example }
+/// ```example
+/// #pdf.embed-file(
+///   "data.csv",
+///   description: "The raw measurements",
+/// )
+///
+/// The raw measurements are attached
+/// to this document.
+/// ```
+///
+/// Display: Embed File
+/// Category: meta
+#[element(Locatable, Synthesize, Show)]
+pub struct EmbedElem {
+    /// Path to the file to embed.
+    ///
+    /// The file is attached under the last component of the path.
+    #[required]
+    #[parse(
+        let Spanned { v: path, span } =
+            args.expect::<Spanned<EcoString>>("path to file")?;
+        let id = vm.location().join(&path).at(span)?;
+        let data = vm.world().file(id).at(span)?;
+        path
+    )]
+    pub path: EcoString,
+
+    /// The raw file data.
+    #[internal]
+    #[required]
+    #[parse(data)]
+    pub data: Bytes,
+
+    /// A description of the file that PDF viewers show next to it.
+    pub description: Option<EcoString>,
+
+    /// The MIME type of the file, e.g. `{"text/csv"}`.
+    pub mime_type: Option<EcoString>,
+}
+
+impl Synthesize for EmbedElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_description(self.description(styles));
+        self.push_mime_type(self.mime_type(styles));
+        Ok(())
+    }
+}
+
+impl Show for EmbedElem {
+    #[tracing::instrument(name = "EmbedElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// A fillable text field in the exported PDF.
+///
+/// In the document itself, the field is an empty box. PDF viewers display
+/// the field's value and let readers edit it.
+///
+/// ## Example { #example }
+/// ```example
+/// Name: #pdf.text-field("name", value: "Jane Doe") \
+/// Comments: \
+/// #pdf.text-field("comments", width: 100%, height: 3em, multiline: true)
+/// ```
+///
+/// Display: Text Field
+/// Category: meta
+#[element(Locatable, Synthesize, Show)]
+pub struct TextFieldElem {
+    /// The name under which the field's value is submitted. Should be unique
+    /// within the document.
+    #[required]
+    pub name: EcoString,
+
+    /// The field's default value.
+    #[default]
+    pub value: EcoString,
+
+    /// The width of the field.
+    #[default(Abs::cm(5.0).into())]
+    pub width: Rel<Length>,
+
+    /// The height of the field.
+    #[default(Em::new(1.4).into())]
+    pub height: Rel<Length>,
+
+    /// Whether the field accepts multiple lines of text.
+    #[default(false)]
+    pub multiline: bool,
+}
+
+impl Synthesize for TextFieldElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_value(self.value(styles));
+        self.push_multiline(self.multiline(styles));
+        Ok(())
+    }
+}
+
+impl Show for TextFieldElem {
+    #[tracing::instrument(name = "TextFieldElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        Ok(field_box(self.width(styles), self.height(styles)))
+    }
+}
+
+/// A checkbox in the exported PDF.
+///
+/// In the document itself, the checkbox is an empty square. PDF viewers
+/// display whether it is checked and let readers toggle it.
+///
+/// ## Example { #example }
+/// ```example
+/// #pdf.checkbox("terms") I accept the terms. \
+/// #pdf.checkbox("news", checked: true) Send me news.
+/// ```
+///
+/// Display: Checkbox
+/// Category: meta
+#[element(Locatable, Synthesize, Show)]
+pub struct CheckboxElem {
+    /// The name under which the checkbox's state is submitted. Should be
+    /// unique within the document.
+    #[required]
+    pub name: EcoString,
+
+    /// Whether the checkbox is checked by default.
+    #[default(false)]
+    pub checked: bool,
+
+    /// The side length of the checkbox.
+    #[default(Em::new(0.8).into())]
+    pub size: Length,
+}
+
+impl Synthesize for CheckboxElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_checked(self.checked(styles));
+        Ok(())
+    }
+}
+
+impl Show for CheckboxElem {
+    #[tracing::instrument(name = "CheckboxElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let size = self.size(styles);
+        Ok(field_box(size.into(), size.into()))
+    }
+}
+
+/// A placeholder for a digital signature in the exported PDF.
+///
+/// The field is left unsigned, so that it can be signed with a PDF viewer or
+/// signing tool later.
+///
+/// ## Example { #example }
+/// ```example
+/// Signed: #pdf.signature("signature")
+/// ```
+///
+/// Display: Signature
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct SignatureElem {
+    /// The name of the signature field. Should be unique within the document.
+    #[required]
+    pub name: EcoString,
+
+    /// The width of the field.
+    #[default(Abs::cm(5.0).into())]
+    pub width: Rel<Length>,
+
+    /// The height of the field.
+    #[default(Abs::cm(1.5).into())]
+    pub height: Rel<Length>,
+}
+
+impl Show for SignatureElem {
+    #[tracing::instrument(name = "SignatureElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        Ok(field_box(self.width(styles), self.height(styles)))
+    }
+}
+
+/// The visible frame of a form field.
+fn field_box(width: Rel<Length>, height: Rel<Length>) -> Content {
+    BoxElem::new()
+        .with_body(Some(
+            RectElem::new()
+                .with_width(Smart::Custom(width))
+                .with_height(Smart::Custom(height))
+                .pack(),
+        ))
+        .pack()
+}
@@ -221,14 +221,13 @@ use crate::prelude::*;
 /// ### final()
 /// Gets the value of the state at the end of the document.
 ///
-/// - location: location (positional, required)
-///   Can be any location. Why is it required then? As noted before, Typst has
-///   to evaluate parts of your code multiple times to determine the values of
-///   all state. By only allowing this method within [`locate`]($func/locate)
-///   calls, the amount of code that can depend on the method's result is
-///   reduced. If you could call `final` directly at the top level of a module,
-///   the evaluation of the whole module and its exports could depend on the
-///   state's value.
+/// - location: location (positional)
+///   Can be any location. The argument is optional and only accepted for
+///   backwards compatibility. Note that the final value is only known once the
+///   document has been laid out. Therefore, this method should be called
+///   within [`locate`]($func/locate) calls or other content that is laid out,
+///   like a page footer. This way, the amount of code that can depend on the
+///   method's result is reduced.
 ///
 /// - returns: any
 ///
@@ -267,7 +266,10 @@ impl State {
         let value = match method {
             "display" => self.display(args.eat()?).into_value(),
             "at" => self.at(&mut vm.vt, args.expect("location")?)?,
-            "final" => self.final_(&mut vm.vt, args.expect("location")?)?,
+            "final" => {
+                args.eat::<Location>()?;
+                self.final_(&mut vm.vt)?
+            }
             "update" => self.update(args.expect("value or function")?).into_value(),
             _ => bail!(span, "type state has no method `{}`", method),
         };
@@ -290,7 +292,7 @@ impl State {
 
     /// Get the value of the state at the final location.
     #[tracing::instrument(skip(self, vt))]
-    pub fn final_(self, vt: &mut Vt, _: Location) -> SourceResult<Value> {
+    pub fn final_(self, vt: &mut Vt) -> SourceResult<Value> {
         let sequence = self.sequence(vt)?;
         Ok(sequence.last().unwrap().clone())
     }
@@ -16,6 +16,8 @@ pub use self::shift::*;
 
 use rustybuzz::Tag;
 use ttf_parser::Rect;
+use typst::diag::HintedStrResult;
+use typst::eval::DateNames;
 use typst::font::{Font, FontStretch, FontStyle, FontWeight, VerticalFontMetric};
 
 use crate::layout::ParElem;
@@ -77,6 +79,13 @@ pub struct TextElem {
     /// هذا عربي.
     ///
     /// ```
+    #[parse({
+        let font: Option<Spanned<FontList>> = args.named("font")?;
+        if let Some(font) = &font {
+            check_font_list(vm, font);
+        }
+        font.map(|font| font.v)
+    })]
     #[default(FontList(vec![FontFamily::new("Linux Libertine")]))]
     pub font: FontList,
 
@@ -298,6 +307,8 @@ pub struct TextElem {
     /// - Hyphenation will use the correct patterns for the language.
     /// - [Smart quotes]($func/smartquote) turns into the correct quotes for the
     ///   language.
+    /// - The exported PDF tags the text with its language (and region), so
+    ///   that screen readers pronounce it correctly.
     /// - And all other things which are language-aware.
     ///
     /// ```example
@@ -315,6 +326,53 @@ pub struct TextElem {
     /// This lets the text processing pipeline make more informed choices.
     pub region: Option<Region>,
 
+    /// Overrides for the names that Typst generates in the text language,
+    /// like the supplement of figures or the title of the bibliography.
+    ///
+    /// The keys are the names of the elements whose name to override:
+    /// `{"image"}` (for figures), `{"table"}`, `{"raw"}` (for listings),
+    /// `{"equation"}`, `{"heading"}`, `{"outline"}`, and `{"bibliography"}`.
+    /// A name given directly applies to all languages. To only set names for
+    /// a specific language (and optionally region), nest them in a dictionary
+    /// under the language code, like `{"de"}` or `{"de-CH"}`. Names for a
+    /// specific language take precedence over general ones.
+    ///
+    /// This also makes it possible to provide complete names for languages
+    /// that Typst doesn't know yet. For this, the dictionary of a language can
+    /// also contain the names of the `months` and `weekdays`. It can then be
+    /// passed to the `names` argument of the datetime's
+    /// [`display`]($type/datetime.display) method as well.
+    ///
+    /// ```example
+    /// #let eo = (
+    ///   image: "Figuro",
+    ///   table: "Tabelo",
+    ///   months: ("januaro", "februaro", "marto", "aprilo", "majo", "junio",
+    ///     "julio", "aŭgusto", "septembro", "oktobro", "novembro", "decembro"),
+    ///   weekdays: ("lundo", "mardo", "merkredo", "ĵaŭdo", "vendredo",
+    ///     "sabato", "dimanĉo"),
+    /// )
+    ///
+    /// #set text(names: (table: "Tab.", eo: eo))
+    ///
+    /// #figure(
+    ///   table(columns: 2)[A][B],
+    ///   caption: [Results],
+    /// )
+    ///
+    /// #set text(lang: "eo")
+    /// #figure(
+    ///   table(columns: 2)[A][B],
+    ///   caption: [Rezultoj],
+    /// )
+    ///
+    /// #datetime(year: 2023, month: 3, day: 29)
+    ///   .display("[day]. [month repr:long]", names: eo)
+    /// ```
+    #[parse(parse_names(args)?)]
+    #[fold]
+    pub names: LocalNames,
+
     /// The dominant direction for text and inline objects. Possible values are:
     ///
     /// - `{auto}`: Automatically infer the direction from the `lang` property.
@@ -562,6 +620,25 @@ impl PlainText for TextElem {
     }
 }
 
+/// Warn about font families that are not available.
+fn check_font_list(vm: &mut Vm, font: &Spanned<FontList>) {
+    let world = vm.world();
+    let book = world.book();
+    let unknown: Vec<_> = font
+        .v
+        .0
+        .iter()
+        .filter(|family| book.select_family(family.as_str()).next().is_none())
+        .collect();
+
+    for family in unknown {
+        vm.vt.tracer.warn(
+            warning!(font.span, "unknown font family: {}", family.as_str())
+                .with_kind("unknown-font"),
+        );
+    }
+}
+
 /// A lowercased font family like "arial".
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct FontFamily(EcoString);
@@ -898,3 +975,152 @@ impl Fold for FontFeatures {
         self
     }
 }
+
+/// Overrides for the names of elements, optionally restricted to a language
+/// and region, and the names of months and weekdays in languages.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct LocalNames {
+    /// The names with the function name of their element.
+    names: Vec<(Option<Locale>, EcoString, EcoString)>,
+    /// The month and weekday names.
+    dates: Vec<(Locale, DateNames)>,
+}
+
+/// A language and optionally a region.
+type Locale = (Lang, Option<Region>);
+
+/// The elements whose names can be overridden.
+const NAMED_ELEMS: &[&str] =
+    &["image", "table", "raw", "equation", "heading", "outline", "bibliography"];
+
+impl LocalNames {
+    /// The overridden name of the element with the given function name in a
+    /// language and region, if any.
+    pub fn get(&self, key: &str, lang: Lang, region: Option<Region>) -> Option<&str> {
+        let find = |locale: Option<Locale>| {
+            self.names
+                .iter()
+                .find(|(l, k, _)| *l == locale && k == key)
+                .map(|(_, _, name)| name.as_str())
+        };
+
+        region
+            .and_then(|region| find(Some((lang, Some(region)))))
+            .or_else(|| find(Some((lang, None))))
+            .or_else(|| find(None))
+    }
+
+    /// Parse the names from a dictionary as passed to `text(names: ..)`.
+    fn from_dict(dict: Dict) -> HintedStrResult<Self> {
+        let mut names = vec![];
+        let mut dates = vec![];
+        for (key, value) in dict {
+            let Value::Dict(nested) = value else {
+                check_named_elem(&key)?;
+                names.push((None, key.into(), value.cast::<EcoString>()?));
+                continue;
+            };
+
+            let locale = parse_locale(&key)?;
+            let mut date_names = Dict::new();
+            for (key, value) in nested {
+                if matches!(key.as_str(), "months" | "weekdays") {
+                    date_names.insert(key, value);
+                    continue;
+                }
+
+                check_named_elem(&key)
+                    .hint("a language can also have `months` and `weekdays`")?;
+                names.push((Some(locale), key.into(), value.cast::<EcoString>()?));
+            }
+
+            if !date_names.is_empty() {
+                dates.push((locale, date_names.into_value().cast::<DateNames>()?));
+            }
+        }
+
+        Ok(Self { names, dates })
+    }
+}
+
+/// Parse the code of a language and optionally a region, like `{"de"}` or
+/// `{"de-CH"}`.
+fn parse_locale(code: &str) -> StrResult<Locale> {
+    let (lang, region) = match code.split_once('-') {
+        Some((lang, region)) => (lang, Some(region.parse::<Region>()?)),
+        None => (code, None),
+    };
+    Ok((lang.parse::<Lang>()?, region))
+}
+
+/// Ensure that the name of an element can be overridden.
+fn check_named_elem(key: &str) -> HintedStrResult<()> {
+    if NAMED_ELEMS.contains(&key) {
+        return Ok(());
+    }
+
+    let valid: Vec<_> = NAMED_ELEMS.iter().map(|name| eco_format!("`{name}`")).collect();
+    Err(eco_format!("cannot override the name of `{key}`")).hint(eco_format!(
+        "names can be overridden for {}",
+        typst::util::separated_list(&valid, "and"),
+    ))
+}
+
+/// Parse the `names` argument of the text function, keeping the hints of
+/// errors.
+fn parse_names(args: &mut Args) -> SourceResult<Option<LocalNames>> {
+    let Some(Spanned { v: dict, span }) = args.named::<Spanned<Dict>>("names")? else {
+        return Ok(None);
+    };
+    LocalNames::from_dict(dict).at(span).map(Some)
+}
+
+cast! {
+    LocalNames,
+    self => {
+        let mut dict = Dict::new();
+        let mut insert = |locale: Option<Locale>, key: Str, value: Value| {
+            let Some((lang, region)) = locale else {
+                dict.insert(key, value);
+                return;
+            };
+            let code: Str = match region {
+                Some(region) => eco_format!("{}-{}", lang.as_str(), region.as_str()),
+                None => lang.as_str().into(),
+            }
+            .into();
+            let mut nested = dict
+                .at(&code, None)
+                .ok()
+                .and_then(|v| v.clone().cast::<Dict>().ok())
+                .unwrap_or_default();
+            nested.insert(key, value);
+            dict.insert(code, nested.into_value());
+        };
+
+        for (locale, names) in self.dates.into_iter().rev() {
+            if let Value::Dict(names) = names.into_value() {
+                for (key, value) in names {
+                    insert(Some(locale), key, value);
+                }
+            }
+        }
+
+        for (locale, key, name) in self.names.into_iter().rev() {
+            insert(locale, key.into(), name.into_value());
+        }
+
+        dict.into_value()
+    },
+    values: Dict => Self::from_dict(values).map_err(|err| err.message)?,
+}
+
+impl Fold for LocalNames {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.names.extend(outer.names);
+        self.dates.extend(outer.dates);
+        self
+    }
+}
@@ -6,13 +6,14 @@ use once_cell::unsync::Lazy as UnsyncLazy;
 use syntect::highlighting as synt;
 use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
 use typst::diag::FileError;
+use typst::geom::Transform;
 use typst::syntax::{self, LinkedNode};
 use typst::util::Bytes;
 
 use super::{
     FontFamily, FontList, Hyphenate, LinebreakElem, SmartQuoteElem, TextElem, TextSize,
 };
-use crate::layout::BlockElem;
+use crate::layout::{BlockElem, ParElem};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 
@@ -134,6 +135,47 @@ pub struct RawElem {
     #[default(HorizontalAlign(GenAlign::Start))]
     pub align: HorizontalAlign,
 
+    /// How to deal with lines that are wider than the raw block. This option
+    /// is ignored if this is not a raw block.
+    ///
+    /// - `{"visible"}`: Lines are broken like normal text and words that are
+    ///   too long extend past the margin.
+    /// - `{"wrap"}`: Long lines are broken up, within words if necessary.
+    ///   Each continuation line starts with the [`marker`]($func/raw.marker)
+    ///   and is indented past the leading whitespace of its source line.
+    /// - `{"shrink"}`: The whole block is scaled down until its longest line
+    ///   fits.
+    ///
+    /// ````example
+    /// #set page(width: 160pt)
+    /// #set raw(overflow: "wrap")
+    ///
+    /// ```rust
+    /// fn main() {
+    ///     println!("This line is far too long");
+    /// }
+    /// ```
+    /// ````
+    #[default(RawOverflow::Visible)]
+    pub overflow: RawOverflow,
+
+    /// The marker to show at the start of continuation lines when long lines
+    /// are wrapped.
+    ///
+    /// ````example
+    /// #set page(width: 160pt)
+    /// #set raw(
+    ///   overflow: "wrap",
+    ///   marker: text(gray)[→],
+    /// )
+    ///
+    /// ```rust
+    /// let message = "This line is far too long";
+    /// ```
+    /// ````
+    #[default(TextElem::packed("↪"))]
+    pub marker: Content,
+
     /// One or multiple additional syntax definitions to load. The syntax
     /// definitions should be in the `sublime-syntax` file format.
     ///
@@ -234,24 +276,32 @@ impl Show for RawElem {
             .map(to_typst)
             .map_or(Color::BLACK, Color::from);
 
-        let mut realized = if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
+        // The highlighted pieces, with a linebreak between each pair of lines.
+        let mut seq = vec![];
+        if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
             let root = match lang.as_deref() {
                 Some("typc") => syntax::parse_code(&text),
                 _ => syntax::parse(&text),
             };
 
-            let mut seq = vec![];
             let highlighter = synt::Highlighter::new(theme);
             highlight_themed(
                 &LinkedNode::new(&root),
                 vec![],
                 &highlighter,
                 &mut |node, style| {
-                    seq.push(styled(&text[node.range()], foreground.into(), style));
+                    for (i, piece) in text[node.range()].split('\n').enumerate() {
+                        if i != 0 {
+                            seq.push(LinebreakElem::new().pack());
+                        }
+
+                        let piece = piece.strip_suffix('\r').unwrap_or(piece);
+                        if !piece.is_empty() {
+                            seq.push(styled(piece, foreground.into(), style));
+                        }
+                    }
                 },
             );
-
-            Content::sequence(seq)
         } else if let Some((syntax_set, syntax)) = lang.and_then(|token| {
             SYNTAXES
                 .find_syntax_by_token(&token)
@@ -262,7 +312,6 @@ impl Show for RawElem {
                         .map(|syntax| (&**extra_syntaxes, syntax))
                 })
         }) {
-            let mut seq = vec![];
             let mut highlighter = syntect::easy::HighlightLines::new(syntax, theme);
             for (i, line) in text.lines().enumerate() {
                 if i != 0 {
@@ -275,19 +324,41 @@ impl Show for RawElem {
                     seq.push(styled(piece, foreground.into(), style));
                 }
             }
-
-            Content::sequence(seq)
         } else {
-            TextElem::packed(text)
-        };
+            for (i, line) in text.split('\n').enumerate() {
+                if i != 0 {
+                    seq.push(LinebreakElem::new().pack());
+                }
+
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                if !line.is_empty() {
+                    seq.push(TextElem::packed(line));
+                }
+            }
+        }
 
-        if self.block(styles) {
-            // Align the text before inserting it into the block.
-            realized = realized.aligned(Axes::with_x(Some(self.align(styles).into())));
-            realized = BlockElem::new().with_body(Some(realized)).pack();
+        if !self.block(styles) {
+            return Ok(Content::sequence(seq));
         }
 
-        Ok(realized)
+        // Align the text before inserting it into the block.
+        let align = Axes::with_x(Some(self.align(styles).into()));
+        let realized = match self.overflow(styles) {
+            RawOverflow::Visible => Content::sequence(seq).aligned(align),
+            RawOverflow::Wrap => {
+                let lines = seq
+                    .split(|piece| piece.is::<LinebreakElem>())
+                    .map(|line| Content::sequence(line.iter().cloned()))
+                    .collect();
+                RawWrapElem::new(lines).pack().aligned(align)
+            }
+            RawOverflow::Shrink => {
+                let body = Content::sequence(seq).aligned(align);
+                RawShrinkElem::new(body, self.align(styles)).pack()
+            }
+        };
+
+        Ok(BlockElem::new().with_body(Some(realized)).pack())
     }
 }
 
@@ -340,6 +411,192 @@ impl PlainText for RawElem {
     }
 }
 
+/// How a raw block deals with lines that are too long.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum RawOverflow {
+    /// Lines extend past the edge of the block.
+    Visible,
+    /// Lines are broken up with a continuation marker.
+    Wrap,
+    /// The block is scaled down until all lines fit.
+    Shrink,
+}
+
+/// The lines of a raw block that wraps long lines.
+///
+/// Display: Wrapped Raw Lines
+/// Category: text
+#[element(Layout)]
+pub struct RawWrapElem {
+    /// The highlighted lines.
+    #[required]
+    pub lines: Vec<Content>,
+}
+
+impl Layout for RawWrapElem {
+    #[tracing::instrument(name = "RawWrapElem::layout", skip_all)]
+    fn layout(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        mut regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let leading = ParElem::leading_in(styles);
+        let width = regions.size.x;
+        let marker = RawElem::marker_in(styles)
+            .layout(vt, styles, Regions::one(regions.base(), Axes::splat(false)))?
+            .into_frame();
+
+        let mut frames = vec![];
+        let mut output = Frame::new(Size::with_x(width));
+        let mut fresh = true;
+        for line in self.lines() {
+            let frame = layout_wrapped_line(vt, styles, &line, &marker, width)?;
+
+            // Move on to the next region if the line doesn't fit anymore.
+            let mut offset = output.height();
+            if !fresh {
+                offset += leading;
+            }
+
+            if offset + frame.height() > regions.size.y
+                && !fresh
+                && (!regions.backlog.is_empty() || regions.last.is_some())
+            {
+                frames.push(std::mem::replace(
+                    &mut output,
+                    Frame::new(Size::with_x(width)),
+                ));
+                regions.next();
+                offset = Abs::zero();
+            }
+
+            output.size_mut().y = offset + frame.height();
+            output.push_frame(Point::with_y(offset), frame);
+            fresh = false;
+        }
+
+        frames.push(output);
+        Ok(Fragment::frames(frames))
+    }
+}
+
+/// Layout a single source line of a raw block, breaking it up if it is too
+/// long and adding the marker in front of each continuation line.
+fn layout_wrapped_line(
+    vt: &mut Vt,
+    styles: StyleChain,
+    line: &Content,
+    marker: &Frame,
+    width: Abs,
+) -> SourceResult<Frame> {
+    // Continuation lines are indented past the line's leading whitespace and
+    // the marker.
+    let text = line.plain_text();
+    let space = &text[..text.len() - text.trim_start().len()];
+    let indent = if space.is_empty() {
+        Abs::zero()
+    } else {
+        measure(vt, styles, &eco_format!("{space}x"))? - measure(vt, styles, "x")?
+    };
+
+    let pod = Regions::one(Size::new(width, Abs::inf()), Axes::new(true, false));
+    let mut frame = line
+        .clone()
+        .styled(ParElem::set_hanging_indent((indent + marker.width()).into()))
+        .styled(ParElem::set_emergency(true))
+        .layout(vt, styles, pod)?
+        .into_frame();
+
+    // An empty line is as high as the marker.
+    if frame.is_empty() {
+        let mut empty = Frame::new(Size::new(width, marker.height()));
+        empty.set_baseline(marker.baseline());
+        return Ok(empty);
+    }
+
+    let mut baselines = vec![];
+    collect_baselines(&frame, Point::zero(), &mut baselines);
+    baselines.sort();
+    baselines.dedup_by(|a, b| a.approx_eq(*b));
+
+    for &baseline in baselines.iter().skip(1) {
+        let pos = Point::new(indent, baseline - marker.baseline());
+        frame.push_frame(pos, marker.clone());
+    }
+
+    Ok(frame)
+}
+
+/// Collect the baselines of all text in a frame.
+fn collect_baselines(frame: &Frame, offset: Point, baselines: &mut Vec<Abs>) {
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                collect_baselines(&group.frame, offset + *pos, baselines)
+            }
+            FrameItem::Text(_) => baselines.push(offset.y + pos.y),
+            _ => {}
+        }
+    }
+}
+
+/// Measure the width of a piece of text.
+fn measure(vt: &mut Vt, styles: StyleChain, text: &str) -> SourceResult<Abs> {
+    let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+    let frame = TextElem::packed(text).layout(vt, styles, pod)?.into_frame();
+    Ok(frame.width())
+}
+
+/// The body of a raw block that is scaled down to fit.
+///
+/// Display: Shrunk Raw Block
+/// Category: text
+#[element(Layout)]
+pub struct RawShrinkElem {
+    /// The highlighted and aligned text.
+    #[required]
+    pub body: Content,
+
+    /// How to align the body in the block if it is narrower than the block.
+    #[required]
+    pub align: HorizontalAlign,
+}
+
+impl Layout for RawShrinkElem {
+    #[tracing::instrument(name = "RawShrinkElem::layout", skip_all)]
+    fn layout(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let pod =
+            Regions::one(Size::new(Abs::inf(), regions.base().y), Axes::splat(false));
+        let mut frame = self.body().layout(vt, styles, pod)?.into_frame();
+
+        let width = regions.size.x;
+        if width.is_finite() && frame.width() > width {
+            let scale = Ratio::new(width / frame.width());
+            let size = frame.size() * scale.get();
+            frame.transform(Transform::scale(scale, scale));
+            frame.set_size(size);
+        }
+
+        // Fill the block's width, placing the body according to its alignment.
+        if regions.expand.x && width.is_finite() {
+            let align = GenAlign::from(self.align()).resolve(styles);
+            let mut output = Frame::new(Size::new(width, frame.height()));
+            output.set_baseline(frame.baseline());
+            let x = align.position(width - frame.width());
+            output.push_frame(Point::with_x(x), frame);
+            frame = output;
+        }
+
+        Ok(Fragment::frame(frame))
+    }
+}
+
 /// Highlight a syntax node in a theme by calling `f` with ranges and their
 /// styles.
 fn highlight_themed<F>(
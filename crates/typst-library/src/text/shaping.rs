@@ -288,6 +288,7 @@ impl<'a> ShapedText<'a> {
                 font,
                 size: self.size,
                 lang,
+                region: self.region,
                 fill: fill.clone(),
                 text: self.text[range.start - self.base..range.end - self.base].into(),
                 glyphs,
@@ -56,7 +56,9 @@ pub struct ImageElem {
     /// The height of the image.
     pub height: Smart<Rel<Length>>,
 
-    /// A text describing the image.
+    /// A text describing the image for readers who cannot see it.
+    ///
+    /// It is exported as the image's alternative description.
     pub alt: Option<EcoString>,
 
     /// How the image should adjust itself to a given area.
@@ -95,6 +97,14 @@ impl Layout for ImageElem {
         )
         .at(self.span())?;
 
+        if image.is_animated() {
+            vt.tracer.warn(
+                warning!(self.span(), "animated images are not supported")
+                    .with_hint("only the first frame is shown".into())
+                    .with_kind("unsupported-image"),
+            );
+        }
+
         let sizing = Axes::new(self.width(styles), self.height(styles));
         let region = sizing
             .zip(regions.base())
@@ -2,7 +2,9 @@
 
 mod image;
 mod line;
+pub mod palette;
 mod path;
+pub mod placeholder;
 mod polygon;
 mod shape;
 
@@ -24,6 +26,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define("circle", CircleElem::func());
     global.define("polygon", PolygonElem::func());
     global.define("path", PathElem::func());
+    global.define("placeholder", placeholder::module());
+    global.define("palette", palette::module());
     global.define("black", Color::BLACK);
     global.define("gray", Color::GRAY);
     global.define("silver", Color::SILVER);
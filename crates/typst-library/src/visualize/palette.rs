@@ -0,0 +1,92 @@
+//! Curated color palettes for charts and diagrams.
+
+use typst::eval::Module;
+
+use crate::prelude::*;
+
+/// A module with arrays of colors that work well together.
+///
+/// The categorical palettes distinguish unrelated series of data, the
+/// sequential ones encode ordered values from light to dark. The `okabe-ito`,
+/// `tol-bright` and `viridis` palettes stay distinguishable for readers with
+/// color vision deficiencies.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    for &(name, colors) in PALETTES {
+        scope.define(name, palette(colors));
+    }
+    Module::new("palette").with_scope(scope)
+}
+
+/// The palettes in the module, as hexadecimal RGB values.
+const PALETTES: &[(&str, &[u32])] = &[
+    // The categorical palette from D3.
+    (
+        "category10",
+        &[
+            0x1f77b4, 0xff7f0e, 0x2ca02c, 0xd62728, 0x9467bd, 0x8c564b, 0xe377c2,
+            0x7f7f7f, 0xbcbd22, 0x17becf,
+        ],
+    ),
+    // The default categorical palette from Tableau.
+    (
+        "tableau10",
+        &[
+            0x4e79a7, 0xf28e2b, 0xe15759, 0x76b7b2, 0x59a14f, 0xedc948, 0xb07aa1,
+            0xff9da7, 0x9c755f, 0xbab0ac,
+        ],
+    ),
+    // The colorblind-safe palette by Masataka Okabe and Kei Ito.
+    (
+        "okabe-ito",
+        &[0x000000, 0xe69f00, 0x56b4e9, 0x009e73, 0xf0e442, 0x0072b2, 0xd55e00, 0xcc79a7],
+    ),
+    // The colorblind-safe bright palette by Paul Tol.
+    (
+        "tol-bright",
+        &[0x4477aa, 0xee6677, 0x228833, 0xccbb44, 0x66ccee, 0xaa3377, 0xbbbbbb],
+    ),
+    // The perceptually uniform sequential palette from matplotlib.
+    (
+        "viridis",
+        &[
+            0x440154, 0x482878, 0x3e4a89, 0x31688e, 0x26828e, 0x1f9e89, 0x35b779,
+            0x6dcd59, 0xb4de2c, 0xfde725,
+        ],
+    ),
+    // The sequential blues from ColorBrewer.
+    (
+        "blues",
+        &[
+            0xf7fbff, 0xdeebf7, 0xc6dbef, 0x9ecae1, 0x6baed6, 0x4292c6, 0x2171b5,
+            0x08519c, 0x08306b,
+        ],
+    ),
+    // The sequential greens from ColorBrewer.
+    (
+        "greens",
+        &[
+            0xf7fcf5, 0xe5f5e0, 0xc7e9c0, 0xa1d99b, 0x74c476, 0x41ab5d, 0x238b45,
+            0x006d2c, 0x00441b,
+        ],
+    ),
+    // The sequential reds from ColorBrewer.
+    (
+        "reds",
+        &[
+            0xfff5f0, 0xfee0d2, 0xfcbba1, 0xfc9272, 0xfb6a4a, 0xef3b2c, 0xcb181d,
+            0xa50f15, 0x67000d,
+        ],
+    ),
+];
+
+/// Turn hexadecimal RGB values into an array of colors.
+fn palette(colors: &[u32]) -> Array {
+    colors
+        .iter()
+        .map(|&hex| {
+            let [_, r, g, b] = hex.to_be_bytes();
+            Value::Color(RgbaColor::new(r, g, b, u8::MAX).into())
+        })
+        .collect()
+}
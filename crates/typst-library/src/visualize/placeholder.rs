@@ -0,0 +1,143 @@
+//! Placeholder content for previews and examples.
+
+use typst::eval::{Datetime, Module, Rng};
+
+use super::RectElem;
+use crate::prelude::*;
+use crate::text::TextElem;
+
+/// A module with functions that generate placeholder content.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define("image", image_func());
+    scope.define("name", name_func());
+    scope.define("date", date_func());
+    Module::new("placeholder").with_scope(scope)
+}
+
+/// Creates a placeholder for an image.
+///
+/// The placeholder is a filled box that is labelled with its dimensions. It
+/// can stand in for images that are not available yet, without having to ship
+/// any image files.
+///
+/// ## Example { #example }
+/// ```example
+/// #placeholder.image(width: 3cm, height: 2cm)
+/// #placeholder.image(width: 3cm, height: 2cm, seed: 1)
+/// #placeholder.image(width: 50%, height: 1cm, fill: aqua)
+/// ```
+///
+/// Display: Placeholder Image
+/// Category: visualize
+#[func]
+pub fn image(
+    /// The width of the placeholder.
+    #[named]
+    #[default(Abs::cm(4.0).into())]
+    width: Rel<Length>,
+    /// The height of the placeholder.
+    #[named]
+    #[default(Abs::cm(3.0).into())]
+    height: Rel<Length>,
+    /// How to fill the placeholder. If none is given, a light color is picked
+    /// based on the seed.
+    #[named]
+    #[default]
+    fill: Option<Paint>,
+    /// The seed that determines the fill color.
+    #[named]
+    #[default(0)]
+    seed: i64,
+) -> Content {
+    let fill = fill.unwrap_or_else(|| {
+        let mut rng = Rng::new(seed);
+        let mut channel = || 180 + rng.below(60) as u8;
+        RgbaColor::new(channel(), channel(), channel(), 255).into()
+    });
+
+    let label =
+        eco_format!("{} × {}", width.into_value().repr(), height.into_value().repr());
+
+    let body = TextElem::packed(label)
+        .styled(TextElem::set_fill(Color::BLACK.lighten(Ratio::new(0.3)).into()))
+        .aligned(Axes::new(Some(Align::Center.into()), Some(Align::Horizon.into())));
+
+    RectElem::new()
+        .with_width(Smart::Custom(width))
+        .with_height(Smart::Custom(height))
+        .with_fill(Some(fill))
+        .with_body(Some(body))
+        .pack()
+}
+
+/// Creates a fake person's name.
+///
+/// The name only depends on the seed, so it is the same in each compilation.
+///
+/// ## Example { #example }
+/// ```example
+/// Written by #placeholder.name() \
+/// Reviewed by #placeholder.name(seed: 7)
+/// ```
+///
+/// Display: Placeholder Name
+/// Category: visualize
+#[func]
+pub fn name(
+    /// The seed that determines the name.
+    #[named]
+    #[default(0)]
+    seed: i64,
+) -> Str {
+    let mut rng = Rng::new(seed);
+    let first = pick(&mut rng, FIRST_NAMES);
+    let last = pick(&mut rng, LAST_NAMES);
+    eco_format!("{first} {last}").into()
+}
+
+/// Pick a random item from a non-empty list.
+fn pick<'a>(rng: &mut Rng, items: &[&'a str]) -> &'a str {
+    items[rng.below(items.len() as u64) as usize]
+}
+
+/// Creates a fake date.
+///
+/// The date lies between the years 1970 and 2029 and only depends on the
+/// seed, so it is the same in each compilation.
+///
+/// ## Example { #example }
+/// ```example
+/// #placeholder.date().display() \
+/// #placeholder.date(seed: 3).display("[day].[month].[year]")
+/// ```
+///
+/// Display: Placeholder Date
+/// Category: visualize
+#[func]
+pub fn date(
+    /// The seed that determines the date.
+    #[named]
+    #[default(0)]
+    seed: i64,
+) -> Datetime {
+    let mut rng = Rng::new(seed);
+    let year = 1970 + rng.below(60) as i32;
+    let month = 1 + rng.below(12) as u8;
+    let day = 1 + rng.below(28) as u8;
+    Datetime::from_ymd(year, month, day).unwrap()
+}
+
+/// First names for placeholder names.
+const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Amara", "Carlos", "Chen", "Emma", "Farah", "Grace", "Hiro", "Ingrid",
+    "Jonas", "Kofi", "Lena", "Mateo", "Mei", "Nadia", "Olga", "Priya", "Ravi", "Sofia",
+    "Tomás", "Yara",
+];
+
+/// Last names for placeholder names.
+const LAST_NAMES: &[&str] = &[
+    "Andersen", "Baker", "Costa", "Dubois", "Eriksson", "Fischer", "García", "Haddad",
+    "Ivanova", "Jensen", "Kowalski", "Larsen", "Mensah", "Nakamura", "Okafor", "Patel",
+    "Rossi", "Schmidt", "Tanaka", "Weber",
+];
@@ -1246,6 +1246,11 @@ impl Named {
     pub fn expr_ident(&self) -> Option<Ident> {
         self.0.cast_last_match()
     }
+
+    /// The right-hand side of the pair as a pattern: `x`, `_` or `(a, b)`.
+    pub fn pattern(&self) -> Pattern {
+        self.0.cast_last_match().unwrap_or_default()
+    }
 }
 
 node! {
@@ -1359,6 +1364,15 @@ impl Binary {
     pub fn rhs(&self) -> Expr {
         self.0.cast_last_match().unwrap_or_default()
     }
+
+    /// The identifier and its default value if this is a binding with a
+    /// default in a destructuring pattern: `x = 1`.
+    pub fn default_binding(&self) -> Option<(Ident, Expr)> {
+        match (self.op(), self.lhs()) {
+            (BinOp::Assign, Expr::Ident(ident)) => Some((ident, self.rhs())),
+            _ => Option::None,
+        }
+    }
 }
 
 /// A binary operator.
@@ -1688,14 +1702,49 @@ impl Destructuring {
         self.0.children().filter_map(SyntaxNode::cast)
     }
 
-    // Returns a list of all identifiers in the pattern.
-    pub fn idents(&self) -> impl Iterator<Item = Ident> + '_ {
-        self.bindings().filter_map(|binding| match binding {
-            DestructuringKind::Normal(Expr::Ident(ident)) => Some(ident),
-            DestructuringKind::Sink(spread) => spread.name(),
-            DestructuringKind::Named(named) => named.expr_ident(),
-            _ => Option::None,
-        })
+    // Returns a list of all identifiers in the pattern, including those of
+    // nested patterns.
+    pub fn idents(&self) -> Vec<Ident> {
+        let mut idents = vec![];
+        for binding in self.bindings() {
+            match binding {
+                DestructuringKind::Normal(expr) => idents.extend(expr_binding(&expr)),
+                DestructuringKind::Sink(spread) => idents.extend(spread.name()),
+                DestructuringKind::Named(named) => match named.pattern() {
+                    Pattern::Normal(expr) => idents.extend(expr_binding(&expr)),
+                    pattern => idents.extend(pattern.idents()),
+                },
+                DestructuringKind::Nested(nested) => idents.extend(nested.idents()),
+                DestructuringKind::Placeholder(_) => {}
+            }
+        }
+        idents
+    }
+
+    // Returns a list of all default values in the pattern, including those of
+    // nested patterns.
+    pub fn defaults(&self) -> Vec<Expr> {
+        let mut defaults = vec![];
+        for binding in self.bindings() {
+            let pattern = match binding {
+                DestructuringKind::Normal(expr) => Pattern::Normal(expr),
+                DestructuringKind::Named(named) => named.pattern(),
+                DestructuringKind::Nested(nested) => Pattern::Destructuring(nested),
+                _ => continue,
+            };
+            defaults.extend(pattern.defaults());
+        }
+        defaults
+    }
+}
+
+/// The identifier bound by an expression in a destructuring pattern: `x` or
+/// `x = 1`.
+fn expr_binding(expr: &Expr) -> Option<Ident> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.clone()),
+        Expr::Binary(binary) => binary.default_binding().map(|(ident, _)| ident),
+        _ => Option::None,
     }
 }
 
@@ -1710,6 +1759,8 @@ pub enum DestructuringKind {
     Named(Named),
     /// A placeholder: `_`.
     Placeholder(Underscore),
+    /// A nested destructuring pattern: `(x, y)`.
+    Nested(Destructuring),
 }
 
 impl AstNode for DestructuringKind {
@@ -1718,6 +1769,7 @@ impl AstNode for DestructuringKind {
             SyntaxKind::Named => node.cast().map(Self::Named),
             SyntaxKind::Spread => node.cast().map(Self::Sink),
             SyntaxKind::Underscore => node.cast().map(Self::Placeholder),
+            SyntaxKind::Destructuring => node.cast().map(Self::Nested),
             _ => node.cast().map(Self::Normal),
         }
     }
@@ -1728,6 +1780,7 @@ impl AstNode for DestructuringKind {
             Self::Named(v) => v.as_untyped(),
             Self::Sink(v) => v.as_untyped(),
             Self::Placeholder(v) => v.as_untyped(),
+            Self::Nested(v) => v.as_untyped(),
         }
     }
 }
@@ -1766,7 +1819,20 @@ impl Pattern {
     pub fn idents(&self) -> Vec<Ident> {
         match self {
             Pattern::Normal(Expr::Ident(ident)) => vec![ident.clone()],
-            Pattern::Destructuring(destruct) => destruct.idents().collect(),
+            Pattern::Destructuring(destruct) => destruct.idents(),
+            _ => vec![],
+        }
+    }
+
+    // Returns a list of all default values in the pattern.
+    pub fn defaults(&self) -> Vec<Expr> {
+        match self {
+            Pattern::Normal(Expr::Binary(binary)) => binary
+                .default_binding()
+                .map(|(_, default)| default)
+                .into_iter()
+                .collect(),
+            Pattern::Destructuring(destruct) => destruct.defaults(),
             _ => vec![],
         }
     }
@@ -1969,6 +2035,15 @@ impl ModuleImport {
         self.0.cast_first_match().unwrap_or_default()
     }
 
+    /// The name under which the module is bound: `e` in
+    /// `import "environment.typ" as e`.
+    pub fn new_name(&self) -> Option<Ident> {
+        self.0
+            .children()
+            .skip_while(|node| node.kind() != SyntaxKind::As)
+            .find_map(SyntaxNode::cast)
+    }
+
     /// The items to be imported.
     pub fn imports(&self) -> Option<Imports> {
         self.0.children().find_map(|node| match node.kind() {
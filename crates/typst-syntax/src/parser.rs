@@ -951,7 +951,7 @@ fn item(p: &mut Parser, keyed: bool) -> SyntaxKind {
     }
 
     if !p.eat_if(SyntaxKind::Underscore) {
-        code_expr(p);
+        code_expr_or_pattern(p);
     }
 
     let kind = match p.node(m).map(SyntaxNode::kind) {
@@ -1138,6 +1138,9 @@ fn module_import(p: &mut Parser) {
     let m = p.marker();
     p.assert(SyntaxKind::Import);
     code_expr(p);
+    if p.eat_if(SyntaxKind::As) {
+        p.expect(SyntaxKind::Ident);
+    }
     if p.eat_if(SyntaxKind::Colon) && !p.eat_if(SyntaxKind::Star) {
         import_items(p);
     }
@@ -1355,7 +1358,7 @@ fn validate_pattern<'a>(
     forbid_expressions: bool,
 ) {
     let mut used_spread = false;
-    for child in children {
+    for child in children.filter(|child| !child.kind().is_trivia()) {
         match child.kind() {
             SyntaxKind::Ident => {
                 if !used.insert(child.text().clone()) {
@@ -1399,17 +1402,51 @@ fn validate_pattern<'a>(
                     child.make_erroneous();
                 }
 
-                if forbid_expressions {
-                    let Some(within) = child.children_mut().last_mut() else { return };
-                    if within.kind() != SyntaxKind::Ident
-                        && within.kind() != SyntaxKind::Underscore
-                    {
-                        within.convert_to_error(eco_format!(
-                            "expected identifier, found {}",
-                            within.kind().name(),
-                        ));
+                let Some(within) = child.children_mut().last_mut() else { return };
+                if matches!(
+                    within.kind(),
+                    SyntaxKind::Array | SyntaxKind::Dict | SyntaxKind::Destructuring
+                ) {
+                    validate_pattern(
+                        within.children_mut().iter_mut(),
+                        used,
+                        forbid_expressions,
+                    );
+                    within.convert_to_kind(SyntaxKind::Destructuring);
+                    if within.children().any(SyntaxNode::erroneous) {
+                        within.make_erroneous();
                         child.make_erroneous();
                     }
+                } else if forbid_expressions
+                    && within.kind() != SyntaxKind::Ident
+                    && within.kind() != SyntaxKind::Underscore
+                    && !is_default_binding(within)
+                {
+                    within.convert_to_error(eco_format!(
+                        "expected identifier, found {}",
+                        within.kind().name(),
+                    ));
+                    child.make_erroneous();
+                }
+            }
+            SyntaxKind::Array | SyntaxKind::Dict | SyntaxKind::Destructuring => {
+                validate_pattern(
+                    child.children_mut().iter_mut(),
+                    used,
+                    forbid_expressions,
+                );
+                child.convert_to_kind(SyntaxKind::Destructuring);
+                if child.children().any(SyntaxNode::erroneous) {
+                    child.make_erroneous();
+                }
+            }
+            SyntaxKind::Binary if is_default_binding(child) => {
+                let Some(within) = child.children_mut().first_mut() else { continue };
+                if !used.insert(within.text().clone()) {
+                    within.convert_to_error(
+                        "at most one binding per identifier is allowed",
+                    );
+                    child.make_erroneous();
                 }
             }
             SyntaxKind::LeftParen
@@ -1428,6 +1465,13 @@ fn validate_pattern<'a>(
     }
 }
 
+/// Whether the node is a binding with a default value: `x = 1`.
+fn is_default_binding(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::Binary
+        && node.children().next().map(SyntaxNode::kind) == Some(SyntaxKind::Ident)
+        && node.children().any(|child| child.kind() == SyntaxKind::Eq)
+}
+
 /// Manages parsing of a stream of tokens.
 struct Parser<'s> {
     text: &'s str,
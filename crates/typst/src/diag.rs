@@ -98,6 +98,8 @@ pub struct SourceDiagnostic {
     /// Additonal hints to the user, indicating how this problem could be avoided
     /// or worked around.
     pub hints: Vec<EcoString>,
+    /// The kind of warning, e.g. `deprecated`, by which it can be suppressed.
+    pub kind: Option<EcoString>,
 }
 
 /// The severity of a [`SourceDiagnostic`].
@@ -118,6 +120,7 @@ impl SourceDiagnostic {
             trace: vec![],
             message: message.into(),
             hints: vec![],
+            kind: None,
         }
     }
 
@@ -129,6 +132,7 @@ impl SourceDiagnostic {
             trace: vec![],
             message: message.into(),
             hints: vec![],
+            kind: None,
         }
     }
 
@@ -143,6 +147,23 @@ impl SourceDiagnostic {
         self.hints.extend(hints);
         self
     }
+
+    /// Suggests a similar name for a misspelled one and, unless the span is
+    /// detached, points to where that name is defined.
+    pub fn with_suggestion(mut self, name: &str, definition: Span) -> Self {
+        self.hints.push(eco_format!("did you mean `{name}`?"));
+        if !definition.is_detached() {
+            self.trace
+                .push(Spanned::new(Tracepoint::Definition(name.into()), definition));
+        }
+        self
+    }
+
+    /// Sets the kind of the warning.
+    pub fn with_kind(mut self, kind: impl Into<EcoString>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
 }
 
 impl From<SyntaxError> for SourceDiagnostic {
@@ -153,6 +174,7 @@ impl From<SyntaxError> for SourceDiagnostic {
             message: error.message,
             trace: vec![],
             hints: error.hints,
+            kind: None,
         }
     }
 }
@@ -166,6 +188,8 @@ pub enum Tracepoint {
     Show(EcoString),
     /// A module import.
     Import,
+    /// The definition of a suggested name.
+    Definition(EcoString),
 }
 
 impl Display for Tracepoint {
@@ -183,6 +207,9 @@ impl Display for Tracepoint {
             Tracepoint::Import => {
                 write!(f, "error occurred while importing this module")
             }
+            Tracepoint::Definition(name) => {
+                write!(f, "`{name}` is defined here")
+            }
         }
     }
 }
@@ -255,6 +282,12 @@ pub struct HintedString {
     pub hints: Vec<EcoString>,
 }
 
+impl From<EcoString> for HintedString {
+    fn from(message: EcoString) -> Self {
+        Self { message, hints: vec![] }
+    }
+}
+
 impl<T> At<T> for Result<T, HintedString> {
     fn at(self, span: Span) -> SourceResult<T> {
         self.map_err(|diags| {
@@ -380,6 +413,9 @@ pub type PackageResult<T> = Result<T, PackageError>;
 pub enum PackageError {
     /// The specified package does not exist.
     NotFound(PackageSpec),
+    /// The specified package is not available locally and downloading is
+    /// disabled.
+    Offline(PackageSpec),
     /// Failed to retrieve the package through the network.
     NetworkFailed,
     /// The package archive was malformed.
@@ -396,6 +432,9 @@ impl Display for PackageError {
             Self::NotFound(spec) => {
                 write!(f, "package not found (searched for {spec})",)
             }
+            Self::Offline(spec) => {
+                write!(f, "package not available offline (searched for {spec})")
+            }
             Self::NetworkFailed => f.pad("failed to load package (network failed)"),
             Self::MalformedArchive => f.pad("failed to load package (archive malformed)"),
             Self::Other => f.pad("failed to load package"),
@@ -6,7 +6,7 @@ use std::ops::Range;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 
 use crate::eval::{cast, dict, Dict, Value};
 use crate::font::Font;
@@ -115,6 +115,12 @@ impl Frame {
     pub fn items(&self) -> std::slice::Iter<'_, (Point, FrameItem)> {
         self.items.iter()
     }
+
+    /// A mutable iterator over the items inside this frame alongside their
+    /// positions relative to the top-left of the frame.
+    pub fn items_mut(&mut self) -> std::slice::IterMut<'_, (Point, FrameItem)> {
+        Arc::make_mut(&mut self.items).iter_mut()
+    }
 }
 
 /// Insert items and subframes.
@@ -471,6 +477,8 @@ pub struct TextItem {
     pub fill: Paint,
     /// The natural language of the text.
     pub lang: Lang,
+    /// The region of the text's language, if any.
+    pub region: Option<Region>,
     /// The item's plain text.
     pub text: EcoString,
     /// The glyphs.
@@ -482,6 +490,14 @@ impl TextItem {
     pub fn width(&self) -> Abs {
         self.glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size)
     }
+
+    /// The BCP 47 language tag of the text, like `de-CH`.
+    pub fn lang_tag(&self) -> EcoString {
+        match self.region {
+            Some(region) => eco_format!("{}-{}", self.lang.as_str(), region.as_str()),
+            None => self.lang.as_str().into(),
+        }
+    }
 }
 
 impl Debug for TextItem {
@@ -668,6 +684,11 @@ pub enum Meta {
     Elem(Content),
     /// The numbering of the current page.
     PageNumbering(Value),
+    /// How PDF viewers should label the current page.
+    PdfPageLabel(PdfPageLabel),
+    /// The structural role of the content within the area this metadata is
+    /// attached to, used to tag exported PDFs.
+    Structure(Structure),
     /// Indicates that content should be hidden. This variant doesn't appear
     /// in the final frames as it is removed alongside the content that should
     /// be hidden.
@@ -684,11 +705,59 @@ impl Debug for Meta {
             Self::Link(dest) => write!(f, "Link({dest:?})"),
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::PageNumbering(value) => write!(f, "PageNumbering({value:?})"),
+            Self::PdfPageLabel(label) => write!(f, "PdfPageLabel({label:?})"),
+            Self::Structure(structure) => write!(f, "Structure({structure:?})"),
             Self::Hide => f.pad("Hide"),
         }
     }
 }
 
+/// A structural role of laid out content that isn't an element of its own.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Structure {
+    /// The first line of a paragraph.
+    Paragraph,
+    /// A further line of the paragraph that started before it.
+    Line,
+    /// A bullet list or numbered list.
+    List,
+    /// The marker of a list item.
+    ListLabel,
+    /// The body of a list item.
+    ListBody,
+    /// A table.
+    Table,
+    /// A table cell in the given row.
+    TableCell(usize),
+}
+
+/// A page label, as displayed by PDF viewers in their page field.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PdfPageLabel {
+    /// Text that precedes the number, e.g. `"A-"`.
+    pub prefix: Option<EcoString>,
+    /// How to display the number. If `None`, the label only consists of the
+    /// prefix.
+    pub style: Option<PdfPageLabelStyle>,
+    /// The number of the page.
+    pub number: NonZeroUsize,
+}
+
+/// A numbering style for PDF page labels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PdfPageLabelStyle {
+    /// Decimal arabic numerals (1, 2, 3).
+    Arabic,
+    /// Lowercase roman numerals (i, ii, iii).
+    LowerRoman,
+    /// Uppercase roman numerals (I, II, III).
+    UpperRoman,
+    /// Lowercase letters (a to z, then aa to zz, and so on).
+    LowerAlpha,
+    /// Uppercase letters (A to Z, then AA to ZZ, and so on).
+    UpperAlpha,
+}
+
 /// A link destination.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Destination {
@@ -3,9 +3,9 @@ use std::fmt::{self, Debug, Formatter};
 use ecow::{eco_format, EcoVec};
 
 use super::{Array, Dict, FromValue, IntoValue, Str, Value};
-use crate::diag::{bail, At, SourceResult};
+use crate::diag::{bail, error, At, SourceResult};
 use crate::syntax::{Span, Spanned};
-use crate::util::pretty_array_like;
+use crate::util::{pretty_array_like, similar};
 
 /// Evaluated arguments to a function.
 #[derive(Clone, PartialEq, Hash)]
@@ -179,6 +179,40 @@ impl Args {
         Ok(())
     }
 
+    /// Like [`finish`](Self::finish), but reports all remaining arguments and
+    /// suggests one of the named parameters for each named argument that looks
+    /// like a misspelling of it. The parameters are only computed if needed.
+    ///
+    /// The `definition` is the span of the called function, if it was defined
+    /// by the user.
+    pub fn finish_with<'a>(
+        self,
+        params: impl FnOnce() -> Vec<&'a str>,
+        definition: Span,
+    ) -> SourceResult<()> {
+        if self.items.is_empty() {
+            return Ok(());
+        }
+
+        let params = params();
+        let errors = self
+            .items
+            .iter()
+            .map(|arg| {
+                let Some(name) = &arg.name else {
+                    return error!(arg.span, "unexpected argument");
+                };
+                let mut error = error!(arg.span, "unexpected argument: {name}");
+                if let Some(param) = similar(name, params.iter().copied()) {
+                    error = error.with_suggestion(param, definition);
+                }
+                error
+            })
+            .collect();
+
+        Err(Box::new(errors))
+    }
+
     /// Extract the positional arguments as an array.
     pub fn to_pos(&self) -> Array {
         self.items
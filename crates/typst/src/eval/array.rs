@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::{Add, AddAssign};
 
 use ecow::{eco_format, EcoString, EcoVec};
@@ -160,6 +161,42 @@ impl Array {
         Ok(None)
     }
 
+    /// Return the first result of the function that is not `none`.
+    pub fn find_map(&self, vm: &mut Vm, func: Func) -> SourceResult<Value> {
+        for item in self.iter() {
+            let args = Args::new(func.span(), [item.clone()]);
+            let result = func.call_vm(vm, args)?;
+            if !matches!(result, Value::None) {
+                return Ok(result);
+            }
+        }
+        Ok(Value::None)
+    }
+
+    /// Return the leading items that match.
+    pub fn take_while(&self, vm: &mut Vm, func: Func) -> SourceResult<Self> {
+        let mut taken = EcoVec::new();
+        for item in self.iter() {
+            let args = Args::new(func.span(), [item.clone()]);
+            if !func.call_vm(vm, args)?.cast::<bool>().at(func.span())? {
+                break;
+            }
+            taken.push(item.clone());
+        }
+        Ok(taken.into())
+    }
+
+    /// Return the items after the leading ones that match.
+    pub fn skip_while(&self, vm: &mut Vm, func: Func) -> SourceResult<Self> {
+        for (i, item) in self.iter().enumerate() {
+            let args = Args::new(func.span(), [item.clone()]);
+            if !func.call_vm(vm, args)?.cast::<bool>().at(func.span())? {
+                return Ok(self.0[i..].iter().cloned().collect());
+            }
+        }
+        Ok(Self::new())
+    }
+
     /// Return the index of the first matching item.
     pub fn position(&self, vm: &mut Vm, func: Func) -> SourceResult<Option<i64>> {
         for (i, item) in self.iter().enumerate() {
@@ -196,11 +233,23 @@ impl Array {
     }
 
     /// Fold all of the array's items into one with a function.
-    pub fn fold(&self, vm: &mut Vm, init: Value, func: Func) -> SourceResult<Value> {
+    pub fn fold(
+        &self,
+        vm: &mut Vm,
+        init: Value,
+        func: Func,
+        until: Option<Func>,
+    ) -> SourceResult<Value> {
         let mut acc = init;
         for item in self.iter() {
             let args = Args::new(func.span(), [acc, item.clone()]);
             acc = func.call_vm(vm, args)?;
+            if let Some(until) = &until {
+                let args = Args::new(until.span(), [acc.clone()]);
+                if until.call_vm(vm, args)?.cast::<bool>().at(until.span())? {
+                    break;
+                }
+            }
         }
         Ok(acc)
     }
@@ -430,6 +479,44 @@ impl Array {
 
         Ok(Self(out))
     }
+
+    /// Splits the array into chunks of the given size. If `exact` is true, a
+    /// trailing chunk that is smaller than the size is discarded.
+    pub fn chunks(&self, size: NonZeroUsize, exact: bool) -> Self {
+        let size = size.get();
+        let to_array = |chunk: &[Value]| Value::Array(chunk.iter().cloned().collect());
+        if exact {
+            self.0.chunks_exact(size).map(to_array).collect()
+        } else {
+            self.0.chunks(size).map(to_array).collect()
+        }
+    }
+
+    /// Returns all overlapping windows of the given size.
+    pub fn windows(&self, size: NonZeroUsize) -> Self {
+        self.0
+            .windows(size.get())
+            .map(|window| Value::Array(window.iter().cloned().collect()))
+            .collect()
+    }
+
+    /// Groups the items of the array by a key, keeping the order in which the
+    /// keys first appear.
+    pub fn group_by(&self, vm: &mut Vm, key: Func) -> SourceResult<Self> {
+        let mut groups: Vec<(Value, EcoVec<Value>)> = vec![];
+        for value in self.iter() {
+            let k = key.call_vm(vm, Args::new(key.span(), [value.clone()]))?;
+            match groups.iter_mut().find(|(other, _)| ops::equal(other, &k)) {
+                Some((_, items)) => items.push(value.clone()),
+                None => groups.push((k, eco_vec![value.clone()])),
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(k, items)| array![k, Value::Array(Self(items))].into_value())
+            .collect())
+    }
 }
 
 impl Debug for Array {
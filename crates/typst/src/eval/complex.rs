@@ -0,0 +1,147 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use ecow::{eco_format, EcoString};
+
+use super::cast;
+use crate::geom::Angle;
+
+/// A complex number with a real and an imaginary part.
+#[derive(Default, Copy, Clone, PartialEq)]
+pub struct Complex {
+    /// The real part.
+    pub re: f64,
+    /// The imaginary part.
+    pub im: f64,
+}
+
+impl Complex {
+    /// The imaginary unit.
+    pub const I: Self = Self { re: 0.0, im: 1.0 };
+
+    /// Create a complex number from its real and imaginary part.
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Create a complex number from its polar representation.
+    pub fn from_polar(radius: f64, angle: Angle) -> Self {
+        Self::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    /// Whether the number is zero.
+    pub fn is_zero(self) -> bool {
+        self.re == 0.0 && self.im == 0.0
+    }
+
+    /// The magnitude of the number.
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The angle between the positive real axis and the number.
+    pub fn arg(self) -> Angle {
+        Angle::rad(self.im.atan2(self.re))
+    }
+
+    /// The complex conjugate of the number.
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Display the number, optionally rounded to a number of digits and in
+    /// polar form.
+    pub fn display(self, digits: Option<usize>, polar: bool) -> EcoString {
+        let number = |v: f64| match digits {
+            Some(digits) => eco_format!("{v:.digits$}"),
+            None => eco_format!("{v}"),
+        };
+
+        if polar {
+            let angle = self.arg().to_deg();
+            return eco_format!("{}∠{}°", number(self.abs()), number(angle));
+        }
+
+        let sign = if self.im.is_sign_negative() { '-' } else { '+' };
+        eco_format!("{} {sign} {}i", number(self.re), number(self.im.abs()))
+    }
+}
+
+impl Debug for Complex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "calc.complex({:?}, {:?})", self.re, self.im)
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.display(None, false))
+    }
+}
+
+impl Hash for Complex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Adding zero turns negative zero into positive zero, so that parts
+        // which compare equal also hash equally.
+        (self.re + 0.0).to_bits().hash(state);
+        (self.im + 0.0).to_bits().hash(state);
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let norm = other.re * other.re + other.im * other.im;
+        Self::new(
+            (self.re * other.re + self.im * other.im) / norm,
+            (self.im * other.re - self.re * other.im) / norm,
+        )
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+cast! {
+    type Complex: "complex",
+}
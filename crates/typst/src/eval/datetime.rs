@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
@@ -6,12 +7,14 @@ use ecow::{eco_format, EcoString, EcoVec};
 use time::error::{Format, InvalidFormatDescription};
 use time::{format_description, PrimitiveDateTime};
 
-use crate::eval::cast;
+use crate::diag::{bail, StrResult};
+use crate::doc::Lang;
+use crate::eval::{cast, dict, Dict, Duration};
 use crate::util::pretty_array_like;
 
 /// A datetime object that represents either a date, a time or a combination of
 /// both.
-#[derive(Clone, Copy, PartialEq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Datetime {
     /// Representation as a date.
     Date(time::Date),
@@ -23,13 +26,29 @@ pub enum Datetime {
 
 impl Datetime {
     /// Display the date and/or time in a certain format.
-    pub fn display(&self, pattern: Option<EcoString>) -> Result<EcoString, EcoString> {
+    ///
+    /// If a language is given, textual month and weekday names are displayed
+    /// in that language instead of English. Custom names take precedence over
+    /// the ones of the language.
+    pub fn display(
+        &self,
+        pattern: Option<EcoString>,
+        lang: Option<Lang>,
+        names: Option<DateNames>,
+    ) -> Result<EcoString, EcoString> {
         let pattern = pattern.as_ref().map(EcoString::as_str).unwrap_or(match self {
             Datetime::Date(_) => "[year]-[month]-[day]",
             Datetime::Time(_) => "[hour]:[minute]:[second]",
             Datetime::Datetime(_) => "[year]-[month]-[day] [hour]:[minute]:[second]",
         });
 
+        let names = names.or_else(|| lang.and_then(DateNames::of));
+        let localized = match (self.date(), names) {
+            (Some(date), Some(names)) => localize_pattern(pattern, date, &names),
+            _ => None,
+        };
+
+        let pattern = localized.as_deref().unwrap_or(pattern);
         let format = format_description::parse(pattern)
             .map_err(format_time_invalid_format_description_error)?;
 
@@ -43,6 +62,15 @@ impl Datetime {
         formatted_result.map_err(format_time_format_error)
     }
 
+    /// Return the date part of the datetime, if existing.
+    fn date(&self) -> Option<time::Date> {
+        match self {
+            Datetime::Date(date) => Some(*date),
+            Datetime::Time(_) => None,
+            Datetime::Datetime(datetime) => Some(datetime.date()),
+        }
+    }
+
     /// Return the year of the datetime, if existing.
     pub fn year(&self) -> Option<i32> {
         match self {
@@ -134,6 +162,273 @@ impl Datetime {
         let time = time::Time::from_hms(hour, minute, second).ok()?;
         Some(Datetime::Datetime(PrimitiveDateTime::new(date, time)))
     }
+
+    /// Parse a date, a time or a datetime from an ISO 8601 string.
+    ///
+    /// Accepts `YYYY-MM-DD`, `HH:MM[:SS]` and the combination of both,
+    /// separated by a `T` or a space. Fractional seconds and a trailing `Z`
+    /// are accepted, but ignored.
+    pub fn from_iso(text: &str) -> StrResult<Self> {
+        let text = text.trim();
+        let (date, time) = match text.split_once(['T', ' ']) {
+            Some((date, time)) => (Some(date), Some(time)),
+            None if text.contains(':') => (None, Some(text)),
+            None => (Some(text), None),
+        };
+
+        let date = date.map(parse_iso_date).transpose()?;
+        let time = time.map(parse_iso_time).transpose()?;
+        Ok(match (date, time) {
+            (Some(date), Some(time)) => {
+                Datetime::Datetime(PrimitiveDateTime::new(date, time))
+            }
+            (Some(date), None) => Datetime::Date(date),
+            (None, Some(time)) => Datetime::Time(time),
+            (None, None) => unreachable!(),
+        })
+    }
+
+    /// Shift the datetime by a duration.
+    ///
+    /// Dates are only shifted by whole days and times wrap around midnight.
+    pub fn checked_add(self, duration: Duration) -> StrResult<Self> {
+        let duration = duration.as_time();
+        let out_of_range = || EcoString::from("resulting datetime is out of range");
+        Ok(match self {
+            Datetime::Date(date) => {
+                Datetime::Date(date.checked_add(duration).ok_or_else(out_of_range)?)
+            }
+            Datetime::Time(time) => Datetime::Time(time + duration),
+            Datetime::Datetime(datetime) => Datetime::Datetime(
+                datetime.checked_add(duration).ok_or_else(out_of_range)?,
+            ),
+        })
+    }
+
+    /// The duration between two datetimes of the same kind.
+    pub fn checked_sub(self, other: Self) -> StrResult<Duration> {
+        Ok(match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => (a - b).into(),
+            (Datetime::Time(a), Datetime::Time(b)) => (a - b).into(),
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => (a - b).into(),
+            _ => bail!("cannot subtract {} from {}", other.kind(), self.kind()),
+        })
+    }
+
+    /// Compare two datetimes of the same kind.
+    pub fn compare(self, other: Self) -> StrResult<Ordering> {
+        Ok(match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => a.cmp(&b),
+            (Datetime::Time(a), Datetime::Time(b)) => a.cmp(&b),
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a.cmp(&b),
+            _ => bail!("cannot compare {} and {}", self.kind(), other.kind()),
+        })
+    }
+
+    /// A human-readable name for the kind of datetime.
+    fn kind(self) -> &'static str {
+        match self {
+            Datetime::Date(_) => "a date",
+            Datetime::Time(_) => "a time",
+            Datetime::Datetime(_) => "a datetime",
+        }
+    }
+}
+
+/// Parse the date part of an ISO 8601 string.
+fn parse_iso_date(text: &str) -> StrResult<time::Date> {
+    let invalid = || eco_format!("invalid date: {text:?}");
+    let mut parts = text.splitn(3, '-');
+    let mut next = || parts.next().ok_or_else(invalid);
+    let year = next()?.parse::<i32>().map_err(|_| invalid())?;
+    let month = next()?.parse::<u8>().map_err(|_| invalid())?;
+    let day = next()?.parse::<u8>().map_err(|_| invalid())?;
+    let month = time::Month::try_from(month).map_err(|_| invalid())?;
+    time::Date::from_calendar_date(year, month, day).map_err(|_| invalid())
+}
+
+/// Parse the time part of an ISO 8601 string.
+fn parse_iso_time(text: &str) -> StrResult<time::Time> {
+    let invalid = || eco_format!("invalid time: {text:?}");
+    let trimmed = text.strip_suffix('Z').unwrap_or(text);
+    let trimmed = trimmed.split('.').next().unwrap_or(trimmed);
+    let mut parts = trimmed.split(':');
+    let mut next = || parts.next().map(|part| part.parse::<u8>().map_err(|_| invalid()));
+    let hour = next().ok_or_else(invalid)??;
+    let minute = next().ok_or_else(invalid)??;
+    let second = next().transpose()?.unwrap_or(0);
+    if next().is_some() {
+        return Err(invalid());
+    }
+    time::Time::from_hms(hour, minute, second).map_err(|_| invalid())
+}
+
+/// The names of the months and weekdays (starting on Monday) to display dates
+/// with.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DateNames {
+    months: Vec<EcoString>,
+    weekdays: Vec<EcoString>,
+    /// The month names to use after a day, in languages that decline them.
+    genitive_months: Option<Vec<EcoString>>,
+}
+
+impl DateNames {
+    /// The names in a language, if it has translations.
+    fn of(lang: Lang) -> Option<Self> {
+        let (months, weekdays) = local_names(lang)?;
+        Some(Self {
+            months: months.split(' ').map(Into::into).collect(),
+            weekdays: weekdays.split(' ').map(Into::into).collect(),
+            genitive_months: genitive_month_names(lang)
+                .map(|months| months.split(' ').map(Into::into).collect()),
+        })
+    }
+}
+
+cast! {
+    DateNames,
+    self => dict! { "months" => self.months, "weekdays" => self.weekdays }.into_value(),
+    mut dict: Dict => {
+        // Other keys are ignored, so that the names of a language given to
+        // `text(names: ..)` can be passed as a whole.
+        let months: Vec<EcoString> = dict.take("months")?.cast()?;
+        let weekdays: Vec<EcoString> = dict.take("weekdays")?.cast()?;
+        if months.len() != 12 {
+            Err("expected 12 month names")?;
+        }
+        if weekdays.len() != 7 {
+            Err("expected 7 weekday names")?;
+        }
+        Self { months, weekdays, genitive_months: None }
+    },
+}
+
+/// Replace textual month and weekday components in a format description with
+/// the given names.
+///
+/// Returns `None` if the pattern doesn't contain any such components.
+fn localize_pattern(
+    pattern: &str,
+    date: time::Date,
+    names: &DateNames,
+) -> Option<EcoString> {
+    let index = usize::from(u8::from(date.month())) - 1;
+    let month = &names.months[index];
+    let genitive_month = names.genitive_months.as_ref().map_or(month, |m| &m[index]);
+    let weekday = &names.weekdays[usize::from(date.weekday().number_days_from_monday())];
+
+    let mut out = EcoString::new();
+    let mut changed = false;
+    let mut after_day = false;
+    let mut rest = pattern;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // Keep escaped brackets as they are.
+        if rest.starts_with("[[") {
+            out.push_str("[[");
+            rest = &rest[2..];
+            continue;
+        }
+
+        // Let the format description parser report unclosed brackets.
+        let Some(end) = rest.find(']') else { break };
+        let component = &rest[1..end];
+        let mut words = component.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let repr = words.find_map(|modifier| modifier.strip_prefix("repr:"));
+
+        // A month that follows a day, as in "15 stycznia", is declined.
+        let month = if after_day { genitive_month } else { month };
+        after_day |= name == "day";
+
+        let replacement = match (name, repr) {
+            ("month", Some("long")) => Some(month.clone()),
+            ("month", Some("short")) => Some(abbreviate(month)),
+            ("weekday", None | Some("long")) => Some(weekday.clone()),
+            ("weekday", Some("short")) => Some(abbreviate(weekday)),
+            _ => None,
+        };
+
+        match replacement {
+            Some(name) => {
+                out.push_str(&name);
+                changed = true;
+            }
+            None => out.push_str(&rest[..=end]),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+/// Shorten a month or weekday name to its first three characters.
+fn abbreviate(name: &str) -> EcoString {
+    name.chars().take(3).collect()
+}
+
+/// The space-separated month and weekday names (starting on Monday) in a
+/// language.
+fn local_names(lang: Lang) -> Option<(&'static str, &'static str)> {
+    Some(match lang {
+        Lang::GERMAN => (
+            "Januar Februar März April Mai Juni Juli August September Oktober November Dezember",
+            "Montag Dienstag Mittwoch Donnerstag Freitag Samstag Sonntag",
+        ),
+        Lang::FRENCH => (
+            "janvier février mars avril mai juin juillet août septembre octobre novembre décembre",
+            "lundi mardi mercredi jeudi vendredi samedi dimanche",
+        ),
+        Lang::SPANISH => (
+            "enero febrero marzo abril mayo junio julio agosto septiembre octubre noviembre diciembre",
+            "lunes martes miércoles jueves viernes sábado domingo",
+        ),
+        Lang::ITALIAN => (
+            "gennaio febbraio marzo aprile maggio giugno luglio agosto settembre ottobre novembre dicembre",
+            "lunedì martedì mercoledì giovedì venerdì sabato domenica",
+        ),
+        Lang::PORTUGUESE => (
+            "janeiro fevereiro março abril maio junho julho agosto setembro outubro novembro dezembro",
+            "segunda-feira terça-feira quarta-feira quinta-feira sexta-feira sábado domingo",
+        ),
+        Lang::DUTCH => (
+            "januari februari maart april mei juni juli augustus september oktober november december",
+            "maandag dinsdag woensdag donderdag vrijdag zaterdag zondag",
+        ),
+        Lang::SWEDISH => (
+            "januari februari mars april maj juni juli augusti september oktober november december",
+            "måndag tisdag onsdag torsdag fredag lördag söndag",
+        ),
+        Lang::DANISH => (
+            "januar februar marts april maj juni juli august september oktober november december",
+            "mandag tirsdag onsdag torsdag fredag lørdag søndag",
+        ),
+        Lang::BOKMÅL | Lang::NYNORSK => (
+            "januar februar mars april mai juni juli august september oktober november desember",
+            "mandag tirsdag onsdag torsdag fredag lørdag søndag",
+        ),
+        Lang::POLISH => (
+            "styczeń luty marzec kwiecień maj czerwiec lipiec sierpień wrzesień październik listopad grudzień",
+            "poniedziałek wtorek środa czwartek piątek sobota niedziela",
+        ),
+        _ => return None,
+    })
+}
+
+/// The space-separated month names in a language that has a different form
+/// for months that follow a day.
+fn genitive_month_names(lang: Lang) -> Option<&'static str> {
+    Some(match lang {
+        Lang::POLISH => {
+            "stycznia lutego marca kwietnia maja czerwca lipca sierpnia września października listopada grudnia"
+        }
+        _ => return None,
+    })
 }
 
 impl Debug for Datetime {
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::Neg;
+use std::str::FromStr;
+
+use ecow::{eco_format, EcoString};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+
+use super::{cast, Cast};
+use crate::diag::StrResult;
+
+/// A fixed-point decimal number with exact arithmetic.
+///
+/// Decimals have up to 28 significant digits. Unlike floats, they represent
+/// numbers like `0.1` exactly, which makes them suitable for monetary values.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Decimal(rust_decimal::Decimal);
+
+impl Decimal {
+    /// Parse a decimal from its textual representation, e.g. `-12.50`.
+    pub fn parse(text: &str) -> StrResult<Self> {
+        rust_decimal::Decimal::from_str(text.trim())
+            .or_else(|_| rust_decimal::Decimal::from_scientific(text.trim()))
+            .map(Self)
+            .map_err(|_| eco_format!("invalid decimal: {text}"))
+    }
+
+    /// Create a decimal from an integer.
+    pub fn from_int(value: i64) -> Self {
+        Self(value.into())
+    }
+
+    /// Create a decimal from a float.
+    ///
+    /// The float's shortest representation is used, so that `0.1` becomes
+    /// exactly `0.1`.
+    pub fn from_float(value: f64) -> StrResult<Self> {
+        if !value.is_finite() {
+            return Err(eco_format!("cannot convert {value} to a decimal"));
+        }
+
+        Self::parse(&eco_format!("{value}")).map_err(|_| too_large())
+    }
+
+    /// Whether the decimal is zero.
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The number of digits after the decimal point.
+    pub fn scale(self) -> u32 {
+        self.0.scale()
+    }
+
+    /// Convert the decimal to an integer, truncating the fractional part.
+    pub fn to_int(self) -> StrResult<i64> {
+        self.0.trunc().to_i64().ok_or_else(|| "number too large".into())
+    }
+
+    /// Convert the decimal to the closest float.
+    pub fn to_float(self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Round the decimal to the given number of digits after the decimal point.
+    pub fn round(self, digits: u32, mode: Rounding) -> Self {
+        let strategy = match mode {
+            Rounding::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Rounding::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Rounding::HalfDown => RoundingStrategy::MidpointTowardZero,
+            Rounding::Up => RoundingStrategy::AwayFromZero,
+            Rounding::Down => RoundingStrategy::ToZero,
+            Rounding::Ceiling => RoundingStrategy::ToPositiveInfinity,
+            Rounding::Floor => RoundingStrategy::ToNegativeInfinity,
+        };
+        Self(self.0.round_dp_with_strategy(digits, strategy))
+    }
+
+    /// Change the number of digits after the decimal point, padding with zeros
+    /// or rounding half to even as necessary.
+    pub fn rescale(self, digits: u32) -> Self {
+        let mut value = self.round(digits, Rounding::HalfEven).0;
+        value.rescale(digits);
+        Self(value)
+    }
+
+    /// The absolute value of the decimal.
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Add two decimals.
+    pub fn checked_add(self, other: Self) -> StrResult<Self> {
+        self.0.checked_add(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Subtract a decimal from another one.
+    pub fn checked_sub(self, other: Self) -> StrResult<Self> {
+        self.0.checked_sub(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Multiply two decimals.
+    pub fn checked_mul(self, other: Self) -> StrResult<Self> {
+        self.0.checked_mul(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Divide a decimal by another one.
+    pub fn checked_div(self, other: Self) -> StrResult<Self> {
+        self.0.checked_div(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Compare the decimal with an integer.
+    pub fn cmp_int(self, other: i64) -> Ordering {
+        self.0.cmp(&other.into())
+    }
+
+    /// Format the decimal in scientific notation.
+    pub fn scientific(self) -> EcoString {
+        eco_format!("{:e}", self.0.normalize())
+    }
+}
+
+/// The error message when a decimal overflows.
+fn too_large() -> EcoString {
+    "value is too large".into()
+}
+
+/// How to round a decimal.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Rounding {
+    /// Round to the nearest neighbour and ties to the even one.
+    #[default]
+    HalfEven,
+    /// Round to the nearest neighbour and ties away from zero.
+    HalfUp,
+    /// Round to the nearest neighbour and ties towards zero.
+    HalfDown,
+    /// Round away from zero.
+    Up,
+    /// Round towards zero.
+    Down,
+    /// Round towards positive infinity.
+    Ceiling,
+    /// Round towards negative infinity.
+    Floor,
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Debug for Decimal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "decimal(\"{}\")", self.0)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+cast! {
+    type Decimal: "decimal",
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use ecow::{eco_format, EcoString};
 
 use super::{array, Array, Str, Value};
-use crate::diag::StrResult;
+use crate::diag::{bail, StrResult};
 use crate::syntax::is_ident;
 use crate::util::{pretty_array_like, separated_list, ArcExt};
 
@@ -38,6 +38,26 @@ impl Dict {
         Self::default()
     }
 
+    /// Create a dictionary from an array of pairs (arrays of length two) of a
+    /// string key and a value. Later pairs override earlier ones with the
+    /// same key.
+    pub fn from_pairs(pairs: Array) -> StrResult<Self> {
+        let mut map = IndexMap::with_capacity(pairs.len());
+        for pair in pairs {
+            let pair = match pair {
+                Value::Array(pair) => pair,
+                v => bail!("expected pair, found {}", v.type_name()),
+            };
+            let [key, value]: [Value; 2] = pair
+                .into_iter()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| "pair must consist of exactly two items")?;
+            map.insert(key.cast::<Str>()?, value);
+        }
+        Ok(map.into())
+    }
+
     /// Whether the dictionary is empty.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -117,6 +137,26 @@ impl Dict {
             .collect()
     }
 
+    /// Combine the dictionary with another one. Pairs of the other dictionary
+    /// take precedence. If `deep` is true, values that are dictionaries in
+    /// both are merged recursively instead of being overridden.
+    pub fn merge(&self, other: Dict, deep: bool) -> Self {
+        let mut result = self.clone();
+        let map = Arc::make_mut(&mut result.0);
+        for (key, value) in other {
+            match (map.get(&key), value) {
+                (Some(Value::Dict(existing)), Value::Dict(value)) if deep => {
+                    let merged = existing.merge(value, true);
+                    map.insert(key, Value::Dict(merged));
+                }
+                (_, value) => {
+                    map.insert(key, value);
+                }
+            }
+        }
+        result
+    }
+
     /// Iterate over pairs of references to the contained keys and values.
     pub fn iter(&self) -> indexmap::map::Iter<Str, Value> {
         self.0.iter()
@@ -0,0 +1,270 @@
+use std::fmt::{self, Debug, Formatter};
+
+use ecow::{eco_format, EcoString, EcoVec};
+
+use crate::diag::StrResult;
+use crate::eval::cast;
+use crate::util::pretty_array_like;
+
+/// Represents a positive or negative span of time.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    /// Create a duration from a number of seconds.
+    pub fn seconds(seconds: i64) -> Self {
+        Self(time::Duration::seconds(seconds))
+    }
+
+    /// Create a duration from weeks, days, hours, minutes, and seconds.
+    pub fn from_components(
+        weeks: i64,
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> StrResult<Self> {
+        [(weeks, 604800), (days, 86400), (hours, 3600), (minutes, 60), (seconds, 1)]
+            .into_iter()
+            .try_fold(0i64, |acc, (count, unit)| {
+                count.checked_mul(unit).and_then(|secs| acc.checked_add(secs))
+            })
+            .map(Self::seconds)
+            .ok_or_else(too_large)
+    }
+
+    /// Whether the duration is zero.
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The duration in seconds.
+    pub fn to_seconds(self) -> f64 {
+        self.0.as_seconds_f64()
+    }
+
+    /// The duration in minutes.
+    pub fn to_minutes(self) -> f64 {
+        self.to_seconds() / 60.0
+    }
+
+    /// The duration in hours.
+    pub fn to_hours(self) -> f64 {
+        self.to_seconds() / 3600.0
+    }
+
+    /// The duration in days.
+    pub fn to_days(self) -> f64 {
+        self.to_seconds() / 86400.0
+    }
+
+    /// The duration in weeks.
+    pub fn to_weeks(self) -> f64 {
+        self.to_seconds() / 604800.0
+    }
+
+    /// Display the duration in a certain format.
+    ///
+    /// The pattern may contain the components `[weeks]`, `[days]`, `[hours]`,
+    /// `[minutes]`, and `[seconds]`. Each component displays the part of the
+    /// duration that isn't already covered by a larger component of the same
+    /// pattern. Components accept a `padding` modifier (`zero`, `space`, or
+    /// `none`) which pads them to two digits.
+    pub fn display(&self, pattern: Option<EcoString>) -> StrResult<EcoString> {
+        let pattern = pattern
+            .as_deref()
+            .unwrap_or("[hours]:[minutes padding:zero]:[seconds padding:zero]");
+
+        // Parse the pattern into literal text and components.
+        let mut pieces = vec![];
+        let mut rest = pattern;
+        while let Some(start) = rest.find('[') {
+            pieces.push(Piece::Literal(&rest[..start]));
+            rest = &rest[start..];
+            if rest.starts_with("[[") {
+                pieces.push(Piece::Literal("["));
+                rest = &rest[2..];
+                continue;
+            }
+
+            let end = rest.find(']').ok_or_else(|| {
+                eco_format!(
+                    "missing closing bracket for bracket at index {}",
+                    pattern.len() - rest.len()
+                )
+            })?;
+
+            pieces.push(Piece::Component(DurationComponent::parse(&rest[1..end])?));
+            rest = &rest[end + 1..];
+        }
+        pieces.push(Piece::Literal(rest));
+
+        // The largest unit in the pattern displays everything, the smaller ones
+        // only what doesn't fit into the next larger unit of the pattern.
+        let negative = self.0.is_negative();
+        let total = self.0.whole_seconds().unsigned_abs();
+        let units: Vec<u64> = pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Component(component) => Some(component.unit),
+                Piece::Literal(_) => None,
+            })
+            .collect();
+
+        let value_of = |unit: u64| {
+            let larger = units.iter().copied().filter(|&u| u > unit).min();
+            let rest = larger.map_or(total, |larger| total % larger);
+            rest / unit
+        };
+
+        let mut out = EcoString::new();
+        if negative {
+            out.push('-');
+        }
+
+        for piece in pieces {
+            match piece {
+                Piece::Literal(text) => out.push_str(text),
+                Piece::Component(component) => {
+                    let value = value_of(component.unit);
+                    match component.padding {
+                        Padding::Zero => out.push_str(&eco_format!("{value:02}")),
+                        Padding::Space => out.push_str(&eco_format!("{value:2}")),
+                        Padding::None => out.push_str(&eco_format!("{value}")),
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Negate the duration.
+    pub fn checked_neg(self) -> StrResult<Self> {
+        Self::default().checked_sub(self)
+    }
+
+    /// Add two durations.
+    pub fn checked_add(self, other: Self) -> StrResult<Self> {
+        self.0.checked_add(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Subtract a duration from another one.
+    pub fn checked_sub(self, other: Self) -> StrResult<Self> {
+        self.0.checked_sub(other.0).map(Self).ok_or_else(too_large)
+    }
+
+    /// Scale the duration by a factor.
+    pub fn checked_mul(self, factor: f64) -> StrResult<Self> {
+        let seconds = self.to_seconds() * factor;
+        if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+            return Err(too_large());
+        }
+        Ok(Self(time::Duration::seconds_f64(seconds)))
+    }
+
+    /// Divide the duration by a number.
+    pub fn checked_div(self, divisor: f64) -> StrResult<Self> {
+        self.checked_mul(1.0 / divisor)
+    }
+
+    /// The ratio between two durations.
+    pub fn ratio(self, other: Self) -> f64 {
+        self.to_seconds() / other.to_seconds()
+    }
+
+    /// The underlying duration of the `time` crate.
+    pub fn as_time(self) -> time::Duration {
+        self.0
+    }
+}
+
+/// The error message when a duration overflows.
+fn too_large() -> EcoString {
+    "duration is too large".into()
+}
+
+/// A part of a duration format pattern.
+enum Piece<'a> {
+    Literal(&'a str),
+    Component(DurationComponent),
+}
+
+/// A component of a duration format pattern.
+struct DurationComponent {
+    /// The number of seconds in the component's unit.
+    unit: u64,
+    /// How to pad the component.
+    padding: Padding,
+}
+
+/// How to pad a component in a duration format pattern.
+enum Padding {
+    Zero,
+    Space,
+    None,
+}
+
+impl DurationComponent {
+    /// Parse a component from the text within its brackets.
+    fn parse(text: &str) -> StrResult<Self> {
+        let mut words = text.split_whitespace();
+        let name = words.next().ok_or("expected component name")?;
+        let unit = match name {
+            "weeks" => 604800,
+            "days" => 86400,
+            "hours" => 3600,
+            "minutes" => 60,
+            "seconds" => 1,
+            _ => return Err(eco_format!("invalid component name '{name}'")),
+        };
+
+        let mut padding = Padding::None;
+        for modifier in words {
+            padding = match modifier {
+                "padding:zero" => Padding::Zero,
+                "padding:space" => Padding::Space,
+                "padding:none" => Padding::None,
+                _ => return Err(eco_format!("invalid modifier '{modifier}'")),
+            };
+        }
+
+        Ok(Self { unit, padding })
+    }
+}
+
+impl Debug for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut seconds = self.0.whole_seconds();
+        let mut pieces = EcoVec::new();
+        for (name, unit) in [
+            ("weeks", 604800),
+            ("days", 86400),
+            ("hours", 3600),
+            ("minutes", 60),
+            ("seconds", 1),
+        ] {
+            let count = seconds / unit;
+            seconds %= unit;
+            if count != 0 {
+                pieces.push(eco_format!("{name}: {count}"));
+            }
+        }
+
+        if pieces.is_empty() {
+            pieces.push("seconds: 0".into());
+        }
+
+        write!(f, "duration{}", &pretty_array_like(&pieces, false))
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+cast! {
+    type Duration: "duration",
+}
@@ -37,6 +37,11 @@ enum Repr {
     Closure(Arc<Prehashed<Closure>>),
     /// A nested function with pre-applied arguments.
     With(Arc<(Func, Args)>),
+    /// A composition of functions, applied from last to first.
+    Composed(Arc<Vec<Func>>),
+    /// A function that collects the given number of positional arguments
+    /// before it is called.
+    Curried(Arc<(Func, usize)>),
 }
 
 impl Func {
@@ -47,6 +52,8 @@ impl Func {
             Repr::Elem(func) => Some(func.info().name),
             Repr::Closure(closure) => closure.name.as_deref(),
             Repr::With(arc) => arc.0.name(),
+            Repr::Composed(_) => None,
+            Repr::Curried(arc) => arc.0.name(),
         }
     }
 
@@ -57,6 +64,28 @@ impl Func {
             Repr::Elem(func) => Some(func.info()),
             Repr::Closure(_) => None,
             Repr::With(arc) => arc.0.info(),
+            Repr::Composed(_) => None,
+            Repr::Curried(arc) => arc.0.info(),
+        }
+    }
+
+    /// The named parameters of a user-defined function along with their
+    /// default values.
+    pub fn named_params(&self) -> Option<Vec<(&str, &Value)>> {
+        match &self.repr {
+            Repr::Closure(closure) => Some(
+                closure
+                    .params
+                    .iter()
+                    .filter_map(|param| match param {
+                        Param::Named(name, default) => Some((name.as_str(), default)),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Repr::With(arc) => arc.0.named_params(),
+            Repr::Curried(arc) => arc.0.named_params(),
+            _ => None,
         }
     }
 
@@ -65,6 +94,16 @@ impl Func {
         self.span
     }
 
+    /// The names of the parameters that can be passed by name.
+    fn param_names(&self) -> Vec<&str> {
+        match self.named_params() {
+            Some(params) => params.into_iter().map(|(name, _)| name).collect(),
+            None => self.info().map_or(vec![], |info| {
+                info.params.iter().filter(|p| p.named).map(|p| p.name).collect()
+            }),
+        }
+    }
+
     /// Attach a span to this function if it doesn't already have one.
     pub fn spanned(mut self, span: Span) -> Self {
         if self.span.is_detached() {
@@ -73,6 +112,27 @@ impl Func {
         self
     }
 
+    /// Whether the function's first positional parameter accepts the value.
+    ///
+    /// This is only a structural check of destructuring patterns: Native
+    /// functions and plain parameters accept any value.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match &self.repr {
+            Repr::Closure(closure) => closure
+                .params
+                .iter()
+                .find_map(|param| match param {
+                    Param::Pos(pattern) => Some(super::pattern_matches(pattern, value)),
+                    _ => None,
+                })
+                .unwrap_or(true),
+            Repr::With(arc) if arc.1.to_pos().is_empty() => arc.0.accepts(value),
+            Repr::Composed(funcs) => funcs.last().map_or(true, |f| f.accepts(value)),
+            Repr::Curried(arc) => arc.0.accepts(value),
+            _ => true,
+        }
+    }
+
     /// Call the function with the given arguments.
     pub fn call_vm(&self, vm: &mut Vm, mut args: Args) -> SourceResult<Value> {
         let _span = tracing::info_span!(
@@ -84,12 +144,12 @@ impl Func {
         match &self.repr {
             Repr::Native(native) => {
                 let value = (native.func)(vm, &mut args)?;
-                args.finish()?;
+                args.finish_with(|| self.param_names(), Span::detached())?;
                 Ok(value)
             }
             Repr::Elem(func) => {
                 let value = func.construct(vm, &mut args)?;
-                args.finish()?;
+                args.finish_with(|| self.param_names(), Span::detached())?;
                 Ok(Value::Content(value))
             }
             Repr::Closure(closure) => {
@@ -114,6 +174,22 @@ impl Func {
                 args.items = arc.1.items.iter().cloned().chain(args.items).collect();
                 arc.0.call_vm(vm, args)
             }
+            Repr::Composed(funcs) => {
+                let Some((last, rest)) = funcs.split_last() else {
+                    return Ok(Value::None);
+                };
+                let mut value = last.call_vm(vm, args)?;
+                for func in rest.iter().rev() {
+                    value = func.call_vm(vm, Args::new(func.span(), [value]))?;
+                }
+                Ok(value)
+            }
+            Repr::Curried(arc) => {
+                if args.to_pos().len() < arc.1 {
+                    return Ok(Value::Func(self.clone().with(args.take())));
+                }
+                arc.0.call_vm(vm, args)
+            }
         }
     }
 
@@ -145,6 +221,55 @@ impl Func {
         Self { repr: Repr::With(Arc::new((self, args))), span }
     }
 
+    /// Compose the function with others.
+    ///
+    /// The resulting function calls the last of the functions with its
+    /// arguments and passes the result on to the previous one, up to this
+    /// function.
+    pub fn compose(self, others: Vec<Func>) -> Self {
+        let span = self.span;
+        let mut funcs = vec![self];
+        funcs.extend(others);
+        Self { repr: Repr::Composed(Arc::new(funcs)), span }
+    }
+
+    /// Curry the function, so that its positional arguments can be applied
+    /// across multiple calls.
+    ///
+    /// The function is only called once it has received `arity` positional
+    /// arguments. If no arity is given, it is derived from the function's
+    /// parameters.
+    pub fn curry(self, arity: Option<usize>) -> StrResult<Self> {
+        let Some(arity) = arity.or_else(|| self.arity()) else {
+            bail!("cannot determine the number of parameters of this function");
+        };
+        let span = self.span;
+        Ok(Self { repr: Repr::Curried(Arc::new((self, arity))), span })
+    }
+
+    /// The number of positional arguments the function expects, if it is
+    /// fixed.
+    fn arity(&self) -> Option<usize> {
+        match &self.repr {
+            Repr::Native(_) | Repr::Elem(_) => {
+                let params = &self.info()?.params;
+                if params.iter().any(|p| p.positional && p.variadic) {
+                    return None;
+                }
+                Some(params.iter().filter(|p| p.positional && p.required).count())
+            }
+            Repr::Closure(closure) => {
+                if closure.params.iter().any(|p| matches!(p, Param::Sink(_))) {
+                    return None;
+                }
+                Some(closure.params.iter().filter(|p| matches!(p, Param::Pos(_))).count())
+            }
+            Repr::With(arc) => Some(arc.0.arity()?.saturating_sub(arc.1.to_pos().len())),
+            Repr::Composed(funcs) => funcs.last()?.arity(),
+            Repr::Curried(arc) => Some(arc.1),
+        }
+    }
+
     /// Extract the element function, if it is one.
     pub fn element(&self) -> Option<ElemFunc> {
         match self.repr {
@@ -174,6 +299,10 @@ impl Func {
                 Err(eco_format!("cannot access fields on user-defined functions"))
             }
             Repr::With(arc) => arc.0.get(field),
+            Repr::Composed(_) => {
+                Err(eco_format!("cannot access fields on composed functions"))
+            }
+            Repr::Curried(arc) => arc.0.get(field),
         }
     }
 }
@@ -409,7 +538,7 @@ impl Closure {
         }
 
         // Ensure all arguments have been used.
-        args.finish()?;
+        args.finish_with(|| this.param_names(), this.span())?;
 
         // Handle control flow.
         let result = closure.body.eval(&mut vm);
@@ -482,8 +611,14 @@ impl<'a> CapturesVisitor<'a> {
             // of named parameters cannot access previous parameter bindings.
             Some(ast::Expr::Closure(expr)) => {
                 for param in expr.params().children() {
-                    if let ast::Param::Named(named) = param {
-                        self.visit(named.expr().as_untyped());
+                    match param {
+                        ast::Param::Named(named) => self.visit(named.expr().as_untyped()),
+                        ast::Param::Pos(pattern) => {
+                            for default in pattern.defaults() {
+                                self.visit(default.as_untyped());
+                            }
+                        }
+                        ast::Param::Sink(_) => {}
                     }
                 }
 
@@ -517,6 +652,12 @@ impl<'a> CapturesVisitor<'a> {
                     self.visit(init.as_untyped());
                 }
 
+                if let ast::LetBindingKind::Normal(pattern) = expr.kind() {
+                    for default in pattern.defaults() {
+                        self.visit(default.as_untyped());
+                    }
+                }
+
                 for ident in expr.kind().idents() {
                     self.bind(ident);
                 }
@@ -530,6 +671,10 @@ impl<'a> CapturesVisitor<'a> {
                 self.internal.enter();
 
                 let pattern = expr.pattern();
+                for default in pattern.defaults() {
+                    self.visit(default.as_untyped());
+                }
+
                 for ident in pattern.idents() {
                     self.bind(ident);
                 }
@@ -542,6 +687,9 @@ impl<'a> CapturesVisitor<'a> {
             // path is evaluated.
             Some(ast::Expr::Import(expr)) => {
                 self.visit(expr.source().as_untyped());
+                if let Some(new_name) = expr.new_name() {
+                    self.bind(new_name);
+                }
                 if let Some(ast::Imports::Items(items)) = expr.imports() {
                     for item in items {
                         self.bind(item);
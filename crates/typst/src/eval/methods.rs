@@ -2,12 +2,14 @@
 
 use ecow::{eco_format, EcoString};
 
-use super::{Args, IntoValue, Str, Value, Vm};
+use super::str::StrSide;
+use super::{array, Args, Array, IntoValue, Str, Value, Vm};
 use crate::diag::{At, Hint, SourceResult};
-use crate::eval::{bail, Datetime};
-use crate::geom::{Align, Axes, Color, Dir, Em, GenAlign};
-use crate::model::{Location, Selector};
+use crate::eval::{bail, Complex, Datetime, Decimal, Duration, Plugin, Rng, Rounding};
+use crate::geom::{Align, Axes, Color, ColorSpace, Dir, Em, GenAlign, Ratio};
+use crate::model::{Location, PageRange, Resolve, Selector, StyleChain, Styles};
 use crate::syntax::Span;
+use crate::util::Bytes;
 
 /// Call a method on a value.
 pub fn call(
@@ -25,6 +27,14 @@ pub fn call(
             "lighten" => color.lighten(args.expect("amount")?).into_value(),
             "darken" => color.darken(args.expect("amount")?).into_value(),
             "negate" => color.negate().into_value(),
+            "saturate" => color.saturate(args.expect("amount")?).into_value(),
+            "desaturate" => color.desaturate(args.expect("amount")?).into_value(),
+            "mix" => {
+                let other = args.expect("other color")?;
+                let ratio = args.eat()?.unwrap_or(Ratio::new(0.5));
+                let space = args.named("space")?.unwrap_or(ColorSpace::Oklab);
+                color.mix_with(other, ratio, space).into_value()
+            }
             "kind" => match color {
                 Color::Luma(_) => vm.items.luma_func.into_value(),
                 Color::Rgba(_) => vm.items.rgb_func.into_value(),
@@ -40,7 +50,7 @@ pub fn call(
                 Color::Cmyk(cmyk) => cmyk.to_array().into_value(),
             },
             "luma" => match color {
-                Color::Luma(luma) => luma.0.into_value(),
+                Color::Luma(luma) => luma.l.into_value(),
                 Color::Rgba(_) => {
                     bail!(span, "cannot obtain the luma value of rgba color")
                 }
@@ -48,6 +58,21 @@ pub fn call(
                     bail!(span, "cannot obtain the luma value of cmyk color")
                 }
             },
+            "hsl" => {
+                let (h, s, l) = color.to_rgba().to_hsl();
+                array![h, Ratio::new(s), Ratio::new(l), alpha(color)].into_value()
+            }
+            "hsv" => {
+                let (h, s, v) = color.to_rgba().to_hsv();
+                array![h, Ratio::new(s), Ratio::new(v), alpha(color)].into_value()
+            }
+            "oklab" => {
+                let (l, a, b) = color.to_rgba().to_oklab();
+                array![Ratio::new(l), a, b, alpha(color)].into_value()
+            }
+            "to-rgb" => Color::Rgba(color.to_rgba()).into_value(),
+            "to-cmyk" => Color::Cmyk(color.to_cmyk()).into_value(),
+            "to-luma" => Color::Luma(color.to_luma()).into_value(),
             _ => return missing(),
         },
 
@@ -76,6 +101,8 @@ pub fn call(
             "find" => string.find(args.expect("pattern")?).into_value(),
             "position" => string.position(args.expect("pattern")?).into_value(),
             "match" => string.match_(args.expect("pattern")?).into_value(),
+            "normalize" => string.normalize(args.eat()?.unwrap_or_default()).into_value(),
+            "sort-key" => string.sort_key(args.named("lang")?).into_value(),
             "matches" => string.matches(args.expect("pattern")?).into_value(),
             "replace" => {
                 let pattern = args.expect("pattern")?;
@@ -90,6 +117,13 @@ pub fn call(
                 string.trim(pattern, at, repeat).into_value()
             }
             "split" => string.split(args.eat()?).into_value(),
+            "pad-start" | "pad-end" => {
+                let width = args.expect("width")?;
+                let fill = args.named("fill")?;
+                let side =
+                    if method == "pad-start" { StrSide::Start } else { StrSide::End };
+                string.pad(width, fill, side).at(span)?.into_value()
+            }
             _ => return missing(),
         },
 
@@ -100,6 +134,19 @@ pub fn call(
                 .at(&args.expect::<EcoString>("field")?, args.named("default")?)
                 .at(span)?,
             "fields" => content.dict().into_value(),
+            "children" => match content.to_sequence() {
+                Some(children) => children.cloned().map(IntoValue::into_value).collect(),
+                None => array![content.clone()],
+            }
+            .into_value(),
+            "text" => content.plain_text().into_value(),
+            "query" => content
+                .query(args.expect("selector")?)
+                .into_iter()
+                .cloned()
+                .map(IntoValue::into_value)
+                .collect::<Array>()
+                .into_value(),
             "location" => content
                 .location()
                 .ok_or("this method can only be called on content returned by query(..)")
@@ -126,12 +173,18 @@ pub fn call(
             }
             "contains" => array.contains(&args.expect("value")?).into_value(),
             "find" => array.find(vm, args.expect("function")?)?.into_value(),
+            "find-map" => array.find_map(vm, args.expect("function")?)?,
             "position" => array.position(vm, args.expect("function")?)?.into_value(),
+            "take-while" => array.take_while(vm, args.expect("function")?)?.into_value(),
+            "skip-while" => array.skip_while(vm, args.expect("function")?)?.into_value(),
             "filter" => array.filter(vm, args.expect("function")?)?.into_value(),
             "map" => array.map(vm, args.expect("function")?)?.into_value(),
-            "fold" => {
-                array.fold(vm, args.expect("initial value")?, args.expect("function")?)?
-            }
+            "fold" => array.fold(
+                vm,
+                args.expect("initial value")?,
+                args.expect("function")?,
+                args.named("until")?,
+            )?,
             "sum" => array.sum(args.named("default")?, span)?,
             "product" => array.product(args.named("default")?, span)?,
             "any" => array.any(vm, args.expect("function")?)?.into_value(),
@@ -148,6 +201,13 @@ pub fn call(
             "zip" => array.zip(args.expect("other")?).into_value(),
             "enumerate" => array.enumerate().into_value(),
             "dedup" => array.dedup(vm, args.named("key")?)?.into_value(),
+            "chunks" => {
+                let size = args.expect("size")?;
+                let exact = args.named("exact")?.unwrap_or(false);
+                array.chunks(size, exact).into_value()
+            }
+            "windows" => array.windows(args.expect("size")?).into_value(),
+            "group-by" => array.group_by(vm, args.expect("key")?)?.into_value(),
             _ => return missing(),
         },
 
@@ -160,11 +220,18 @@ pub fn call(
             "keys" => dict.keys().into_value(),
             "values" => dict.values().into_value(),
             "pairs" => dict.pairs().into_value(),
+            "merge" => {
+                let other = args.expect("other")?;
+                let deep = args.named("deep")?.unwrap_or(false);
+                dict.merge(other, deep).into_value()
+            }
             _ => return missing(),
         },
 
         Value::Func(func) => match method {
             "with" => func.with(args.take()).into_value(),
+            "compose" => func.compose(args.all()?).into_value(),
+            "curry" => func.curry(args.eat()?).at(span)?.into_value(),
             "where" => {
                 let fields = args.to_named();
                 args.items.retain(|arg| arg.name.is_none());
@@ -179,19 +246,28 @@ pub fn call(
 
         Value::Length(length) => match method {
             unit @ ("pt" | "cm" | "mm" | "inches") => {
-                if length.em != Em::zero() {
-                    return Err(eco_format!("cannot convert a length with non-zero em units ({length:?}) to {unit}"))
-                        .hint(eco_format!("use 'length.abs.{unit}()' instead to ignore its em component"))
-                        .at(span);
-                }
+                let abs = match args.eat::<Styles>()? {
+                    Some(styles) => length.resolve(StyleChain::new(&styles)),
+                    None if length.em != Em::zero() => {
+                        return Err(eco_format!("cannot convert a length with non-zero em units ({length:?}) to {unit}"))
+                            .hint(eco_format!("use 'length.abs.{unit}()' instead to ignore its em component"))
+                            .at(span);
+                    }
+                    None => length.abs,
+                };
                 match unit {
-                    "pt" => length.abs.to_pt().into_value(),
-                    "cm" => length.abs.to_cm().into_value(),
-                    "mm" => length.abs.to_mm().into_value(),
-                    "inches" => length.abs.to_inches().into_value(),
+                    "pt" => abs.to_pt().into_value(),
+                    "cm" => abs.to_cm().into_value(),
+                    "mm" => abs.to_mm().into_value(),
+                    "inches" => abs.to_inches().into_value(),
                     _ => unreachable!(),
                 }
             }
+            "ems" => {
+                let styles: Styles = args.expect("styles")?;
+                let size = (vm.items.em)(StyleChain::new(&styles));
+                (length.em.get() + Em::from_length(length.abs, size).get()).into_value()
+            }
             _ => return missing(),
         },
 
@@ -231,12 +307,21 @@ pub fn call(
                             args.named_or_find::<bool>("inclusive")?.unwrap_or(true);
                         selector.clone().after(location, inclusive).into_value()
                     }
+                    "within" => {
+                        let Some(PageRange(start, end)) = args.named("page")? else {
+                            bail!(args.span, "missing argument: page");
+                        };
+                        selector.clone().within(start, end).into_value()
+                    }
                     _ => return missing(),
                 }
             } else if let Some(&datetime) = dynamic.downcast::<Datetime>() {
                 match method {
                     "display" => {
-                        datetime.display(args.eat()?).at(args.span)?.into_value()
+                        let pattern = args.eat()?;
+                        let lang = args.named("lang")?;
+                        let names = args.named("names")?;
+                        datetime.display(pattern, lang, names).at(args.span)?.into_value()
                     }
                     "year" => datetime.year().into_value(),
                     "month" => datetime.month().into_value(),
@@ -247,6 +332,54 @@ pub fn call(
                     "second" => datetime.second().into_value(),
                     _ => return missing(),
                 }
+            } else if let Some(&rng) = dynamic.downcast::<Rng>() {
+                // A temporary generator can't be advanced, so its state is
+                // simply dropped after the call.
+                let mut rng = rng;
+                call_rng(&mut rng, method, &mut args, span)?
+            } else if let Some(&duration) = dynamic.downcast::<Duration>() {
+                match method {
+                    "display" => {
+                        duration.display(args.eat()?).at(args.span)?.into_value()
+                    }
+                    "seconds" => duration.to_seconds().into_value(),
+                    "minutes" => duration.to_minutes().into_value(),
+                    "hours" => duration.to_hours().into_value(),
+                    "days" => duration.to_days().into_value(),
+                    "weeks" => duration.to_weeks().into_value(),
+                    _ => return missing(),
+                }
+            } else if let Some(&decimal) = dynamic.downcast::<Decimal>() {
+                match method {
+                    "round" => {
+                        let digits = args.eat::<u32>()?.unwrap_or(0);
+                        let mode = args.named::<Rounding>("mode")?.unwrap_or_default();
+                        decimal.round(digits, mode).into_value()
+                    }
+                    "rescale" => decimal.rescale(args.expect("digits")?).into_value(),
+                    "abs" => decimal.abs().into_value(),
+                    _ => return missing(),
+                }
+            } else if let Some(&complex) = dynamic.downcast::<Complex>() {
+                match method {
+                    "re" => complex.re.into_value(),
+                    "im" => complex.im.into_value(),
+                    "abs" => complex.abs().into_value(),
+                    "arg" => complex.arg().into_value(),
+                    "conj" => complex.conj().into_value(),
+                    "polar" => array![complex.abs(), complex.arg()].into_value(),
+                    "display" => {
+                        let digits = args.named("digits")?;
+                        let polar = args.named("polar")?.unwrap_or(false);
+                        complex.display(digits, polar).into_value()
+                    }
+                    _ => return missing(),
+                }
+            } else if let Some(plugin) = dynamic.downcast::<Plugin>() {
+                if plugin.functions().all(|name| name != method) {
+                    return missing();
+                }
+                plugin.call(method, args.all::<Bytes>()?).at(span)?.into_value()
             } else if let Some(direction) = dynamic.downcast::<Dir>() {
                 match method {
                     "axis" => direction.axis().description().into_value(),
@@ -310,6 +443,13 @@ pub fn call_mut(
             _ => return missing(),
         },
 
+        Value::Dyn(dynamic) => {
+            let Some(&rng) = dynamic.downcast::<Rng>() else { return missing() };
+            let mut rng = rng;
+            output = call_rng(&mut rng, method, &mut args, span)?;
+            *value = Value::dynamic(rng);
+        }
+
         _ => return missing(),
     }
 
@@ -345,16 +485,48 @@ pub fn call_access<'a>(
     Ok(slot)
 }
 
+/// Call a method on a random number generator, advancing it.
+fn call_rng(
+    rng: &mut Rng,
+    method: &str,
+    args: &mut Args,
+    span: Span,
+) -> SourceResult<Value> {
+    Ok(match method {
+        "int" => {
+            let low = args.expect("lower bound")?;
+            let high = args.expect("upper bound")?;
+            rng.int(low, high).at(span)?.into_value()
+        }
+        "float" => rng.float().into_value(),
+        "shuffle" => rng.shuffle(args.expect("array")?).into_value(),
+        "choice" => rng.choice(&args.expect("array")?).at(span)?,
+        _ => return Err(missing_method("rng", method)).at(span),
+    })
+}
+
 /// Whether a specific method is mutating.
 pub fn is_mutating(method: &str) -> bool {
     matches!(method, "push" | "pop" | "insert" | "remove")
 }
 
+/// Whether a specific method advances a random number generator.
+///
+/// These methods are only mutating when called on a generator.
+pub fn is_rng_method(method: &str) -> bool {
+    matches!(method, "int" | "float" | "shuffle" | "choice")
+}
+
 /// Whether a specific method is an accessor.
 pub fn is_accessor(method: &str) -> bool {
     matches!(method, "first" | "last" | "at")
 }
 
+/// The alpha channel of a color as a ratio.
+fn alpha(color: Color) -> Ratio {
+    Ratio::new(color.alpha() as f64 / 255.0)
+}
+
 /// The missing method error message.
 #[cold]
 fn missing_method(type_name: &str, method: &str) -> String {
@@ -368,11 +540,20 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("lighten", true),
             ("darken", true),
             ("negate", false),
+            ("saturate", true),
+            ("desaturate", true),
+            ("mix", true),
             ("kind", false),
             ("hex", false),
             ("rgba", false),
             ("cmyk", false),
             ("luma", false),
+            ("hsl", false),
+            ("hsv", false),
+            ("oklab", false),
+            ("to-rgb", false),
+            ("to-cmyk", false),
+            ("to-luma", false),
         ],
         "string" => &[
             ("len", false),
@@ -386,9 +567,13 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("last", false),
             ("match", true),
             ("matches", true),
+            ("normalize", true),
+            ("pad-start", true),
+            ("pad-end", true),
             ("position", true),
             ("replace", true),
             ("slice", true),
+            ("sort-key", true),
             ("split", true),
             ("starts-with", true),
             ("trim", true),
@@ -398,6 +583,9 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("has", true),
             ("at", true),
             ("fields", false),
+            ("children", false),
+            ("text", false),
+            ("query", true),
             ("location", false),
         ],
         "array" => &[
@@ -407,6 +595,7 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("contains", true),
             ("filter", true),
             ("find", true),
+            ("find-map", true),
             ("first", false),
             ("flatten", false),
             ("fold", true),
@@ -422,28 +611,67 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("remove", true),
             ("rev", false),
             ("slice", true),
+            ("skip-while", true),
             ("sorted", false),
+            ("take-while", true),
             ("enumerate", false),
             ("zip", true),
+            ("chunks", true),
+            ("windows", true),
+            ("group-by", true),
         ],
         "dictionary" => &[
             ("at", true),
             ("insert", true),
             ("keys", false),
             ("len", false),
+            ("merge", true),
             ("pairs", false),
             ("remove", true),
             ("values", false),
         ],
-        "function" => &[("where", true), ("with", true)],
-        "length" => &[("pt", false), ("cm", false), ("mm", false), ("inches", false)],
+        "function" => {
+            &[("compose", true), ("curry", false), ("where", true), ("with", true)]
+        }
+        "length" => &[
+            ("pt", false),
+            ("cm", false),
+            ("mm", false),
+            ("inches", false),
+            ("ems", true),
+        ],
         "angle" => &[("deg", false), ("rad", false)],
         "arguments" => &[("named", false), ("pos", false)],
         "location" => &[("page", false), ("position", false), ("page-numbering", false)],
-        "selector" => &[("or", true), ("and", true), ("before", true), ("after", true)],
+        "selector" => &[
+            ("or", true),
+            ("and", true),
+            ("before", true),
+            ("after", true),
+            ("within", true),
+        ],
         "direction" => {
             &[("axis", false), ("start", false), ("end", false), ("inv", false)]
         }
+        "duration" => &[
+            ("display", true),
+            ("seconds", false),
+            ("minutes", false),
+            ("hours", false),
+            ("days", false),
+            ("weeks", false),
+        ],
+        "decimal" => &[("round", true), ("rescale", true), ("abs", false)],
+        "complex" => &[
+            ("re", false),
+            ("im", false),
+            ("abs", false),
+            ("arg", false),
+            ("conj", false),
+            ("polar", false),
+            ("display", true),
+        ],
+        "rng" => &[("int", true), ("float", false), ("shuffle", true), ("choice", true)],
         "alignment" => &[("axis", false), ("inv", false)],
         "2d alignment" => &[("inv", false)],
         "counter" => &[
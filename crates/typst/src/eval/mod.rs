@@ -14,7 +14,10 @@ mod str;
 mod value;
 mod args;
 mod auto;
+mod complex;
 mod datetime;
+mod decimal;
+mod duration;
 mod fields;
 mod func;
 mod int;
@@ -22,6 +25,8 @@ mod methods;
 mod module;
 mod none;
 pub mod ops;
+mod plugin;
+mod rng;
 mod scope;
 mod symbol;
 mod tracer;
@@ -43,14 +48,19 @@ pub use self::auto::AutoValue;
 pub use self::cast::{
     cast, Cast, CastInfo, FromValue, IntoResult, IntoValue, Never, Reflect, Variadics,
 };
-pub use self::datetime::Datetime;
+pub use self::complex::Complex;
+pub use self::datetime::{DateNames, Datetime};
+pub use self::decimal::{Decimal, Rounding};
 pub use self::dict::{dict, Dict};
+pub use self::duration::Duration;
 pub use self::fields::fields_on;
 pub use self::func::{Func, FuncInfo, NativeFunc, Param, ParamInfo};
 pub use self::library::{set_lang_items, LangItems, Library};
 pub use self::methods::methods_on;
 pub use self::module::Module;
 pub use self::none::NoneValue;
+pub use self::plugin::Plugin;
+pub use self::rng::Rng;
 pub use self::scope::{Scope, Scopes};
 pub use self::str::{format_str, Regex, Str};
 pub use self::symbol::Symbol;
@@ -80,6 +90,7 @@ use crate::syntax::{
     parse, parse_code, parse_math, FileId, PackageSpec, PackageVersion, Source, Span,
     Spanned, SyntaxKind, SyntaxNode,
 };
+use crate::util::similar;
 use crate::World;
 
 const MAX_ITERATIONS: usize = 10_000;
@@ -580,13 +591,13 @@ impl Eval for ast::Strong {
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let body = self.body();
         if body.exprs().next().is_none() {
-            vm.vt
-                .tracer
-                .warn(warning!(self.span(), "no text within stars").with_hint(
-                EcoString::from(
-                    "using multiple consecutive stars (e.g. **) has no additional effect",
-                ),
-            ));
+            let hint =
+                "using multiple consecutive stars (e.g. **) has no additional effect";
+            vm.vt.tracer.warn(
+                warning!(self.span(), "no text within stars")
+                    .with_hint(hint.into())
+                    .with_kind("empty-strong"),
+            );
         }
 
         Ok((vm.items.strong)(body.eval(vm)?))
@@ -714,7 +725,7 @@ impl Eval for ast::MathIdent {
 
     #[tracing::instrument(name = "MathIdent::eval", skip_all)]
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
-        vm.scopes.get_in_math(self).cloned().at(self.span())
+        lookup(vm, self, self.span(), true)
     }
 }
 
@@ -793,7 +804,40 @@ impl Eval for ast::Ident {
 
     #[tracing::instrument(name = "Ident::eval", skip_all)]
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
-        vm.scopes.get(self).cloned().at(self.span())
+        lookup(vm, self, self.span(), false)
+    }
+}
+
+/// Look up a variable, suggesting a similar one if it doesn't exist.
+fn lookup(vm: &Vm, var: &str, span: Span, math: bool) -> SourceResult<Value> {
+    let result = if math { vm.scopes.get_in_math(var) } else { vm.scopes.get(var) };
+    result.cloned().map_err(|message| {
+        let mut error = SourceDiagnostic::error(span, message);
+        if let Some((name, value)) = vm.scopes.similar(var, math) {
+            error = error.with_suggestion(name, definition(value));
+        }
+        Box::new(vec![error])
+    })
+}
+
+/// Access a field on a value, suggesting a similar field if it doesn't exist.
+fn access_field(value: &Value, field: &str, span: Span) -> SourceResult<Value> {
+    value.field(field).map_err(|message| {
+        let mut error = SourceDiagnostic::error(span, message);
+        let names = value.field_names();
+        if let Some(name) = similar(field, names.iter().map(EcoString::as_str)) {
+            let span = value.field(name).map_or(Span::detached(), |v| definition(&v));
+            error = error.with_suggestion(name, span);
+        }
+        Box::new(vec![error])
+    })
+}
+
+/// Where a value was defined, if it was defined by the user.
+fn definition(value: &Value) -> Span {
+    match value {
+        Value::Func(func) => func.span(),
+        _ => Span::detached(),
     }
 }
 
@@ -1096,7 +1140,7 @@ impl Eval for ast::FieldAccess {
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let value = self.target().eval(vm)?;
         let field = self.field();
-        value.field(&field).at(field.span())
+        access_field(&value, &field, field.span())
     }
 }
 
@@ -1123,7 +1167,7 @@ impl Eval for ast::FuncCall {
             let field_span = field.span();
             let field = field.take();
             let point = || Tracepoint::Call(Some(field.clone()));
-            if methods::is_mutating(&field) {
+            if methods::is_mutating(&field) || advances_rng(vm, &target, &field)? {
                 let args = args.eval(vm)?;
                 let target = target.access(vm)?;
 
@@ -1141,7 +1185,7 @@ impl Eval for ast::FuncCall {
                         span,
                     );
                 }
-                (target.field(&field).at(field_span)?, args)
+                (access_field(target, &field, field_span)?, args)
             } else {
                 let target = target.eval(vm)?;
                 let args = args.eval(vm)?;
@@ -1155,7 +1199,7 @@ impl Eval for ast::FuncCall {
                         span,
                     );
                 }
-                (target.field(&field).at(field_span)?, args)
+                (access_field(&target, &field, field_span)?, args)
             }
         } else {
             (callee.eval(vm)?, args.eval(vm)?)
@@ -1211,6 +1255,34 @@ fn in_math(expr: &ast::Expr) -> bool {
     }
 }
 
+/// Whether a method call advances a random number generator that is stored
+/// in a variable, in which case the stored generator must be updated.
+///
+/// Only expressions that can be mutated are evaluated here, so that calls on
+/// temporary values are not evaluated twice.
+fn advances_rng(vm: &mut Vm, target: &ast::Expr, method: &str) -> SourceResult<bool> {
+    fn is_place(expr: &ast::Expr) -> bool {
+        match expr {
+            ast::Expr::Ident(_) => true,
+            ast::Expr::Parenthesized(paren) => is_place(&paren.expr()),
+            ast::Expr::FieldAccess(access) => is_place(&access.target()),
+            ast::Expr::FuncCall(call) => match call.callee() {
+                ast::Expr::FieldAccess(access) => {
+                    methods::is_accessor(&access.field()) && is_place(&access.target())
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    if !methods::is_rng_method(method) || !is_place(target) {
+        return Ok(false);
+    }
+
+    Ok(matches!(target.eval(vm)?, Value::Dyn(dynamic) if dynamic.is::<Rng>()))
+}
+
 impl Eval for ast::Args {
     type Output = Args;
 
@@ -1311,7 +1383,7 @@ fn define_pattern(
             vm.define(ident, value);
             Ok(Value::None)
         }
-        _ => bail!(expr.span(), "nested patterns are currently not supported"),
+        _ => bail!(expr.span(), "expected identifier, found expression"),
     })
 }
 
@@ -1345,45 +1417,93 @@ where
             Ok(Value::None)
         }
         ast::Pattern::Placeholder(_) => Ok(Value::None),
-        ast::Pattern::Destructuring(destruct) => match value {
-            Value::Array(value) => destructure_array(vm, pattern, value, f, destruct),
-            Value::Dict(value) => destructure_dict(vm, value, f, destruct),
-            _ => bail!(pattern.span(), "cannot destructure {}", value.type_name()),
-        },
+        ast::Pattern::Destructuring(destruct) => {
+            destructure_value(vm, destruct, value, &f)?;
+            Ok(Value::None)
+        }
+    }
+}
+
+/// Destruct an array or dictionary into a (possibly nested) pattern.
+fn destructure_value<F>(
+    vm: &mut Vm,
+    destruct: &ast::Destructuring,
+    value: Value,
+    f: &F,
+) -> SourceResult<()>
+where
+    F: Fn(&mut Vm, ast::Expr, Value) -> SourceResult<Value>,
+{
+    match value {
+        Value::Array(value) => destructure_array(vm, value, f, destruct),
+        Value::Dict(value) => destructure_dict(vm, value, f, destruct),
+        _ => bail!(destruct.span(), "cannot destructure {}", value.type_name()),
     }
 }
 
+/// Bind a single value, falling back to the default of the binding if the
+/// value is missing.
+fn destructure_binding<F>(
+    vm: &mut Vm,
+    expr: ast::Expr,
+    value: Option<Value>,
+    f: &F,
+    missing: &str,
+) -> SourceResult<()>
+where
+    F: Fn(&mut Vm, ast::Expr, Value) -> SourceResult<Value>,
+{
+    if let ast::Expr::Binary(binary) = &expr {
+        if let Some((ident, default)) = binary.default_binding() {
+            let value = match value {
+                Some(value) => value,
+                None => default.eval(vm)?,
+            };
+            f(vm, ast::Expr::Ident(ident), value)?;
+            return Ok(());
+        }
+    }
+
+    let Some(value) = value else { bail!(expr.span(), "{missing}") };
+    f(vm, expr, value)?;
+    Ok(())
+}
+
 fn destructure_array<F>(
     vm: &mut Vm,
-    pattern: &ast::Pattern,
     value: Array,
-    f: F,
+    f: &F,
     destruct: &ast::Destructuring,
-) -> SourceResult<Value>
+) -> SourceResult<()>
 where
     F: Fn(&mut Vm, ast::Expr, Value) -> SourceResult<Value>,
 {
+    const MISSING: &str = "not enough elements to destructure";
+
     let mut i = 0;
     let len = value.as_slice().len();
+    let count = destruct.bindings().count();
+    let has_defaults = !destruct.defaults().is_empty();
     for p in destruct.bindings() {
         match p {
             ast::DestructuringKind::Normal(expr) => {
-                let Ok(v) = value.at(i as i64, None) else {
-                        bail!(expr.span(), "not enough elements to destructure");
-                    };
-                f(vm, expr, v.clone())?;
+                let v = value.as_slice().get(i).cloned();
+                destructure_binding(vm, expr, v, f, MISSING)?;
                 i += 1;
             }
             ast::DestructuringKind::Sink(spread) => {
-                let sink_size = (1 + len).checked_sub(destruct.bindings().count());
-                let sink = sink_size.and_then(|s| value.as_slice().get(i..i + s));
+                let sink_size =
+                    (1 + len).checked_sub(count).or(has_defaults.then_some(0));
+                // Bindings with defaults may have moved past the end.
+                let start = i.min(len);
+                let sink = sink_size.and_then(|s| value.as_slice().get(start..start + s));
                 if let (Some(sink_size), Some(sink)) = (sink_size, sink) {
                     if let Some(expr) = spread.expr() {
                         f(vm, expr, Value::Array(sink.into()))?;
                     }
-                    i += sink_size;
+                    i = start + sink_size;
                 } else {
-                    bail!(pattern.span(), "not enough elements to destructure")
+                    bail!(destruct.span(), "{MISSING}")
                 }
             }
             ast::DestructuringKind::Named(named) => {
@@ -1393,53 +1513,82 @@ where
                 if i < len {
                     i += 1
                 } else {
-                    bail!(underscore.span(), "not enough elements to destructure")
+                    bail!(underscore.span(), "{MISSING}")
                 }
             }
+            ast::DestructuringKind::Nested(nested) => {
+                let Some(v) = value.as_slice().get(i) else {
+                    bail!(nested.span(), "{MISSING}");
+                };
+                destructure_value(vm, &nested, v.clone(), f)?;
+                i += 1;
+            }
         }
     }
     if i < len {
-        bail!(pattern.span(), "too many elements to destructure");
+        bail!(destruct.span(), "too many elements to destructure");
     }
 
-    Ok(Value::None)
+    Ok(())
 }
 
 fn destructure_dict<F>(
     vm: &mut Vm,
     dict: Dict,
-    f: F,
+    f: &F,
     destruct: &ast::Destructuring,
-) -> SourceResult<Value>
+) -> SourceResult<()>
 where
     F: Fn(&mut Vm, ast::Expr, Value) -> SourceResult<Value>,
 {
+    const MISSING: &str = "destructuring key not found in dictionary";
+
     let mut sink = None;
     let mut used = HashSet::new();
     for p in destruct.bindings() {
         match p {
             ast::DestructuringKind::Normal(ast::Expr::Ident(ident)) => {
-                let v = dict
-                    .at(&ident, None)
-                    .map_err(|_| "destructuring key not found in dictionary")
-                    .at(ident.span())?;
+                let v = dict.at(&ident, None).map_err(|_| MISSING).at(ident.span())?;
                 f(vm, ast::Expr::Ident(ident.clone()), v.clone())?;
                 used.insert(ident.take());
             }
+            ast::DestructuringKind::Normal(expr) if is_default_binding(&expr) => {
+                let ast::Expr::Binary(binary) = &expr else { continue };
+                let Some((ident, _)) = binary.default_binding() else { continue };
+                let v = dict.at(&ident, None).ok().cloned();
+                destructure_binding(vm, expr, v, f, MISSING)?;
+                used.insert(ident.take());
+            }
             ast::DestructuringKind::Sink(spread) => sink = spread.expr(),
             ast::DestructuringKind::Named(named) => {
                 let name = named.name();
-                let v = dict
-                    .at(&name, None)
-                    .map_err(|_| "destructuring key not found in dictionary")
-                    .at(name.span())?;
-                f(vm, named.expr(), v.clone())?;
+                let v = dict.at(&name, None).ok().cloned();
+                match named.pattern() {
+                    ast::Pattern::Normal(expr) => {
+                        if v.is_none() && !is_default_binding(&expr) {
+                            bail!(name.span(), "{MISSING}");
+                        }
+                        destructure_binding(vm, expr, v, f, MISSING)?;
+                    }
+                    ast::Pattern::Placeholder(_) => {
+                        if v.is_none() {
+                            bail!(name.span(), "{MISSING}");
+                        }
+                    }
+                    ast::Pattern::Destructuring(nested) => {
+                        let Some(v) = v else { bail!(name.span(), "{MISSING}") };
+                        destructure_value(vm, &nested, v, f)?;
+                    }
+                }
                 used.insert(name.take());
             }
             ast::DestructuringKind::Placeholder(_) => {}
             ast::DestructuringKind::Normal(expr) => {
                 bail!(expr.span(), "expected key, found expression");
             }
+            ast::DestructuringKind::Nested(nested) => {
+                bail!(nested.span(), "expected key, found destructuring pattern");
+            }
         }
     }
 
@@ -1453,7 +1602,74 @@ where
         f(vm, expr, Value::Dict(sink))?;
     }
 
-    Ok(Value::None)
+    Ok(())
+}
+
+/// Whether the expression is a binding with a default value: `x = 1`.
+fn is_default_binding(expr: &ast::Expr) -> bool {
+    matches!(expr, ast::Expr::Binary(binary) if binary.default_binding().is_some())
+}
+
+/// Whether the value fits the structure of the pattern, that is, whether
+/// destructuring it would succeed.
+pub(crate) fn pattern_matches(pattern: &ast::Pattern, value: &Value) -> bool {
+    match pattern {
+        ast::Pattern::Normal(_) | ast::Pattern::Placeholder(_) => true,
+        ast::Pattern::Destructuring(destruct) => destructuring_matches(destruct, value),
+    }
+}
+
+fn destructuring_matches(destruct: &ast::Destructuring, value: &Value) -> bool {
+    match value {
+        Value::Array(array) => {
+            let items = array.as_slice();
+            let mut required = 0;
+            let mut optional = 0;
+            let mut sink = false;
+            for (i, binding) in destruct.bindings().enumerate() {
+                match binding {
+                    ast::DestructuringKind::Normal(expr) if is_default_binding(&expr) => {
+                        optional += 1
+                    }
+                    ast::DestructuringKind::Normal(_)
+                    | ast::DestructuringKind::Placeholder(_) => required += 1,
+                    ast::DestructuringKind::Nested(nested) if !sink => {
+                        match items.get(i) {
+                            Some(item) if destructuring_matches(&nested, item) => {
+                                required += 1
+                            }
+                            _ => return false,
+                        }
+                    }
+                    ast::DestructuringKind::Nested(_) => required += 1,
+                    ast::DestructuringKind::Sink(_) => sink = true,
+                    ast::DestructuringKind::Named(_) => return false,
+                }
+            }
+            items.len() >= required && (sink || items.len() <= required + optional)
+        }
+        Value::Dict(dict) => destruct.bindings().all(|binding| match binding {
+            ast::DestructuringKind::Normal(ast::Expr::Ident(ident)) => {
+                dict.contains(&ident)
+            }
+            ast::DestructuringKind::Normal(expr) => is_default_binding(&expr),
+            ast::DestructuringKind::Named(named) => {
+                match (named.pattern(), dict.at(&named.name(), None)) {
+                    (ast::Pattern::Destructuring(nested), Ok(v)) => {
+                        destructuring_matches(&nested, v)
+                    }
+                    (_, Ok(_)) => true,
+                    (ast::Pattern::Normal(expr), Err(_)) => is_default_binding(&expr),
+                    (_, Err(_)) => false,
+                }
+            }
+            ast::DestructuringKind::Nested(_) => false,
+            ast::DestructuringKind::Sink(_) | ast::DestructuringKind::Placeholder(_) => {
+                true
+            }
+        }),
+        _ => false,
+    }
 }
 
 impl Eval for ast::LetBinding {
@@ -1685,14 +1901,21 @@ impl Eval for ast::ForLoop {
 }
 
 /// Applies imports from `import` to the current scope.
-fn apply_imports<V: IntoValue>(
+fn apply_imports<V: IntoValue + Clone>(
     imports: Option<ast::Imports>,
+    new_name: Option<ast::Ident>,
     vm: &mut Vm,
     source_value: V,
     name: impl Fn(&V) -> EcoString,
     scope: impl Fn(&V) -> &Scope,
 ) -> SourceResult<()> {
+    let renamed = new_name.is_some();
+    if let Some(new_name) = new_name {
+        vm.define(new_name, source_value.clone());
+    }
+
     match imports {
+        None if renamed => {}
         None => {
             vm.scopes.top.define(name(&source_value), source_value);
         }
@@ -1733,6 +1956,7 @@ impl Eval for ast::ModuleImport {
             }
             apply_imports(
                 self.imports(),
+                self.new_name(),
                 vm,
                 func,
                 |func| func.info().unwrap().name.into(),
@@ -1742,6 +1966,7 @@ impl Eval for ast::ModuleImport {
             let module = import(vm, source, span, true)?;
             apply_imports(
                 self.imports(),
+                self.new_name(),
                 vm,
                 module,
                 |module| module.name().clone(),
@@ -5,7 +5,7 @@ use std::fmt::Debug;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Complex, Datetime, Decimal, Duration, Regex, Value};
 use crate::diag::{bail, StrResult};
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
 use Value::*;
@@ -47,6 +47,7 @@ pub fn pos(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(v),
         Relative(v) => Relative(v),
         Fraction(v) => Fraction(v),
+        Dyn(v) if v.is::<Decimal>() || v.is::<Complex>() => Dyn(v),
         v => mismatch!("cannot apply '+' to {}", v),
     })
 }
@@ -61,6 +62,15 @@ pub fn neg(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(-v),
         Relative(v) => Relative(-v),
         Fraction(v) => Fraction(-v),
+        Dyn(v) if v.is::<Duration>() => {
+            Value::dynamic(v.downcast::<Duration>().unwrap().checked_neg()?)
+        }
+        Dyn(v) if v.is::<Decimal>() => {
+            Value::dynamic(-*v.downcast::<Decimal>().unwrap())
+        }
+        Dyn(v) if v.is::<Complex>() => {
+            Value::dynamic(-*v.downcast::<Complex>().unwrap())
+        }
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -105,6 +115,16 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Array(a), Array(b)) => Array(a + b),
         (Dict(a), Dict(b)) => Dict(a + b),
 
+        (a, b) if is_decimal_pair(&a, &b) => {
+            let (a, b) = decimal_pair(&a, &b)?;
+            Value::dynamic(a.checked_add(b)?)
+        }
+
+        (a, b) if is_complex_pair(&a, &b) => {
+            let (a, b) = complex_pair(&a, &b)?;
+            Value::dynamic(a + b)
+        }
+
         (Color(color), Length(thickness)) | (Length(thickness), Color(color)) => {
             Value::dynamic(PartialStroke {
                 paint: Smart::Custom(color.into()),
@@ -128,6 +148,23 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
                 }));
             };
 
+            // Durations can be added to each other and to datetimes.
+            match (a.downcast::<Datetime>(), a.downcast::<Duration>()) {
+                (Some(&a), _) => {
+                    if let Some(&b) = b.downcast::<Duration>() {
+                        return Ok(Value::dynamic(a.checked_add(b)?));
+                    }
+                }
+                (_, Some(&a)) => {
+                    if let Some(&b) = b.downcast::<Duration>() {
+                        return Ok(Value::dynamic(a.checked_add(b)?));
+                    } else if let Some(&b) = b.downcast::<Datetime>() {
+                        return Ok(Value::dynamic(b.checked_add(a)?));
+                    }
+                }
+                _ => {}
+            }
+
             mismatch!("cannot add {} and {}", a, b);
         }
 
@@ -159,6 +196,32 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a - b),
 
+        (a, b) if is_decimal_pair(&a, &b) => {
+            let (a, b) = decimal_pair(&a, &b)?;
+            Value::dynamic(a.checked_sub(b)?)
+        }
+
+        (a, b) if is_complex_pair(&a, &b) => {
+            let (a, b) = complex_pair(&a, &b)?;
+            Value::dynamic(a - b)
+        }
+
+        (Dyn(a), Dyn(b)) => {
+            if let Some(&a) = a.downcast::<Datetime>() {
+                if let Some(&b) = b.downcast::<Datetime>() {
+                    return Ok(Value::dynamic(a.checked_sub(b)?));
+                } else if let Some(&b) = b.downcast::<Duration>() {
+                    return Ok(Value::dynamic(a.checked_add(b.checked_neg()?)?));
+                }
+            } else if let Some(&a) = a.downcast::<Duration>() {
+                if let Some(&b) = b.downcast::<Duration>() {
+                    return Ok(Value::dynamic(a.checked_sub(b)?));
+                }
+            }
+
+            mismatch!("cannot subtract {1} from {0}", a, b);
+        }
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
@@ -212,6 +275,29 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Content(a), b @ Int(_)) => Content(a.repeat(b.cast()?)),
         (a @ Int(_), Content(b)) => Content(b.repeat(a.cast()?)),
 
+        (a, b) if is_decimal_pair(&a, &b) => {
+            let (a, b) = decimal_pair(&a, &b)?;
+            Value::dynamic(a.checked_mul(b)?)
+        }
+
+        (a, b) if is_complex_pair(&a, &b) => {
+            let (a, b) = complex_pair(&a, &b)?;
+            Value::dynamic(a * b)
+        }
+
+        (Dyn(a), Int(b)) if a.is::<Duration>() => {
+            Value::dynamic(a.downcast::<Duration>().unwrap().checked_mul(b as f64)?)
+        }
+        (Dyn(a), Float(b)) if a.is::<Duration>() => {
+            Value::dynamic(a.downcast::<Duration>().unwrap().checked_mul(b)?)
+        }
+        (Int(a), Dyn(b)) if b.is::<Duration>() => {
+            Value::dynamic(b.downcast::<Duration>().unwrap().checked_mul(a as f64)?)
+        }
+        (Float(a), Dyn(b)) if b.is::<Duration>() => {
+            Value::dynamic(b.downcast::<Duration>().unwrap().checked_mul(a)?)
+        }
+
         (a, b) => mismatch!("cannot multiply {} with {}", a, b),
     })
 }
@@ -252,6 +338,28 @@ pub fn div(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Fraction(a), Float(b)) => Fraction(a / b),
         (Fraction(a), Fraction(b)) => Float(a / b),
 
+        (a, b) if is_decimal_pair(&a, &b) => {
+            let (a, b) = decimal_pair(&a, &b)?;
+            Value::dynamic(a.checked_div(b)?)
+        }
+
+        (a, b) if is_complex_pair(&a, &b) => {
+            let (a, b) = complex_pair(&a, &b)?;
+            Value::dynamic(a / b)
+        }
+
+        (Dyn(a), Int(b)) if a.is::<Duration>() => {
+            Value::dynamic(a.downcast::<Duration>().unwrap().checked_div(b as f64)?)
+        }
+        (Dyn(a), Float(b)) if a.is::<Duration>() => {
+            Value::dynamic(a.downcast::<Duration>().unwrap().checked_div(b)?)
+        }
+        (Dyn(a), Dyn(b)) if a.is::<Duration>() && b.is::<Duration>() => Float(
+            a.downcast::<Duration>()
+                .unwrap()
+                .ratio(*b.downcast::<Duration>().unwrap()),
+        ),
+
         (a, b) => mismatch!("cannot divide {} by {}", a, b),
     })
 }
@@ -266,10 +374,52 @@ fn is_zero(v: &Value) -> bool {
         Ratio(v) => v.is_zero(),
         Relative(v) => v.is_zero(),
         Fraction(v) => v.is_zero(),
+        Dyn(ref v) => {
+            v.downcast::<Duration>().is_some_and(|d| d.is_zero())
+                || v.downcast::<Decimal>().is_some_and(|d| d.is_zero())
+                || v.downcast::<Complex>().is_some_and(|c| c.is_zero())
+        }
         _ => false,
     }
 }
 
+/// Whether one of the values is a decimal and the other one a decimal or an
+/// integer.
+fn is_decimal_pair(a: &Value, b: &Value) -> bool {
+    let is_decimal = |v: &Value| matches!(v, Dyn(v) if v.is::<Decimal>());
+    (is_decimal(a) && (is_decimal(b) || matches!(b, Int(_))))
+        || (matches!(a, Int(_)) && is_decimal(b))
+}
+
+/// Convert a pair of decimals or a decimal and an integer into two decimals.
+fn decimal_pair(a: &Value, b: &Value) -> StrResult<(Decimal, Decimal)> {
+    let convert = |v: &Value| match v {
+        Int(v) => Ok(Decimal::from_int(*v)),
+        Dyn(v) if v.is::<Decimal>() => Ok(*v.downcast::<Decimal>().unwrap()),
+        v => Err(eco_format!("expected decimal, found {}", v.type_name())),
+    };
+    Ok((convert(a)?, convert(b)?))
+}
+
+/// Whether one of the values is a complex number and the other one a complex
+/// number, an integer, or a float.
+fn is_complex_pair(a: &Value, b: &Value) -> bool {
+    let is_complex = |v: &Value| matches!(v, Dyn(v) if v.is::<Complex>());
+    let is_real = |v: &Value| matches!(v, Int(_) | Float(_));
+    (is_complex(a) && (is_complex(b) || is_real(b))) || (is_real(a) && is_complex(b))
+}
+
+/// Convert a pair of complex or real numbers into two complex numbers.
+fn complex_pair(a: &Value, b: &Value) -> StrResult<(Complex, Complex)> {
+    let convert = |v: &Value| match v {
+        Int(v) => Ok(Complex::from(*v as f64)),
+        Float(v) => Ok(Complex::from(*v)),
+        Dyn(v) if v.is::<Complex>() => Ok(*v.downcast::<Complex>().unwrap()),
+        v => Err(eco_format!("expected complex, found {}", v.type_name())),
+    };
+    Ok((convert(a)?, convert(b)?))
+}
+
 /// Try to divide two lengths.
 fn try_div_length(a: Length, b: Length) -> StrResult<f64> {
     a.try_div(b).ok_or_else(|| "cannot divide these two lengths".into())
@@ -347,6 +497,7 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Color(a), Color(b)) => a == b,
         (Symbol(a), Symbol(b)) => a == b,
         (Str(a), Str(b)) => a == b,
+        (Bytes(a), Bytes(b)) => a == b,
         (Label(a), Label(b)) => a == b,
         (Content(a), Content(b)) => a == b,
         (Array(a), Array(b)) => a == b,
@@ -359,6 +510,12 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         // Some technically different things should compare equal.
         (&Int(a), &Float(b)) => a as f64 == b,
         (&Float(a), &Int(b)) => a == b as f64,
+        (Int(a), Dyn(b)) => {
+            b.downcast::<Decimal>().is_some_and(|b| b.cmp_int(*a).is_eq())
+        }
+        (Dyn(a), Int(b)) => {
+            a.downcast::<Decimal>().is_some_and(|a| a.cmp_int(*b).is_eq())
+        }
         (&Length(a), &Relative(b)) => a == b.abs && b.rel.is_zero(),
         (&Ratio(a), &Relative(b)) => a == b.rel && b.abs.is_zero(),
         (&Relative(a), &Length(b)) => a.abs == b && a.rel.is_zero(),
@@ -380,6 +537,29 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Relative(a), Relative(b)) => try_cmp_values(a, b)?,
         (Fraction(a), Fraction(b)) => a.cmp(b),
         (Str(a), Str(b)) => a.cmp(b),
+        (Dyn(a), Dyn(b)) => {
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                a.compare(b)?
+            } else if let (Some(a), Some(b)) =
+                (a.downcast::<Duration>(), b.downcast::<Duration>())
+            {
+                a.cmp(b)
+            } else if let (Some(a), Some(b)) =
+                (a.downcast::<Decimal>(), b.downcast::<Decimal>())
+            {
+                a.cmp(b)
+            } else {
+                mismatch!("cannot compare {} and {}", lhs, rhs);
+            }
+        }
+        (Int(a), Dyn(b)) if b.is::<Decimal>() => {
+            b.downcast::<Decimal>().unwrap().cmp_int(*a).reverse()
+        }
+        (Dyn(a), Int(b)) if a.is::<Decimal>() => {
+            a.downcast::<Decimal>().unwrap().cmp_int(*b)
+        }
 
         // Some technically different things should be comparable.
         (Int(a), Float(b)) => try_cmp_values(&(*a as f64), b)?,
@@ -0,0 +1,237 @@
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use ecow::{eco_format, EcoString};
+use wasmi::{AsContext, AsContextMut, Caller, Engine, Linker, Module};
+
+use super::cast;
+use crate::diag::{bail, StrResult};
+use crate::util::Bytes;
+
+/// The name of the module from which plugins import the host functions.
+const HOST_MODULE: &str = "typst_env";
+
+/// A loaded WebAssembly plugin.
+///
+/// Plugins communicate with Typst through a minimal protocol: Each exported
+/// function receives the lengths of its byte arguments as 32-bit integers and
+/// fetches the argument data by calling the host function
+/// `wasm_minimal_protocol_write_args_to_buffer` with a pointer to a buffer of
+/// sufficient size. It then hands its output to the host through
+/// `wasm_minimal_protocol_send_result_to_host` and returns `0` on success or
+/// `1` if the output is an error message.
+#[derive(Clone)]
+pub struct Plugin(Arc<Repr>);
+
+/// The internal representation of a plugin.
+struct Repr {
+    /// The raw WebAssembly bytes.
+    bytes: Bytes,
+    /// The functions exported by the plugin.
+    functions: Vec<(EcoString, wasmi::Func)>,
+    /// The instance's store.
+    store: Mutex<Store>,
+}
+
+/// The store of a plugin instance.
+type Store = wasmi::Store<StoreData>;
+
+/// The data in a plugin instance's store.
+#[derive(Default)]
+struct StoreData {
+    /// The arguments for the current call.
+    args: Vec<Bytes>,
+    /// The output of the current call.
+    output: Vec<u8>,
+    /// A memory access error which occurred during the current call.
+    memory_error: Option<MemoryError>,
+}
+
+/// An out-of-bounds memory access of a plugin.
+struct MemoryError {
+    /// Where the access started.
+    offset: usize,
+    /// The length of the access.
+    length: usize,
+    /// Whether the access was a write.
+    write: bool,
+}
+
+impl Plugin {
+    /// Create a new plugin from raw WebAssembly bytes.
+    #[comemo::memoize]
+    pub fn new(bytes: Bytes) -> StrResult<Self> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, bytes.as_slice())
+            .map_err(|err| eco_format!("failed to load WebAssembly module ({err})"))?;
+
+        let mut linker = Linker::new(&engine);
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "wasm_minimal_protocol_send_result_to_host",
+                send_result_to_host,
+            )
+            .unwrap();
+        linker
+            .func_wrap(
+                HOST_MODULE,
+                "wasm_minimal_protocol_write_args_to_buffer",
+                write_args_to_buffer,
+            )
+            .unwrap();
+
+        let mut store = Store::new(&engine, StoreData::default());
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|pre_instance| pre_instance.start(&mut store))
+            .map_err(|err| eco_format!("failed to instantiate plugin ({err})"))?;
+
+        // The host functions need access to the plugin's memory.
+        if !matches!(
+            instance.get_export(&store, "memory"),
+            Some(wasmi::Extern::Memory(_))
+        ) {
+            bail!("plugin does not export its memory");
+        }
+
+        let functions = instance
+            .exports(&store)
+            .filter_map(|export| {
+                let name = export.name().into();
+                export.into_func().map(|func| (name, func))
+            })
+            .collect();
+
+        Ok(Self(Arc::new(Repr { bytes, functions, store: Mutex::new(store) })))
+    }
+
+    /// Call the plugin function with the given name.
+    pub fn call(&self, name: &str, args: Vec<Bytes>) -> StrResult<Bytes> {
+        let func = self
+            .0
+            .functions
+            .iter()
+            .find(|(v, _)| v == name)
+            .map(|&(_, func)| func)
+            .ok_or_else(|| {
+                eco_format!("plugin does not contain a function called {name}")
+            })?;
+
+        let mut store = self.0.store.lock().unwrap();
+        let ty = func.ty(store.as_context());
+
+        if ty.params().iter().any(|&v| v != wasmi::core::ValueType::I32) {
+            bail!(
+                "plugin function `{name}` has a parameter that is not a 32-bit integer"
+            );
+        }
+
+        if ty.results() != [wasmi::core::ValueType::I32] {
+            bail!("plugin function `{name}` does not return exactly one 32-bit integer");
+        }
+
+        let expected = ty.params().len();
+        let given = args.len();
+        if expected != given {
+            bail!(
+                "plugin function takes {expected} argument{}, but {given} {} given",
+                if expected == 1 { "" } else { "s" },
+                if given == 1 { "was" } else { "were" },
+            );
+        }
+
+        // The function receives the lengths of the arguments and fetches their
+        // data through a host function.
+        let lengths = args
+            .iter()
+            .map(|arg| wasmi::Value::I32(arg.len() as i32))
+            .collect::<Vec<_>>();
+
+        store.data_mut().args = args;
+
+        let mut code = wasmi::Value::I32(-1);
+        func.call(store.as_context_mut(), &lengths, std::slice::from_mut(&mut code))
+            .map_err(|err| eco_format!("plugin panicked: {err}"))?;
+
+        if let Some(MemoryError { offset, length, write }) =
+            store.data_mut().memory_error.take()
+        {
+            let kind = if write { "write" } else { "read" };
+            bail!(
+                "plugin tried to {kind} out of bounds: \
+                 pointer {offset:#x} is out of bounds for {kind} of length {length}"
+            );
+        }
+
+        let output = std::mem::take(&mut store.data_mut().output);
+        match code {
+            wasmi::Value::I32(0) => {}
+            wasmi::Value::I32(1) => match std::str::from_utf8(&output) {
+                Ok(message) => bail!("plugin errored with: {message}"),
+                Err(_) => {
+                    bail!("plugin errored, but did not return a valid error message")
+                }
+            },
+            _ => bail!("plugin did not respect the protocol"),
+        }
+
+        Ok(output.into())
+    }
+
+    /// The names of the functions exported by the plugin.
+    pub fn functions(&self) -> impl Iterator<Item = &EcoString> {
+        self.0.functions.iter().map(|(name, _)| name)
+    }
+}
+
+/// Write the arguments of the current call into the plugin's memory.
+fn write_args_to_buffer(mut caller: Caller<StoreData>, ptr: u32) {
+    let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+    let args = std::mem::take(&mut caller.data_mut().args);
+    let mut offset = ptr as usize;
+    for arg in args {
+        if memory.write(&mut caller, offset, arg.as_slice()).is_err() {
+            caller.data_mut().memory_error =
+                Some(MemoryError { offset, length: arg.len(), write: true });
+            return;
+        }
+        offset += arg.len();
+    }
+}
+
+/// Read the output of the current call from the plugin's memory.
+fn send_result_to_host(mut caller: Caller<StoreData>, ptr: u32, len: u32) {
+    let memory = caller.get_export("memory").unwrap().into_memory().unwrap();
+    let mut buffer = std::mem::take(&mut caller.data_mut().output);
+    buffer.resize(len as usize, 0);
+    if memory.read(&caller, ptr as _, &mut buffer).is_err() {
+        caller.data_mut().memory_error =
+            Some(MemoryError { offset: ptr as _, length: len as _, write: false });
+        return;
+    }
+    caller.data_mut().output = buffer;
+}
+
+impl Debug for Plugin {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad("plugin(..)")
+    }
+}
+
+impl PartialEq for Plugin {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.bytes == other.0.bytes
+    }
+}
+
+impl Hash for Plugin {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.bytes.hash(state);
+    }
+}
+
+cast! {
+    type Plugin: "plugin",
+}
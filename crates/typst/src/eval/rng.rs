@@ -0,0 +1,98 @@
+use std::fmt::{self, Debug, Formatter};
+
+use super::{cast, Array, Value};
+use crate::diag::{bail, StrResult};
+
+/// A seedable pseudo-random number generator.
+///
+/// The generator is an implementation of xoshiro256**, seeded through
+/// SplitMix64. Its output only depends on the seed, so documents using it are
+/// reproducible across compilations and platforms.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rng([u64; 4]);
+
+impl Rng {
+    /// Create a new generator from a seed.
+    pub fn new(seed: i64) -> Self {
+        let mut state = seed as u64;
+        let mut next = || {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+        Self([next(), next(), next(), next()])
+    }
+
+    /// Produce the next raw 64-bit output and advance the generator.
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// A uniformly distributed integer in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        // Lemire's method with rejection to avoid any bias.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = (self.next_u64() as u128) * (bound as u128);
+            if (product as u64) >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// A uniformly distributed integer in `low..high`.
+    pub fn int(&mut self, low: i64, high: i64) -> StrResult<i64> {
+        if low >= high {
+            bail!("upper bound must be larger than lower bound");
+        }
+
+        let span = high.wrapping_sub(low) as u64;
+        Ok(low.wrapping_add(self.below(span) as i64))
+    }
+
+    /// A uniformly distributed float in `0.0..1.0`.
+    pub fn float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// A copy of the array with its items in random order.
+    pub fn shuffle(&mut self, array: Array) -> Array {
+        let mut items: Vec<Value> = array.into_iter().collect();
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+        items.into_iter().collect()
+    }
+
+    /// A random item of the array.
+    pub fn choice(&mut self, array: &Array) -> StrResult<Value> {
+        if array.is_empty() {
+            bail!("cannot choose from an empty array");
+        }
+
+        let index = self.below(array.len() as u64) as i64;
+        array.at(index, None).cloned()
+    }
+}
+
+impl Debug for Rng {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad("rng(..)")
+    }
+}
+
+cast! {
+    type Rng: "rng",
+}
@@ -6,6 +6,7 @@ use ecow::{eco_format, EcoString};
 
 use super::{IntoValue, Library, Value};
 use crate::diag::{bail, StrResult};
+use crate::util::similar;
 
 /// A stack of scopes.
 #[derive(Debug, Default, Clone)]
@@ -54,6 +55,35 @@ impl<'a> Scopes<'a> {
             .ok_or_else(|| eco_format!("unknown variable: {}", var))
     }
 
+    /// Find a variable with a name similar to a misspelled one.
+    ///
+    /// Closer scopes are preferred over the standard library.
+    pub fn similar(&self, var: &str, math: bool) -> Option<(&str, &Value)> {
+        let scopes: Vec<&Scope> = std::iter::once(&self.top)
+            .chain(self.scopes.iter().rev())
+            .chain(self.base.map(|base| {
+                if math {
+                    base.math.scope()
+                } else {
+                    base.global.scope()
+                }
+            }))
+            .collect();
+
+        let name = similar(
+            var,
+            scopes
+                .iter()
+                .copied()
+                .flat_map(|scope| scope.iter().map(|(name, _)| name.as_str())),
+        )?;
+
+        scopes
+            .into_iter()
+            .find_map(|scope| scope.get(name))
+            .map(|value| (name, value))
+    }
+
     /// Try to access a variable mutably.
     pub fn get_mut(&mut self, var: &str) -> StrResult<&mut Value> {
         std::iter::once(&mut self.top)
@@ -4,10 +4,13 @@ use std::hash::{Hash, Hasher};
 use std::ops::{Add, AddAssign, Deref, Range};
 
 use ecow::EcoString;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{cast, dict, Args, Array, Dict, Func, IntoValue, Value, Vm};
+use super::{cast, dict, Args, Array, Cast, Dict, Func, IntoValue, Value, Vm};
 use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::doc::Lang;
 use crate::geom::GenAlign;
 
 /// Create a new [`Str`] from a format string.
@@ -314,6 +317,23 @@ impl Str {
         Ok(output.into())
     }
 
+    /// Pad the string at the start or end with a fill grapheme cluster until
+    /// it consists of at least `width` grapheme clusters.
+    pub fn pad(&self, width: usize, fill: Option<Str>, side: StrSide) -> StrResult<Self> {
+        let fill = fill.as_deref().unwrap_or(" ");
+        if fill.graphemes(true).count() != 1 {
+            bail!("fill must be exactly one grapheme cluster");
+        }
+
+        let count = width.saturating_sub(self.graphemes(true).count());
+        let padding = fill.repeat(count);
+        Ok(match side {
+            StrSide::Start => format!("{padding}{}", self.as_str()),
+            StrSide::End => format!("{}{padding}", self.as_str()),
+        }
+        .into())
+    }
+
     /// Repeat the string a number of times.
     pub fn repeat(&self, n: i64) -> StrResult<Self> {
         let n = usize::try_from(n)
@@ -324,6 +344,52 @@ impl Str {
         Ok(Self(self.0.repeat(n)))
     }
 
+    /// Convert the string to the given Unicode normalization form.
+    pub fn normalize(&self, form: NormalForm) -> Self {
+        match form {
+            NormalForm::Nfc => self.nfc().collect(),
+            NormalForm::Nfd => self.nfd().collect(),
+            NormalForm::Nfkc => self.nfkc().collect(),
+            NormalForm::Nfkd => self.nfkd().collect(),
+        }
+    }
+
+    /// Produce a key for sorting strings according to the conventions of a
+    /// language.
+    ///
+    /// Letters are primarily compared without their case and diacritics. Only
+    /// if these are equal, diacritics and then the original strings decide.
+    /// Letters that some languages sort separately (like the Swedish `ä`)
+    /// are sorted after `z`.
+    pub fn sort_key(&self, lang: Option<Lang>) -> Self {
+        let tailoring = lang.map_or(&[][..], tailoring);
+        let mut primary = String::new();
+        let mut secondary = String::new();
+        for c in self.nfc().flat_map(char::to_lowercase) {
+            if let Some(&(_, weight)) = tailoring.iter().find(|&&(k, _)| k == c) {
+                primary.push_str(weight);
+                continue;
+            }
+
+            match c {
+                'ß' => primary.push_str("ss"),
+                'æ' => primary.push_str("ae"),
+                'œ' => primary.push_str("oe"),
+                'ø' => primary.push('o'),
+                _ => {
+                    for d in std::iter::once(c).nfd() {
+                        if is_combining_mark(d) {
+                            secondary.push(d);
+                        } else {
+                            primary.push(d);
+                        }
+                    }
+                }
+            }
+        }
+        format!("{primary}\0{secondary}\0{}", self.as_str()).into()
+    }
+
     /// Resolve an index, if it is within bounds.
     /// Errors on invalid char boundaries.
     fn locate_opt(&self, index: i64) -> StrResult<Option<usize>> {
@@ -585,6 +651,55 @@ cast! {
     regex: Regex => Self::Regex(regex),
 }
 
+/// Letters that a language sorts differently from the default.
+///
+/// The weights use characters from the end of the Unicode range, so that
+/// the letters sort after `z` or right after the letter they are based on.
+fn tailoring(lang: Lang) -> &'static [(char, &'static str)] {
+    match lang.as_str() {
+        "sv" | "fi" => &[
+            ('å', "z\u{10FFF0}"),
+            ('ä', "z\u{10FFF1}"),
+            ('æ', "z\u{10FFF1}"),
+            ('ö', "z\u{10FFF2}"),
+            ('ø', "z\u{10FFF2}"),
+        ],
+        "da" | "nb" | "nn" | "no" => &[
+            ('æ', "z\u{10FFF0}"),
+            ('ä', "z\u{10FFF0}"),
+            ('ø', "z\u{10FFF1}"),
+            ('ö', "z\u{10FFF1}"),
+            ('å', "z\u{10FFF2}"),
+        ],
+        "es" => &[('ñ', "n\u{10FFF0}")],
+        "tr" => &[
+            ('ç', "c\u{10FFF0}"),
+            ('ğ', "g\u{10FFF0}"),
+            ('ı', "h\u{10FFF0}"),
+            ('ö', "o\u{10FFF0}"),
+            ('ş', "s\u{10FFF0}"),
+            ('ü', "u\u{10FFF0}"),
+        ],
+        _ => &[],
+    }
+}
+
+/// A Unicode normalization form.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum NormalForm {
+    /// Canonical composition, where characters are composed where possible.
+    #[default]
+    Nfc,
+    /// Canonical decomposition, where characters are decomposed into a base
+    /// and combining marks.
+    Nfd,
+    /// Compatibility composition, which additionally replaces compatibility
+    /// characters like ligatures with their equivalents.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
 /// A side of a string.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum StrSide {
@@ -1,11 +1,12 @@
 use std::collections::HashSet;
 
-use ecow::{eco_vec, EcoVec};
+use ecow::{eco_vec, EcoString, EcoVec};
 
 use super::Value;
 use crate::diag::SourceDiagnostic;
 use crate::syntax::{FileId, Span};
 use crate::util::hash128;
+use crate::World;
 
 /// Traces warnings and which values existed for an expression at a span.
 #[derive(Default, Clone)]
@@ -14,6 +15,7 @@ pub struct Tracer {
     values: EcoVec<Value>,
     warnings: EcoVec<SourceDiagnostic>,
     warnings_set: HashSet<u128>,
+    suppressions: EcoVec<(Span, EcoVec<EcoString>)>,
 }
 
 impl Tracer {
@@ -27,6 +29,7 @@ impl Tracer {
             values: eco_vec![],
             warnings: eco_vec![],
             warnings_set: HashSet::new(),
+            suppressions: eco_vec![],
         }
     }
 
@@ -39,6 +42,39 @@ impl Tracer {
     pub fn warnings(self) -> EcoVec<SourceDiagnostic> {
         self.warnings
     }
+
+    /// Remove the warnings that lie within a scope in which they were
+    /// suppressed.
+    pub fn apply_suppressions(&mut self, world: &dyn World) {
+        if self.suppressions.is_empty() {
+            return;
+        }
+
+        let suppressions = std::mem::take(&mut self.suppressions);
+        self.warnings.retain(|warning| {
+            !suppressions.iter().any(|(span, kinds)| {
+                (kinds.is_empty()
+                    || warning.kind.as_ref().is_some_and(|kind| kinds.contains(kind)))
+                    && contains(world, *span, warning.span)
+            })
+        });
+    }
+}
+
+/// Whether the node at the `inner` span lies within the node at the `outer`
+/// span.
+fn contains(world: &dyn World, outer: Span, inner: Span) -> bool {
+    if outer.id() != inner.id() {
+        return false;
+    }
+
+    let Ok(source) = world.source(outer.id()) else { return false };
+    let (Some(outer), Some(inner)) = (source.find(outer), source.find(inner)) else {
+        return false;
+    };
+
+    let (outer, inner) = (outer.range(), inner.range());
+    outer.start <= inner.start && inner.end <= outer.end
 }
 
 #[comemo::track]
@@ -59,6 +95,12 @@ impl Tracer {
         }
     }
 
+    /// Suppress warnings of the given kinds, or all of them if there are no
+    /// kinds, within the node at the span.
+    pub fn suppress(&mut self, span: Span, kinds: EcoVec<EcoString>) {
+        self.suppressions.push((span, kinds));
+    }
+
     /// Add a warning.
     pub fn warn(&mut self, warning: SourceDiagnostic) {
         // Check if warning is a duplicate.
@@ -4,12 +4,13 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use ecow::eco_format;
+use ecow::{eco_format, EcoString};
+use serde::{Serialize, Serializer};
 use siphasher::sip128::{Hasher128, SipHasher13};
 
 use super::{
-    cast, fields, format_str, ops, Args, Array, CastInfo, Content, Dict, FromValue, Func,
-    IntoValue, Module, Reflect, Str, Symbol,
+    cast, fields, format_str, ops, Args, Array, CastInfo, Complex, Content, Decimal,
+    Dict, FromValue, Func, IntoValue, Module, Reflect, Str, Symbol,
 };
 use crate::diag::StrResult;
 use crate::geom::{Abs, Angle, Color, Em, Fr, Length, Ratio, Rel};
@@ -140,6 +141,27 @@ impl Value {
         }
     }
 
+    /// The names of the fields that can be accessed on the value.
+    pub fn field_names(&self) -> Vec<EcoString> {
+        match self {
+            Self::Symbol(symbol) => symbol.modifiers().map(Into::into).collect(),
+            Self::Dict(dict) => dict.iter().map(|(key, _)| key.as_str().into()).collect(),
+            Self::Content(content) => {
+                content.fields().map(|(name, _)| name.clone()).collect()
+            }
+            Self::Module(module) => {
+                module.scope().iter().map(|(name, _)| name.clone()).collect()
+            }
+            Self::Func(func) => func.info().map_or(vec![], |info| {
+                info.scope.iter().map(|(name, _)| name.clone()).collect()
+            }),
+            _ => fields::fields_on(self.type_name())
+                .iter()
+                .map(|&name| name.into())
+                .collect(),
+        }
+    }
+
     /// Return the debug representation of the value.
     pub fn repr(&self) -> Str {
         format_str!("{:?}", self)
@@ -165,6 +187,12 @@ impl Value {
             Self::Content(v) => v,
             Self::Func(_) => Content::empty(),
             Self::Module(module) => module.content(),
+            Self::Dyn(v) if v.is::<Decimal>() => {
+                item!(text)(eco_format!("{}", v.downcast::<Decimal>().unwrap()))
+            }
+            Self::Dyn(v) if v.is::<Complex>() => {
+                item!(text)(eco_format!("{}", v.downcast::<Complex>().unwrap()))
+            }
             _ => item!(raw)(self.repr().into(), Some("typc".into()), false),
         }
     }
@@ -251,6 +279,25 @@ impl Hash for Value {
     }
 }
 
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::None => serializer.serialize_none(),
+            Self::Bool(v) => serializer.serialize_bool(*v),
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::Float(v) => serializer.serialize_f64(*v),
+            Self::Str(v) => serializer.serialize_str(v),
+            Self::Bytes(v) => serializer.serialize_bytes(v),
+            Self::Content(v) => v.serialize(serializer),
+            Self::Array(v) => serializer.collect_seq(v.iter()),
+            Self::Dict(v) => {
+                serializer.collect_map(v.iter().map(|(k, v)| (k.as_str(), v)))
+            }
+            _ => serializer.serialize_str(&self.repr()),
+        }
+    }
+}
+
 /// A dynamic value.
 #[derive(Clone, Hash)]
 #[allow(clippy::derived_hash_with_manual_eq)]
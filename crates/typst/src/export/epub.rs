@@ -0,0 +1,385 @@
+//! Exporting into EPUB files.
+//!
+//! Since there is no reflowable HTML representation of documents yet, the
+//! book is a fixed-layout EPUB 3: Each page becomes one XHTML document that
+//! contains the page as an inline SVG. The navigation document is built from
+//! the document's headings.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::num::NonZeroUsize;
+
+use super::svg;
+use crate::doc::{Document, Frame, FrameItem, Lang};
+use crate::geom::Smart;
+use crate::model::{Content, Introspector};
+use crate::util::hash128;
+
+/// Export a document into an EPUB file.
+///
+/// Returns the raw bytes making up the EPUB file.
+#[tracing::instrument(skip_all)]
+pub fn epub(document: &Document) -> Vec<u8> {
+    let introspector = Introspector::new(&document.pages);
+    let lang = language(document);
+
+    let mut zip = ZipWriter::new();
+    zip.add("mimetype", b"application/epub+zip", false);
+    zip.add("META-INF/container.xml", CONTAINER.as_bytes(), true);
+
+    for (i, frame) in document.pages.iter().enumerate() {
+        let page = write_page(frame, i + 1, lang);
+        zip.add(&format!("OEBPS/{}", page_name(i + 1)), page.as_bytes(), true);
+    }
+
+    let nav = write_nav(&introspector, lang);
+    zip.add("OEBPS/nav.xhtml", nav.as_bytes(), true);
+
+    let package = write_package(document, lang);
+    zip.add("OEBPS/package.opf", package.as_bytes(), true);
+
+    zip.finish()
+}
+
+/// The container file, which points to the package document.
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+<rootfiles><rootfile full-path="OEBPS/package.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+
+/// The file name of the page with the given number.
+fn page_name(number: usize) -> String {
+    format!("page-{number}.xhtml")
+}
+
+/// Determine the most common language of the document's text.
+fn language(document: &Document) -> Lang {
+    let mut languages = HashMap::new();
+    for frame in &document.pages {
+        count_languages(frame, &mut languages);
+    }
+
+    languages
+        .into_iter()
+        .max_by_key(|&(lang, count)| (count, lang))
+        .map_or(Lang::ENGLISH, |(lang, _)| lang)
+}
+
+/// Count the glyphs per language in a frame.
+fn count_languages(frame: &Frame, languages: &mut HashMap<Lang, usize>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => count_languages(&group.frame, languages),
+            FrameItem::Text(text) => {
+                *languages.entry(text.lang).or_default() += text.glyphs.len();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Write the XHTML document for a single page.
+fn write_page(frame: &Frame, number: usize, lang: Lang) -> String {
+    let size = frame.size();
+    let (w, h) = (size.x.to_pt().ceil(), size.y.to_pt().ceil());
+    let mut xhtml = xhtml_head(&format!("Page {number}"), lang);
+    write!(
+        xhtml,
+        r#"<meta name="viewport" content="width={w}, height={h}"/></head><body style="margin:0">"#,
+    )
+    .unwrap();
+    xhtml.push_str(&svg(frame).0);
+    xhtml.push_str("</body></html>");
+    xhtml
+}
+
+/// Write the navigation document with a table of contents.
+fn write_nav(introspector: &Introspector, lang: Lang) -> String {
+    let mut xhtml = xhtml_head("Contents", lang);
+    xhtml.push_str(r#"</head><body><nav epub:type="toc"><ol>"#);
+
+    // The levels of the headings whose list items are still open.
+    let mut open: Vec<NonZeroUsize> = vec![];
+
+    for heading in introspector.query(&item!(heading_func).select()) {
+        if !outlined(&heading) {
+            continue;
+        }
+
+        let level = heading.expect_field::<NonZeroUsize>("level");
+        let title = heading.expect_field::<Content>("body").plain_text();
+        let page = introspector.page(heading.location().unwrap());
+
+        if open.last().is_some_and(|&last| level > last) {
+            xhtml.push_str("<ol>");
+        } else {
+            while open.last().is_some_and(|&last| level <= last) {
+                open.pop();
+                xhtml.push_str("</li>");
+                if open.last().is_some_and(|&last| level <= last) {
+                    xhtml.push_str("</ol>");
+                }
+            }
+        }
+
+        write!(
+            xhtml,
+            r#"<li><a href="{}">{}</a>"#,
+            page_name(page.get()),
+            Escape(title.trim())
+        )
+        .unwrap();
+        open.push(level);
+    }
+
+    if open.is_empty() {
+        // The table of contents must not be empty, so we link the first page.
+        write!(xhtml, r#"<li><a href="{}">Start</a>"#, page_name(1)).unwrap();
+        open.push(NonZeroUsize::new(1).unwrap());
+    }
+
+    while open.pop().is_some() {
+        xhtml.push_str("</li>");
+        if !open.is_empty() {
+            xhtml.push_str("</ol>");
+        }
+    }
+
+    xhtml.push_str("</ol></nav></body></html>");
+    xhtml
+}
+
+/// Whether a heading should appear in the table of contents.
+fn outlined(heading: &Content) -> bool {
+    heading
+        .expect_field::<Smart<bool>>("bookmarked")
+        .unwrap_or_else(|| heading.expect_field::<bool>("outlined"))
+}
+
+/// Write the package document with metadata, manifest, and spine.
+fn write_package(document: &Document, lang: Lang) -> String {
+    let mut opf = String::new();
+    opf.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    opf.push_str(
+        r#"<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">"#,
+    );
+
+    opf.push_str(r#"<metadata xmlns:dc="http://purl.org/dc/elements/1.1/">"#);
+    write!(
+        opf,
+        r#"<dc:identifier id="id">urn:typst:{:032x}</dc:identifier>"#,
+        hash128(document)
+    )
+    .unwrap();
+    let title = document.title.as_deref().unwrap_or("Untitled");
+    write!(opf, "<dc:title>{}</dc:title>", Escape(title)).unwrap();
+    for author in &document.author {
+        write!(opf, "<dc:creator>{}</dc:creator>", Escape(author)).unwrap();
+    }
+    write!(opf, "<dc:language>{}</dc:language>", lang.as_str()).unwrap();
+    opf.push_str(r#"<meta property="dcterms:modified">1970-01-01T00:00:00Z</meta>"#);
+    opf.push_str(r#"<meta property="rendition:layout">pre-paginated</meta>"#);
+    opf.push_str("</metadata>");
+
+    opf.push_str("<manifest>");
+    opf.push_str(
+        r#"<item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>"#,
+    );
+    for number in 1..=document.pages.len() {
+        write!(
+            opf,
+            r#"<item id="page-{number}" href="{}" media-type="application/xhtml+xml" properties="svg"/>"#,
+            page_name(number)
+        )
+        .unwrap();
+    }
+    opf.push_str("</manifest>");
+
+    opf.push_str("<spine>");
+    for number in 1..=document.pages.len() {
+        write!(opf, r#"<itemref idref="page-{number}"/>"#).unwrap();
+    }
+    opf.push_str("</spine></package>");
+    opf
+}
+
+/// Write the start of an XHTML document up to the open `head` element.
+fn xhtml_head(title: &str, lang: Lang) -> String {
+    let mut xhtml = String::new();
+    xhtml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?><!DOCTYPE html>"#);
+    write!(
+        xhtml,
+        r#"<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="{0}" xml:lang="{0}">"#,
+        lang.as_str()
+    )
+    .unwrap();
+    write!(xhtml, "<head><title>{}</title>", Escape(title)).unwrap();
+    xhtml
+}
+
+/// Displays text with XML special characters escaped.
+struct Escape<'a>(&'a str);
+
+impl std::fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a ZIP archive, as required by the EPUB container format.
+struct ZipWriter {
+    /// The archive's bytes written so far.
+    buf: Vec<u8>,
+    /// The central directory entries of all files.
+    central: Vec<u8>,
+    /// The number of files in the archive.
+    count: u16,
+}
+
+impl ZipWriter {
+    /// Create a new, empty archive.
+    fn new() -> Self {
+        Self { buf: vec![], central: vec![], count: 0 }
+    }
+
+    /// Add a file to the archive, optionally compressing it.
+    fn add(&mut self, name: &str, data: &[u8], compress: bool) {
+        let crc = crc32fast::hash(data);
+        let (method, stored) = if compress {
+            (8u16, miniz_oxide::deflate::compress_to_vec(data, 6))
+        } else {
+            (0u16, data.to_vec())
+        };
+
+        let offset = self.buf.len() as u32;
+        let header = |buf: &mut Vec<u8>| {
+            buf.extend(20u16.to_le_bytes()); // Version needed to extract.
+            buf.extend(0u16.to_le_bytes()); // Flags.
+            buf.extend(method.to_le_bytes());
+            buf.extend(0u16.to_le_bytes()); // Modification time.
+            buf.extend(0x21u16.to_le_bytes()); // Modification date (1980-01-01).
+            buf.extend(crc.to_le_bytes());
+            buf.extend((stored.len() as u32).to_le_bytes());
+            buf.extend((data.len() as u32).to_le_bytes());
+            buf.extend((name.len() as u16).to_le_bytes());
+            buf.extend(0u16.to_le_bytes()); // Extra field length.
+        };
+
+        self.buf.extend(0x04034b50u32.to_le_bytes());
+        header(&mut self.buf);
+        self.buf.extend(name.as_bytes());
+        self.buf.extend(&stored);
+
+        self.central.extend(0x02014b50u32.to_le_bytes());
+        self.central.extend(20u16.to_le_bytes()); // Version made by.
+        header(&mut self.central);
+        self.central.extend(0u16.to_le_bytes()); // Comment length.
+        self.central.extend(0u16.to_le_bytes()); // Disk number.
+        self.central.extend(0u16.to_le_bytes()); // Internal attributes.
+        self.central.extend(0u32.to_le_bytes()); // External attributes.
+        self.central.extend(offset.to_le_bytes());
+        self.central.extend(name.as_bytes());
+        self.count += 1;
+    }
+
+    /// Finish the archive by writing the central directory.
+    fn finish(mut self) -> Vec<u8> {
+        let offset = self.buf.len() as u32;
+        let size = self.central.len() as u32;
+        self.buf.extend(self.central);
+        self.buf.extend(0x06054b50u32.to_le_bytes());
+        self.buf.extend(0u16.to_le_bytes()); // Number of this disk.
+        self.buf.extend(0u16.to_le_bytes()); // Disk with the central directory.
+        self.buf.extend(self.count.to_le_bytes());
+        self.buf.extend(self.count.to_le_bytes());
+        self.buf.extend(size.to_le_bytes());
+        self.buf.extend(offset.to_le_bytes());
+        self.buf.extend(0u16.to_le_bytes()); // Comment length.
+        self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read the files of an archive back through its central directory.
+    fn unzip(data: &[u8]) -> Vec<(String, Vec<u8>, bool)> {
+        let u16_at = |i: usize| u16::from_le_bytes([data[i], data[i + 1]]) as usize;
+        let u32_at = |i: usize| {
+            u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]) as usize
+        };
+
+        let end = data.len() - 22;
+        assert_eq!(u32_at(end), 0x06054b50);
+        let count = u16_at(end + 10);
+        let mut central = u32_at(end + 16);
+        assert_eq!(central + u32_at(end + 12), end);
+
+        let mut files = vec![];
+        for _ in 0..count {
+            assert_eq!(u32_at(central), 0x02014b50);
+            let method = u16_at(central + 10);
+            let crc = u32_at(central + 16);
+            let stored = u32_at(central + 20);
+            let name_len = u16_at(central + 28);
+            let offset = u32_at(central + 42);
+            let name = &data[central + 46..central + 46 + name_len];
+
+            // The local header must agree with the central directory.
+            assert_eq!(u32_at(offset), 0x04034b50);
+            assert_eq!(data[offset + 4..offset + 30], data[central + 6..central + 32]);
+            assert_eq!(&data[offset + 30..offset + 30 + name_len], name);
+
+            let start = offset + 30 + name_len;
+            let raw = &data[start..start + stored];
+            let content = match method {
+                0 => raw.to_vec(),
+                8 => miniz_oxide::inflate::decompress_to_vec(raw).unwrap(),
+                _ => panic!("unknown compression method {method}"),
+            };
+            assert_eq!(crc32fast::hash(&content) as usize, crc);
+            assert_eq!(u32_at(central + 24), content.len());
+
+            let name = std::str::from_utf8(name).unwrap().to_string();
+            files.push((name, content, method == 8));
+            central += 46 + name_len;
+        }
+
+        files
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let text = "Hello, world! ".repeat(100);
+        let mut zip = ZipWriter::new();
+        zip.add("plain.txt", b"stored as is", false);
+        zip.add("dir/text.txt", text.as_bytes(), true);
+        zip.add("empty", b"", true);
+
+        let files = unzip(&zip.finish());
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0], ("plain.txt".into(), b"stored as is".to_vec(), false));
+        assert_eq!(files[1], ("dir/text.txt".into(), text.into_bytes(), true));
+        assert_eq!(files[2], ("empty".into(), vec![], true));
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let text = r#"Tom & "Jerry" <3 > 'x'"#;
+        let xml = format!(r#"<a b="{0}">{0}</a>"#, Escape(text));
+        let document = roxmltree::Document::parse(&xml).unwrap();
+        let root = document.root_element();
+        assert_eq!(root.attribute("b"), Some(text));
+        assert_eq!(root.text(), Some(text));
+    }
+}
@@ -1,7 +1,14 @@
 //! Exporting into external formats.
 
+mod epub;
 mod pdf;
 mod render;
+mod svg;
 
-pub use self::pdf::pdf;
+pub use self::epub::epub;
+pub use self::pdf::{
+    pdf, pdf_with_options, PdfEncryption, PdfFontOptions, PdfOptions, PdfOutputIntent,
+    PdfSizeOptions,
+};
 pub use self::render::render;
+pub use self::svg::svg;
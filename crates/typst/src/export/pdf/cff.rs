@@ -0,0 +1,426 @@
+use ttf_parser::{GlyphId, OutlineBuilder};
+
+use crate::font::Font;
+
+/// The SID of the first custom string in a CFF font.
+const FIRST_CUSTOM_SID: i32 = 391;
+
+/// Convert the outlines of a font into a CID-keyed CFF font program.
+///
+/// The CIDs of the resulting font are the glyph ids of the original font.
+/// Glyphs that are not in `glyphs` (if given) get empty outlines, so the
+/// program only contains the used outlines. Hinting is not carried over.
+pub fn convert(font: &Font, name: &str, glyphs: Option<&[u16]>) -> Vec<u8> {
+    let ttf = font.ttf();
+    let count = ttf.number_of_glyphs();
+    let scale = 1000.0 / font.units_per_em();
+
+    let charstrings: Vec<Vec<u8>> = (0..count)
+        .map(|g| {
+            let advance = ttf.glyph_hor_advance(GlyphId(g)).unwrap_or(0);
+            let mut builder = CharStringBuilder::new(advance as f64 * scale, scale);
+            let included = glyphs.map_or(true, |glyphs| glyphs.binary_search(&g).is_ok());
+            if g == 0 || included {
+                ttf.outline_glyph(GlyphId(g), &mut builder);
+            }
+            builder.finish()
+        })
+        .collect();
+
+    build(name, &charstrings)
+}
+
+/// Assemble a CID-keyed CFF font program from charstrings, one per glyph.
+fn build(name: &str, charstrings: &[Vec<u8>]) -> Vec<u8> {
+    let count = charstrings.len() as u16;
+    let header = [1, 0, 4, 4];
+    let name_index = index(&[name.as_bytes()]);
+    let string_index = index(&[b"Adobe".as_slice(), b"Identity"]);
+    let global_subrs = index::<&[u8]>(&[]);
+
+    // All glyphs but the first one map to consecutive CIDs.
+    let mut charset = vec![];
+    if count > 1 {
+        charset.push(2);
+        charset.extend(1u16.to_be_bytes());
+        charset.extend((count - 2).to_be_bytes());
+    } else {
+        charset.push(0);
+    }
+
+    // All glyphs use the first and only font dictionary.
+    let mut fd_select = vec![3];
+    fd_select.extend(1u16.to_be_bytes());
+    fd_select.extend(0u16.to_be_bytes());
+    fd_select.push(0);
+    fd_select.extend(count.to_be_bytes());
+
+    let charstrings_index = index(charstrings);
+
+    let mut private = vec![];
+    dict_int(&mut private, 0);
+    private.push(20);
+    dict_int(&mut private, 0);
+    private.push(21);
+
+    // Compute the offsets of the sections. The top dictionary has the same
+    // size regardless of the offsets since they are encoded with a fixed
+    // width.
+    let top_len = index(&[top_dict(count, [0; 4])]).len();
+    let charset_offset = header.len()
+        + name_index.len()
+        + top_len
+        + string_index.len()
+        + global_subrs.len();
+    let fd_select_offset = charset_offset + charset.len();
+    let charstrings_offset = fd_select_offset + fd_select.len();
+    let fd_array_offset = charstrings_offset + charstrings_index.len();
+    let fd_len = index(&[font_dict(private.len(), 0)]).len();
+    let private_offset = fd_array_offset + fd_len;
+    let fd_array = index(&[font_dict(private.len(), private_offset)]);
+
+    let top = top_dict(
+        count,
+        [charset_offset, fd_select_offset, charstrings_offset, fd_array_offset],
+    );
+
+    let mut out = vec![];
+    out.extend(header);
+    out.extend(name_index);
+    out.extend(index(&[top]));
+    out.extend(string_index);
+    out.extend(global_subrs);
+    out.extend(charset);
+    out.extend(fd_select);
+    out.extend(charstrings_index);
+    out.extend(fd_array);
+    out.extend(private);
+    out
+}
+
+/// Write the top dictionary of a CID-keyed font.
+///
+/// The offsets are those of the charset, the FDSelect, the charstrings, and
+/// the FDArray.
+fn top_dict(count: u16, offsets: [usize; 4]) -> Vec<u8> {
+    let [charset, fd_select, charstrings, fd_array] = offsets;
+    let mut dict = vec![];
+
+    // Registry "Adobe", ordering "Identity", supplement 0.
+    dict_int(&mut dict, FIRST_CUSTOM_SID);
+    dict_int(&mut dict, FIRST_CUSTOM_SID + 1);
+    dict_int(&mut dict, 0);
+    dict.extend([12, 30]);
+
+    dict_int(&mut dict, count.into());
+    dict.extend([12, 34]);
+    dict_int(&mut dict, charset as i32);
+    dict.push(15);
+    dict_int(&mut dict, fd_select as i32);
+    dict.extend([12, 37]);
+    dict_int(&mut dict, charstrings as i32);
+    dict.push(17);
+    dict_int(&mut dict, fd_array as i32);
+    dict.extend([12, 36]);
+    dict
+}
+
+/// Write a font dictionary that references the private dictionary.
+fn font_dict(private_len: usize, private_offset: usize) -> Vec<u8> {
+    let mut dict = vec![];
+    dict_int(&mut dict, private_len as i32);
+    dict_int(&mut dict, private_offset as i32);
+    dict.push(18);
+    dict
+}
+
+/// Encode an integer operand of a dictionary with a fixed width.
+fn dict_int(out: &mut Vec<u8>, value: i32) {
+    out.push(29);
+    out.extend(value.to_be_bytes());
+}
+
+/// Write a CFF INDEX structure.
+fn index<T: AsRef<[u8]>>(items: &[T]) -> Vec<u8> {
+    let mut out = vec![];
+    out.extend((items.len() as u16).to_be_bytes());
+    if items.is_empty() {
+        return out;
+    }
+
+    let total: usize = items.iter().map(|item| item.as_ref().len()).sum();
+    let off_size: u8 = match total + 1 {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x10000..=0xFFFFFF => 3,
+        _ => 4,
+    };
+
+    out.push(off_size);
+    let mut offset = 1;
+    for len in std::iter::once(0).chain(items.iter().map(|item| item.as_ref().len())) {
+        offset += len;
+        let bytes = (offset as u32).to_be_bytes();
+        out.extend(&bytes[4 - off_size as usize..]);
+    }
+
+    for item in items {
+        out.extend(item.as_ref());
+    }
+
+    out
+}
+
+/// Builds a Type 2 charstring from glyph outlines.
+///
+/// Charstring operands can't exceed the range of an `i16`, so segments with
+/// longer deltas are split into several ones.
+struct CharStringBuilder {
+    data: Vec<u8>,
+    width: Option<i16>,
+    scale: f64,
+    current: (i32, i32),
+    start: (f64, f64),
+}
+
+impl CharStringBuilder {
+    fn new(width: f64, scale: f64) -> Self {
+        // A width that can't be encoded is left out. PDF viewers take the
+        // advances from the font dictionary anyway.
+        let width = i16::try_from(width.round() as i32).ok();
+        Self {
+            data: vec![],
+            width: width.filter(|&width| width != 0),
+            scale,
+            current: (0, 0),
+            start: (0.0, 0.0),
+        }
+    }
+
+    /// Write the glyph width before the first operator.
+    fn write_width(&mut self) {
+        if let Some(width) = self.width.take() {
+            write_number(&mut self.data, width);
+        }
+    }
+
+    /// Scale a point from font units and round it.
+    fn scaled(&self, x: f64, y: f64) -> (i32, i32) {
+        ((x * self.scale).round() as i32, (y * self.scale).round() as i32)
+    }
+
+    /// Whether the deltas between the current point and the given points
+    /// fit into charstring operands.
+    fn fits(&self, points: &[(i32, i32)]) -> bool {
+        let mut current = self.current;
+        points.iter().all(|&point| {
+            let delta = (point.0 - current.0, point.1 - current.1);
+            current = point;
+            i16::try_from(delta.0).is_ok() && i16::try_from(delta.1).is_ok()
+        })
+    }
+
+    /// Write a scaled point as a delta to the current point. The delta
+    /// must fit.
+    fn write_point(&mut self, point: (i32, i32)) {
+        write_number(&mut self.data, (point.0 - self.current.0) as i16);
+        write_number(&mut self.data, (point.1 - self.current.1) as i16);
+        self.current = point;
+    }
+
+    /// Write a move or line to a point (in font units) with the given
+    /// operator, split into as many segments as needed.
+    fn write_straight(&mut self, x: f32, y: f32, op: u8) {
+        let (x0, y0) = self.current;
+        let (x1, y1) = self.scaled(x as f64, y as f64);
+        let max = (x1 - x0).abs().max((y1 - y0).abs()) as i64;
+        let n = ((max + i16::MAX as i64 - 1) / i16::MAX as i64).max(1);
+        for i in 1..=n {
+            let lerp = |a: i32, b: i32| a + ((b - a) as i64 * i / n) as i32;
+            self.write_point((lerp(x0, x1), lerp(y0, y1)));
+            self.data.push(op);
+        }
+    }
+
+    /// Write a cubic curve from the current point (in font units),
+    /// subdividing it until its deltas fit.
+    fn write_curve(
+        &mut self,
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        p3: (f64, f64),
+    ) {
+        let points = [p1, p2, p3].map(|(x, y)| self.scaled(x, y));
+        if self.fits(&points) {
+            for point in points {
+                self.write_point(point);
+            }
+            self.data.push(8);
+            return;
+        }
+
+        let mid = |a: (f64, f64), b: (f64, f64)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        let (q0, q1, q2) = (mid(p0, p1), mid(p1, p2), mid(p2, p3));
+        let (r0, r1) = (mid(q0, q1), mid(q1, q2));
+        let s = mid(r0, r1);
+        self.write_curve(p0, q0, r0, s);
+        self.write_curve(s, r1, q2, p3);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.write_width();
+        self.data.push(14);
+        self.data
+    }
+}
+
+impl OutlineBuilder for CharStringBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.write_width();
+        self.write_straight(x, y, 21);
+        self.start = (x as f64, y as f64);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.write_straight(x, y, 5);
+        self.start = (x as f64, y as f64);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        // Elevate the quadratic curve to a cubic one.
+        let (x0, y0) = self.start;
+        let (qx, qy) = (x1 as f64, y1 as f64);
+        let (px, py) = (x as f64, y as f64);
+        let c1 = (x0 + 2.0 / 3.0 * (qx - x0), y0 + 2.0 / 3.0 * (qy - y0));
+        let c2 = (px + 2.0 / 3.0 * (qx - px), py + 2.0 / 3.0 * (qy - py));
+        self.curve_to(c1.0 as f32, c1.1 as f32, c2.0 as f32, c2.1 as f32, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p1 = (x1 as f64, y1 as f64);
+        let p2 = (x2 as f64, y2 as f64);
+        let p3 = (x as f64, y as f64);
+        self.write_curve(self.start, p1, p2, p3);
+        self.start = p3;
+    }
+
+    fn close(&mut self) {
+        // Paths are closed implicitly by the next move or the end of the
+        // charstring.
+    }
+}
+
+/// Encode an integer operand of a Type 2 charstring.
+fn write_number(out: &mut Vec<u8>, value: i16) {
+    match value {
+        -107..=107 => out.push((value + 139) as u8),
+        108..=1131 => {
+            let v = value - 108;
+            out.extend([((v >> 8) + 247) as u8, v as u8]);
+        }
+        -1131..=-108 => {
+            let v = -value - 108;
+            out.extend([((v >> 8) + 251) as u8, v as u8]);
+        }
+        _ => {
+            out.push(28);
+            out.extend(value.to_be_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the end points of the segments of an outline.
+    #[derive(Default)]
+    struct Recorder(Vec<(char, i32, i32)>);
+
+    impl Recorder {
+        fn push(&mut self, kind: char, x: f32, y: f32) {
+            self.0.push((kind, x.round() as i32, y.round() as i32));
+        }
+    }
+
+    impl OutlineBuilder for Recorder {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.push('M', x, y);
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.push('L', x, y);
+        }
+
+        fn quad_to(&mut self, _: f32, _: f32, x: f32, y: f32) {
+            self.push('C', x, y);
+        }
+
+        fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, x: f32, y: f32) {
+            self.push('C', x, y);
+        }
+
+        fn close(&mut self) {}
+    }
+
+    fn parse_outline(data: &[u8], glyph: u16) -> Vec<(char, i32, i32)> {
+        let table = ttf_parser::cff::Table::parse(data).unwrap();
+        let mut recorder = Recorder::default();
+        table.outline(GlyphId(glyph), &mut recorder).ok();
+        recorder.0
+    }
+
+    #[test]
+    fn test_convert_round_trip() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../assets/fonts/IBMPlexSans-Regular.ttf"
+        );
+        let font = Font::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+        let ttf = font.ttf();
+        let scale = 1000.0 / font.units_per_em();
+        let glyphs =
+            [0, ttf.glyph_index('a').unwrap().0, ttf.glyph_index('&').unwrap().0];
+        let mut subset = glyphs.to_vec();
+        subset.sort();
+
+        let data = convert(&font, "IBMPlexSans", Some(&subset));
+        let table = ttf_parser::cff::Table::parse(&data).unwrap();
+        assert_eq!(table.number_of_glyphs(), ttf.number_of_glyphs());
+
+        for glyph in glyphs {
+            let mut recorder = Recorder::default();
+            ttf.outline_glyph(GlyphId(glyph), &mut recorder);
+            let expected: Vec<_> = recorder
+                .0
+                .into_iter()
+                .map(|(kind, x, y)| {
+                    let scale = |v: i32| (v as f64 * scale).round() as i32;
+                    (kind, scale(x), scale(y))
+                })
+                .collect();
+            assert!(!expected.is_empty());
+            assert_eq!(parse_outline(&data, glyph), expected);
+        }
+
+        // Glyphs outside of the subset have empty outlines.
+        let other = ttf.glyph_index('b').unwrap().0;
+        assert!(parse_outline(&data, other).is_empty());
+    }
+
+    #[test]
+    fn test_convert_long_deltas() {
+        let mut builder = CharStringBuilder::new(40000.0, 1.0);
+        builder.move_to(-50000.0, 0.0);
+        builder.line_to(100000.0, -70000.0);
+        builder.curve_to(0.0, 90000.0, -90000.0, 90000.0, -50000.0, 10.0);
+        let data =
+            build("Test", &[CharStringBuilder::new(0.0, 1.0).finish(), builder.finish()]);
+
+        let outline = parse_outline(&data, 1);
+        assert_eq!(outline.last(), Some(&('C', -50000, 10)));
+        assert!(outline.contains(&('L', 100000, -70000)));
+        assert!(outline.len() > 3);
+    }
+}
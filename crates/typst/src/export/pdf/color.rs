@@ -0,0 +1,139 @@
+use ecow::{eco_format, EcoString};
+use pdf_writer::{Filter, Finish, Name, Ref};
+
+use super::{deflate, PdfContext, RefExt};
+use crate::geom::{CmykColor, Color};
+use crate::util::Bytes;
+
+/// Settings for converting the colors of an exported PDF for print.
+///
+/// Text and shape colors are written as device CMYK. Colors that are already
+/// CMYK are kept as they are, others are converted with [`to_cmyk`], which
+/// doesn't use the profile. Raster and vector images keep their own color
+/// spaces. For exact print colors, specify them as CMYK in the document.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PdfOutputIntent {
+    /// An ICC profile describing the target CMYK output condition. It is
+    /// embedded as the document's output intent, which is only written if a
+    /// profile is given. Colors are not converted through it.
+    pub profile: Option<Bytes>,
+    /// The name of the output condition, e.g. `FOGRA39`.
+    pub condition: EcoString,
+    /// Whether colors with full black ink overprint the inks below them
+    /// instead of knocking them out. This avoids white gaps around black
+    /// text when the color separations are slightly misregistered.
+    pub overprint_black: bool,
+}
+
+impl Default for PdfOutputIntent {
+    fn default() -> Self {
+        Self {
+            profile: None,
+            condition: "Custom".into(),
+            overprint_black: false,
+        }
+    }
+}
+
+/// Convert a color into device CMYK.
+///
+/// The conversion is the naive one and doesn't take the output profile into
+/// account. Grays only use black ink, so that gray text stays sharp in print.
+pub fn to_cmyk(color: Color) -> CmykColor {
+    match color {
+        Color::Luma(luma) => CmykColor::new(0, 0, 0, u8::MAX - luma.l).with_alpha(luma.a),
+        Color::Rgba(rgba) => rgba.to_cmyk(),
+        Color::Cmyk(cmyk) => cmyk,
+    }
+}
+
+/// Whether a color overprints with the given settings.
+pub fn overprints(intent: Option<&PdfOutputIntent>, color: Color) -> bool {
+    intent.is_some_and(|intent| intent.overprint_black)
+        && matches!(color, Color::Cmyk(cmyk) if cmyk.k == u8::MAX)
+}
+
+/// The name of the graphics state that enables or disables overprinting for
+/// stroking and non-stroking operations.
+pub fn overprint_state(stroke: bool, fill: bool) -> Name<'static> {
+    match (stroke, fill) {
+        (false, false) => Name(b"Ovp00"),
+        (false, true) => Name(b"Ovp01"),
+        (true, false) => Name(b"Ovp10"),
+        (true, true) => Name(b"Ovp11"),
+    }
+}
+
+/// Write the graphics states used for overprinting into an `ExtGState`
+/// resource dictionary.
+pub fn write_overprint_states(states: &mut pdf_writer::Dict) {
+    for stroke in [false, true] {
+        for fill in [false, true] {
+            let mut state = states.insert(overprint_state(stroke, fill)).dict();
+            state.pair(Name(b"Type"), Name(b"ExtGState"));
+            state.pair(Name(b"OP"), stroke);
+            state.pair(Name(b"op"), fill);
+            state.pair(Name(b"OPM"), 1);
+            state.finish();
+        }
+    }
+}
+
+/// The name of the graphics state that sets the alpha value of stroking or
+/// non-stroking operations.
+pub fn alpha_state(stroke: bool, alpha: u8) -> EcoString {
+    eco_format!("{}{alpha}", if stroke { "Sa" } else { "Fa" })
+}
+
+/// Write the graphics states used for transparent colors into an `ExtGState`
+/// resource dictionary.
+pub fn write_alpha_states(states: &mut pdf_writer::Dict, alphas: &[(bool, u8)]) {
+    for &(stroke, alpha) in alphas {
+        let name = alpha_state(stroke, alpha);
+        let mut state = states.insert(Name(name.as_bytes())).dict();
+        state.pair(Name(b"Type"), Name(b"ExtGState"));
+        state.pair(Name(if stroke { b"CA" } else { b"ca" }), alpha as f32 / 255.0);
+        state.finish();
+    }
+}
+
+/// Make a page a transparency group, so that transparent content on it is
+/// blended consistently across viewers. Blending happens in device CMYK when
+/// the document targets print.
+pub fn write_transparency_group(page: &mut pdf_writer::Dict, cmyk: bool) {
+    let mut group = page.insert(Name(b"Group")).dict();
+    group.pair(Name(b"Type"), Name(b"Group"));
+    group.pair(Name(b"S"), Name(b"Transparency"));
+    let space = if cmyk { Name(b"DeviceCMYK") } else { Name(b"DeviceRGB") };
+    group.pair(Name(b"CS"), space);
+    group.finish();
+}
+
+/// Write the output intent of the document.
+///
+/// Returns the reference of the output intent dictionary, if there is a
+/// profile to write it for.
+#[tracing::instrument(skip_all)]
+pub fn write_output_intent(ctx: &mut PdfContext, intent: &PdfOutputIntent) -> Option<Ref> {
+    let profile = intent.profile.as_ref()?;
+    let profile_ref = ctx.alloc.bump();
+    let compressed = deflate(profile, ctx.options.size.compression_level);
+    let data = ctx.crypt.stream(profile_ref, &compressed);
+    let mut stream = ctx.writer.stream(profile_ref, &data);
+    stream.filter(Filter::FlateDecode);
+    stream.pair(Name(b"N"), 4);
+    stream.pair(Name(b"Alternate"), Name(b"DeviceCMYK"));
+    stream.finish();
+
+    let intent_ref = ctx.alloc.bump();
+    let mut dict = ctx.writer.indirect(intent_ref).dict();
+    dict.pair(Name(b"Type"), Name(b"OutputIntent"));
+    dict.pair(Name(b"S"), Name(b"GTS_PDFX"));
+    let condition = || ctx.crypt.text(intent_ref, &intent.condition);
+    dict.pair(Name(b"OutputConditionIdentifier"), condition());
+    dict.pair(Name(b"Info"), condition());
+    dict.pair(Name(b"DestOutputProfile"), profile_ref);
+    dict.finish();
+
+    Some(intent_ref)
+}
@@ -0,0 +1,123 @@
+use ecow::{eco_format, EcoString};
+use pdf_writer::{Filter, Finish, Name, Ref};
+
+use super::{deflate, PdfContext, RefExt};
+use crate::model::Content;
+use crate::util::Bytes;
+
+/// Write all files attached with `pdf.embed-file`.
+///
+/// Files are attached under the last component of their path. A file that is
+/// embedded multiple times is only attached once and different files with the
+/// same name are told apart by a number.
+///
+/// Returns the reference of the name tree that maps the file names to their
+/// specifications and the references of the specifications, if any files
+/// were attached.
+#[tracing::instrument(skip_all)]
+pub fn write_embedded_files(ctx: &mut PdfContext) -> Option<(Ref, Vec<Ref>)> {
+    let mut seen: Vec<(EcoString, Bytes)> = vec![];
+    let mut files: Vec<(EcoString, Ref)> = vec![];
+    for elem in std::mem::take(&mut ctx.embeds) {
+        let path = elem.expect_field::<EcoString>("path");
+        let data = elem.expect_field::<Bytes>("data");
+        let base: EcoString = path.rsplit(['/', '\\']).next().unwrap_or_default().into();
+        if seen.contains(&(base.clone(), data.clone())) {
+            continue;
+        }
+
+        let name = unique_name(&base, |name| files.iter().any(|(n, _)| n == name));
+        let description = optional(&elem, "description");
+        let mime_type = optional(&elem, "mime-type");
+
+        let file_ref = ctx.alloc.bump();
+        let compressed = deflate(&data, ctx.options.size.compression_level);
+        let encrypted = ctx.crypt.stream(file_ref, &compressed);
+        let mut stream = ctx.writer.stream(file_ref, &encrypted);
+        stream.filter(Filter::FlateDecode);
+        stream.pair(Name(b"Type"), Name(b"EmbeddedFile"));
+        if let Some(mime_type) = &mime_type {
+            stream.pair(Name(b"Subtype"), Name(mime_type.as_bytes()));
+        }
+        stream
+            .insert(Name(b"Params"))
+            .dict()
+            .pair(Name(b"Size"), data.len() as i32);
+        stream.finish();
+
+        let spec_ref = ctx.alloc.bump();
+        let mut spec = ctx.writer.indirect(spec_ref).dict();
+        spec.pair(Name(b"Type"), Name(b"Filespec"));
+        spec.pair(Name(b"F"), ctx.crypt.str(spec_ref, name.as_bytes()));
+        spec.pair(Name(b"UF"), ctx.crypt.text(spec_ref, &name));
+        if let Some(description) = &description {
+            spec.pair(Name(b"Desc"), ctx.crypt.text(spec_ref, description));
+        }
+        spec.pair(Name(b"AFRelationship"), Name(b"Data"));
+
+        let mut ef = spec.insert(Name(b"EF")).dict();
+        ef.pair(Name(b"F"), file_ref);
+        ef.pair(Name(b"UF"), file_ref);
+        ef.finish();
+        spec.finish();
+
+        files.push((name, spec_ref));
+        seen.push((base, data));
+    }
+
+    if files.is_empty() {
+        return None;
+    }
+
+    // The keys of a name tree must be sorted.
+    files.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+    let tree_ref = ctx.alloc.bump();
+    let mut tree = ctx.writer.indirect(tree_ref).dict();
+    let mut names = tree.insert(Name(b"Names")).array();
+    for (name, spec_ref) in &files {
+        names.item(ctx.crypt.str(tree_ref, name.as_bytes()));
+        names.item(*spec_ref);
+    }
+    names.finish();
+    tree.finish();
+
+    Some((tree_ref, files.into_iter().map(|(_, spec_ref)| spec_ref).collect()))
+}
+
+/// Make a file name unique by numbering it before its extension, e.g.
+/// `data (2).csv`.
+fn unique_name(name: &str, taken: impl Fn(&str) -> bool) -> EcoString {
+    if !taken(name) {
+        return name.into();
+    }
+
+    let (stem, ext) = match name.rfind('.').filter(|&i| i > 0) {
+        Some(i) => name.split_at(i),
+        None => (name, ""),
+    };
+
+    (2..)
+        .map(|i| eco_format!("{stem} ({i}){ext}"))
+        .find(|candidate| !taken(candidate))
+        .unwrap()
+}
+
+/// Extract an optional string field from an element.
+fn optional(elem: &Content, field: &str) -> Option<EcoString> {
+    elem.field(field).and_then(|value| value.cast().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name() {
+        let taken = ["data.csv", "data (2).csv", "notes"];
+        let is_taken = |name: &str| taken.contains(&name);
+        assert_eq!(unique_name("image.png", is_taken), "image.png");
+        assert_eq!(unique_name("data.csv", is_taken), "data (3).csv");
+        assert_eq!(unique_name("notes", is_taken), "notes (2)");
+    }
+}
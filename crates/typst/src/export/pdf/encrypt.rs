@@ -0,0 +1,556 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::consts::U16;
+use aes::cipher::{BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit, StreamCipher};
+use aes::Aes128;
+use ecow::EcoString;
+use md5::{Digest, Md5};
+use pdf_writer::{
+    Date, Finish, Name, Null, Obj, PdfWriter, Primitive, Ref, Str, TextStr,
+};
+use rc4::Rc4;
+
+use super::{PdfContext, RefExt};
+
+/// Settings for encrypting an exported PDF with the standard security handler.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PdfEncryption {
+    /// The password required to open the document. May be empty, in which
+    /// case any reader can open the document, but the permissions still
+    /// apply.
+    pub user_password: EcoString,
+    /// The password that grants full access. If empty, one is derived from
+    /// the document and the user password, so that exporting the same
+    /// document twice yields the same file.
+    pub owner_password: EcoString,
+    /// Whether the document may be printed.
+    pub allow_print: bool,
+    /// Whether text and graphics may be copied out of the document.
+    pub allow_copy: bool,
+}
+
+impl Default for PdfEncryption {
+    fn default() -> Self {
+        Self {
+            user_password: EcoString::new(),
+            owner_password: EcoString::new(),
+            allow_print: true,
+            allow_copy: true,
+        }
+    }
+}
+
+impl PdfEncryption {
+    /// The permission flags, as defined in section 7.6.3.2 of the PDF
+    /// specification.
+    fn permissions(&self) -> i32 {
+        // All bits except the two lowest ones are set by default.
+        let mut flags: u32 = !0b11;
+        if !self.allow_print {
+            flags &= !(1 << 2 | 1 << 11);
+        }
+        if !self.allow_copy {
+            flags &= !(1 << 4);
+        }
+        flags as i32
+    }
+}
+
+/// The padding string used to extend passwords to 32 bytes.
+const PADDING: [u8; 32] = [
+    0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA,
+    0x01, 0x08, 0x2E, 0x2E, 0x00, 0xB6, 0xD0, 0x68, 0x3E, 0x80, 0x2F, 0x0C, 0xA9, 0xFE,
+    0x64, 0x53, 0x69, 0x7A,
+];
+
+/// Encrypts the strings and streams of the document's objects while they are
+/// written.
+///
+/// Does nothing if the document isn't encrypted.
+#[derive(Default)]
+pub struct Crypt(Option<Encryptor>);
+
+impl Crypt {
+    /// Whether the document is encrypted.
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Encrypt the data of the stream object with the given reference.
+    pub fn stream<'a>(&self, id: Ref, data: &'a [u8]) -> Cow<'a, [u8]> {
+        match &self.0 {
+            Some(encryptor) => Cow::Owned(encryptor.encrypt(id, data)),
+            None => Cow::Borrowed(data),
+        }
+    }
+
+    /// Encrypt a byte string within the object with the given reference.
+    pub fn str<'a>(&self, id: Ref, bytes: &'a [u8]) -> CryptStr<'a> {
+        match &self.0 {
+            Some(encryptor) => CryptStr::Encrypted(encryptor.encrypt(id, bytes)),
+            None => CryptStr::Plain(Str(bytes)),
+        }
+    }
+
+    /// Encrypt a text string within the object with the given reference.
+    pub fn text<'a>(&self, id: Ref, text: &'a str) -> CryptStr<'a> {
+        match &self.0 {
+            Some(encryptor) => {
+                CryptStr::Encrypted(encryptor.encrypt(id, &encode_text(text)))
+            }
+            None => CryptStr::Text(TextStr(text)),
+        }
+    }
+
+    /// Encrypt a date within the object with the given reference.
+    pub fn date(&self, id: Ref, date: Date) -> CryptStr<'static> {
+        match &self.0 {
+            Some(encryptor) => {
+                CryptStr::Encrypted(encryptor.encrypt(id, &encode_date(date)))
+            }
+            None => CryptStr::Date(date),
+        }
+    }
+
+    /// Copy the objects written by another writer into the document's writer.
+    ///
+    /// The objects are read back from the other writer's output and written
+    /// anew, so that their strings and streams are encrypted while they are
+    /// written, just like the document's own objects. Returns `None` if the
+    /// output could not be read.
+    pub fn copy(&self, writer: &mut PdfWriter, other: PdfWriter) -> Option<()> {
+        let document = lopdf::Document::load_mem(&other.finish()).ok()?;
+        for (&(num, _), object) in &document.objects {
+            let id = Ref::new(i32::try_from(num).ok()?);
+            match object {
+                lopdf::Object::Stream(stream) => {
+                    let data = self.stream(id, &stream.content);
+                    let mut obj = writer.stream(id, &data);
+                    for (key, value) in stream.dict.iter() {
+                        if key.as_slice() != b"Length" {
+                            self.write(id, obj.insert(Name(key)), value);
+                        }
+                    }
+                }
+                _ => self.write(id, writer.indirect(id), object),
+            }
+        }
+        Some(())
+    }
+
+    /// Write a direct object read back from another writer, encrypting its
+    /// strings.
+    fn write(&self, id: Ref, obj: Obj, object: &lopdf::Object) {
+        match object {
+            lopdf::Object::Null | lopdf::Object::Stream(_) => obj.primitive(Null),
+            lopdf::Object::Boolean(value) => obj.primitive(*value),
+            lopdf::Object::Integer(value) => obj.primitive(*value as i32),
+            lopdf::Object::Real(value) => obj.primitive(*value),
+            lopdf::Object::Name(name) => obj.primitive(Name(name)),
+            lopdf::Object::String(bytes, _) => obj.primitive(self.str(id, bytes)),
+            lopdf::Object::Reference((num, _)) => obj.primitive(Ref::new(*num as i32)),
+            lopdf::Object::Array(items) => {
+                let mut array = obj.array();
+                for item in items {
+                    self.write(id, array.push(), item);
+                }
+            }
+            lopdf::Object::Dictionary(entries) => {
+                let mut dict = obj.dict();
+                for (key, value) in entries.iter() {
+                    self.write(id, dict.insert(Name(key)), value);
+                }
+            }
+        }
+    }
+
+    /// Reference the encryption dictionary and the file identifier from the
+    /// trailer of the finished PDF file.
+    pub fn finish(self, mut pdf: Vec<u8>) -> Vec<u8> {
+        let Some(encryptor) = self.0 else { return pdf };
+
+        // The trailer dictionary is the last one before the `startxref`
+        // keyword at the end of the file. It follows all objects and the
+        // cross-reference table, so extending it doesn't invalidate any
+        // offsets.
+        let end = rfind(&pdf, b"startxref").and_then(|at| rfind(&pdf[..at], b">>"));
+        if let Some(end) = end {
+            let hex: String = encryptor.id.iter().map(|b| format!("{b:02X}")).collect();
+            let entries = format!(
+                "  /Encrypt {} 0 R\n  /ID [<{hex}> <{hex}>]\n",
+                encryptor.dict_ref.get()
+            );
+            pdf.splice(end..end, entries.into_bytes());
+        }
+
+        pdf
+    }
+}
+
+/// A string that is encrypted if the document is.
+pub enum CryptStr<'a> {
+    /// An unencrypted byte string.
+    Plain(Str<'a>),
+    /// An unencrypted text string.
+    Text(TextStr<'a>),
+    /// An unencrypted date.
+    Date(Date),
+    /// The encrypted bytes of a string.
+    Encrypted(Vec<u8>),
+}
+
+impl Primitive for CryptStr<'_> {
+    fn write(self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Plain(string) => string.write(buf),
+            Self::Text(text) => text.write(buf),
+            Self::Date(date) => date.write(buf),
+            Self::Encrypted(bytes) => Str(&bytes).write(buf),
+        }
+    }
+}
+
+/// The bytes of a text string: In PDFDocEncoding if the text is printable
+/// ASCII, for which it matches ASCII, otherwise in UTF-16 with a byte order
+/// mark.
+fn encode_text(text: &str) -> Vec<u8> {
+    if text.bytes().all(|b| matches!(b, 32..=126)) {
+        return text.as_bytes().to_vec();
+    }
+
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in text.encode_utf16() {
+        bytes.extend(unit.to_be_bytes());
+    }
+    bytes
+}
+
+/// The bytes of a date string.
+fn encode_date(date: Date) -> Vec<u8> {
+    // Dates only consist of digits and a few punctuation characters, so they
+    // are written as literal strings without any escapes.
+    let mut buf = vec![];
+    date.write(&mut buf);
+    buf[1..buf.len() - 1].to_vec()
+}
+
+/// The state needed to encrypt the document's objects.
+struct Encryptor {
+    /// The reference of the encryption dictionary.
+    dict_ref: Ref,
+    /// The file identifier.
+    id: [u8; 16],
+    /// The file encryption key.
+    key: [u8; 16],
+    /// The cipher from which initialization vectors are derived by encrypting
+    /// a counter.
+    iv_cipher: Aes128,
+    /// How many initialization vectors were derived so far.
+    ivs: Cell<u128>,
+}
+
+impl Encryptor {
+    /// Encrypt data within the object with the given reference with AES-128
+    /// in CBC mode, prefixed with the initialization vector.
+    fn encrypt(&self, id: Ref, data: &[u8]) -> Vec<u8> {
+        let count = self.ivs.get();
+        self.ivs.set(count + 1);
+        let mut iv = count.to_be_bytes().into();
+        self.iv_cipher.encrypt_block(&mut iv);
+        aes_cbc(&object_key(&self.key, id), iv.into(), data)
+    }
+}
+
+/// Write the encryption dictionary and derive the file key.
+///
+/// This uses revision 4 of the standard security handler with AES-128
+/// (`AESV2`) for both streams and strings. The document's metadata stays
+/// unencrypted so that it can be indexed.
+///
+/// The generated owner password and the initialization vectors are derived
+/// from the file identifier and the settings instead of being random, so that
+/// exporting the same document twice yields the same file.
+#[tracing::instrument(skip_all)]
+pub fn write_encryption(ctx: &mut PdfContext, encryption: &PdfEncryption, id: [u8; 16]) {
+    let user = pad(encryption.user_password.as_bytes());
+    let owner = if encryption.owner_password.is_empty() {
+        pad(&derive_secret(encryption, &id, b"owner"))
+    } else {
+        pad(encryption.owner_password.as_bytes())
+    };
+
+    let permissions = encryption.permissions();
+    let o = owner_entry(&owner, &user);
+    let key = file_key(&user, &o, permissions, &id);
+    let u = user_entry(&key, &id);
+
+    let dict_ref = ctx.alloc.bump();
+    let mut dict = ctx.writer.indirect(dict_ref).dict();
+    dict.pair(Name(b"Filter"), Name(b"Standard"));
+    dict.pair(Name(b"V"), 4);
+    dict.pair(Name(b"R"), 4);
+    dict.pair(Name(b"Length"), 128);
+
+    let mut filters = dict.insert(Name(b"CF")).dict();
+    let mut std_cf = filters.insert(Name(b"StdCF")).dict();
+    std_cf.pair(Name(b"CFM"), Name(b"AESV2"));
+    std_cf.pair(Name(b"AuthEvent"), Name(b"DocOpen"));
+    std_cf.pair(Name(b"Length"), 16);
+    std_cf.finish();
+    filters.finish();
+
+    dict.pair(Name(b"StmF"), Name(b"StdCF"));
+    dict.pair(Name(b"StrF"), Name(b"StdCF"));
+    dict.pair(Name(b"O"), Str(&o));
+    dict.pair(Name(b"U"), Str(&u));
+    dict.pair(Name(b"P"), permissions);
+    dict.pair(Name(b"EncryptMetadata"), false);
+    dict.finish();
+
+    let iv_key = derive_secret(encryption, &id, b"iv");
+    ctx.crypt = Crypt(Some(Encryptor {
+        dict_ref,
+        id,
+        key,
+        iv_cipher: Aes128::new(&iv_key.into()),
+        ivs: Cell::new(0),
+    }));
+}
+
+/// Derive a secret for the given purpose from the file identifier and the
+/// encryption settings.
+///
+/// The passwords are part of the input, so the secret can't be reproduced
+/// without knowing them.
+fn derive_secret(encryption: &PdfEncryption, id: &[u8; 16], purpose: &[u8]) -> [u8; 16] {
+    let mut md5 = Md5::new();
+    md5.update(purpose);
+    md5.update(id);
+    for password in [&encryption.user_password, &encryption.owner_password] {
+        md5.update((password.len() as u64).to_le_bytes());
+        md5.update(password.as_bytes());
+    }
+    md5.update(encryption.permissions().to_le_bytes());
+    md5.finalize().into()
+}
+
+/// Pad or truncate a password to 32 bytes.
+fn pad(password: &[u8]) -> [u8; 32] {
+    let len = password.len().min(32);
+    let mut padded = PADDING;
+    padded[..len].copy_from_slice(&password[..len]);
+    padded[len..].copy_from_slice(&PADDING[..32 - len]);
+    padded
+}
+
+/// Compute the `O` entry of the encryption dictionary (algorithm 3).
+fn owner_entry(owner: &[u8; 32], user: &[u8; 32]) -> [u8; 32] {
+    let mut digest: [u8; 16] = Md5::digest(owner).into();
+    for _ in 0..50 {
+        digest = Md5::digest(digest).into();
+    }
+
+    let mut entry = *user;
+    for i in 0..20 {
+        let key = digest.map(|b| b ^ i);
+        rc4(&key, &mut entry);
+    }
+    entry
+}
+
+/// Compute the file encryption key (algorithm 2).
+fn file_key(user: &[u8; 32], o: &[u8; 32], permissions: i32, id: &[u8; 16]) -> [u8; 16] {
+    let mut md5 = Md5::new();
+    md5.update(user);
+    md5.update(o);
+    md5.update(permissions.to_le_bytes());
+    md5.update(id);
+    md5.update([0xFF; 4]);
+
+    let mut key: [u8; 16] = md5.finalize().into();
+    for _ in 0..50 {
+        key = Md5::digest(key).into();
+    }
+    key
+}
+
+/// Compute the `U` entry of the encryption dictionary (algorithm 5).
+fn user_entry(key: &[u8; 16], id: &[u8; 16]) -> [u8; 32] {
+    let mut md5 = Md5::new();
+    md5.update(PADDING);
+    md5.update(id);
+    let mut hash: [u8; 16] = md5.finalize().into();
+
+    for i in 0..20 {
+        let key = key.map(|b| b ^ i);
+        rc4(&key, &mut hash);
+    }
+
+    let mut entry = [0; 32];
+    entry[..16].copy_from_slice(&hash);
+    entry
+}
+
+/// The key for the object with the given reference (algorithm 1).
+fn object_key(key: &[u8; 16], id: Ref) -> [u8; 16] {
+    let mut md5 = Md5::new();
+    md5.update(key);
+    md5.update(&id.get().to_le_bytes()[..3]);
+    md5.update([0, 0]);
+    md5.update(b"sAlT");
+    md5.finalize().into()
+}
+
+/// Encrypt or decrypt data in place with the RC4 stream cipher and a 128-bit
+/// key.
+fn rc4(key: &[u8; 16], data: &mut [u8]) {
+    Rc4::<U16>::new(key.into()).apply_keystream(data);
+}
+
+/// Encrypt data with AES-128 in CBC mode, padded as described in RFC 8018,
+/// and prefix it with the initialization vector.
+fn aes_cbc(key: &[u8; 16], iv: [u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut out = iv.to_vec();
+    out.extend(
+        cbc::Encryptor::<Aes128>::new(key.into(), &iv.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(data),
+    );
+    out
+}
+
+/// Find the last occurrence of a byte sequence.
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode a hexadecimal string.
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// A crypt with an all-zero key.
+    fn crypt() -> Crypt {
+        Crypt(Some(Encryptor {
+            dict_ref: Ref::new(1),
+            id: [0; 16],
+            key: [0; 16],
+            iv_cipher: Aes128::new(&[0; 16].into()),
+            ivs: Cell::new(0),
+        }))
+    }
+
+    #[test]
+    fn test_aes_cbc() {
+        // The first block of the CBC-AES128 example from appendix F.2.1 of
+        // NIST SP 800-38A, followed by a full block of padding.
+        let key = hex("2b7e151628aed2a6abf7158809cf4f3c").try_into().unwrap();
+        let iv: [u8; 16] = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+        let data = hex("6bc1bee22e409f96e93d7e117393172a");
+        let encrypted = aes_cbc(&key, iv, &data);
+        assert_eq!(encrypted[..16], iv);
+        assert_eq!(
+            encrypted[16..],
+            hex("7649abac8119b246cee98e9b12e9197d8964e0b149c10b7b682e6e39aaeb731c"),
+        );
+        assert_eq!(aes_cbc(&key, iv, &[1; 15]).len(), 32);
+    }
+
+    #[test]
+    fn test_key_derivation() {
+        let owner = pad(b"owner");
+        let user = pad(b"user");
+        let id: [u8; 16] = std::array::from_fn(|i| i as u8);
+        let permissions = PdfEncryption::default().permissions();
+        assert_eq!(permissions, -4);
+
+        let o = owner_entry(&owner, &user);
+        assert_eq!(
+            o.as_slice(),
+            hex("0ba3835f88f90388e74e54584125ce142be0de24c6b0d37746e075b891756671"),
+        );
+
+        let key = file_key(&user, &o, permissions, &id);
+        assert_eq!(key.as_slice(), hex("d640913055548cf686a4134c2533d9f6"));
+
+        let u = user_entry(&key, &id);
+        assert_eq!(u[..16], hex("90f938e985aec2730174ce893aedde65"));
+        assert_eq!(u[16..], [0; 16]);
+
+        let object = object_key(&key, Ref::new(7));
+        assert_eq!(object.as_slice(), hex("993882d9ee9f6574dfbff65ac4cc2757"));
+    }
+
+    #[test]
+    fn test_derive_secret() {
+        let encryption = PdfEncryption::default();
+        let secret = derive_secret(&encryption, &[0; 16], b"iv");
+        assert_eq!(secret, derive_secret(&encryption, &[0; 16], b"iv"));
+        assert_ne!(secret, derive_secret(&encryption, &[0; 16], b"owner"));
+        assert_ne!(secret, derive_secret(&encryption, &[1; 16], b"iv"));
+
+        let user = PdfEncryption { user_password: "secret".into(), ..encryption };
+        assert_ne!(secret, derive_secret(&user, &[0; 16], b"iv"));
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!(pad(b""), PADDING);
+        assert_eq!(pad(b"ab")[..4], [b'a', b'b', 0x28, 0xBF]);
+        assert_eq!(pad(&[b'x'; 40]), [b'x'; 32]);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode_text("Typst"), b"Typst");
+        assert_eq!(encode_text("(a)"), b"(a)");
+        assert_eq!(encode_text("ü"), [0xFE, 0xFF, 0x00, 0xFC]);
+        assert_eq!(encode_date(Date::new(2023).month(7)), b"D:202307");
+    }
+
+    #[test]
+    fn test_copy() {
+        let mut other = PdfWriter::new();
+        other
+            .stream(Ref::new(3), b"endobj\n\nstream data")
+            .pair(Name(b"A"), 1);
+        other
+            .indirect(Ref::new(4))
+            .dict()
+            .pair(Name(b"B"), Ref::new(3))
+            .pair(Name(b"C"), Str(b"string data"));
+
+        let mut writer = PdfWriter::new();
+        crypt().copy(&mut writer, other).unwrap();
+        let buf = writer.finish();
+        let document = lopdf::Document::load_mem(&buf).unwrap();
+        let stream = document.get_object((3, 0)).unwrap().as_stream().unwrap();
+        assert_eq!(stream.dict.get(b"A").unwrap().as_i64().unwrap(), 1);
+        assert_eq!(stream.content.len(), 48);
+
+        let dict = document.get_object((4, 0)).unwrap().as_dict().unwrap();
+        assert_eq!(dict.get(b"B").unwrap().as_reference().unwrap(), (3, 0));
+        assert_eq!(dict.get(b"C").unwrap().as_str().unwrap().len(), 32);
+        assert!(!buf.windows(4).any(|w| w == b"data"));
+    }
+
+    #[test]
+    fn test_finish() {
+        let mut writer = PdfWriter::new();
+        writer.catalog(Ref::new(2)).pages(Ref::new(3));
+        let pdf = crypt().finish(writer.finish());
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+        let trailer = &document.trailer;
+        assert_eq!(trailer.get(b"Root").unwrap().as_reference().unwrap(), (2, 0));
+        assert_eq!(trailer.get(b"Encrypt").unwrap().as_reference().unwrap(), (1, 0));
+        assert_eq!(trailer.get(b"ID").unwrap().as_array().unwrap().len(), 2);
+    }
+}
@@ -6,8 +6,10 @@ use pdf_writer::{Filter, Finish, Name, Rect, Str};
 use ttf_parser::{name_id, GlyphId, Tag};
 use unicode_general_category::GeneralCategory;
 
-use super::{deflate, EmExt, PdfContext, RefExt};
+use super::{cff, deflate, EmExt, PdfContext, RefExt};
+use crate::diag::warning;
 use crate::font::Font;
+use crate::syntax::Span;
 use crate::util::{Bytes, SliceExt};
 
 const CMAP_NAME: Name = Name(b"Custom");
@@ -17,9 +19,29 @@ const SYSTEM_INFO: SystemInfo = SystemInfo {
     supplement: 0,
 };
 
+/// Settings for embedding fonts into an exported PDF.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PdfFontOptions {
+    /// Whether to only embed the glyphs that are used in the document. Fonts
+    /// whose license forbids subsetting are always embedded fully.
+    pub subset: bool,
+    /// Whether to convert TrueType outlines into CFF outlines.
+    pub force_cff: bool,
+    /// Font families that must not be embedded, for example because their
+    /// license doesn't permit it. Matched case-insensitively.
+    pub exclude: Vec<EcoString>,
+}
+
+impl Default for PdfFontOptions {
+    fn default() -> Self {
+        Self { subset: true, force_cff: false, exclude: vec![] }
+    }
+}
+
 /// Embed all used fonts into the PDF.
 #[tracing::instrument(skip_all)]
 pub fn write_fonts(ctx: &mut PdfContext) {
+    let options = ctx.options;
     for font in ctx.font_map.items() {
         let type0_ref = ctx.alloc.bump();
         let cid_ref = ctx.alloc.bump();
@@ -36,7 +58,46 @@ pub fn write_fonts(ctx: &mut PdfContext) {
             .find_name(name_id::POST_SCRIPT_NAME)
             .unwrap_or_else(|| "unknown".to_string());
 
-        let base_font = eco_format!("ABCDEF+{}", postscript_name);
+        // Determine whether and how the font may be embedded.
+        let family = &font.info().family;
+        let permissions = embedding_permissions(ttf);
+        let excluded = options
+            .fonts
+            .exclude
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(family));
+        let embed = !excluded && permissions.embeddable;
+        if !embed {
+            let reason = if excluded {
+                "it was excluded from embedding"
+            } else {
+                "its license does not permit embedding"
+            };
+            ctx.warnings.push(
+                warning!(
+                    Span::detached(),
+                    "font {} is not embedded because {}",
+                    family,
+                    reason,
+                )
+                .with_hint("the PDF may not display correctly".into())
+                .with_kind("font-not-embedded"),
+            );
+        }
+
+        let subset = embed && options.fonts.subset && permissions.subsettable;
+        let has_cff = ttf
+            .raw_face()
+            .table(Tag::from_bytes(b"CFF "))
+            .or(ttf.raw_face().table(Tag::from_bytes(b"CFF2")))
+            .is_some();
+        let convert = embed && options.fonts.force_cff && !has_cff;
+
+        let base_font = if subset {
+            eco_format!("ABCDEF+{}", postscript_name)
+        } else {
+            postscript_name.as_str().into()
+        };
         let base_font = Name(base_font.as_bytes());
 
         // Write the base font object referencing the CID font.
@@ -47,22 +108,21 @@ pub fn write_fonts(ctx: &mut PdfContext) {
             .descendant_font(cid_ref)
             .to_unicode(cmap_ref);
 
-        // Check for the presence of CFF outlines to select the correct
-        // CID-Font subtype.
-        let subtype = match ttf
-            .raw_face()
-            .table(Tag::from_bytes(b"CFF "))
-            .or(ttf.raw_face().table(Tag::from_bytes(b"CFF2")))
-        {
-            Some(_) => CidFontType::Type0,
-            None => CidFontType::Type2,
-        };
+        // Select the CID-Font subtype based on the outlines that are
+        // embedded.
+        let subtype =
+            if has_cff || convert { CidFontType::Type0 } else { CidFontType::Type2 };
 
         // Write the CID font referencing the font descriptor.
         let mut cid = ctx.writer.cid_font(cid_ref);
         cid.subtype(subtype);
         cid.base_font(base_font);
-        cid.system_info(SYSTEM_INFO);
+        let mut system_info = cid.insert(Name(b"CIDSystemInfo")).dict();
+        let SystemInfo { registry, ordering, supplement } = SYSTEM_INFO;
+        system_info.pair(Name(b"Registry"), ctx.crypt.str(cid_ref, registry.0));
+        system_info.pair(Name(b"Ordering"), ctx.crypt.str(cid_ref, ordering.0));
+        system_info.pair(Name(b"Supplement"), supplement);
+        system_info.finish();
         cid.font_descriptor(descriptor_ref);
         cid.default_width(0.0);
 
@@ -126,21 +186,35 @@ pub fn write_fonts(ctx: &mut PdfContext) {
             .cap_height(cap_height)
             .stem_v(stem_v);
 
-        match subtype {
-            CidFontType::Type0 => font_descriptor.font_file3(data_ref),
-            CidFontType::Type2 => font_descriptor.font_file2(data_ref),
-        };
+        if embed {
+            match subtype {
+                CidFontType::Type0 => font_descriptor.font_file3(data_ref),
+                CidFontType::Type2 => font_descriptor.font_file2(data_ref),
+            };
+        }
 
         font_descriptor.finish();
 
         // Write the /ToUnicode character map, which maps glyph ids back to
         // unicode codepoints to enable copying out of the PDF.
         let cmap = create_cmap(ttf, glyph_set);
-        ctx.writer.cmap(cmap_ref, &cmap.finish());
+        ctx.writer.cmap(cmap_ref, &ctx.crypt.stream(cmap_ref, &cmap.finish()));
 
-        // Subset and write the font's bytes.
+        if !embed {
+            continue;
+        }
+
+        // Subset, convert, and write the font's bytes.
         let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-        let data = subset_font(font, &glyphs);
+        let glyphs = subset.then_some(glyphs.as_slice());
+        let level = ctx.options.size.compression_level;
+        let data = if convert {
+            convert_font(font, &postscript_name, glyphs, level)
+        } else {
+            subset_font(font, glyphs, level)
+        };
+
+        let data = ctx.crypt.stream(data_ref, &data);
         let mut stream = ctx.writer.stream(data_ref, &data);
         stream.filter(Filter::FlateDecode);
 
@@ -152,14 +226,54 @@ pub fn write_fonts(ctx: &mut PdfContext) {
     }
 }
 
-/// Subset a font to the given glyphs.
+/// Subset a font to the given glyphs or to all of its glyphs if none are
+/// given.
 #[comemo::memoize]
-fn subset_font(font: &Font, glyphs: &[u16]) -> Bytes {
+fn subset_font(font: &Font, glyphs: Option<&[u16]>, level: u8) -> Bytes {
     let data = font.data();
+    let all: Vec<u16>;
+    let glyphs = match glyphs {
+        Some(glyphs) => glyphs,
+        None => {
+            all = (0..font.ttf().number_of_glyphs()).collect();
+            &all
+        }
+    };
+
     let profile = subsetter::Profile::pdf(glyphs);
     let subsetted = subsetter::subset(data, font.index(), profile);
     let data = subsetted.as_deref().unwrap_or(data);
-    deflate(data).into()
+    deflate(data, level).into()
+}
+
+/// Convert the outlines of a font to CFF, keeping only the given glyphs if
+/// there are any.
+#[comemo::memoize]
+fn convert_font(font: &Font, name: &str, glyphs: Option<&[u16]>, level: u8) -> Bytes {
+    deflate(&cff::convert(font, name, glyphs), level).into()
+}
+
+/// What a font's license permits when embedding it into a document.
+struct EmbeddingPermissions {
+    embeddable: bool,
+    subsettable: bool,
+}
+
+/// Read the embedding permissions from the `fsType` field of a font's OS/2
+/// table.
+fn embedding_permissions(ttf: &ttf_parser::Face) -> EmbeddingPermissions {
+    let fs_type = ttf
+        .raw_face()
+        .table(Tag::from_bytes(b"OS/2"))
+        .and_then(|os2| os2.get(8..10))
+        .map_or(0, |bytes| u16::from_be_bytes([bytes[0], bytes[1]]));
+
+    // Bit 1 marks restricted license embedding and bit 9 forbids embedding
+    // anything but bitmaps, which we don't support. Bit 8 forbids subsetting.
+    EmbeddingPermissions {
+        embeddable: fs_type & 0x000F != 0x0002 && fs_type & 0x0200 == 0,
+        subsettable: fs_type & 0x0100 == 0,
+    }
 }
 
 /// Create a /ToUnicode CMap.
@@ -0,0 +1,130 @@
+use ecow::EcoString;
+use pdf_writer::{Content, Finish, Name, Rect, Ref};
+
+use super::encrypt::Crypt;
+use super::{PdfContext, RefExt};
+use crate::model;
+
+/// The default appearance of text in form fields.
+const DEFAULT_APPEARANCE: &[u8] = b"/Helv 0 Tf 0 g";
+
+/// Write the widget annotations for the form fields of a page.
+///
+/// The fields are merged with their widgets, so each field is a single
+/// indirect object that is referenced from the page's annotations and the
+/// document's interactive form.
+#[tracing::instrument(skip_all)]
+pub fn write_fields(
+    ctx: &mut PdfContext,
+    page_ref: Ref,
+    fields: &[(model::Content, Rect)],
+    refs: &[Ref],
+) {
+    for ((elem, rect), &id) in fields.iter().zip(refs) {
+        let name = elem.expect_field::<EcoString>("name");
+        let kind = elem.func().name();
+
+        // Checkboxes need appearance streams for both of their states.
+        let appearances = (kind == "checkbox").then(|| {
+            let on = ctx.alloc.bump();
+            let off = ctx.alloc.bump();
+            write_checkbox_appearances(ctx, *rect, on, off);
+            (on, off)
+        });
+
+        let mut field = ctx.writer.indirect(id).dict();
+        field.pair(Name(b"Type"), Name(b"Annot"));
+        field.pair(Name(b"Subtype"), Name(b"Widget"));
+        field.pair(Name(b"Rect"), *rect);
+        field.pair(Name(b"P"), page_ref);
+        field.pair(Name(b"F"), 4);
+        field.pair(Name(b"T"), ctx.crypt.text(id, &name));
+
+        match kind {
+            "textfield" => {
+                let value = elem.expect_field::<EcoString>("value");
+                field.pair(Name(b"FT"), Name(b"Tx"));
+                field.pair(Name(b"V"), ctx.crypt.text(id, &value));
+                field.pair(Name(b"DV"), ctx.crypt.text(id, &value));
+                field.pair(Name(b"DA"), ctx.crypt.str(id, DEFAULT_APPEARANCE));
+                if elem.expect_field::<bool>("multiline") {
+                    field.pair(Name(b"Ff"), 1 << 12);
+                }
+            }
+            "checkbox" => {
+                let state = if elem.expect_field::<bool>("checked") {
+                    Name(b"Yes")
+                } else {
+                    Name(b"Off")
+                };
+                field.pair(Name(b"FT"), Name(b"Btn"));
+                field.pair(Name(b"V"), state);
+                field.pair(Name(b"DV"), state);
+                field.pair(Name(b"AS"), state);
+                if let Some((on, off)) = appearances {
+                    let mut ap = field.insert(Name(b"AP")).dict();
+                    let mut normal = ap.insert(Name(b"N")).dict();
+                    normal.pair(Name(b"Yes"), on);
+                    normal.pair(Name(b"Off"), off);
+                    normal.finish();
+                    ap.finish();
+                }
+            }
+            _ => {
+                field.pair(Name(b"FT"), Name(b"Sig"));
+            }
+        }
+
+        field.finish();
+        ctx.form_fields.push(id);
+    }
+}
+
+/// Write the appearance streams of a checkbox in its checked and unchecked
+/// state.
+fn write_checkbox_appearances(ctx: &mut PdfContext, rect: Rect, on: Ref, off: Ref) {
+    let w = (rect.x2 - rect.x1).abs();
+    let h = (rect.y2 - rect.y1).abs();
+    let bbox = Rect::new(0.0, 0.0, w, h);
+
+    let mut check = Content::new();
+    check.set_line_width(0.12 * w.min(h));
+    check.move_to(0.2 * w, 0.5 * h);
+    check.line_to(0.42 * w, 0.25 * h);
+    check.line_to(0.8 * w, 0.78 * h);
+    check.stroke();
+
+    let data = check.finish();
+    ctx.writer.form_xobject(on, &ctx.crypt.stream(on, &data)).bbox(bbox);
+    ctx.writer.form_xobject(off, &ctx.crypt.stream(off, &[])).bbox(bbox);
+}
+
+/// Write the interactive form dictionary into the catalog, if there are any
+/// form fields.
+pub fn write_acro_form(
+    fields: &[Ref],
+    crypt: &Crypt,
+    catalog_ref: Ref,
+    dict: &mut pdf_writer::Dict,
+) {
+    if fields.is_empty() {
+        return;
+    }
+
+    let mut form = dict.insert(Name(b"AcroForm")).dict();
+    form.insert(Name(b"Fields")).array().items(fields.iter().copied());
+    form.pair(Name(b"NeedAppearances"), true);
+    form.pair(Name(b"DA"), crypt.str(catalog_ref, DEFAULT_APPEARANCE));
+
+    let mut resources = form.insert(Name(b"DR")).dict();
+    let mut fonts = resources.insert(Name(b"Font")).dict();
+    let mut helv = fonts.insert(Name(b"Helv")).dict();
+    helv.pair(Name(b"Type"), Name(b"Font"));
+    helv.pair(Name(b"Subtype"), Name(b"Type1"));
+    helv.pair(Name(b"BaseFont"), Name(b"Helvetica"));
+    helv.pair(Name(b"Encoding"), Name(b"WinAnsiEncoding"));
+    helv.finish();
+    fonts.finish();
+    resources.finish();
+    form.finish();
+}
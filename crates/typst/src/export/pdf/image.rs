@@ -1,29 +1,42 @@
+use std::borrow::Cow;
 use std::io::Cursor;
 
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
 use image::{DynamicImage, GenericImageView, Rgba};
-use pdf_writer::{Filter, Finish};
+use pdf_writer::{Filter, Finish, PdfWriter, Rect};
 
 use super::{deflate, PdfContext, RefExt};
+use crate::diag::warning;
+use crate::geom::Size;
 use crate::image::{DecodedImage, Image, RasterFormat};
+use crate::syntax::Span;
 use crate::util::Bytes;
 
 /// Embed all used images into the PDF.
 #[tracing::instrument(skip_all)]
 pub fn write_images(ctx: &mut PdfContext) {
+    let options = &ctx.options.size;
     for image in ctx.image_map.items() {
         let image_ref = ctx.alloc.bump();
         let icc_ref = ctx.alloc.bump();
         ctx.image_refs.push(image_ref);
 
-        let width = image.width();
-        let height = image.height();
+        // Downsample the image if its resolution is higher than necessary.
+        let resized = options
+            .image_dpi
+            .and_then(|dpi| downsampled_size(image, *ctx.image_sizes.get(image)?, dpi));
+        let (width, height) = resized.unwrap_or((image.width(), image.height()));
+        let level = options.compression_level;
 
         // Add the primary image.
         // TODO: Error if image could not be encoded.
         match image.decoded().as_ref() {
             DecodedImage::Raster(dynamic, icc, _) => {
                 // TODO: Error if image could not be encoded.
-                let (data, filter, has_color) = encode_image(image);
+                let (data, filter, has_color) =
+                    encode_image(image, resized, options.jpeg_quality, level);
+                let data = ctx.crypt.stream(image_ref, &data);
                 let mut image = ctx.writer.image_xobject(image_ref, &data);
                 image.filter(filter);
                 image.width(width as i32);
@@ -42,11 +55,13 @@ pub fn write_images(ctx: &mut PdfContext) {
                 // Add a second gray-scale image containing the alpha values if
                 // this image has an alpha channel.
                 if dynamic.color().has_alpha() {
-                    let (alpha_data, alpha_filter) = encode_alpha(dynamic);
+                    let (alpha_data, alpha_filter) =
+                        encode_alpha(dynamic, resized, level);
                     let mask_ref = ctx.alloc.bump();
                     image.s_mask(mask_ref);
                     image.finish();
 
+                    let alpha_data = ctx.crypt.stream(mask_ref, &alpha_data);
                     let mut mask = ctx.writer.image_xobject(mask_ref, &alpha_data);
                     mask.filter(alpha_filter);
                     mask.width(width as i32);
@@ -58,7 +73,8 @@ pub fn write_images(ctx: &mut PdfContext) {
                 }
 
                 if let Some(icc) = icc {
-                    let compressed = deflate(&icc.0);
+                    let compressed = deflate(&icc.0, level);
+                    let compressed = ctx.crypt.stream(icc_ref, &compressed);
                     let mut stream = ctx.writer.icc_profile(icc_ref, &compressed);
                     stream.filter(Filter::FlateDecode);
                     if has_color {
@@ -71,51 +87,95 @@ pub fn write_images(ctx: &mut PdfContext) {
                 }
             }
             DecodedImage::Svg(svg) => {
-                let next_ref = svg2pdf::convert_tree_into(
-                    svg,
-                    svg2pdf::Options::default(),
-                    &mut ctx.writer,
-                    image_ref,
-                );
-                ctx.alloc = next_ref;
+                let options = svg2pdf::Options::default();
+                ctx.alloc = if ctx.crypt.is_enabled() {
+                    // The converter writes its objects directly, so they are
+                    // written into the document anew to encrypt them.
+                    let mut writer = PdfWriter::new();
+                    let next_ref =
+                        svg2pdf::convert_tree_into(svg, options, &mut writer, image_ref);
+                    if ctx.crypt.copy(&mut ctx.writer, writer).is_none() {
+                        ctx.writer
+                            .form_xobject(image_ref, &[])
+                            .bbox(Rect::new(0.0, 0.0, 1.0, 1.0));
+                        ctx.warnings.push(warning!(
+                            Span::detached(),
+                            "failed to encrypt an SVG image, it was left out",
+                        ));
+                    }
+                    next_ref
+                } else {
+                    svg2pdf::convert_tree_into(svg, options, &mut ctx.writer, image_ref)
+                };
             }
         }
     }
 }
 
+/// The size in pixels to which an image should be downsampled so that it has
+/// the given resolution at the size it is shown at, if that is smaller than its
+/// actual size.
+fn downsampled_size(image: &Image, shown: Size, dpi: u32) -> Option<(u32, u32)> {
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    let needed_width = shown.x.to_inches() * dpi as f64;
+    let needed_height = shown.y.to_inches() * dpi as f64;
+    let scale = (needed_width / width).max(needed_height / height);
+    if !scale.is_finite() || scale >= 1.0 {
+        return None;
+    }
+
+    let width = ((width * scale).round() as u32).max(1);
+    let height = ((height * scale).round() as u32).max(1);
+    Some((width, height))
+}
+
+/// Resize an image to the given size in pixels, if any.
+fn resize(dynamic: &DynamicImage, size: Option<(u32, u32)>) -> Cow<'_, DynamicImage> {
+    match size {
+        Some((width, height)) => {
+            Cow::Owned(dynamic.resize_exact(width, height, FilterType::Lanczos3))
+        }
+        None => Cow::Borrowed(dynamic),
+    }
+}
+
 /// Encode an image with a suitable filter and return the data, filter and
 /// whether the image has color.
 ///
 /// Skips the alpha channel as that's encoded separately.
 #[comemo::memoize]
 #[tracing::instrument(skip_all)]
-fn encode_image(image: &Image) -> (Bytes, Filter, bool) {
+fn encode_image(
+    image: &Image,
+    size: Option<(u32, u32)>,
+    jpeg_quality: u8,
+    level: u8,
+) -> (Bytes, Filter, bool) {
     let decoded = image.decoded();
     let (dynamic, format) = match decoded.as_ref() {
         DecodedImage::Raster(dynamic, _, format) => (dynamic, *format),
         _ => panic!("can only encode raster image"),
     };
 
-    match (format, dynamic) {
+    let dynamic = resize(dynamic, size);
+    match (format, &*dynamic) {
         // 8-bit gray JPEG.
         (RasterFormat::Jpg, DynamicImage::ImageLuma8(_)) => {
-            let mut data = Cursor::new(vec![]);
-            dynamic.write_to(&mut data, image::ImageFormat::Jpeg).unwrap();
-            (data.into_inner().into(), Filter::DctDecode, false)
+            let data = encode_jpeg(&dynamic, jpeg_quality);
+            (data.into(), Filter::DctDecode, false)
         }
 
         // 8-bit RGB JPEG (CMYK JPEGs get converted to RGB earlier).
         (RasterFormat::Jpg, DynamicImage::ImageRgb8(_)) => {
-            let mut data = Cursor::new(vec![]);
-            dynamic.write_to(&mut data, image::ImageFormat::Jpeg).unwrap();
-            (data.into_inner().into(), Filter::DctDecode, true)
+            let data = encode_jpeg(&dynamic, jpeg_quality);
+            (data.into(), Filter::DctDecode, true)
         }
 
         // TODO: Encode flate streams with PNG-predictor?
 
         // 8-bit gray PNG.
         (RasterFormat::Png, DynamicImage::ImageLuma8(luma)) => {
-            let data = deflate(luma.as_raw());
+            let data = deflate(luma.as_raw(), level);
             (data.into(), Filter::FlateDecode, false)
         }
 
@@ -129,15 +189,29 @@ fn encode_image(image: &Image) -> (Bytes, Filter, bool) {
                 pixels.push(b);
             }
 
-            let data = deflate(&pixels);
+            let data = deflate(&pixels, level);
             (data.into(), Filter::FlateDecode, true)
         }
     }
 }
 
+/// Encode an 8-bit gray or RGB image as a JPEG with the given quality.
+fn encode_jpeg(dynamic: &DynamicImage, quality: u8) -> Vec<u8> {
+    let mut data = Cursor::new(vec![]);
+    JpegEncoder::new_with_quality(&mut data, quality.clamp(1, 100))
+        .encode(dynamic.as_bytes(), dynamic.width(), dynamic.height(), dynamic.color())
+        .unwrap();
+    data.into_inner()
+}
+
 /// Encode an image's alpha channel if present.
 #[tracing::instrument(skip_all)]
-fn encode_alpha(dynamic: &DynamicImage) -> (Vec<u8>, Filter) {
+fn encode_alpha(
+    dynamic: &DynamicImage,
+    size: Option<(u32, u32)>,
+    level: u8,
+) -> (Vec<u8>, Filter) {
+    let dynamic = resize(dynamic, size);
     let pixels: Vec<_> = dynamic.pixels().map(|(_, _, Rgba([_, _, _, a]))| a).collect();
-    (deflate(&pixels), Filter::FlateDecode)
+    (deflate(&pixels, level), Filter::FlateDecode)
 }
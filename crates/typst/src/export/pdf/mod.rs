@@ -1,38 +1,121 @@
 //! Exporting into PDF documents.
 
+mod cff;
+mod color;
+mod embed;
+mod encrypt;
 mod font;
+mod forms;
 mod image;
 mod outline;
 mod page;
+mod tags;
 
 use std::cmp::Eq;
 use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 
 use ecow::EcoString;
 use pdf_writer::types::Direction;
-use pdf_writer::{Finish, Name, PdfWriter, Ref, TextStr};
-use xmp_writer::{LangId, RenditionClass, XmpWriter};
+use pdf_writer::{Date, Finish, Name, PdfWriter, Ref};
+use xmp_writer::{DateTime, LangId, RenditionClass, XmpWriter};
 
+pub use self::color::PdfOutputIntent;
+use self::encrypt::Crypt;
+pub use self::encrypt::PdfEncryption;
+pub use self::font::PdfFontOptions;
 use self::page::Page;
-use crate::doc::{Document, Lang};
+use self::tags::PageTags;
+use crate::diag::SourceDiagnostic;
+use crate::doc::{Document, Lang, PdfPageLabel, PdfPageLabelStyle};
+use crate::eval::Datetime;
 use crate::font::Font;
-use crate::geom::{Abs, Dir, Em};
+use crate::geom::{Abs, Dir, Em, Size};
 use crate::image::Image;
-use crate::model::Introspector;
+use crate::model::{Content, Introspector};
+use crate::util::hash128;
 
 /// Export a document into a PDF file.
 ///
 /// Returns the raw bytes making up the PDF file.
 #[tracing::instrument(skip_all)]
 pub fn pdf(document: &Document) -> Vec<u8> {
-    let mut ctx = PdfContext::new(document);
+    pdf_with_options(document, &PdfOptions::default()).0
+}
+
+/// Export a document into a PDF file with custom settings.
+///
+/// Returns the raw bytes making up the PDF file and warnings about problems
+/// that did not prevent the export, like fonts that could not be embedded.
+#[tracing::instrument(skip_all)]
+pub fn pdf_with_options(
+    document: &Document,
+    options: &PdfOptions,
+) -> (Vec<u8>, Vec<SourceDiagnostic>) {
+    let mut ctx = PdfContext::new(document, options);
     page::construct_pages(&mut ctx, &document.pages);
+    if let Some(encryption) = &options.encryption {
+        let id = file_id(&ctx);
+        encrypt::write_encryption(&mut ctx, encryption, id);
+    }
+
     font::write_fonts(&mut ctx);
     image::write_images(&mut ctx);
     page::write_page_tree(&mut ctx);
     write_catalog(&mut ctx);
-    ctx.writer.finish()
+
+    let buf = ctx.crypt.finish(ctx.writer.finish());
+    (buf, ctx.warnings)
+}
+
+/// Settings for PDF export.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct PdfOptions {
+    /// Whether and how to encrypt the document.
+    pub encryption: Option<PdfEncryption>,
+    /// Whether to write text and shape colors as CMYK for print, and for
+    /// which output condition.
+    pub output_intent: Option<PdfOutputIntent>,
+    /// How to embed the used fonts.
+    pub fonts: PdfFontOptions,
+    /// How to reduce the size of the file.
+    pub size: PdfSizeOptions,
+    /// The ranges of page numbers, starting at 1, to export. All pages are
+    /// exported if this is `None`.
+    pub pages: Option<Vec<RangeInclusive<usize>>>,
+    /// The date to record as the creation and modification date of the
+    /// document, in UTC. No date is recorded if this is `None`, so that the
+    /// output only depends on the document.
+    pub creation_date: Option<Datetime>,
+    /// Whether the document should be accessible to readers using assistive
+    /// technology. If so, content that lacks information for them, like
+    /// images without an alternative description, is reported as a warning.
+    pub accessible: bool,
+}
+
+/// Settings for reducing the size of a PDF.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PdfSizeOptions {
+    /// The resolution in dots per inch to which raster images are downsampled
+    /// if they have a higher resolution at the largest size they are shown
+    /// at.
+    pub image_dpi: Option<u32>,
+    /// The quality, from 1 to 100, with which JPEG images are encoded.
+    pub jpeg_quality: u8,
+    /// How strongly streams are compressed, from 0 (not at all) to 10
+    /// (smallest output, but slowest).
+    pub compression_level: u8,
+}
+
+impl Default for PdfSizeOptions {
+    fn default() -> Self {
+        Self {
+            image_dpi: None,
+            jpeg_quality: 75,
+            compression_level: 6,
+        }
+    }
 }
 
 /// Identifies the color space definitions.
@@ -42,10 +125,16 @@ const D65_GRAY: Name<'static> = Name(b"d65gray");
 /// Context for exporting a whole PDF document.
 pub struct PdfContext<'a> {
     document: &'a Document,
+    options: &'a PdfOptions,
     introspector: Introspector,
     writer: PdfWriter,
     pages: Vec<Page>,
     page_heights: Vec<f32>,
+    /// For each page of the document, its index among the exported pages,
+    /// if it is exported.
+    page_indices: Vec<Option<usize>>,
+    /// The largest size at which each image is shown in the document.
+    image_sizes: HashMap<Image, Size>,
     alloc: Ref,
     page_tree_ref: Ref,
     font_refs: Vec<Ref>,
@@ -60,19 +149,36 @@ pub struct PdfContext<'a> {
     /// PDF's /ToUnicode map for glyphs that don't have an entry in the font's
     /// cmap. This is important for copy-paste and searching.
     glyph_sets: HashMap<Font, BTreeMap<u16, EcoString>>,
-    languages: HashMap<Lang, usize>,
+    /// How many glyphs of each language the document contains. This is
+    /// ordered so that the output does not depend on hashing.
+    languages: BTreeMap<Lang, usize>,
+    /// The structure of each written page, for the structure tree.
+    tags: Vec<(Ref, PageTags)>,
+    /// Elements that attach files to the document.
+    embeds: Vec<Content>,
+    /// The label of each page, if it has one.
+    page_labels: Vec<Option<PdfPageLabel>>,
+    /// The form fields of all pages.
+    form_fields: Vec<Ref>,
+    /// Problems that did not prevent the export.
+    warnings: Vec<SourceDiagnostic>,
+    /// Encrypts strings and streams if the document is encrypted.
+    crypt: Crypt,
 }
 
 impl<'a> PdfContext<'a> {
-    fn new(document: &'a Document) -> Self {
+    fn new(document: &'a Document, options: &'a PdfOptions) -> Self {
         let mut alloc = Ref::new(1);
         let page_tree_ref = alloc.bump();
         Self {
             document,
+            options,
             introspector: Introspector::new(&document.pages),
             writer: PdfWriter::new(),
             pages: vec![],
             page_heights: vec![],
+            page_indices: vec![],
+            image_sizes: HashMap::new(),
             alloc,
             page_tree_ref,
             page_refs: vec![],
@@ -81,11 +187,28 @@ impl<'a> PdfContext<'a> {
             font_map: Remapper::new(),
             image_map: Remapper::new(),
             glyph_sets: HashMap::new(),
-            languages: HashMap::new(),
+            languages: BTreeMap::new(),
+            tags: vec![],
+            embeds: vec![],
+            page_labels: vec![],
+            form_fields: vec![],
+            warnings: vec![],
+            crypt: Crypt::default(),
         }
     }
 }
 
+/// An identifier for the file that only depends on the document's metadata
+/// and the contents of its pages, so that it is the same across exports.
+fn file_id(ctx: &PdfContext) -> [u8; 16] {
+    let pages: Vec<_> = ctx
+        .pages
+        .iter()
+        .map(|page| (page.encoded.size, &page.encoded.content))
+        .collect();
+    hash128(&(&ctx.document.title, &ctx.document.author, pages)).to_be_bytes()
+}
+
 /// Write the document catalog.
 #[tracing::instrument(skip_all)]
 fn write_catalog(ctx: &mut PdfContext) {
@@ -104,23 +227,49 @@ fn write_catalog(ctx: &mut PdfContext) {
     // Write the outline tree.
     let outline_root_id = outline::write_outline(ctx);
 
+    // Write the structure tree for accessibility.
+    let page_tags = std::mem::take(&mut ctx.tags);
+    let struct_tree_root_id = tags::write_struct_tree(ctx, &page_tags);
+
+    // Write the attached files.
+    let embedded_files = embed::write_embedded_files(ctx);
+
+    // Write the output intent for print.
+    let options = ctx.options;
+    let output_intent_id = options
+        .output_intent
+        .as_ref()
+        .and_then(|intent| color::write_output_intent(ctx, intent));
+
     // Write the document information.
-    let mut info = ctx.writer.document_info(ctx.alloc.bump());
+    let info_ref = ctx.alloc.bump();
+    let crypt = &ctx.crypt;
+    let mut info = ctx.writer.document_info(info_ref);
     let mut xmp = XmpWriter::new();
     if let Some(title) = &ctx.document.title {
-        info.title(TextStr(title));
+        info.pair(Name(b"Title"), crypt.text(info_ref, title));
         xmp.title([(None, title.as_str())]);
     }
 
     let authors = &ctx.document.author;
     if !authors.is_empty() {
-        info.author(TextStr(&authors.join(", ")));
+        info.pair(Name(b"Author"), crypt.text(info_ref, &authors.join(", ")));
         xmp.creator(authors.iter().map(|s| s.as_str()));
     }
-    info.creator(TextStr("Typst"));
+    info.pair(Name(b"Creator"), crypt.text(info_ref, "Typst"));
+    if let Some(date) = options.creation_date {
+        if let Some(pdf_date) = pdf_date(date) {
+            info.pair(Name(b"CreationDate"), crypt.date(info_ref, pdf_date));
+            info.pair(Name(b"ModDate"), crypt.date(info_ref, pdf_date));
+        }
+        if let Some(xmp_date) = xmp_date(date) {
+            xmp.create_date(xmp_date);
+            xmp.modify_date(xmp_date);
+        }
+    }
     info.finish();
     xmp.creator_tool("Typst");
-    xmp.num_pages(ctx.document.pages.len() as u32);
+    xmp.num_pages(ctx.page_refs.len() as u32);
     xmp.format("application/pdf");
     xmp.language(ctx.languages.keys().map(|lang| LangId(lang.as_str())));
     xmp.rendition_class(RenditionClass::Proof);
@@ -134,7 +283,9 @@ fn write_catalog(ctx: &mut PdfContext) {
     meta_stream.finish();
 
     // Write the document catalog.
-    let mut catalog = ctx.writer.catalog(ctx.alloc.bump());
+    let catalog_ref = ctx.alloc.bump();
+    let crypt = &ctx.crypt;
+    let mut catalog = ctx.writer.catalog(catalog_ref);
     catalog.pages(ctx.page_tree_ref);
     catalog.viewer_preferences().direction(dir);
     catalog.pair(Name(b"Metadata"), meta_ref);
@@ -143,16 +294,129 @@ fn write_catalog(ctx: &mut PdfContext) {
         catalog.outlines(outline_root_id);
     }
 
+    let label_ranges = page_label_ranges(&ctx.page_labels);
+    if !label_ranges.is_empty() {
+        let mut labels = catalog.insert(Name(b"PageLabels")).dict();
+        let mut nums = labels.insert(Name(b"Nums")).array();
+        for (index, label) in label_ranges {
+            nums.item(index as i32);
+            let mut dict = nums.push().dict();
+            if let Some(style) = label.style {
+                dict.pair(
+                    Name(b"S"),
+                    Name(match style {
+                        PdfPageLabelStyle::Arabic => b"D",
+                        PdfPageLabelStyle::LowerRoman => b"r",
+                        PdfPageLabelStyle::UpperRoman => b"R",
+                        PdfPageLabelStyle::LowerAlpha => b"a",
+                        PdfPageLabelStyle::UpperAlpha => b"A",
+                    }),
+                );
+            }
+            if let Some(prefix) = &label.prefix {
+                dict.pair(Name(b"P"), crypt.text(catalog_ref, prefix));
+            }
+            if label.number.get() != 1 {
+                dict.pair(Name(b"St"), label.number.get() as i32);
+            }
+            dict.finish();
+        }
+        nums.finish();
+        labels.finish();
+    }
+
+    if let Some((tree_ref, spec_refs)) = embedded_files {
+        catalog
+            .insert(Name(b"Names"))
+            .dict()
+            .pair(Name(b"EmbeddedFiles"), tree_ref);
+        catalog.insert(Name(b"AF")).array().items(spec_refs);
+    }
+
+    if let Some(output_intent_id) = output_intent_id {
+        catalog.insert(Name(b"OutputIntents")).array().item(output_intent_id);
+    }
+
+    forms::write_acro_form(&ctx.form_fields, crypt, catalog_ref, &mut catalog);
+
+    if let Some(struct_tree_root_id) = struct_tree_root_id {
+        catalog.pair(Name(b"StructTreeRoot"), struct_tree_root_id);
+        catalog.insert(Name(b"MarkInfo")).dict().pair(Name(b"Marked"), true);
+    }
+
     if let Some(lang) = lang {
-        catalog.lang(TextStr(lang.as_str()));
+        catalog.pair(Name(b"Lang"), crypt.text(catalog_ref, lang.as_str()));
+    }
+}
+
+/// Convert a datetime into a PDF date in UTC.
+fn pdf_date(datetime: Datetime) -> Option<Date> {
+    let year = datetime.year().filter(|&year| year >= 0)? as u16;
+    let mut date = Date::new(year);
+    if let Some(month) = datetime.month() {
+        date = date.month(month);
+    }
+    if let Some(day) = datetime.day() {
+        date = date.day(day);
+    }
+    if let Some(hour) = datetime.hour() {
+        date = date.hour(hour);
+    }
+    if let Some(minute) = datetime.minute() {
+        date = date.minute(minute);
+    }
+    if let Some(second) = datetime.second() {
+        date = date.second(second);
     }
+    Some(date.utc_offset_hour(0).utc_offset_minute(0))
+}
+
+/// Convert a datetime into an XMP date in UTC.
+fn xmp_date(datetime: Datetime) -> Option<DateTime> {
+    let year = datetime.year().filter(|&year| year >= 0)? as u16;
+    let (month, day) = (datetime.month()?, datetime.day()?);
+    Some(match (datetime.hour(), datetime.minute(), datetime.second()) {
+        (Some(hour), Some(minute), Some(second)) => {
+            DateTime::new(year, month, day, hour, minute, second, 0, 0)
+        }
+        _ => DateTime::date(year, month, day),
+    })
+}
+
+/// Determine the pages at which a new page label range starts.
+///
+/// Returns nothing if no page is numbered, so that viewers fall back to their
+/// default labels.
+fn page_label_ranges(labels: &[Option<PdfPageLabel>]) -> Vec<(usize, &PdfPageLabel)> {
+    if labels
+        .iter()
+        .flatten()
+        .all(|label| label.style.is_none() && label.prefix.is_none())
+    {
+        return vec![];
+    }
+
+    let mut ranges = vec![];
+    let mut prev: Option<&PdfPageLabel> = None;
+    for (i, label) in labels.iter().enumerate() {
+        let Some(label) = label else { continue };
+        let continues = prev.is_some_and(|prev| {
+            prev.style == label.style
+                && prev.prefix == label.prefix
+                && prev.number.get() + 1 == label.number.get()
+        });
+        if !continues {
+            ranges.push((i, label));
+        }
+        prev = Some(label);
+    }
+    ranges
 }
 
 /// Compress data with the DEFLATE algorithm.
 #[tracing::instrument(skip_all)]
-fn deflate(data: &[u8]) -> Vec<u8> {
-    const COMPRESSION_LEVEL: u8 = 6;
-    miniz_oxide::deflate::compress_to_vec_zlib(data, COMPRESSION_LEVEL)
+fn deflate(data: &[u8], level: u8) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec_zlib(data, level.min(10))
 }
 
 /// Assigns new, consecutive PDF-internal indices to items.
@@ -184,13 +448,6 @@ where
         self.to_pdf[&item]
     }
 
-    fn pdf_indices<'a>(
-        &'a self,
-        refs: &'a [Ref],
-    ) -> impl Iterator<Item = (Ref, usize)> + 'a {
-        refs.iter().copied().zip(0..self.to_pdf.len())
-    }
-
     fn items(&self) -> impl Iterator<Item = &T> + '_ {
         self.to_items.iter()
     }
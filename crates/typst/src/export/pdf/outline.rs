@@ -1,6 +1,6 @@
 use std::num::NonZeroUsize;
 
-use pdf_writer::{Finish, Ref, TextStr};
+use pdf_writer::{Finish, Name, Ref};
 
 use super::{AbsExt, PdfContext, RefExt};
 use crate::geom::{Abs, Smart};
@@ -159,12 +159,12 @@ fn write_outline_item(
     }
 
     let body = node.element.expect_field::<Content>("body");
-    outline.title(TextStr(body.plain_text().trim()));
+    outline.pair(Name(b"Title"), ctx.crypt.text(id, body.plain_text().trim()));
 
     let loc = node.element.location().unwrap();
     let pos = ctx.introspector.position(loc);
-    let index = pos.page.get() - 1;
-    if let Some(&height) = ctx.page_heights.get(index) {
+    if let Some(index) = ctx.page_indices.get(pos.page.get() - 1).copied().flatten() {
+        let height = ctx.page_heights[index];
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
         outline.dest().page(ctx.page_refs[index]).xyz(
             pos.point.x.to_f32(),
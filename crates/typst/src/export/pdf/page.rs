@@ -1,24 +1,46 @@
-use ecow::eco_format;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use ecow::{eco_format, EcoString};
 use pdf_writer::types::{
     ActionType, AnnotationType, ColorSpaceOperand, LineCapStyle, LineJoinStyle,
 };
-use pdf_writer::writers::ColorSpace;
-use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str};
+use pdf_writer::writers::{Annotation, ColorSpace};
+use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str, TextStr};
 
-use super::{deflate, AbsExt, EmExt, PdfContext, RefExt, D65_GRAY, SRGB};
-use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
+use super::tags::{Area, PageTags, TagKind};
+use super::{
+    color, deflate, forms, AbsExt, EmExt, PdfContext, PdfOptions, PdfOutputIntent,
+    RefExt, Remapper, D65_GRAY, SRGB,
+};
+use crate::diag::SourceDiagnostic;
+use crate::doc::{
+    Destination, Frame, FrameItem, GroupItem, Lang, Meta, PdfPageLabel, TextItem,
+};
 use crate::font::Font;
 use crate::geom::{
     self, Abs, Color, Em, Geometry, LineCap, LineJoin, Numeric, Paint, Point, Ratio,
     Shape, Size, Stroke, Transform,
 };
 use crate::image::Image;
+use crate::model;
+use crate::syntax::Span;
 
 /// Construct page objects.
 #[tracing::instrument(skip_all)]
 pub fn construct_pages(ctx: &mut PdfContext, frames: &[Frame]) {
-    for frame in frames {
-        construct_page(ctx, frame);
+    for (i, frame) in frames.iter().enumerate() {
+        let exported =
+            ctx.options.pages.as_ref().map_or(true, |ranges| {
+                ranges.iter().any(|range| range.contains(&(i + 1)))
+            });
+
+        if exported {
+            ctx.page_indices.push(Some(ctx.page_refs.len()));
+            construct_page(ctx, frame);
+        } else {
+            ctx.page_indices.push(None);
+        }
     }
 }
 
@@ -29,14 +51,68 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
     ctx.page_refs.push(page_ref);
     ctx.page_heights.push(frame.height().to_f32());
 
+    // Encoding is memoized, so that unchanged pages are reused when the
+    // document is exported repeatedly, e.g. in watch mode.
+    let encoded = encode_page(frame, ctx.options);
+
+    if ctx.options.accessible {
+        for &span in &encoded.missing_alts {
+            ctx.warnings.push(
+                SourceDiagnostic::warning(span, "image has no alternative description")
+                    .with_kind("missing-alt")
+                    .with_hint("add an `alt` text to the image or its figure".into()),
+            );
+        }
+    }
+
+    for font in &encoded.fonts {
+        ctx.font_map.insert(font.clone());
+    }
+
+    for (image, &size) in encoded.images.iter().zip(&encoded.image_sizes) {
+        ctx.image_map.insert(image.clone());
+        let largest = ctx.image_sizes.entry(image.clone()).or_insert(size);
+        *largest = Size::new(largest.x.max(size.x), largest.y.max(size.y));
+    }
+
+    for (font, glyphs) in &encoded.glyph_sets {
+        let glyph_set = ctx.glyph_sets.entry(font.clone()).or_default();
+        for (&id, text) in glyphs {
+            glyph_set.entry(id).or_insert_with(|| text.clone());
+        }
+    }
+
+    for (&lang, &count) in &encoded.languages {
+        *ctx.languages.entry(lang).or_insert(0) += count;
+    }
+
+    ctx.embeds.extend(encoded.embeds.iter().cloned());
+    ctx.page_labels.push(encoded.label.clone());
+    ctx.pages.push(Page { id: page_ref, encoded });
+}
+
+/// Encode a frame into a content stream and collect everything the page
+/// needs from the rest of the document.
+#[comemo::memoize]
+fn encode_page(frame: &Frame, options: &PdfOptions) -> Arc<EncodedPage> {
     let mut ctx = PageContext {
-        parent: ctx,
-        page_ref,
+        options,
         content: Content::new(),
         state: State::default(),
         saves: vec![],
         bottom: 0.0,
         links: vec![],
+        tags: PageTags::default(),
+        label: None,
+        fields: vec![],
+        fonts: Remapper::new(),
+        images: Remapper::new(),
+        image_sizes: vec![],
+        glyph_sets: HashMap::new(),
+        languages: HashMap::new(),
+        embeds: vec![],
+        alphas: BTreeSet::new(),
+        missing_alts: vec![],
     };
 
     let size = frame.size();
@@ -55,70 +131,108 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
     // Encode the page into the content stream.
     write_frame(&mut ctx, frame);
 
-    let page = Page {
+    Arc::new(EncodedPage {
         size,
-        content: ctx.content,
-        id: ctx.page_ref,
+        content: deflate(&ctx.content.finish(), options.size.compression_level),
         links: ctx.links,
-    };
-
-    ctx.parent.pages.push(page);
+        tags: ctx.tags,
+        fields: ctx.fields,
+        label: ctx.label,
+        fonts: ctx.fonts.items().cloned().collect(),
+        images: ctx.images.items().cloned().collect(),
+        image_sizes: ctx.image_sizes,
+        glyph_sets: ctx.glyph_sets,
+        languages: ctx.languages,
+        embeds: ctx.embeds,
+        alphas: ctx.alphas.into_iter().collect(),
+        missing_alts: ctx.missing_alts,
+    })
 }
 
 /// Write the page tree.
 #[tracing::instrument(skip_all)]
 pub fn write_page_tree(ctx: &mut PdfContext) {
-    for page in std::mem::take(&mut ctx.pages).into_iter() {
-        write_page(ctx, page);
+    for (i, page) in std::mem::take(&mut ctx.pages).into_iter().enumerate() {
+        write_page(ctx, i, page);
     }
 
     let mut pages = ctx.writer.pages(ctx.page_tree_ref);
     pages
         .count(ctx.page_refs.len() as i32)
         .kids(ctx.page_refs.iter().copied());
+    pages.finish();
+}
 
-    let mut resources = pages.resources();
+/// Write a page tree node.
+#[tracing::instrument(skip_all)]
+fn write_page(ctx: &mut PdfContext, index: usize, page: Page) {
+    let content_id = ctx.alloc.bump();
+    let encoded = &page.encoded;
+
+    let mut page_writer = ctx.writer.page(page.id);
+    page_writer.parent(ctx.page_tree_ref);
+
+    let w = encoded.size.x.to_f32();
+    let h = encoded.size.y.to_f32();
+    page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+    page_writer.contents(content_id);
+    page_writer.pair(Name(b"StructParents"), index as i32);
+
+    // Blend transparent content in a known color space.
+    if !encoded.alphas.is_empty() {
+        let cmyk = ctx.options.output_intent.is_some();
+        color::write_transparency_group(&mut page_writer, cmyk);
+    }
+
+    // The resource names in the content stream are local to the page, so
+    // that the content stream doesn't depend on the other pages.
+    let mut resources = page_writer.resources();
     let mut spaces = resources.color_spaces();
     spaces.insert(SRGB).start::<ColorSpace>().srgb();
     spaces.insert(D65_GRAY).start::<ColorSpace>().d65_gray();
     spaces.finish();
 
+    let overprint = ctx
+        .options
+        .output_intent
+        .as_ref()
+        .is_some_and(|intent| intent.overprint_black);
+
+    if overprint || !encoded.alphas.is_empty() {
+        let mut states = resources.insert(Name(b"ExtGState")).dict();
+        if overprint {
+            color::write_overprint_states(&mut states);
+        }
+        color::write_alpha_states(&mut states, &encoded.alphas);
+        states.finish();
+    }
+
     let mut fonts = resources.fonts();
-    for (font_ref, f) in ctx.font_map.pdf_indices(&ctx.font_refs) {
-        let name = eco_format!("F{}", f);
+    for (i, font) in encoded.fonts.iter().enumerate() {
+        let name = eco_format!("F{}", i);
+        let font_ref = ctx.font_refs[ctx.font_map.map(font.clone())];
         fonts.pair(Name(name.as_bytes()), font_ref);
     }
 
     fonts.finish();
 
     let mut images = resources.x_objects();
-    for (image_ref, im) in ctx.image_map.pdf_indices(&ctx.image_refs) {
-        let name = eco_format!("Im{}", im);
+    for (i, image) in encoded.images.iter().enumerate() {
+        let name = eco_format!("Im{}", i);
+        let image_ref = ctx.image_refs[ctx.image_map.map(image.clone())];
         images.pair(Name(name.as_bytes()), image_ref);
     }
 
     images.finish();
     resources.finish();
-    pages.finish();
-}
 
-/// Write a page tree node.
-#[tracing::instrument(skip_all)]
-fn write_page(ctx: &mut PdfContext, page: Page) {
-    let content_id = ctx.alloc.bump();
+    let field_refs: Vec<Ref> = encoded.fields.iter().map(|_| ctx.alloc.bump()).collect();
+    let mut annotations = page_writer.insert(Name(b"Annots")).array();
+    annotations.items(field_refs.iter().copied());
 
-    let mut page_writer = ctx.writer.page(page.id);
-    page_writer.parent(ctx.page_tree_ref);
-
-    let w = page.size.x.to_f32();
-    let h = page.size.y.to_f32();
-    page_writer.media_box(Rect::new(0.0, 0.0, w, h));
-    page_writer.contents(content_id);
-
-    let mut annotations = page_writer.annotations();
-    for (dest, rect) in page.links {
-        let mut annotation = annotations.push();
-        annotation.subtype(AnnotationType::Link).rect(rect);
+    for (dest, rect) in &encoded.links {
+        let mut annotation = annotations.push().start::<Annotation>();
+        annotation.subtype(AnnotationType::Link).rect(*rect);
         annotation.border(0.0, 0.0, 0.0, None);
 
         let pos = match dest {
@@ -126,16 +240,17 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
                 annotation
                     .action()
                     .action_type(ActionType::Uri)
-                    .uri(Str(uri.as_bytes()));
+                    .pair(Name(b"URI"), ctx.crypt.str(page.id, uri.as_bytes()));
                 continue;
             }
-            Destination::Position(pos) => pos,
-            Destination::Location(loc) => ctx.introspector.position(loc),
+            Destination::Position(pos) => *pos,
+            Destination::Location(loc) => ctx.introspector.position(*loc),
         };
 
-        let index = pos.page.get() - 1;
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
-        if let Some(&height) = ctx.page_heights.get(index) {
+        let index = ctx.page_indices.get(pos.page.get() - 1).copied().flatten();
+        if let Some(index) = index {
+            let height = ctx.page_heights[index];
             annotation
                 .action()
                 .action_type(ActionType::GoTo)
@@ -147,33 +262,77 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
 
     annotations.finish();
     page_writer.finish();
+    ctx.tags.push((page.id, encoded.tags.clone()));
+
+    forms::write_fields(ctx, page.id, &encoded.fields, &field_refs);
 
-    let data = page.content.finish();
-    let data = deflate(&data);
-    ctx.writer.stream(content_id, &data).filter(Filter::FlateDecode);
+    ctx.writer
+        .stream(content_id, &ctx.crypt.stream(content_id, &encoded.content))
+        .filter(Filter::FlateDecode);
 }
 
 /// Data for an exported page.
 pub struct Page {
     /// The indirect object id of the page.
     pub id: Ref,
+    /// The page's encoded contents.
+    pub encoded: Arc<EncodedPage>,
+}
+
+/// The contents of a page, independent of the rest of the document.
+pub struct EncodedPage {
     /// The page's dimensions.
     pub size: Size,
-    /// The page's content stream.
-    pub content: Content,
+    /// The page's compressed content stream.
+    pub content: Vec<u8>,
     /// Links in the PDF coordinate system.
     pub links: Vec<(Destination, Rect)>,
+    /// The structure of the page's content.
+    pub tags: PageTags,
+    /// Form fields in the PDF coordinate system.
+    pub fields: Vec<(model::Content, Rect)>,
+    /// The page's label, if it has one.
+    pub label: Option<PdfPageLabel>,
+    /// The fonts used on the page, in the order of their local names.
+    pub fonts: Vec<Font>,
+    /// The images used on the page, in the order of their local names.
+    pub images: Vec<Image>,
+    /// The largest size at which each image is shown on the page, in the
+    /// same order.
+    pub image_sizes: Vec<Size>,
+    /// For each font the glyphs used on the page and their text.
+    pub glyph_sets: HashMap<Font, BTreeMap<u16, EcoString>>,
+    /// How many glyphs of each language the page contains.
+    pub languages: HashMap<Lang, usize>,
+    /// Elements on the page that attach files to the document.
+    pub embeds: Vec<model::Content>,
+    /// The alpha values of the transparent colors used on the page, each
+    /// with whether it is used for stroking.
+    pub alphas: Vec<(bool, u8)>,
+    /// The spans of the images on the page without an alternative
+    /// description.
+    pub missing_alts: Vec<Span>,
 }
 
 /// An exporter for the contents of a single PDF page.
-struct PageContext<'a, 'b> {
-    parent: &'a mut PdfContext<'b>,
-    page_ref: Ref,
+struct PageContext<'a> {
+    options: &'a PdfOptions,
     content: Content,
     state: State,
     saves: Vec<State>,
     bottom: f32,
     links: Vec<(Destination, Rect)>,
+    tags: PageTags,
+    label: Option<PdfPageLabel>,
+    fields: Vec<(model::Content, Rect)>,
+    fonts: Remapper<Font>,
+    images: Remapper<Image>,
+    image_sizes: Vec<Size>,
+    glyph_sets: HashMap<Font, BTreeMap<u16, EcoString>>,
+    languages: HashMap<Lang, usize>,
+    embeds: Vec<model::Content>,
+    alphas: BTreeSet<(bool, u8)>,
+    missing_alts: Vec<Span>,
 }
 
 /// A simulated graphics state used to deduplicate graphics state changes and
@@ -186,9 +345,14 @@ struct State {
     fill_space: Option<Name<'static>>,
     stroke: Option<Stroke>,
     stroke_space: Option<Name<'static>>,
+    /// Whether stroking and non-stroking operations overprint.
+    overprint: (bool, bool),
+    /// The alpha values of stroking and non-stroking operations. `None` as
+    /// long as both are opaque.
+    alpha: Option<(u8, u8)>,
 }
 
-impl PageContext<'_, '_> {
+impl PageContext<'_> {
     fn save_state(&mut self) {
         self.saves.push(self.state.clone());
         self.content.save_state();
@@ -214,8 +378,8 @@ impl PageContext<'_, '_> {
 
     fn set_font(&mut self, font: &Font, size: Abs) {
         if self.state.font.as_ref().map(|(f, s)| (f, *s)) != Some((font, size)) {
-            self.parent.font_map.insert(font.clone());
-            let name = eco_format!("F{}", self.parent.font_map.map(font.clone()));
+            self.fonts.insert(font.clone());
+            let name = eco_format!("F{}", self.fonts.map(font.clone()));
             self.content.set_font(Name(name.as_bytes()), size.to_f32());
             self.state.font = Some((font.clone(), size));
         }
@@ -225,10 +389,11 @@ impl PageContext<'_, '_> {
         if self.state.fill.as_ref() != Some(fill) {
             let f = |c| c as f32 / 255.0;
             let Paint::Solid(color) = fill;
+            let color = self.output_color(*color);
             match color {
                 Color::Luma(c) => {
                     self.set_fill_color_space(D65_GRAY);
-                    self.content.set_fill_gray(f(c.0));
+                    self.content.set_fill_gray(f(c.l));
                 }
                 Color::Rgba(c) => {
                     self.set_fill_color_space(SRGB);
@@ -239,6 +404,9 @@ impl PageContext<'_, '_> {
                     self.content.set_fill_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
                 }
             }
+            let overprint = color::overprints(self.output_intent(), color);
+            self.set_overprint(self.state.overprint.0, overprint);
+            self.set_alpha(self.alpha().0, color.alpha());
             self.state.fill = Some(fill.clone());
         }
     }
@@ -267,10 +435,11 @@ impl PageContext<'_, '_> {
 
             let f = |c| c as f32 / 255.0;
             let Paint::Solid(color) = paint;
+            let color = self.output_color(*color);
             match color {
                 Color::Luma(c) => {
                     self.set_stroke_color_space(D65_GRAY);
-                    self.content.set_stroke_gray(f(c.0));
+                    self.content.set_stroke_gray(f(c.l));
                 }
                 Color::Rgba(c) => {
                     self.set_stroke_color_space(SRGB);
@@ -281,6 +450,9 @@ impl PageContext<'_, '_> {
                     self.content.set_stroke_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
                 }
             }
+            let overprint = color::overprints(self.output_intent(), color);
+            self.set_overprint(overprint, self.state.overprint.1);
+            self.set_alpha(color.alpha(), self.alpha().1);
 
             self.content.set_line_width(thickness.to_f32());
             if self.state.stroke.as_ref().map(|s| &s.line_cap) != Some(line_cap) {
@@ -316,6 +488,45 @@ impl PageContext<'_, '_> {
     fn reset_stroke_color_space(&mut self) {
         self.state.stroke_space = None;
     }
+
+    fn output_intent(&self) -> Option<&PdfOutputIntent> {
+        self.options.output_intent.as_ref()
+    }
+
+    /// Convert a color for the output intent of the document, if any.
+    fn output_color(&self, color: Color) -> Color {
+        match self.output_intent() {
+            Some(_) => color::to_cmyk(color).into(),
+            None => color,
+        }
+    }
+
+    fn set_overprint(&mut self, stroke: bool, fill: bool) {
+        if self.state.overprint != (stroke, fill) {
+            self.content.set_parameters(color::overprint_state(stroke, fill));
+            self.state.overprint = (stroke, fill);
+        }
+    }
+
+    /// The current alpha values of stroking and non-stroking operations.
+    fn alpha(&self) -> (u8, u8) {
+        self.state.alpha.unwrap_or((u8::MAX, u8::MAX))
+    }
+
+    fn set_alpha(&mut self, stroke: u8, fill: u8) {
+        let (current_stroke, current_fill) = self.alpha();
+        if current_stroke != stroke {
+            let name = color::alpha_state(true, stroke);
+            self.content.set_parameters(Name(name.as_bytes()));
+            self.alphas.insert((true, stroke));
+        }
+        if current_fill != fill {
+            let name = color::alpha_state(false, fill);
+            self.content.set_parameters(Name(name.as_bytes()));
+            self.alphas.insert((false, fill));
+        }
+        self.state.alpha = Some((stroke, fill));
+    }
 }
 
 /// Encode a frame into the content stream.
@@ -327,12 +538,32 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
             FrameItem::Group(group) => write_group(ctx, pos, group),
             FrameItem::Text(text) => write_text(ctx, x, y, text),
             FrameItem::Shape(shape, _) => write_shape(ctx, x, y, shape),
-            FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
+            FrameItem::Image(image, size, span) => {
+                write_image(ctx, x, y, image, *size, *span)
+            }
             FrameItem::Meta(meta, size) => match meta {
                 Meta::Link(dest) => write_link(ctx, pos, dest, *size),
-                Meta::Elem(_) => {}
+                Meta::Elem(elem) => {
+                    match elem.func().name() {
+                        "embed" => ctx.embeds.push(elem.clone()),
+                        "textfield" | "checkbox" | "signature" => {
+                            write_field(ctx, pos, elem, *size)
+                        }
+                        _ => {}
+                    }
+                    if let Some(area) = Area::elem(elem, pos, *size, ctx.state.transform)
+                    {
+                        ctx.tags.push_area(area);
+                    }
+                }
+                Meta::Structure(structure) => {
+                    let transform = ctx.state.transform;
+                    ctx.tags
+                        .push_area(Area::structure(*structure, pos, *size, transform));
+                }
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PdfPageLabel(label) => ctx.label = Some(label.clone()),
             },
         }
     }
@@ -363,14 +594,37 @@ fn write_group(ctx: &mut PageContext, pos: Point, group: &GroupItem) {
 
 /// Encode a text run into the content stream.
 fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
-    *ctx.parent.languages.entry(text.lang).or_insert(0) += text.glyphs.len();
+    *ctx.languages.entry(text.lang).or_insert(0) += text.glyphs.len();
 
-    let glyph_set = ctx.parent.glyph_sets.entry(text.font.clone()).or_default();
+    let glyph_set = ctx.glyph_sets.entry(text.font.clone()).or_default();
     for g in &text.glyphs {
         let segment = &text.text[g.range()];
         glyph_set.entry(g.id).or_insert_with(|| segment.into());
     }
 
+    let point = Point::new(Abs::pt(x.into()), Abs::pt(y.into()));
+    let lang = text.lang_tag();
+    let (role, mcid) = ctx.tags.mark(
+        point.transform(ctx.state.transform),
+        Some(lang.clone()),
+        TagKind::Paragraph,
+    );
+
+    // Text in another language than the rest of its structure element, like
+    // a quote in a paragraph, carries its own language, so that screen
+    // readers switch their pronunciation.
+    let foreign = ctx.tags.lang_of(mcid) != Some(lang.as_str());
+    let mut marked = ctx
+        .content
+        .begin_marked_content_with_properties(Name(role.as_bytes()));
+    let mut properties = marked.properties();
+    properties.pair(Name(b"MCID"), mcid);
+    if foreign {
+        properties.pair(Name(b"Lang"), TextStr(&lang));
+    }
+    properties.finish();
+    marked.finish();
+
     ctx.set_fill(&text.fill);
     ctx.set_font(&text.font, text.size);
     ctx.content.begin_text();
@@ -414,6 +668,7 @@ fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
     items.finish();
     positioned.finish();
     ctx.content.end_text();
+    ctx.content.end_marked_content();
 }
 
 /// Encode a geometrical shape into the content stream.
@@ -430,6 +685,9 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
         return;
     }
 
+    // Shapes are decorative, so they are not part of the structure tree.
+    ctx.content.begin_marked_content(Name(b"Artifact"));
+
     if let Some(fill) = &shape.fill {
         ctx.set_fill(fill);
     }
@@ -463,6 +721,8 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
         (None, Some(_)) => ctx.content.stroke(),
         (Some(_), Some(_)) => ctx.content.fill_nonzero_and_stroke(),
     };
+
+    ctx.content.end_marked_content();
 }
 
 /// Encode a bezier path into the content stream.
@@ -489,11 +749,48 @@ fn write_path(ctx: &mut PageContext, x: f32, y: f32, path: &geom::Path) {
 }
 
 /// Encode a vector or raster image into the content stream.
-fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size) {
-    ctx.parent.image_map.insert(image.clone());
-    let name = eco_format!("Im{}", ctx.parent.image_map.map(image.clone()));
+fn write_image(
+    ctx: &mut PageContext,
+    x: f32,
+    y: f32,
+    image: &Image,
+    size: Size,
+    span: Span,
+) {
+    ctx.images.insert(image.clone());
+    let index = ctx.images.map(image.clone());
+    let name = eco_format!("Im{index}");
     let w = size.x.to_f32();
     let h = size.y.to_f32();
+
+    // Remember the largest size at which the image is shown, including the
+    // scaling of the current transformation.
+    let transform = ctx.state.transform;
+    let shown = Size::new(
+        size.x * transform.sx.get().hypot(transform.ky.get()),
+        size.y * transform.kx.get().hypot(transform.sy.get()),
+    );
+    match ctx.image_sizes.get_mut(index) {
+        Some(largest) => {
+            *largest = Size::new(largest.x.max(shown.x), largest.y.max(shown.y))
+        }
+        None => ctx.image_sizes.push(shown),
+    }
+
+    let point = Point::new(Abs::pt(x.into()), Abs::pt(y.into()));
+    let point = point.transform(ctx.state.transform);
+    if image.alt().is_none() && !ctx.tags.has_alt(point) {
+        ctx.missing_alts.push(span);
+    }
+
+    let (role, mcid) =
+        ctx.tags
+            .mark(point, None, TagKind::Figure(image.alt().map(Into::into)));
+    ctx.content
+        .begin_marked_content_with_properties(Name(role.as_bytes()))
+        .properties()
+        .pair(Name(b"MCID"), mcid);
+
     ctx.content.save_state();
     ctx.content.transform([w, 0.0, 0.0, -h, x, y + h]);
 
@@ -512,10 +809,34 @@ fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size)
     }
 
     ctx.content.restore_state();
+    ctx.content.end_marked_content();
 }
 
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size) {
+    let rect = bounding_rect(ctx, pos, size);
+    ctx.links.push((dest.clone(), rect));
+}
+
+/// Save a form field for later writing as a widget annotation.
+fn write_field(ctx: &mut PageContext, pos: Point, elem: &model::Content, size: Size) {
+    // The field's meta data is attached to all of its frames, but only the
+    // outermost one is relevant.
+    if ctx
+        .fields
+        .iter()
+        .any(|(field, _)| field.location() == elem.location())
+    {
+        return;
+    }
+
+    let rect = bounding_rect(ctx, pos, size);
+    ctx.fields.push((elem.clone(), rect));
+}
+
+/// Compute the bounding box of a transformed area in the PDF coordinate
+/// system.
+fn bounding_rect(ctx: &PageContext, pos: Point, size: Size) -> Rect {
     let mut min_x = Abs::inf();
     let mut min_y = Abs::inf();
     let mut max_x = -Abs::inf();
@@ -539,9 +860,7 @@ fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size)
     let x2 = max_x.to_f32();
     let y1 = max_y.to_f32();
     let y2 = min_y.to_f32();
-    let rect = Rect::new(x1, y1, x2, y2);
-
-    ctx.links.push((dest.clone(), rect));
+    Rect::new(x1, y1, x2, y2)
 }
 
 impl From<&LineCap> for LineCapStyle {
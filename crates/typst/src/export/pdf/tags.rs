@@ -0,0 +1,588 @@
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
+use pdf_writer::{Finish, Name, Ref};
+
+use super::{PdfContext, RefExt};
+use crate::doc::Structure;
+use crate::geom::{Abs, Point, Size, Transform};
+use crate::model::Content;
+
+/// A structure element of a page.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    /// What kind of structure the tagged content represents.
+    pub kind: TagKind,
+    /// The BCP 47 tag of the natural language of the tagged content.
+    pub lang: Option<EcoString>,
+    /// The index of the parent structure element, if it isn't the root.
+    pub parent: Option<usize>,
+    /// The marked content sequences and structure elements within this one,
+    /// in reading order.
+    pub kids: Vec<Kid>,
+}
+
+/// A child of a structure element.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kid {
+    /// A marked content sequence with the given identifier.
+    Mcid(i32),
+    /// A nested structure element, as an index into the page's tags.
+    Tag(usize),
+}
+
+/// The kind of a structure element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagKind {
+    /// A heading of the given level.
+    Heading(NonZeroUsize),
+    /// A paragraph of text.
+    Paragraph,
+    /// A figure or image with an optional alternative description.
+    Figure(Option<EcoString>),
+    /// A mathematical formula.
+    Formula,
+    /// A list of items.
+    List,
+    /// An item of a list, grouping its label and body.
+    ListItem,
+    /// The marker or term of a list item.
+    ListLabel,
+    /// The body of a list item.
+    ListBody,
+    /// A table.
+    Table,
+    /// A row of a table.
+    TableRow,
+    /// A cell of a table.
+    TableCell,
+}
+
+impl TagKind {
+    /// The kind of structure an element represents, if it is a structural
+    /// one.
+    fn of(elem: &Content) -> Option<Self> {
+        Some(match elem.func().name() {
+            "heading" => Self::Heading(elem.expect_field("level")),
+            "figure" => Self::Figure(find_alt(elem)),
+            "equation" if elem.expect_field::<bool>("block") => Self::Formula,
+            _ => return None,
+        })
+    }
+
+    /// The standard structure type for this kind.
+    pub fn role(&self) -> EcoString {
+        match self {
+            Self::Heading(level) => eco_format!("H{}", level.get().min(6)),
+            Self::Paragraph => "P".into(),
+            Self::Figure(_) => "Figure".into(),
+            Self::Formula => "Formula".into(),
+            Self::List => "L".into(),
+            Self::ListItem => "LI".into(),
+            Self::ListLabel => "Lbl".into(),
+            Self::ListBody => "LBody".into(),
+            Self::Table => "Table".into(),
+            Self::TableRow => "TR".into(),
+            Self::TableCell => "TD".into(),
+        }
+    }
+
+    /// Whether paragraphs directly within this kind of structure are part of
+    /// it instead of being paragraphs of their own.
+    fn absorbs_paragraphs(&self) -> bool {
+        matches!(self, Self::Heading(_) | Self::ListLabel | Self::Formula)
+    }
+}
+
+/// The area covered by a structure element on a page.
+#[derive(Debug, Clone)]
+pub struct Area {
+    /// The top-left corner in page coordinates.
+    min: Point,
+    /// The bottom-right corner in page coordinates.
+    max: Point,
+    /// The kind of structure the content in this area belongs to.
+    kind: TagKind,
+    /// The structural role the area was created from, if it isn't an element.
+    structure: Option<Structure>,
+}
+
+impl Area {
+    /// Create an area for an element, if the element is a structural one.
+    pub fn elem(elem: &Content, pos: Point, size: Size, ts: Transform) -> Option<Self> {
+        TagKind::of(elem).map(|kind| Self::new(kind, None, pos, size, ts))
+    }
+
+    /// Create an area for structure that isn't an element of its own.
+    pub fn structure(
+        structure: Structure,
+        pos: Point,
+        size: Size,
+        ts: Transform,
+    ) -> Self {
+        let kind = match structure {
+            Structure::Paragraph | Structure::Line => TagKind::Paragraph,
+            Structure::List => TagKind::List,
+            Structure::ListLabel => TagKind::ListLabel,
+            Structure::ListBody => TagKind::ListBody,
+            Structure::Table => TagKind::Table,
+            Structure::TableCell(_) => TagKind::TableCell,
+        };
+        Self::new(kind, Some(structure), pos, size, ts)
+    }
+
+    /// Create an area from a frame region in local coordinates.
+    fn new(
+        kind: TagKind,
+        structure: Option<Structure>,
+        pos: Point,
+        size: Size,
+        ts: Transform,
+    ) -> Self {
+        let mut min = Point::splat(Abs::inf());
+        let mut max = Point::splat(-Abs::inf());
+        for point in [
+            pos,
+            pos + Point::with_x(size.x),
+            pos + Point::with_y(size.y),
+            pos + size.to_point(),
+        ] {
+            let t = point.transform(ts);
+            min = min.min(t);
+            max = max.max(t);
+        }
+
+        Self { min, max, kind, structure }
+    }
+
+    /// The size of the area in square points.
+    fn extent(&self) -> f64 {
+        let size = self.max - self.min;
+        size.x.to_pt() * size.y.to_pt()
+    }
+
+    /// Whether the area contains the point.
+    ///
+    /// Text is positioned at its baseline, which often coincides with the
+    /// edge of its frame, so points on the edge count as contained.
+    fn contains(&self, point: Point) -> bool {
+        let eps = Abs::pt(0.01);
+        (self.min.x - eps..=self.max.x + eps).contains(&point.x)
+            && (self.min.y - eps..=self.max.y + eps).contains(&point.y)
+    }
+
+    /// Whether the area contains the other area.
+    fn encloses(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+}
+
+/// Find the alternative description of a figure, falling back to the one of
+/// its first image and its caption.
+fn find_alt(figure: &Content) -> Option<EcoString> {
+    if let Some(alt) = figure.field("alt").and_then(|v| v.cast().ok()) {
+        return Some(alt);
+    }
+
+    let mut alt = None;
+    figure.traverse(&mut |elem| {
+        if alt.is_none() && elem.func().name() == "image" {
+            alt = elem.field("alt").and_then(|v| v.cast().ok());
+        }
+    });
+    alt.or_else(|| {
+        figure
+            .field("caption")
+            .and_then(|v| v.cast::<Content>().ok())
+            .map(|caption| caption.plain_text())
+    })
+}
+
+/// Collects the structure of a single page.
+///
+/// Structure elements are built from the areas of structural content, like
+/// headings, lists, tables and paragraphs: Since the areas are registered
+/// before the content within them, each tagged item belongs to the smallest
+/// area containing it and each area is nested into the smallest area
+/// enclosing it. Among areas of the same size, the one registered last wins.
+#[derive(Debug, Default, Clone)]
+pub struct PageTags {
+    /// The structure elements of the page.
+    pub tags: Vec<Tag>,
+    /// The indices of the structure elements that are children of the root.
+    pub roots: Vec<usize>,
+    /// For each marked content identifier the index of its tag.
+    pub parents: Vec<usize>,
+    /// The areas of structural content encountered so far, each with the
+    /// index of its tag.
+    areas: Vec<(Area, usize)>,
+    /// For each list or table tag, the most recent item or row tag in it.
+    groups: Vec<(usize, usize, Option<usize>)>,
+    /// The tag of the last tagged item if it lies outside of any area.
+    loose: Option<usize>,
+}
+
+impl PageTags {
+    /// Register the area of a structural element.
+    pub fn push_area(&mut self, area: Area) {
+        let parent = self.innermost(|other| other.encloses(&area));
+        if area.kind == TagKind::Paragraph {
+            if let Some(i) = parent {
+                if self.areas[i].0.kind.absorbs_paragraphs() {
+                    return;
+                }
+            }
+        }
+
+        // The frames within an element carry its area, too, but they don't
+        // represent structure elements of their own.
+        if area.structure.is_none()
+            && self.areas.iter().any(|(other, _)| {
+                other.structure.is_none()
+                    && other.kind == area.kind
+                    && other.encloses(&area)
+            })
+        {
+            return;
+        }
+
+        let mut parent_tag = parent.map(|i| self.areas[i].1);
+        if let Some(container) = parent_tag {
+            parent_tag = Some(match (&self.tags[container].kind, area.structure) {
+                (TagKind::List, Some(Structure::ListLabel)) => {
+                    self.group(container, TagKind::ListItem, None, true)
+                }
+                (TagKind::List, Some(Structure::ListBody)) => {
+                    self.group(container, TagKind::ListItem, None, false)
+                }
+                (TagKind::Table, Some(Structure::TableCell(row))) => {
+                    self.group(container, TagKind::TableRow, Some(row), false)
+                }
+                _ => container,
+            });
+        }
+
+        // Further lines of a paragraph join the paragraph's tag if it is the
+        // last structure element in the same parent.
+        if area.structure == Some(Structure::Line) {
+            let last = match parent_tag {
+                Some(parent) => {
+                    self.tags[parent].kids.iter().rev().find_map(|kid| match *kid {
+                        Kid::Tag(i) => Some(i),
+                        Kid::Mcid(_) => None,
+                    })
+                }
+                None => self.roots.last().copied(),
+            };
+            if let Some(last) = last.filter(|&i| self.tags[i].kind == TagKind::Paragraph)
+            {
+                self.areas.push((area, last));
+                return;
+            }
+        }
+
+        let tag = self.push_tag(area.kind.clone(), parent_tag);
+        self.areas.push((area, tag));
+    }
+
+    /// Whether the item at the given position (in page coordinates) lies in a
+    /// figure with an alternative description.
+    pub fn has_alt(&self, point: Point) -> bool {
+        self.areas.iter().any(|(area, _)| {
+            area.contains(point) && matches!(area.kind, TagKind::Figure(Some(_)))
+        })
+    }
+
+    /// Assign a marked content identifier to an item at the given position
+    /// (in page coordinates).
+    ///
+    /// Images get a figure of their own unless they are within one. Items
+    /// outside of any structural area get the fallback kind, grouping
+    /// consecutive text.
+    pub fn mark(
+        &mut self,
+        point: Point,
+        lang: Option<EcoString>,
+        fallback: TagKind,
+    ) -> (EcoString, i32) {
+        let area = self.innermost(|area| area.contains(point));
+        let image = matches!(fallback, TagKind::Figure(_));
+        let index = match area {
+            Some(i) if image && !matches!(self.areas[i].0.kind, TagKind::Figure(_)) => {
+                self.push_tag(fallback, Some(self.areas[i].1))
+            }
+            Some(i) => self.areas[i].1,
+            None => match self.loose {
+                Some(last) if !image && self.tags[last].kind == fallback => last,
+                _ => self.push_tag(fallback, None),
+            },
+        };
+
+        self.loose = area.is_none().then_some(index);
+
+        let mcid = self.parents.len() as i32;
+        let tag = &mut self.tags[index];
+        tag.kids.push(Kid::Mcid(mcid));
+        if tag.lang.is_none() {
+            tag.lang = lang;
+        }
+
+        self.parents.push(index);
+        (tag.kind.role(), mcid)
+    }
+
+    /// The language of the structure element that the marked content with
+    /// the given identifier belongs to.
+    pub fn lang_of(&self, mcid: i32) -> Option<&str> {
+        let index = *self.parents.get(usize::try_from(mcid).ok()?)?;
+        self.tags[index].lang.as_deref()
+    }
+
+    /// Whether the tag or any tag within it contains marked content.
+    pub fn has_content(&self, index: usize) -> bool {
+        self.tags[index].kids.iter().any(|kid| match *kid {
+            Kid::Mcid(_) => true,
+            Kid::Tag(child) => self.has_content(child),
+        })
+    }
+
+    /// The index of the innermost area that matches the predicate.
+    fn innermost(&self, f: impl Fn(&Area) -> bool) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (i, (area, _)) in self.areas.iter().enumerate() {
+            let extent = area.extent();
+            if f(area) && best.map_or(true, |(_, min)| extent <= min) {
+                best = Some((i, extent));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Create a new tag with an optional parent tag.
+    fn push_tag(&mut self, kind: TagKind, parent: Option<usize>) -> usize {
+        let index = self.tags.len();
+        self.tags.push(Tag { kind, lang: None, parent, kids: vec![] });
+        match parent {
+            Some(parent) => self.tags[parent].kids.push(Kid::Tag(index)),
+            None => self.roots.push(index),
+        }
+        index
+    }
+
+    /// Find or start the list item or table row in a list or table that the
+    /// next area belongs to.
+    fn group(
+        &mut self,
+        container: usize,
+        kind: TagKind,
+        key: Option<usize>,
+        fresh: bool,
+    ) -> usize {
+        let current = self.groups.iter().rposition(|&(c, _, _)| c == container);
+        if let Some(i) = current {
+            let (_, group, current_key) = self.groups[i];
+            if !fresh && current_key == key {
+                return group;
+            }
+        }
+
+        let group = self.push_tag(kind, Some(container));
+        self.groups.push((container, group, key));
+        group
+    }
+}
+
+/// Write the structure tree of the document.
+///
+/// Returns the reference of the structure tree root, if there is any tagged
+/// content.
+#[tracing::instrument(skip_all)]
+pub fn write_struct_tree(ctx: &mut PdfContext, pages: &[(Ref, PageTags)]) -> Option<Ref> {
+    if pages.iter().all(|(_, page)| page.parents.is_empty()) {
+        return None;
+    }
+
+    let root_ref = ctx.alloc.bump();
+    let mut elems = vec![];
+    let mut parent_tree = vec![];
+
+    for (page_ref, page) in pages {
+        // Structure elements without any content are left out.
+        let refs: Vec<Option<Ref>> = (0..page.tags.len())
+            .map(|i| page.has_content(i).then(|| ctx.alloc.bump()))
+            .collect();
+
+        for (tag, &id) in page.tags.iter().zip(&refs) {
+            let Some(id) = id else { continue };
+            let mut elem = ctx.writer.indirect(id).dict();
+            elem.pair(Name(b"Type"), Name(b"StructElem"));
+            elem.pair(Name(b"S"), Name(tag.kind.role().as_bytes()));
+            elem.pair(Name(b"P"), tag.parent.and_then(|i| refs[i]).unwrap_or(root_ref));
+            elem.pair(Name(b"Pg"), *page_ref);
+
+            let mut kids = elem.insert(Name(b"K")).array();
+            for kid in &tag.kids {
+                match *kid {
+                    Kid::Mcid(mcid) => kids.item(mcid),
+                    Kid::Tag(child) => match refs[child] {
+                        Some(child) => kids.item(child),
+                        None => continue,
+                    },
+                };
+            }
+            kids.finish();
+
+            if let TagKind::Figure(Some(alt)) = &tag.kind {
+                elem.pair(Name(b"Alt"), ctx.crypt.text(id, alt));
+            }
+            if let Some(lang) = &tag.lang {
+                elem.pair(Name(b"Lang"), ctx.crypt.text(id, lang));
+            }
+            elem.finish();
+        }
+
+        elems.extend(page.roots.iter().filter_map(|&i| refs[i]));
+        parent_tree
+            .push(page.parents.iter().filter_map(|&i| refs[i]).collect::<Vec<_>>());
+    }
+
+    let mut root = ctx.writer.indirect(root_ref).dict();
+    root.pair(Name(b"Type"), Name(b"StructTreeRoot"));
+    root.insert(Name(b"K")).array().items(elems);
+
+    let mut tree = root.insert(Name(b"ParentTree")).dict();
+    let mut nums = tree.insert(Name(b"Nums")).array();
+    for (i, refs) in parent_tree.into_iter().enumerate() {
+        nums.item(i as i32);
+        nums.push().array().items(refs);
+    }
+    nums.finish();
+    tree.finish();
+
+    root.pair(Name(b"ParentTreeNextKey"), pages.len() as i32);
+    root.finish();
+
+    Some(root_ref)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An area of the given kind spanning the given rectangle.
+    fn area(structure: Structure, x: f64, y: f64, w: f64, h: f64) -> Area {
+        let pos = Point::new(Abs::pt(x), Abs::pt(y));
+        let size = Size::new(Abs::pt(w), Abs::pt(h));
+        Area::structure(structure, pos, size, Transform::identity())
+    }
+
+    /// Mark text at the given position.
+    fn text(tags: &mut PageTags, x: f64, y: f64) -> EcoString {
+        let point = Point::new(Abs::pt(x), Abs::pt(y));
+        tags.mark(point, Some("en".into()), TagKind::Paragraph).0
+    }
+
+    /// The roles of the tag and its descendants, as nested lists.
+    fn tree(tags: &PageTags, index: usize) -> String {
+        let tag = &tags.tags[index];
+        let mut out = tag.kind.role().to_string();
+        let kids: Vec<String> = tag
+            .kids
+            .iter()
+            .filter_map(|kid| match *kid {
+                Kid::Mcid(mcid) => Some(mcid.to_string()),
+                Kid::Tag(child) => tags.has_content(child).then(|| tree(tags, child)),
+            })
+            .collect();
+        if !kids.is_empty() {
+            out.push_str(&format!("({})", kids.join(" ")));
+        }
+        out
+    }
+
+    /// The roles of the whole page.
+    fn roots(tags: &PageTags) -> Vec<String> {
+        tags.roots
+            .iter()
+            .filter(|&&i| tags.has_content(i))
+            .map(|&i| tree(tags, i))
+            .collect()
+    }
+
+    #[test]
+    fn test_tags_paragraphs() {
+        let mut tags = PageTags::default();
+        tags.push_area(area(Structure::Paragraph, 0.0, 0.0, 100.0, 10.0));
+        assert_eq!(text(&mut tags, 0.0, 8.0), "P");
+        tags.push_area(area(Structure::Line, 0.0, 15.0, 100.0, 10.0));
+        assert_eq!(text(&mut tags, 0.0, 23.0), "P");
+        tags.push_area(area(Structure::Paragraph, 0.0, 30.0, 100.0, 10.0));
+        text(&mut tags, 0.0, 38.0);
+        assert_eq!(roots(&tags), ["P(0 1)", "P(2)"]);
+    }
+
+    #[test]
+    fn test_tags_lists() {
+        let mut tags = PageTags::default();
+        tags.push_area(area(Structure::List, 0.0, 0.0, 100.0, 40.0));
+        for y in [0.0, 20.0] {
+            tags.push_area(area(Structure::ListLabel, 0.0, y, 10.0, 20.0));
+            text(&mut tags, 0.0, y + 10.0);
+            tags.push_area(area(Structure::ListBody, 20.0, y, 80.0, 20.0));
+            tags.push_area(area(Structure::Paragraph, 20.0, y, 80.0, 20.0));
+            text(&mut tags, 20.0, y + 10.0);
+        }
+        assert_eq!(roots(&tags), ["L(LI(Lbl(0) LBody(P(1))) LI(Lbl(2) LBody(P(3))))"],);
+    }
+
+    #[test]
+    fn test_tags_tables() {
+        let mut tags = PageTags::default();
+        tags.push_area(area(Structure::Table, 0.0, 0.0, 100.0, 40.0));
+        for (row, y) in [(0, 0.0), (1, 20.0)] {
+            for x in [0.0, 50.0] {
+                tags.push_area(area(Structure::TableCell(row), x, y, 50.0, 20.0));
+                text(&mut tags, x, y + 10.0);
+            }
+        }
+        assert_eq!(roots(&tags), ["Table(TR(TD(0) TD(1)) TR(TD(2) TD(3)))"]);
+    }
+
+    #[test]
+    fn test_tags_headings_and_loose_text() {
+        let mut tags = PageTags::default();
+        let heading = Area::new(
+            TagKind::Heading(NonZeroUsize::new(2).unwrap()),
+            None,
+            Point::zero(),
+            Size::new(Abs::pt(40.0), Abs::pt(20.0)),
+            Transform::identity(),
+        );
+
+        // The heading's text also carries the heading's area and its line
+        // spans the full width.
+        tags.push_area(heading.clone());
+        tags.push_area(area(Structure::Paragraph, 0.0, 0.0, 100.0, 20.0));
+        tags.push_area(heading);
+        assert_eq!(tags.areas.len(), 2);
+        assert_eq!(text(&mut tags, 0.0, 10.0), "H2");
+
+        // Text outside of any area, like in a page's background, is grouped
+        // while it's consecutive.
+        text(&mut tags, 0.0, 50.0);
+        text(&mut tags, 0.0, 90.0);
+        assert_eq!(roots(&tags), ["H2(0)", "P(1 2)"]);
+    }
+
+    #[test]
+    fn test_tags_images() {
+        let mut tags = PageTags::default();
+        tags.push_area(area(Structure::Paragraph, 0.0, 0.0, 100.0, 20.0));
+        text(&mut tags, 0.0, 10.0);
+        let point = Point::new(Abs::pt(50.0), Abs::pt(5.0));
+        let (role, _) = tags.mark(point, None, TagKind::Figure(None));
+        assert_eq!(role, "Figure");
+        assert_eq!(roots(&tags), ["P(0 Figure(1))"]);
+        assert!(!tags.has_alt(point));
+    }
+}
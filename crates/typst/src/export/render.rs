@@ -66,6 +66,8 @@ fn render_frame(
                 Meta::Link(_) => {}
                 Meta::Elem(_) => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PdfPageLabel(_) => {}
+                Meta::Structure(_) => {}
                 Meta::Hide => {}
             },
         }
@@ -334,7 +336,8 @@ fn render_outline_glyph(
         let mut pixmap = sk::Pixmap::new(mw + 2, mh + 2)?;
         for x in 0..mw {
             for y in 0..mh {
-                let alpha = bitmap.coverage[(y * mw + x) as usize];
+                let cov = bitmap.coverage[(y * mw + x) as usize];
+                let alpha = ((cov as u16 * c.a as u16 + 127) / 255) as u8;
                 let color = sk::ColorU8::from_rgba(c.r, c.g, c.b, alpha).premultiply();
                 pixmap.pixels_mut()[((y + 1) * (mw + 2) + (x + 1)) as usize] = color;
             }
@@ -366,7 +369,7 @@ fn render_outline_glyph(
         // Premultiply the text color.
         let Paint::Solid(color) = text.fill;
         let c = color.to_rgba();
-        let color = sk::ColorU8::from_rgba(c.r, c.g, c.b, 255).premultiply().get();
+        let color = sk::ColorU8::from_rgba(c.r, c.g, c.b, c.a).premultiply().get();
 
         // Blend the glyph bitmap with the existing pixels on the canvas.
         let pixels = bytemuck::cast_slice_mut::<u8, u32>(canvas.data_mut());
@@ -379,7 +382,7 @@ fn render_outline_glyph(
                 }
 
                 let pi = (y * cw + x) as usize;
-                if cov == 255 {
+                if cov == 255 && c.a == 255 {
                     pixels[pi] = color;
                     continue;
                 }
@@ -0,0 +1,503 @@
+//! Exporting into SVG files.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter, Write};
+
+use base64::Engine;
+use ttf_parser::{GlyphId, OutlineBuilder};
+
+use crate::diag::{warning, SourceDiagnostic};
+use crate::doc::{Frame, FrameItem, GroupItem, TextItem};
+use crate::font::Font;
+use crate::geom::{
+    self, Abs, Color, Geometry, LineCap, LineJoin, Paint, PathItem, Shape, Size, Stroke,
+    Transform,
+};
+use crate::image::{Image, ImageFormat, RasterFormat, VectorFormat};
+use crate::syntax::Span;
+
+/// Export a frame into an SVG file.
+///
+/// Text is converted into paths, so the resulting file does not depend on any
+/// fonts being installed. Bitmap glyphs and images are embedded into the file.
+///
+/// Returns the SVG markup and warnings about content that could not be
+/// exported faithfully, like color glyphs.
+#[tracing::instrument(skip_all)]
+pub fn svg(frame: &Frame) -> (String, Vec<SourceDiagnostic>) {
+    let mut ctx = SvgContext::default();
+    let mut body = String::new();
+    ctx.write_frame(&mut body, frame);
+
+    let size = frame.size();
+    let mut svg = String::new();
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}pt" height="{h}pt" viewBox="0 0 {w} {h}">"#,
+        w = Num(size.x.to_pt()),
+        h = Num(size.y.to_pt()),
+    )
+    .unwrap();
+
+    if !ctx.defs.is_empty() {
+        svg.push_str("<defs>");
+        svg.push_str(&ctx.defs);
+        svg.push_str("</defs>");
+    }
+
+    svg.push_str(&body);
+    svg.push_str("</svg>");
+    (svg, ctx.warnings)
+}
+
+/// Additional state for SVG export.
+#[derive(Default)]
+struct SvgContext {
+    /// Definitions that are referenced from the body.
+    defs: String,
+    /// The ids of glyph outlines that were already defined.
+    glyphs: HashMap<(Font, u16), Option<usize>>,
+    /// The number of definitions created so far.
+    count: usize,
+    /// The fonts whose color glyphs we already warned about.
+    warned: HashSet<Font>,
+    /// Warnings about content that could not be exported faithfully.
+    warnings: Vec<SourceDiagnostic>,
+}
+
+impl SvgContext {
+    /// Create a new unique id for a definition.
+    fn next_id(&mut self) -> usize {
+        self.count += 1;
+        self.count
+    }
+
+    /// Write a frame's items.
+    fn write_frame(&mut self, svg: &mut String, frame: &Frame) {
+        for (pos, item) in frame.items() {
+            let x = pos.x.to_pt();
+            let y = pos.y.to_pt();
+            write!(svg, r#"<g transform="translate({} {})">"#, Num(x), Num(y)).unwrap();
+            match item {
+                FrameItem::Group(group) => self.write_group(svg, group),
+                FrameItem::Text(text) => self.write_text(svg, text),
+                FrameItem::Shape(shape, _) => write_shape(svg, shape),
+                FrameItem::Image(image, size, _) => write_image(svg, image, *size),
+                FrameItem::Meta(..) => {}
+            }
+            svg.push_str("</g>");
+        }
+    }
+
+    /// Write a group with optional transform and clipping.
+    fn write_group(&mut self, svg: &mut String, group: &GroupItem) {
+        svg.push_str("<g");
+        if group.transform != Transform::identity() {
+            write!(svg, r#" transform="{}""#, Matrix(group.transform)).unwrap();
+        }
+
+        if group.clips {
+            let id = self.next_id();
+            let size = group.frame.size();
+            write!(
+                self.defs,
+                r#"<clipPath id="c{id}"><rect width="{}" height="{}"/></clipPath>"#,
+                Num(size.x.to_pt()),
+                Num(size.y.to_pt()),
+            )
+            .unwrap();
+            write!(svg, r#" clip-path="url(#c{id})""#).unwrap();
+        }
+
+        svg.push('>');
+        self.write_frame(svg, &group.frame);
+        svg.push_str("</g>");
+    }
+
+    /// Write a text run as references to glyph outlines.
+    ///
+    /// Glyphs without an outline fall back to their bitmap, if any.
+    fn write_text(&mut self, svg: &mut String, text: &TextItem) {
+        let scale = text.size.to_pt() / text.font.units_per_em();
+        write!(
+            svg,
+            r#"<g transform="scale({} {})"{}>"#,
+            Num(scale),
+            Num(-scale),
+            FillAttrs(&text.fill),
+        )
+        .unwrap();
+
+        // Bitmaps are written after the flipped group.
+        let mut bitmaps = String::new();
+        let mut x = 0.0;
+        for glyph in &text.glyphs {
+            let offset = x + glyph.x_offset.at(text.size).to_pt();
+            if let Some(id) = self.glyph(&text.font, glyph.id) {
+                write!(
+                    svg,
+                    r##"<use xlink:href="#g{id}" x="{}"/>"##,
+                    Num(offset / scale)
+                )
+                .unwrap();
+            } else {
+                write_bitmap_glyph(&mut bitmaps, text, glyph.id, offset);
+            }
+
+            if text.font.ttf().glyph_svg_image(GlyphId(glyph.id)).is_some() {
+                self.warn_color_glyphs(&text.font);
+            }
+
+            x += glyph.x_advance.at(text.size).to_pt();
+        }
+
+        svg.push_str("</g>");
+        svg.push_str(&bitmaps);
+    }
+
+    /// Warn once per font that its color glyphs are not supported.
+    fn warn_color_glyphs(&mut self, font: &Font) {
+        if self.warned.insert(font.clone()) {
+            let family = &font.info().family;
+            self.warnings.push(
+                warning!(
+                    Span::detached(),
+                    "color glyphs of font {} are not supported in SVG",
+                    family,
+                )
+                .with_hint("they are exported as monochrome outlines, if any".into())
+                .with_kind("unsupported-glyph"),
+            );
+        }
+    }
+
+    /// Define the outline of a glyph, if it isn't defined yet.
+    ///
+    /// Returns `None` if the glyph has no outline.
+    fn glyph(&mut self, font: &Font, id: u16) -> Option<usize> {
+        if let Some(&defined) = self.glyphs.get(&(font.clone(), id)) {
+            return defined;
+        }
+
+        let mut builder = SvgPathBuilder(String::new());
+        let defined = font.ttf().outline_glyph(GlyphId(id), &mut builder).map(|_| {
+            let num = self.next_id();
+            write!(self.defs, r#"<path id="g{num}" d="{}"/>"#, builder.0.trim_end())
+                .unwrap();
+            num
+        });
+
+        self.glyphs.insert((font.clone(), id), defined);
+        defined
+    }
+}
+
+/// Write a bitmap glyph as an embedded image, if the font has one.
+fn write_bitmap_glyph(svg: &mut String, text: &TextItem, id: u16, x: f64) {
+    // Request the largest available strike.
+    let Some(raster) = text.font.ttf().glyph_raster_image(GlyphId(id), u16::MAX) else {
+        return;
+    };
+    let Ok(image) = Image::new(raster.data.into(), raster.format.into(), None) else {
+        return;
+    };
+
+    // Same positioning as in the PNG renderer.
+    let size = text.size.to_pt();
+    let width = image.width() as f64 / image.height() as f64 * size;
+    let dx = raster.x as f64 / image.width() as f64 * size;
+    let dy = raster.y as f64 / image.height() as f64 * size;
+    write!(svg, r#"<g transform="translate({} {})">"#, Num(x + dx), Num(-size - dy))
+        .unwrap();
+    write_image(svg, &image, Size::new(Abs::pt(width), text.size));
+    svg.push_str("</g>");
+}
+
+/// Write a geometrical shape.
+fn write_shape(svg: &mut String, shape: &Shape) {
+    let d = match &shape.geometry {
+        Geometry::Line(target) => {
+            format!("M0 0L{} {}", Num(target.x.to_pt()), Num(target.y.to_pt()))
+        }
+        Geometry::Rect(size) => {
+            let w = Num(size.x.to_pt());
+            let h = Num(size.y.to_pt());
+            format!("M0 0H{w}V{h}H0Z")
+        }
+        Geometry::Path(path) => convert_path(path),
+    };
+
+    write!(svg, r#"<path d="{d}""#).unwrap();
+    match &shape.fill {
+        Some(fill) => write!(svg, "{}", FillAttrs(fill)).unwrap(),
+        None => svg.push_str(r#" fill="none""#),
+    }
+
+    if let Some(stroke) = &shape.stroke {
+        write_stroke(svg, stroke);
+    }
+
+    svg.push_str("/>");
+}
+
+/// Write the attributes of a stroke.
+fn write_stroke(svg: &mut String, stroke: &Stroke) {
+    let Stroke {
+        paint,
+        thickness,
+        line_cap,
+        line_join,
+        dash_pattern,
+        miter_limit,
+    } = stroke;
+
+    let Paint::Solid(color) = paint;
+    write!(svg, r#" stroke="{}"{}"#, Hex(*color), Opacity("stroke-opacity", *color))
+        .unwrap();
+    write!(svg, r#" stroke-width="{}""#, Num(thickness.to_pt())).unwrap();
+
+    let cap = match line_cap {
+        LineCap::Butt => "butt",
+        LineCap::Round => "round",
+        LineCap::Square => "square",
+    };
+
+    let join = match line_join {
+        LineJoin::Miter => "miter",
+        LineJoin::Round => "round",
+        LineJoin::Bevel => "bevel",
+    };
+
+    write!(svg, r#" stroke-linecap="{cap}" stroke-linejoin="{join}""#).unwrap();
+    write!(svg, r#" stroke-miterlimit="{}""#, Num(miter_limit.0)).unwrap();
+
+    if let Some(pattern) = dash_pattern {
+        if !pattern.array.is_empty() {
+            svg.push_str(r#" stroke-dasharray=""#);
+            for (i, length) in pattern.array.iter().enumerate() {
+                if i > 0 {
+                    svg.push(' ');
+                }
+                write!(svg, "{}", Num(length.to_pt())).unwrap();
+            }
+            write!(svg, r#"" stroke-dashoffset="{}""#, Num(pattern.phase.to_pt()))
+                .unwrap();
+        }
+    }
+}
+
+/// Write an embedded image.
+fn write_image(svg: &mut String, image: &Image, size: Size) {
+    let mime = match image.format() {
+        ImageFormat::Raster(RasterFormat::Png) => "image/png",
+        ImageFormat::Raster(RasterFormat::Jpg) => "image/jpeg",
+        ImageFormat::Raster(RasterFormat::Gif) => "image/gif",
+        ImageFormat::Vector(VectorFormat::Svg) => "image/svg+xml",
+    };
+
+    let data = base64::engine::general_purpose::STANDARD.encode(image.data());
+    write!(
+        svg,
+        r#"<image width="{}" height="{}" preserveAspectRatio="none" xlink:href="data:{mime};base64,{data}"/>"#,
+        Num(size.x.to_pt()),
+        Num(size.y.to_pt()),
+    )
+    .unwrap();
+}
+
+/// Convert a Typst path into SVG path data.
+fn convert_path(path: &geom::Path) -> String {
+    let mut d = String::new();
+    for item in &path.0 {
+        match item {
+            PathItem::MoveTo(p) => {
+                write!(d, "M{} {}", Num(p.x.to_pt()), Num(p.y.to_pt())).unwrap()
+            }
+            PathItem::LineTo(p) => {
+                write!(d, "L{} {}", Num(p.x.to_pt()), Num(p.y.to_pt())).unwrap()
+            }
+            PathItem::CubicTo(p1, p2, p3) => write!(
+                d,
+                "C{} {} {} {} {} {}",
+                Num(p1.x.to_pt()),
+                Num(p1.y.to_pt()),
+                Num(p2.x.to_pt()),
+                Num(p2.y.to_pt()),
+                Num(p3.x.to_pt()),
+                Num(p3.y.to_pt()),
+            )
+            .unwrap(),
+            PathItem::ClosePath => d.push('Z'),
+        }
+    }
+    d
+}
+
+/// Collects glyph outlines as SVG path data.
+struct SvgPathBuilder(String);
+
+impl OutlineBuilder for SvgPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "M{} {}", Num(x.into()), Num(y.into())).unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "L{} {}", Num(x.into()), Num(y.into())).unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(
+            self.0,
+            "Q{} {} {} {}",
+            Num(x1.into()),
+            Num(y1.into()),
+            Num(x.into()),
+            Num(y.into())
+        )
+        .unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(
+            self.0,
+            "C{} {} {} {} {} {}",
+            Num(x1.into()),
+            Num(y1.into()),
+            Num(x2.into()),
+            Num(y2.into()),
+            Num(x.into()),
+            Num(y.into())
+        )
+        .unwrap();
+    }
+
+    fn close(&mut self) {
+        self.0.push('Z');
+    }
+}
+
+/// Displays a number with at most three decimal places.
+struct Num(f64);
+
+impl Display for Num {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Adding zero turns a negative zero into a positive one.
+        let rounded = (self.0 * 1000.0).round() / 1000.0 + 0.0;
+        Display::fmt(&rounded, f)
+    }
+}
+
+/// Displays a color as a six-digit hex code, without its alpha channel.
+struct Hex(Color);
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let rgba = self.0.to_rgba();
+        write!(f, "#{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b)
+    }
+}
+
+/// Displays the fill attributes for a paint.
+struct FillAttrs<'a>(&'a Paint);
+
+impl Display for FillAttrs<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Paint::Solid(color) = self.0;
+        write!(f, r#" fill="{}"{}"#, Hex(*color), Opacity("fill-opacity", *color))
+    }
+}
+
+/// Displays an opacity attribute if the color is not opaque.
+struct Opacity(&'static str, Color);
+
+impl Display for Opacity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let alpha = self.1.to_rgba().a;
+        if alpha != u8::MAX {
+            write!(f, r#" {}="{}""#, self.0, Num(alpha as f64 / 255.0))?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays a transform as an SVG matrix.
+struct Matrix(Transform);
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Transform { sx, ky, kx, sy, tx, ty } = self.0;
+        write!(
+            f,
+            "matrix({} {} {} {} {} {})",
+            Num(sx.get()),
+            Num(ky.get()),
+            Num(kx.get()),
+            Num(sy.get()),
+            Num(tx.to_pt()),
+            Num(ty.to_pt()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::{Glyph, Lang};
+    use crate::geom::{Em, Point};
+
+    /// Load one of the test fonts.
+    fn font(name: &str) -> Font {
+        let path = format!("{}/../../assets/fonts/{name}", env!("CARGO_MANIFEST_DIR"));
+        Font::new(std::fs::read(path).unwrap().into(), 0).unwrap()
+    }
+
+    /// Export a frame with the given text in the given font.
+    fn export(font: Font, text: &str) -> (String, Vec<SourceDiagnostic>) {
+        let glyphs = text
+            .chars()
+            .map(|c| Glyph {
+                id: font.ttf().glyph_index(c).unwrap().0,
+                x_advance: Em::one(),
+                x_offset: Em::zero(),
+                range: 0..c.len_utf8() as u16,
+                span: (Span::detached(), 0),
+            })
+            .collect();
+
+        let mut frame = Frame::new(Size::new(Abs::pt(100.0), Abs::pt(20.0)));
+        let item = TextItem {
+            font,
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            lang: Lang::ENGLISH,
+            region: None,
+            text: text.into(),
+            glyphs,
+        };
+        frame.push(Point::with_y(Abs::pt(15.0)), FrameItem::Text(item));
+        svg(&frame)
+    }
+
+    #[test]
+    fn test_svg_outline_glyphs() {
+        let (svg, warnings) = export(font("IBMPlexSans-Regular.ttf"), "Hi");
+        assert_eq!(svg.matches("<use ").count(), 2);
+        assert!(!svg.contains("<image"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_svg_bitmap_glyphs() {
+        let (svg, warnings) = export(font("NotoColorEmoji.ttf"), "😀");
+        assert!(!svg.contains("<use "));
+        assert!(svg.contains(r#"xlink:href="data:image/png;base64,"#));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_svg_color_glyphs_warn() {
+        let (_, warnings) = export(font("TwitterColorEmoji.ttf"), "😀😃");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind.as_deref(), Some("unsupported-glyph"));
+    }
+}
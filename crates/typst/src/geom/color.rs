@@ -45,6 +45,42 @@ impl Color {
         }
     }
 
+    /// The alpha channel of this color.
+    pub fn alpha(self) -> u8 {
+        match self {
+            Self::Luma(luma) => luma.a,
+            Self::Rgba(rgba) => rgba.a,
+            Self::Cmyk(cmyk) => cmyk.a,
+        }
+    }
+
+    /// Replace the alpha channel of this color.
+    pub fn with_alpha(self, a: u8) -> Self {
+        match self {
+            Self::Luma(luma) => Self::Luma(luma.with_alpha(a)),
+            Self::Rgba(rgba) => Self::Rgba(RgbaColor { a, ..rgba }),
+            Self::Cmyk(cmyk) => Self::Cmyk(cmyk.with_alpha(a)),
+        }
+    }
+
+    /// Convert this color to CMYK.
+    pub fn to_cmyk(self) -> CmykColor {
+        match self {
+            Self::Luma(luma) => luma.to_cmyk(),
+            Self::Rgba(rgba) => rgba.to_cmyk(),
+            Self::Cmyk(cmyk) => cmyk,
+        }
+    }
+
+    /// Convert this color to grayscale.
+    pub fn to_luma(self) -> LumaColor {
+        match self {
+            Self::Luma(luma) => luma,
+            Self::Rgba(rgba) => rgba.to_luma(),
+            Self::Cmyk(cmyk) => cmyk.to_rgba().to_luma(),
+        }
+    }
+
     /// Lighten this color by the given factor.
     pub fn lighten(self, factor: Ratio) -> Self {
         match self {
@@ -63,6 +99,34 @@ impl Color {
         }
     }
 
+    /// Increase the saturation of this color by the given factor.
+    ///
+    /// Grayscale colors are not affected.
+    pub fn saturate(self, factor: Ratio) -> Self {
+        self.map_saturation(|s| s + (1.0 - s) * factor.get())
+    }
+
+    /// Decrease the saturation of this color by the given factor.
+    ///
+    /// Grayscale colors are not affected.
+    pub fn desaturate(self, factor: Ratio) -> Self {
+        self.map_saturation(|s| s - s * factor.get())
+    }
+
+    /// Change the HSL saturation of this color, keeping its kind.
+    fn map_saturation(self, f: impl Fn(f64) -> f64) -> Self {
+        let map = |rgba: RgbaColor| {
+            let (h, s, l) = rgba.to_hsl();
+            RgbaColor::from_hsl(h, f(s).clamp(0.0, 1.0), l, rgba.a)
+        };
+
+        match self {
+            Self::Luma(luma) => Self::Luma(luma),
+            Self::Rgba(rgba) => Self::Rgba(map(rgba)),
+            Self::Cmyk(cmyk) => Self::Cmyk(map(cmyk.to_rgba()).to_cmyk()),
+        }
+    }
+
     /// Negate this color.
     pub fn negate(self) -> Self {
         match self {
@@ -96,6 +160,16 @@ impl Color {
         let mixed = acc.map(|v| v / total);
         Ok(vec4_to_rgba(mixed, space).into())
     }
+
+    /// Mix this color with another one.
+    ///
+    /// The ratio is the share of the other color in the result.
+    pub fn mix_with(self, other: Self, ratio: Ratio, space: ColorSpace) -> Self {
+        let weight = ratio.get().clamp(0.0, 1.0) as f32;
+        let colors = [WeightedColor(self, 1.0 - weight), WeightedColor(other, weight)];
+        // The weights always sum up to one, so mixing cannot fail.
+        Self::mix(colors, space).unwrap_or(self)
+    }
 }
 
 impl Debug for Color {
@@ -178,50 +252,71 @@ pub enum ColorSpace {
 
 /// An 8-bit grayscale color.
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
-pub struct LumaColor(pub u8);
+pub struct LumaColor {
+    /// The gray component.
+    pub l: u8,
+    /// Alpha channel.
+    pub a: u8,
+}
 
 impl LumaColor {
-    /// Construct a new luma color.
-    pub const fn new(luma: u8) -> Self {
-        Self(luma)
+    /// Construct a new, opaque luma color.
+    pub const fn new(l: u8) -> Self {
+        Self { l, a: u8::MAX }
+    }
+
+    /// Set the alpha channel of this color.
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { l: self.l, a }
     }
 
-    /// Convert to an opque RGBA color.
+    /// Convert to an RGBA color.
     pub const fn to_rgba(self) -> RgbaColor {
-        RgbaColor::new(self.0, self.0, self.0, u8::MAX)
+        RgbaColor::new(self.l, self.l, self.l, self.a)
     }
 
     /// Convert to CMYK as a fraction of true black.
     pub fn to_cmyk(self) -> CmykColor {
         CmykColor::new(
-            round_u8(self.0 as f64 * 0.75),
-            round_u8(self.0 as f64 * 0.68),
-            round_u8(self.0 as f64 * 0.67),
-            round_u8(self.0 as f64 * 0.90),
+            round_u8(self.l as f64 * 0.75),
+            round_u8(self.l as f64 * 0.68),
+            round_u8(self.l as f64 * 0.67),
+            round_u8(self.l as f64 * 0.90),
         )
+        .with_alpha(self.a)
     }
 
     /// Lighten this color by a factor.
+    ///
+    /// The alpha channel is not affected.
     pub fn lighten(self, factor: Ratio) -> Self {
-        let inc = round_u8((u8::MAX - self.0) as f64 * factor.get());
-        Self(self.0.saturating_add(inc))
+        let inc = round_u8((u8::MAX - self.l) as f64 * factor.get());
+        Self { l: self.l.saturating_add(inc), a: self.a }
     }
 
     /// Darken this color by a factor.
+    ///
+    /// The alpha channel is not affected.
     pub fn darken(self, factor: Ratio) -> Self {
-        let dec = round_u8(self.0 as f64 * factor.get());
-        Self(self.0.saturating_sub(dec))
+        let dec = round_u8(self.l as f64 * factor.get());
+        Self { l: self.l.saturating_sub(dec), a: self.a }
     }
 
     /// Negate this color.
+    ///
+    /// The alpha channel is not affected.
     pub fn negate(self) -> Self {
-        Self(u8::MAX - self.0)
+        Self { l: u8::MAX - self.l, a: self.a }
     }
 }
 
 impl Debug for LumaColor {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "luma({})", self.0)
+        write!(f, "luma({}", self.l)?;
+        if self.a != u8::MAX {
+            write!(f, ", {:.1}%", 100.0 * (self.a as f64 / 255.0))?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -289,6 +384,121 @@ impl RgbaColor {
         }
     }
 
+    /// Construct a color from HSL components.
+    ///
+    /// Saturation and lightness are fractions between zero and one.
+    pub fn from_hsl(hue: Angle, saturation: f64, lightness: f64, a: u8) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        Self::from_hue_chroma(hue, chroma, lightness - chroma / 2.0, a)
+    }
+
+    /// Construct a color from HSV components.
+    ///
+    /// Saturation and value are fractions between zero and one.
+    pub fn from_hsv(hue: Angle, saturation: f64, value: f64, a: u8) -> Self {
+        let chroma = value * saturation;
+        Self::from_hue_chroma(hue, chroma, value - chroma, a)
+    }
+
+    /// Construct a color from a hue, a chroma and the amount that is added to
+    /// all channels.
+    fn from_hue_chroma(hue: Angle, chroma: f64, min: f64, a: u8) -> Self {
+        let h = hue.to_deg().rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let f = |c: f64| round_u8(255.0 * (c + min).clamp(0.0, 1.0));
+        Self::new(f(r), f(g), f(b), a)
+    }
+
+    /// Construct a color from Oklab components.
+    pub fn from_oklab(l: f64, a: f64, b: f64, alpha: u8) -> Self {
+        let oklab = oklab::Oklab { l: l as f32, a: a as f32, b: b as f32 };
+        let oklab::RGB { r, g, b } = oklab::oklab_to_srgb(oklab);
+        Self::new(r, g, b, alpha)
+    }
+
+    /// Convert this color to HSL, without the alpha channel.
+    ///
+    /// Returns the hue, saturation and lightness.
+    pub fn to_hsl(self) -> (Angle, f64, f64) {
+        let (hue, max, min) = self.hue();
+        let lightness = (max + min) / 2.0;
+        let saturation = if lightness <= 0.0 || lightness >= 1.0 {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Convert this color to HSV, without the alpha channel.
+    ///
+    /// Returns the hue, saturation and value.
+    pub fn to_hsv(self) -> (Angle, f64, f64) {
+        let (hue, max, min) = self.hue();
+        let saturation = if max <= 0.0 { 0.0 } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    /// The hue of this color along with its largest and smallest channel.
+    fn hue(self) -> (Angle, f64, f64) {
+        let r = self.r as f64 / 255.0;
+        let g = self.g as f64 / 255.0;
+        let b = self.b as f64 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let h = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        (Angle::deg(60.0 * h), max, min)
+    }
+
+    /// Convert this color to Oklab, without the alpha channel.
+    ///
+    /// Returns the lightness and the a and b components.
+    pub fn to_oklab(self) -> (f64, f64, f64) {
+        let oklab = oklab::srgb_to_oklab(oklab::RGB { r: self.r, g: self.g, b: self.b });
+        (oklab.l as f64, oklab.a as f64, oklab.b as f64)
+    }
+
+    /// Convert this color to grayscale.
+    ///
+    /// Weighs the channels by their contribution to the perceived brightness.
+    pub fn to_luma(self) -> LumaColor {
+        let luma =
+            0.2126 * self.r as f64 + 0.7152 * self.g as f64 + 0.0722 * self.b as f64;
+        LumaColor::new(round_u8(luma)).with_alpha(self.a)
+    }
+
+    /// Convert this color to CMYK.
+    pub fn to_cmyk(self) -> CmykColor {
+        let r = self.r as f64 / 255.0;
+        let g = self.g as f64 / 255.0;
+        let b = self.b as f64 / 255.0;
+        let k = 1.0 - r.max(g).max(b);
+        if k == 1.0 {
+            return CmykColor::new(0, 0, 0, u8::MAX).with_alpha(self.a);
+        }
+
+        let f = |c: f64| round_u8(255.0 * (1.0 - c - k) / (1.0 - k));
+        CmykColor::new(f(r), f(g), f(b), round_u8(255.0 * k)).with_alpha(self.a)
+    }
+
     /// Converts this color to a RGB Hex Code.
     pub fn to_hex(self) -> EcoString {
         if self.a != 255 {
@@ -378,12 +588,19 @@ pub struct CmykColor {
     pub y: u8,
     /// The key (black) component.
     pub k: u8,
+    /// Alpha channel.
+    pub a: u8,
 }
 
 impl CmykColor {
-    /// Construct a new CMYK color.
+    /// Construct a new, opaque CMYK color.
     pub const fn new(c: u8, m: u8, y: u8, k: u8) -> Self {
-        Self { c, m, y, k }
+        Self { c, m, y, k, a: u8::MAX }
+    }
+
+    /// Set the alpha channel of this color.
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { c: self.c, m: self.m, y: self.y, k: self.k, a }
     }
 
     /// Convert this color to RGBA.
@@ -394,10 +611,17 @@ impl CmykColor {
             round_u8(255.0 * (1.0 - c) * (1.0 - k))
         };
 
-        RgbaColor { r: f(self.c), g: f(self.m), b: f(self.y), a: 255 }
+        RgbaColor {
+            r: f(self.c),
+            g: f(self.m),
+            b: f(self.y),
+            a: self.a,
+        }
     }
 
     /// Lighten this color by a factor.
+    ///
+    /// The alpha channel is not affected.
     pub fn lighten(self, factor: Ratio) -> Self {
         let lighten = |c: u8| c.saturating_sub(round_u8(c as f64 * factor.get()));
         Self {
@@ -405,10 +629,13 @@ impl CmykColor {
             m: lighten(self.m),
             y: lighten(self.y),
             k: lighten(self.k),
+            a: self.a,
         }
     }
 
     /// Darken this color by a factor.
+    ///
+    /// The alpha channel is not affected.
     pub fn darken(self, factor: Ratio) -> Self {
         let darken =
             |c: u8| c.saturating_add(round_u8((u8::MAX - c) as f64 * factor.get()));
@@ -417,22 +644,25 @@ impl CmykColor {
             m: darken(self.m),
             y: darken(self.y),
             k: darken(self.k),
+            a: self.a,
         }
     }
 
     /// Negate this color.
     ///
-    /// Does not affect the key component.
+    /// Does not affect the key component and the alpha channel.
     pub fn negate(self) -> Self {
         Self {
             c: u8::MAX - self.c,
             m: u8::MAX - self.m,
             y: u8::MAX - self.y,
             k: self.k,
+            a: self.a,
         }
     }
 
-    /// Converts this color to an array of C, M, Y, K components.
+    /// Converts this color to an array of C, M, Y, K components, without the
+    /// alpha channel.
     pub fn to_array(self) -> Array {
         // convert to ratio
         let g = |c| Ratio::new(c as f64 / 255.0);
@@ -445,12 +675,16 @@ impl Debug for CmykColor {
         let g = |c| 100.0 * (c as f64 / 255.0);
         write!(
             f,
-            "cmyk({:.1}%, {:.1}%, {:.1}%, {:.1}%)",
+            "cmyk({:.1}%, {:.1}%, {:.1}%, {:.1}%",
             g(self.c),
             g(self.m),
             g(self.y),
             g(self.k),
-        )
+        )?;
+        if self.a != u8::MAX {
+            write!(f, ", {:.1}%", g(self.a))?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -501,4 +735,42 @@ mod tests {
         test("hmmm", "color string contains non-hexadecimal letters");
         test("14B2AH", "color string contains non-hexadecimal letters");
     }
+
+    #[test]
+    fn test_rgba_to_cmyk() {
+        #[track_caller]
+        fn test(rgba: RgbaColor, cmyk: CmykColor) {
+            assert_eq!(rgba.to_cmyk(), cmyk);
+            assert_eq!(cmyk.to_rgba(), rgba);
+        }
+
+        test(RgbaColor::new(0, 0, 0, 255), CmykColor::new(0, 0, 0, 255));
+        test(RgbaColor::new(255, 255, 255, 255), CmykColor::new(0, 0, 0, 0));
+        test(
+            RgbaColor::new(255, 0, 0, 128),
+            CmykColor::new(0, 255, 255, 0).with_alpha(128),
+        );
+        test(RgbaColor::new(0, 128, 128, 255), CmykColor::new(255, 0, 0, 127));
+    }
+
+    #[test]
+    fn test_rgba_to_hsl_and_hsv() {
+        #[track_caller]
+        fn test(rgba: RgbaColor, hue: f64, hsl: (f64, f64), hsv: (f64, f64)) {
+            let (h, s, l) = rgba.to_hsl();
+            assert!((h.to_deg() - hue).abs() < 0.5);
+            assert_eq!(RgbaColor::from_hsl(h, s, l, rgba.a), rgba);
+            assert!((s - hsl.0).abs() < 0.01 && (l - hsl.1).abs() < 0.01);
+
+            let (h, s, v) = rgba.to_hsv();
+            assert_eq!(RgbaColor::from_hsv(h, s, v, rgba.a), rgba);
+            assert!((s - hsv.0).abs() < 0.01 && (v - hsv.1).abs() < 0.01);
+        }
+
+        test(RgbaColor::new(0, 0, 0, 255), 0.0, (0.0, 0.0), (0.0, 0.0));
+        test(RgbaColor::new(255, 255, 255, 255), 0.0, (0.0, 1.0), (0.0, 1.0));
+        test(RgbaColor::new(255, 0, 0, 128), 0.0, (1.0, 0.5), (1.0, 1.0));
+        test(RgbaColor::new(0, 128, 128, 255), 180.0, (1.0, 0.25), (1.0, 0.5));
+        test(RgbaColor::new(191, 64, 191, 255), 300.0, (0.5, 0.5), (0.66, 0.75));
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, HashSet};
 
 use ecow::{eco_format, EcoString};
 use if_chain::if_chain;
@@ -8,8 +8,9 @@ use super::analyze::analyze_labels;
 use super::{analyze_expr, analyze_import, plain_docs_sentence, summarize_font_family};
 use crate::doc::Frame;
 use crate::eval::{fields_on, format_str, methods_on, CastInfo, Library, Scope, Value};
+use crate::syntax::ast::{self, AstNode};
 use crate::syntax::{
-    ast, is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
+    is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
 };
 use crate::util::separated_list;
 use crate::World;
@@ -34,6 +35,7 @@ pub fn autocomplete(
         || complete_field_accesses(&mut ctx)
         || complete_imports(&mut ctx)
         || complete_rules(&mut ctx)
+        || complete_dict_keys(&mut ctx)
         || complete_params(&mut ctx)
         || complete_markup(&mut ctx)
         || complete_math(&mut ctx)
@@ -593,7 +595,7 @@ fn show_rule_recipe_completions(ctx: &mut CompletionContext) {
 /// Complete call and set rule parameters.
 fn complete_params(ctx: &mut CompletionContext) -> bool {
     // Ensure that we are in a function call or set rule's argument list.
-    let (callee, set, args) = if_chain! {
+    let (callee, callee_node, set, args) = if_chain! {
         if let Some(parent) = ctx.leaf.parent();
         if let Some(parent) = match parent.kind() {
             SyntaxKind::Named => parent.parent(),
@@ -608,8 +610,9 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
             ast::Expr::Set(set) => Some(set.target()),
             _ => None,
         };
+        if let Some(callee_node) = grand.children().find(|child| child.is::<ast::Ident>());
         then {
-            (callee, set, args)
+            (callee, callee_node, set, args)
         } else {
             return false;
         }
@@ -635,7 +638,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
                 ctx.from = ctx.cursor.min(next.offset());
             }
 
-            named_param_value_completions(ctx, &callee, &param);
+            named_param_value_completions(ctx, &callee, &callee_node, &param);
             return true;
         }
     }
@@ -655,7 +658,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
                 _ => None,
             }).collect();
 
-            param_completions(ctx, &callee, set, &exclude);
+            param_completions(ctx, &callee, &callee_node, set, &exclude);
             return true;
         }
     }
@@ -667,6 +670,7 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
 fn param_completions(
     ctx: &mut CompletionContext,
     callee: &ast::Ident,
+    callee_node: &LinkedNode,
     set: bool,
     exclude: &[ast::Ident],
 ) {
@@ -674,7 +678,12 @@ fn param_completions(
         if let Some(Value::Func(func)) = ctx.global.get(callee);
         if let Some(info) = func.info();
         then { info }
-        else { return; }
+        else {
+            if !set {
+                user_param_completions(ctx, callee_node, exclude);
+            }
+            return;
+        }
     };
 
     for param in &info.params {
@@ -705,10 +714,45 @@ fn param_completions(
     }
 }
 
+/// Add completions for the named parameters of a user-defined function.
+fn user_param_completions(
+    ctx: &mut CompletionContext,
+    callee: &LinkedNode,
+    exclude: &[ast::Ident],
+) {
+    let Some(Value::Func(func)) = analyze_expr(ctx.world, callee).into_iter().next()
+    else {
+        return;
+    };
+
+    let Some(params) = func.named_params() else { return };
+    for (name, default) in params {
+        if exclude.iter().any(|ident| ident.as_str() == name) {
+            continue;
+        }
+
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Param,
+            label: name.into(),
+            apply: Some(eco_format!("{name}: ${{}}")),
+            detail: Some(eco_format!(
+                "A {}, {} by default.",
+                default.type_name(),
+                default.repr()
+            )),
+        });
+    }
+
+    if ctx.before.ends_with(',') {
+        ctx.enrich(" ", "");
+    }
+}
+
 /// Add completions for the values of a named function parameter.
 fn named_param_value_completions(
     ctx: &mut CompletionContext,
     callee: &ast::Ident,
+    callee_node: &LinkedNode,
     name: &str,
 ) {
     let param = if_chain! {
@@ -717,7 +761,10 @@ fn named_param_value_completions(
         if let Some(param) = info.param(name);
         if param.named;
         then { param }
-        else { return; }
+        else {
+            user_param_value_completions(ctx, callee_node, name);
+            return;
+        }
     };
 
     ctx.cast_completions(&param.cast);
@@ -731,6 +778,67 @@ fn named_param_value_completions(
     }
 }
 
+/// Add completions for the value of a named parameter of a user-defined
+/// function.
+fn user_param_value_completions(
+    ctx: &mut CompletionContext,
+    callee: &LinkedNode,
+    name: &str,
+) {
+    let Some(Value::Func(func)) = analyze_expr(ctx.world, callee).into_iter().next()
+    else {
+        return;
+    };
+
+    let Some(default) = func
+        .named_params()
+        .and_then(|params| params.into_iter().find(|&(param, _)| param == name))
+        .map(|(_, default)| default.clone())
+    else {
+        return;
+    };
+
+    ctx.value_completion(None, &default, false, Some("The default value."));
+    ctx.scope_completions(false, |value| value.type_name() == default.type_name());
+
+    if ctx.before.ends_with(':') {
+        ctx.enrich(" ", "");
+    }
+}
+
+/// Complete the keys of a dictionary: "dict.at(|)", "dict.at("ke|")".
+fn complete_dict_keys(ctx: &mut CompletionContext) -> bool {
+    if_chain! {
+        if matches!(ctx.leaf.kind(), SyntaxKind::LeftParen | SyntaxKind::Str);
+        if let Some(args) = ctx.leaf.parent();
+        if args.kind() == SyntaxKind::Args;
+        if let Some(call) = args.parent();
+        if call.kind() == SyntaxKind::FuncCall;
+        if let Some(callee) = call.children().next();
+        if let Some(access) = callee.cast::<ast::FieldAccess>();
+        if access.field().as_str() == "at";
+        if let Some(target) = callee.children().next();
+        if let Some(Value::Dict(dict)) = analyze_expr(ctx.world, &target).into_iter().next();
+        then {
+            if ctx.leaf.kind() == SyntaxKind::Str {
+                ctx.from = ctx.leaf.offset();
+            }
+
+            for (key, value) in dict.iter() {
+                ctx.value_completion(
+                    None,
+                    &Value::Str(key.clone()),
+                    false,
+                    Some(value.repr().as_str()),
+                );
+            }
+            return true;
+        }
+    }
+
+    false
+}
+
 /// Complete in code mode.
 fn complete_code(ctx: &mut CompletionContext) -> bool {
     if matches!(
@@ -874,6 +982,12 @@ fn code_completions(ctx: &mut CompletionContext, hashtag: bool) {
         "Imports variables from another file.",
     );
 
+    ctx.snippet_completion(
+        "import (renamed)",
+        "import \"${file}.typ\" as ${name}",
+        "Imports a module from another file under a new name.",
+    );
+
     ctx.snippet_completion(
         "import (package)",
         "import \"@${}\": ${items}",
@@ -1156,17 +1270,33 @@ impl<'a> CompletionContext<'a> {
     /// Add completions for definitions that are available at the cursor.
     /// Filters the global/math scope with the given filter.
     fn scope_completions(&mut self, parens: bool, filter: impl Fn(&Value) -> bool) {
-        let mut defined = BTreeSet::new();
+        // Maps the names of local bindings to the signatures of the functions
+        // they define, if any.
+        let mut defined = BTreeMap::new();
+        let mut imported = vec![];
+        let mut seen = HashSet::new();
 
         let mut ancestor = Some(self.leaf.clone());
         while let Some(node) = &ancestor {
             let mut sibling = Some(node.clone());
             while let Some(node) = &sibling {
                 if let Some(v) = node.cast::<ast::LetBinding>() {
+                    let signature = match v.init() {
+                        Some(ast::Expr::Closure(closure)) => {
+                            Some(closure.params().as_untyped().clone().into_text())
+                        }
+                        _ => None,
+                    };
+
                     for ident in v.kind().idents() {
-                        defined.insert(ident.take());
+                        defined.entry(ident.take()).or_insert_with(|| signature.clone());
                     }
                 }
+
+                if let Some(v) = node.cast::<ast::ModuleImport>() {
+                    imported.push(v);
+                }
+
                 sibling = node.prev_sibling();
             }
 
@@ -1175,7 +1305,7 @@ impl<'a> CompletionContext<'a> {
                     if node.prev_sibling_kind() != Some(SyntaxKind::In) {
                         let pattern = v.pattern();
                         for ident in pattern.idents() {
-                            defined.insert(ident.take());
+                            defined.entry(ident.take()).or_insert(None);
                         }
                     }
                 }
@@ -1187,6 +1317,47 @@ impl<'a> CompletionContext<'a> {
             break;
         }
 
+        // Add the items of imported modules.
+        for import in imported {
+            let ast::Expr::Str(path) = import.source() else { continue };
+            let Some(module) = analyze_import(self.world, self.source, &path.get())
+            else {
+                continue;
+            };
+
+            let scope = module.scope();
+            match import.imports() {
+                Some(ast::Imports::Wildcard) => {
+                    for (name, value) in scope.iter() {
+                        if defined.contains_key(name) || !seen.insert(name.clone()) {
+                            continue;
+                        }
+                        if filter(value) {
+                            self.value_completion(
+                                Some(name.clone()),
+                                value,
+                                parens,
+                                None,
+                            );
+                        }
+                    }
+                }
+                Some(ast::Imports::Items(items)) => {
+                    for ident in items {
+                        let name = ident.take();
+                        let Some(value) = scope.get(&name) else { continue };
+                        if defined.contains_key(&name) || !seen.insert(name.clone()) {
+                            continue;
+                        }
+                        if filter(value) {
+                            self.value_completion(Some(name), value, parens, None);
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+
         let in_math = matches!(
             self.leaf.parent_kind(),
             Some(SyntaxKind::Equation)
@@ -1197,19 +1368,29 @@ impl<'a> CompletionContext<'a> {
 
         let scope = if in_math { self.math } else { self.global };
         for (name, value) in scope.iter() {
-            if filter(value) && !defined.contains(name) {
+            if filter(value) && !defined.contains_key(name) && !seen.contains(name) {
                 self.value_completion(Some(name.clone()), value, parens, None);
             }
         }
 
-        for name in defined {
-            if !name.is_empty() {
-                self.completions.push(Completion {
+        for (name, signature) in defined {
+            if name.is_empty() {
+                continue;
+            }
+
+            match signature {
+                Some(signature) => self.completions.push(Completion {
+                    kind: CompletionKind::Func,
+                    apply: parens.then(|| eco_format!("{name}(${{}})")),
+                    label: name,
+                    detail: Some(signature),
+                }),
+                None => self.completions.push(Completion {
                     kind: CompletionKind::Constant,
                     label: name,
                     apply: None,
                     detail: None,
-                });
+                }),
             }
         }
     }
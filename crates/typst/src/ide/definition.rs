@@ -0,0 +1,466 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use ecow::EcoString;
+
+use crate::syntax::ast::{self, AstNode};
+use crate::syntax::{is_ident, FileId, LinkedNode, Source, Span, SyntaxKind};
+use crate::World;
+
+/// Loads the source file with the given id.
+type Loader<'a> = dyn Fn(FileId) -> Option<Source> + 'a;
+
+/// Find the definition of the identifier at the cursor.
+///
+/// Returns the span of the identifier that introduces the binding. This lies in
+/// another file if the binding was imported from there. For a module that is
+/// imported without a new name, this is the span of the import's path.
+pub fn definition(world: &dyn World, source: &Source, cursor: usize) -> Option<Span> {
+    find_definition(&|id| world.source(id).ok(), source, cursor)
+}
+
+/// Find the definition of the identifier at the cursor, loading other files
+/// with `load`.
+fn find_definition(load: &Loader, source: &Source, cursor: usize) -> Option<Span> {
+    let root = LinkedNode::new(source.root());
+    let ident = ident_at(&root, cursor)?;
+    resolve(load, source, &ident)
+}
+
+/// An edit that renames one occurrence of a binding.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenameEdit {
+    /// The file the occurrence is in.
+    pub id: FileId,
+    /// The byte range of the occurrence in the file.
+    pub range: Range<usize>,
+    /// The text to replace the occurrence with.
+    pub text: EcoString,
+}
+
+/// Compute the edits that rename the binding referenced at the cursor.
+///
+/// The edits cover the definition and all references to it in the files of
+/// the project, that is the main file, the given source file, the file that
+/// contains the definition and all files they import or include. Returns
+/// `None` if there is no binding at the cursor, if it can't be renamed (like
+/// a module named after its file), or if the new name is not a valid
+/// identifier.
+pub fn rename(
+    world: &dyn World,
+    source: &Source,
+    cursor: usize,
+    new_name: &str,
+) -> Option<Vec<RenameEdit>> {
+    rename_in(&|id| world.source(id).ok(), &world.main(), source, cursor, new_name)
+}
+
+/// Compute the rename edits, loading other files with `load`.
+fn rename_in(
+    load: &Loader,
+    main: &Source,
+    source: &Source,
+    cursor: usize,
+    new_name: &str,
+) -> Option<Vec<RenameEdit>> {
+    if !is_ident(new_name) {
+        return None;
+    }
+
+    let target = find_definition(load, source, cursor)?;
+    let defining = load(target.id())?;
+    let node = defining.find(target)?;
+    if !matches!(node.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent) {
+        return None;
+    }
+
+    let name = node.get().text().clone();
+    let mut edits = vec![];
+    for source in project_sources(load, [main.clone(), source.clone(), defining]) {
+        let root = LinkedNode::new(source.root());
+        collect_references(load, &source, &root, target, &name, &mut |node| {
+            edits.push(RenameEdit {
+                id: source.id(),
+                range: node.range(),
+                text: new_name.into(),
+            });
+        });
+    }
+
+    Some(edits)
+}
+
+/// The given source files and all files they transitively import or include.
+fn project_sources(load: &Loader, roots: [Source; 3]) -> Vec<Source> {
+    let mut seen = HashSet::new();
+    let mut sources = vec![];
+    let mut queue = roots.to_vec();
+    while let Some(source) = queue.pop() {
+        if !seen.insert(source.id()) {
+            continue;
+        }
+
+        let mut paths = vec![];
+        collect_paths(&LinkedNode::new(source.root()), &mut paths);
+        queue.extend(paths.iter().filter_map(|path| {
+            let id = source.id().join(path).ok()?;
+            (!seen.contains(&id)).then(|| load(id)).flatten()
+        }));
+
+        sources.push(source);
+    }
+    sources
+}
+
+/// Collect the paths of all imports and includes in a node.
+fn collect_paths(node: &LinkedNode, paths: &mut Vec<EcoString>) {
+    let path = if let Some(import) = node.cast::<ast::ModuleImport>() {
+        Some(import.source())
+    } else {
+        node.cast::<ast::ModuleInclude>().map(|include| include.source())
+    };
+
+    if let Some(ast::Expr::Str(path)) = path {
+        paths.push(path.get());
+    }
+
+    for child in node.children() {
+        collect_paths(&child, paths);
+    }
+}
+
+/// Find the identifier at or directly in front of the cursor.
+fn ident_at<'a>(root: &LinkedNode<'a>, cursor: usize) -> Option<LinkedNode<'a>> {
+    [cursor, cursor + 1]
+        .into_iter()
+        .filter_map(|cursor| root.leaf_at(cursor))
+        .find(|leaf| matches!(leaf.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent))
+}
+
+/// Call `f` for every identifier in `node` that resolves to `target`.
+///
+/// Only identifiers with the target's name are resolved, as a binding can't
+/// be referenced under another name.
+fn collect_references(
+    load: &Loader,
+    source: &Source,
+    node: &LinkedNode,
+    target: Span,
+    name: &str,
+    f: &mut impl FnMut(&LinkedNode),
+) {
+    if matches!(node.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent) {
+        if node.get().text() == name && resolve(load, source, node) == Some(target) {
+            f(node);
+        }
+        return;
+    }
+
+    for child in node.children() {
+        collect_references(load, source, &child, target, name, f);
+    }
+}
+
+/// Resolve an identifier to the identifier that introduces its binding.
+fn resolve(load: &Loader, source: &Source, ident: &LinkedNode) -> Option<Span> {
+    let name = ident.get().text();
+
+    if let Some(parent) = ident.parent() {
+        // A field access into an imported module: "m.item".
+        if parent.kind() == SyntaxKind::FieldAccess && ident.index() > 0 {
+            let target = parent.children().next()?;
+            if target.kind() != SyntaxKind::Ident {
+                return None;
+            }
+
+            let span = resolve(load, source, &target)?;
+            let module = load(span.id())?;
+            let node = module.find(span)?;
+            let import = node.parent()?.cast::<ast::ModuleImport>()?;
+            return import_item(load, &module, &import, name);
+        }
+
+        // The name of a named argument or a dictionary key.
+        if parent.kind() == SyntaxKind::Named
+            && ident.index() == 0
+            && parent.parent_kind() != Some(SyntaxKind::Params)
+        {
+            return None;
+        }
+    }
+
+    introduced(load, source, ident).or_else(|| lookup(load, source, ident, name))
+}
+
+/// If the identifier itself introduces a binding, resolve it.
+///
+/// Imported items resolve to their definition in the imported file.
+fn introduced(load: &Loader, source: &Source, ident: &LinkedNode) -> Option<Span> {
+    let span = ident.span();
+    let mut node = ident.clone();
+    while let Some(parent) = node.parent() {
+        if let Some(binding) = parent.cast::<ast::LetBinding>() {
+            if binding.kind().idents().iter().any(|i| i.span() == span) {
+                return Some(span);
+            }
+        }
+
+        if let Some(for_loop) = parent.cast::<ast::ForLoop>() {
+            if for_loop.pattern().idents().iter().any(|i| i.span() == span) {
+                return Some(span);
+            }
+        }
+
+        if let Some(closure) = parent.cast::<ast::Closure>() {
+            if closure_idents(&closure).iter().any(|i| i.span() == span) {
+                return Some(span);
+            }
+        }
+
+        if let Some(import) = parent.cast::<ast::ModuleImport>() {
+            if import.new_name().map(|i| i.span()) == Some(span) {
+                return Some(span);
+            }
+
+            if node.kind() == SyntaxKind::ImportItems {
+                return import_item(load, source, &import, ident.get().text())
+                    .or(Some(span));
+            }
+        }
+
+        node = parent.clone();
+    }
+
+    None
+}
+
+/// Find the closest binding with the given name that is visible from the
+/// identifier.
+fn lookup(
+    load: &Loader,
+    source: &Source,
+    ident: &LinkedNode,
+    name: &str,
+) -> Option<Span> {
+    let mut ancestor = ident.clone();
+    loop {
+        let mut sibling = ancestor.prev_sibling();
+        while let Some(node) = sibling {
+            if let Some(binding) = node.cast::<ast::LetBinding>() {
+                if let Some(found) =
+                    binding.kind().idents().into_iter().find(|i| i.as_str() == name)
+                {
+                    return Some(found.span());
+                }
+            }
+
+            if let Some(import) = node.cast::<ast::ModuleImport>() {
+                if let Some(found) = import.new_name().filter(|i| i.as_str() == name) {
+                    return Some(found.span());
+                }
+
+                // A module imported without a new name is bound to the stem
+                // of its file name.
+                if import.new_name().is_none()
+                    && import.imports().is_none()
+                    && module_name(source, &import).as_deref() == Some(name)
+                {
+                    return Some(import.source().span());
+                }
+
+                match import.imports() {
+                    Some(ast::Imports::Items(items)) => {
+                        if let Some(item) = items.into_iter().find(|i| i.as_str() == name)
+                        {
+                            return import_item(load, source, &import, name)
+                                .or(Some(item.span()));
+                        }
+                    }
+                    Some(ast::Imports::Wildcard) => {
+                        if let Some(span) = import_item(load, source, &import, name) {
+                            return Some(span);
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            sibling = node.prev_sibling();
+        }
+
+        let parent = ancestor.parent()?.clone();
+        if let Some(for_loop) = parent.cast::<ast::ForLoop>() {
+            if ancestor.prev_sibling_kind() != Some(SyntaxKind::In) {
+                if let Some(found) =
+                    for_loop.pattern().idents().into_iter().find(|i| i.as_str() == name)
+                {
+                    return Some(found.span());
+                }
+            }
+        }
+
+        if let Some(closure) = parent.cast::<ast::Closure>() {
+            if closure.body().span() == ancestor.span() {
+                if let Some(found) =
+                    closure_idents(&closure).into_iter().find(|i| i.as_str() == name)
+                {
+                    return Some(found.span());
+                }
+            }
+        }
+
+        ancestor = parent;
+    }
+}
+
+/// The identifiers a closure binds in its body: its name and its parameters.
+fn closure_idents(closure: &ast::Closure) -> Vec<ast::Ident> {
+    let mut idents: Vec<_> = closure.name().into_iter().collect();
+    for param in closure.params().children() {
+        match param {
+            ast::Param::Pos(pattern) => idents.extend(pattern.idents()),
+            ast::Param::Named(named) => idents.push(named.name()),
+            ast::Param::Sink(spread) => idents.extend(spread.name()),
+        }
+    }
+    idents
+}
+
+/// The name a module import binds if it has no new name.
+fn module_name(source: &Source, import: &ast::ModuleImport) -> Option<String> {
+    let ast::Expr::Str(path) = import.source() else { return None };
+    let id = source.id().join(&path.get()).ok()?;
+    Some(id.path().file_stem()?.to_string_lossy().into())
+}
+
+/// Find the top-level definition of an item in an imported file.
+fn import_item(
+    load: &Loader,
+    source: &Source,
+    import: &ast::ModuleImport,
+    name: &str,
+) -> Option<Span> {
+    let ast::Expr::Str(path) = import.source() else { return None };
+    let id = source.id().join(&path.get()).ok()?;
+    let module = load(id)?;
+    let root = LinkedNode::new(module.root());
+
+    // The last top-level binding determines the module's item.
+    let found = root
+        .children()
+        .filter_map(|child| child.cast::<ast::LetBinding>())
+        .flat_map(|binding| binding.kind().idents())
+        .filter(|i| i.as_str() == name)
+        .last()?;
+
+    Some(found.span())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use super::*;
+
+    /// A set of source files with `/main.typ` as the main file.
+    struct Files(HashMap<FileId, Source>);
+
+    impl Files {
+        fn new(files: &[(&str, &str)]) -> Self {
+            Self(
+                files
+                    .iter()
+                    .map(|&(path, text)| {
+                        let id = FileId::new(None, Path::new(path));
+                        (id, Source::new(id, text.into()))
+                    })
+                    .collect(),
+            )
+        }
+
+        fn get(&self, path: &str) -> &Source {
+            &self.0[&FileId::new(None, Path::new(path))]
+        }
+
+        /// Rename the binding with the cursor after the first occurrence of
+        /// `needle` in a file and return the edited files with the start of
+        /// each edited range.
+        fn rename(&self, path: &str, needle: &str) -> Option<Vec<(String, usize)>> {
+            let load = |id| self.0.get(&id).cloned();
+            let source = self.get(path);
+            let cursor = source.text().find(needle).unwrap() + needle.len();
+            let edits = rename_in(&load, self.get("/main.typ"), source, cursor, "new")?;
+            let mut found: Vec<_> = edits
+                .into_iter()
+                .map(|edit| {
+                    assert_eq!(edit.text, "new");
+                    (edit.id.path().display().to_string(), edit.range.start)
+                })
+                .collect();
+            found.sort();
+            Some(found)
+        }
+    }
+
+    #[test]
+    fn test_rename_all_importers() {
+        let files = Files::new(&[
+            ("/main.typ", "#include \"b.typ\"\n#include \"sub/c.typ\""),
+            ("/a.typ", "#let item = 1"),
+            ("/b.typ", "#import \"a.typ\": item\n#item"),
+            ("/sub/c.typ", "#import \"../a.typ\"\n#a.item"),
+        ]);
+
+        let expected = vec![
+            ("/a.typ".into(), 5),
+            ("/b.typ".into(), 17),
+            ("/b.typ".into(), 23),
+            ("/sub/c.typ".into(), 22),
+        ];
+
+        // The result is the same no matter where the rename starts.
+        assert_eq!(files.rename("/a.typ", "it"), Some(expected.clone()));
+        assert_eq!(files.rename("/b.typ", "#it"), Some(expected.clone()));
+        assert_eq!(files.rename("/sub/c.typ", ".it"), Some(expected));
+    }
+
+    #[test]
+    fn test_rename_respects_scopes() {
+        let files = Files::new(&[(
+            "/main.typ",
+            "#let x = 1\n#let f(x) = x + 1\n#for x in (1, 2) [#x]\n#x",
+        )]);
+
+        let outer = Some(vec![("/main.typ".into(), 5), ("/main.typ".into(), 52)]);
+        assert_eq!(files.rename("/main.typ", "let x"), outer);
+        assert_eq!(
+            files.rename("/main.typ", "(x"),
+            Some(vec![("/main.typ".into(), 18), ("/main.typ".into(), 23)])
+        );
+    }
+
+    #[test]
+    fn test_rename_module_without_name() {
+        let files = Files::new(&[
+            ("/main.typ", "#import \"a.typ\"\n#a.item"),
+            ("/a.typ", "#let item = 1"),
+        ]);
+
+        // The module's name comes from its file and can't be renamed.
+        let source = files.get("/main.typ");
+        let load = |id| files.0.get(&id).cloned();
+        let module = find_definition(&load, source, 17).unwrap();
+        assert_eq!(source.find(module).unwrap().kind(), SyntaxKind::Str);
+        assert_eq!(files.rename("/main.typ", "#a"), None);
+        assert_eq!(files.rename("/main.typ", ".it").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_rename_invalid_name() {
+        let files = Files::new(&[("/main.typ", "#let x = 1")]);
+        let load = |id| files.0.get(&id).cloned();
+        let source = files.get("/main.typ");
+        assert_eq!(rename_in(&load, source, source, 5, "1x"), None);
+    }
+}
@@ -3,7 +3,7 @@ use std::num::NonZeroUsize;
 use ecow::EcoString;
 
 use crate::doc::{Destination, Frame, FrameItem, Meta, Position};
-use crate::geom::{Geometry, Point, Size};
+use crate::geom::{Abs, Geometry, Point, Size, Transform};
 use crate::model::Introspector;
 use crate::syntax::{FileId, LinkedNode, Source, Span, SyntaxKind};
 use crate::World;
@@ -162,6 +162,164 @@ fn find_in_frame(frame: &Frame, span: Span) -> Option<Point> {
     None
 }
 
+/// A region on a page whose content was produced by a piece of source code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRegion {
+    /// The page and top left corner of the region.
+    pub position: Position,
+    /// The size of the region.
+    pub size: Size,
+    /// The source code that produced the region and the byte offset of the
+    /// region's start within it, like the spans of glyphs.
+    pub span: (Span, u16),
+}
+
+impl SourceRegion {
+    /// The byte offset in the source file at which the region starts.
+    ///
+    /// For text, this is the position of the region's first character rather
+    /// than the start of the whole text node.
+    pub fn offset(&self, source: &Source) -> Option<usize> {
+        let (span, span_offset) = self.span;
+        let node = source.find(span)?;
+        Some(if node.kind() == SyntaxKind::Text {
+            let range = node.range();
+            (range.start + usize::from(span_offset)).min(range.end)
+        } else {
+            node.offset()
+        })
+    }
+}
+
+/// Map the content of all pages back to the source code that produced it.
+///
+/// Runs of glyphs from the same span are merged into one region. Regions of
+/// transformed content are the bounding boxes of their transformed
+/// rectangles. This allows PDF viewers and editors to jump between the
+/// document and the source without access to the compiler, similar to
+/// SyncTeX.
+pub fn source_regions(frames: &[Frame]) -> Vec<SourceRegion> {
+    let mut regions = vec![];
+    for (i, frame) in frames.iter().enumerate() {
+        let page = NonZeroUsize::new(i + 1).unwrap();
+        collect_regions(&mut regions, frame, page, Transform::identity());
+    }
+    regions
+}
+
+/// Collect the source regions of a frame with the given transformation to
+/// page coordinates.
+fn collect_regions(
+    regions: &mut Vec<SourceRegion>,
+    frame: &Frame,
+    page: NonZeroUsize,
+    ts: Transform,
+) {
+    for (pos, item) in frame.items() {
+        let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+        match item {
+            FrameItem::Group(group) => {
+                collect_regions(
+                    regions,
+                    &group.frame,
+                    page,
+                    ts.pre_concat(group.transform),
+                );
+            }
+
+            FrameItem::Text(text) => {
+                // Merge runs of glyphs before transforming them, so that
+                // rotated text also results in one region per run.
+                let mut runs: Vec<(Abs, Abs, (Span, u16))> = vec![];
+                let mut x = Abs::zero();
+                for glyph in &text.glyphs {
+                    let width = glyph.x_advance.at(text.size);
+                    match runs.last_mut() {
+                        Some((_, run_width, span)) if span.0 == glyph.span.0 => {
+                            *run_width += width;
+                            span.1 = span.1.min(glyph.span.1);
+                        }
+                        _ => runs.push((x, width, glyph.span)),
+                    }
+                    x += width;
+                }
+
+                for (start, width, span) in runs {
+                    let origin = Point::new(start, -text.size);
+                    let size = Size::new(width, text.size);
+                    push_region(regions, page, ts, origin, size, span);
+                }
+            }
+
+            FrameItem::Shape(shape, span) => {
+                if let Geometry::Rect(size) = shape.geometry {
+                    push_region(regions, page, ts, Point::zero(), size, (*span, 0));
+                }
+            }
+
+            FrameItem::Image(_, size, span) => {
+                push_region(regions, page, ts, Point::zero(), *size, (*span, 0))
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Add the region of a transformed rectangle unless it has no source code.
+///
+/// Extends the previous region instead if it was produced by the same span
+/// and the new one continues it on the same line.
+fn push_region(
+    regions: &mut Vec<SourceRegion>,
+    page: NonZeroUsize,
+    ts: Transform,
+    origin: Point,
+    size: Size,
+    span: (Span, u16),
+) {
+    if span.0.is_detached() {
+        return;
+    }
+
+    let (point, size) = bounding_box(ts, origin, size);
+    match regions.last_mut() {
+        Some(last)
+            if last.span.0 == span.0
+                && last.position.page == page
+                && last.position.point.y.approx_eq(point.y)
+                && last.size.y.approx_eq(size.y)
+                && (last.position.point.x + last.size.x).approx_eq(point.x) =>
+        {
+            last.size.x += size.x;
+            last.span.1 = last.span.1.min(span.1);
+        }
+        _ => {
+            regions.push(SourceRegion { position: Position { page, point }, size, span })
+        }
+    }
+}
+
+/// The axis-aligned bounding box of a transformed rectangle.
+fn bounding_box(ts: Transform, origin: Point, size: Size) -> (Point, Size) {
+    let corners = [
+        origin,
+        origin + Point::with_x(size.x),
+        origin + Point::with_y(size.y),
+        origin + size.to_point(),
+    ]
+    .map(|corner| corner.transform(ts));
+
+    let mut min = corners[0];
+    let mut max = corners[0];
+    for corner in &corners[1..] {
+        min = min.min(*corner);
+        max = max.max(*corner);
+    }
+
+    (min, (max - min).to_size())
+}
+
 /// Whether a rectangle with the given size at the given position contains the
 /// click position.
 fn is_in_rect(pos: Point, size: Size, click: Point) -> bool {
@@ -170,3 +328,69 @@ fn is_in_rect(pos: Point, size: Size, click: Point) -> bool {
         && pos.y <= click.y
         && pos.y + size.y >= click.y
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::doc::{Glyph, GroupItem, Lang, TextItem};
+    use crate::font::Font;
+    use crate::geom::{Angle, Color, Em};
+
+    /// A frame with the glyphs of the given text range of a source, rotated
+    /// by a quarter turn.
+    fn rotated(span: Span, text: &str, start: u16) -> Frame {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../assets/fonts/IBMPlexSans-Regular.ttf"
+        );
+        let font = Font::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+        let glyphs = (0..text.len() as u16)
+            .map(|i| Glyph {
+                id: 0,
+                x_advance: Em::new(0.5),
+                x_offset: Em::zero(),
+                range: i..i + 1,
+                span: (span, start + i),
+            })
+            .collect();
+
+        let mut inner = Frame::new(Size::splat(Abs::pt(50.0)));
+        let item = TextItem {
+            font,
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            lang: Lang::ENGLISH,
+            region: None,
+            text: text.into(),
+            glyphs,
+        };
+        inner.push(Point::new(Abs::pt(10.0), Abs::pt(20.0)), FrameItem::Text(item));
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::rotate(Angle::deg(90.0));
+        let mut frame = Frame::new(Size::splat(Abs::pt(100.0)));
+        frame.push(Point::with_x(Abs::pt(50.0)), FrameItem::Group(group));
+        frame
+    }
+
+    #[test]
+    fn test_source_regions_transformed() {
+        let id = FileId::new(None, Path::new("/main.typ"));
+        let source = Source::new(id, "Hello".into());
+        let span = source.root().children().next().unwrap().span();
+        let regions = source_regions(&[rotated(span, "llo", 2)]);
+        assert_eq!(regions.len(), 1);
+
+        // The glyphs span 10pt to 25pt horizontally and 10pt to 20pt
+        // vertically before the rotation.
+        let region = &regions[0];
+        let point = region.position.point;
+        assert!(point.x.approx_eq(Abs::pt(30.0)));
+        assert!(point.y.approx_eq(Abs::pt(10.0)));
+        assert!(region.size.x.approx_eq(Abs::pt(10.0)));
+        assert!(region.size.y.approx_eq(Abs::pt(15.0)));
+        assert_eq!(region.offset(&source), Some(2));
+    }
+}
@@ -2,14 +2,18 @@
 
 mod analyze;
 mod complete;
+mod definition;
 mod highlight;
 mod jump;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::{autocomplete, Completion, CompletionKind};
+pub use self::definition::{definition, rename, RenameEdit};
 pub use self::highlight::{highlight, highlight_html, Tag};
-pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
+pub use self::jump::{
+    jump_from_click, jump_from_cursor, source_regions, Jump, SourceRegion,
+};
 pub use self::tooltip::{tooltip, Tooltip};
 
 use std::fmt::Write;
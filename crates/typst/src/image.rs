@@ -12,7 +12,7 @@ use image::codecs::gif::GifDecoder;
 use image::codecs::jpeg::JpegDecoder;
 use image::codecs::png::PngDecoder;
 use image::io::Limits;
-use image::{ImageDecoder, ImageResult};
+use image::{AnimationDecoder, ImageDecoder, ImageResult};
 use usvg::{TreeParsing, TreeTextToPath};
 
 use crate::diag::{format_xml_like_error, StrResult};
@@ -52,20 +52,14 @@ impl Image {
         alt: Option<EcoString>,
     ) -> StrResult<Self> {
         let loader = PreparedLoader::default();
-        let decoded = match format {
-            ImageFormat::Raster(format) => decode_raster(&data, format)?,
+        let size = match format {
+            ImageFormat::Raster(format) => decode_raster(&data, format)?.size(),
             ImageFormat::Vector(VectorFormat::Svg) => {
-                decode_svg(&data, (&loader as &dyn SvgFontLoader).track())?
+                decode_svg(&data, (&loader as &dyn SvgFontLoader).track())?.size()
             }
         };
 
-        Ok(Self(Arc::new(Prehashed::new(Repr {
-            data,
-            format,
-            size: decoded.size(),
-            loader,
-            alt,
-        }))))
+        Ok(Self(Arc::new(Prehashed::new(Repr { data, format, size, loader, alt }))))
     }
 
     /// Create a font-dependant image from a buffer and a format.
@@ -78,17 +72,17 @@ impl Image {
         alt: Option<EcoString>,
     ) -> StrResult<Self> {
         let loader = WorldLoader::new(world, fallback_family);
-        let decoded = match format {
-            ImageFormat::Raster(format) => decode_raster(&data, format)?,
+        let size = match format {
+            ImageFormat::Raster(format) => decode_raster(&data, format)?.size(),
             ImageFormat::Vector(VectorFormat::Svg) => {
-                decode_svg(&data, (&loader as &dyn SvgFontLoader).track())?
+                decode_svg(&data, (&loader as &dyn SvgFontLoader).track())?.size()
             }
         };
 
         Ok(Self(Arc::new(Prehashed::new(Repr {
             data,
             format,
-            size: decoded.size(),
+            size,
             loader: loader.into_prepared(),
             alt,
         }))))
@@ -124,7 +118,18 @@ impl Image {
         self.0.alt.as_deref()
     }
 
+    /// Whether the image has multiple frames. Only the first one is shown.
+    pub fn is_animated(&self) -> bool {
+        self.format() == ImageFormat::Raster(RasterFormat::Gif)
+            && is_animated_gif(self.data())
+    }
+
     /// The decoded version of the image.
+    ///
+    /// The image is decoded when it is created, so that broken data is
+    /// reported right away. The decoded pixels are cached by the image data,
+    /// so that an image used many times, or across compilations, is only
+    /// decoded once.
     pub fn decoded(&self) -> Arc<DecodedImage> {
         match self.format() {
             ImageFormat::Raster(format) => decode_raster(self.data(), format),
@@ -234,6 +239,7 @@ pub struct IccProfile(pub Vec<u8>);
 
 /// Decode a raster image.
 #[comemo::memoize]
+#[tracing::instrument(skip_all)]
 fn decode_raster(data: &Bytes, format: RasterFormat) -> StrResult<Arc<DecodedImage>> {
     fn decode_with<'a, T: ImageDecoder<'a>>(
         decoder: ImageResult<T>,
@@ -256,6 +262,13 @@ fn decode_raster(data: &Bytes, format: RasterFormat) -> StrResult<Arc<DecodedIma
     Ok(Arc::new(DecodedImage::Raster(dynamic, icc, format)))
 }
 
+/// Whether a GIF image has more than one frame.
+#[comemo::memoize]
+fn is_animated_gif(data: &Bytes) -> bool {
+    GifDecoder::new(io::Cursor::new(data))
+        .is_ok_and(|decoder| decoder.into_frames().take(2).count() > 1)
+}
+
 /// Decode an SVG image.
 #[comemo::memoize]
 fn decode_svg(
@@ -68,6 +68,13 @@ use crate::util::Bytes;
 /// Compile a source file into a fully layouted document.
 #[tracing::instrument(skip_all)]
 pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
+    let result = typeset(world, tracer);
+    tracer.apply_suppressions(world);
+    result
+}
+
+/// Evaluate and typeset the main source file.
+fn typeset(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
     let route = Route::default();
 
     // Call `track` just once to keep comemo's ID stable.
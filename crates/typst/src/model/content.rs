@@ -5,6 +5,7 @@ use std::ops::{Add, AddAssign};
 
 use comemo::Prehashed;
 use ecow::{eco_format, EcoString, EcoVec};
+use serde::{Serialize, Serializer};
 
 use super::{
     element, Behave, Behaviour, ElemFunc, Element, Guard, Label, Locatable, Location,
@@ -364,6 +365,16 @@ impl Content {
         self.attrs.push(Attr::Location(location));
     }
 
+    /// Replace the location of this content, if it has one.
+    pub fn replace_location(&mut self, location: Location) {
+        for attr in self.attrs.make_mut() {
+            if let Attr::Location(prev) = attr {
+                *prev = location;
+                return;
+            }
+        }
+    }
+
     /// Queries the content tree for all elements that match the given selector.
     ///
     /// Elements produced in `show` rules will not be included in the results.
@@ -405,7 +416,7 @@ impl Content {
     }
 
     /// Traverse this content.
-    fn traverse<'a, F>(&'a self, f: &mut F)
+    pub(crate) fn traverse<'a, F>(&'a self, f: &mut F)
     where
         F: FnMut(&'a Content),
     {
@@ -437,6 +448,16 @@ impl Content {
     }
 }
 
+impl Serialize for Content {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        static FUNC: EcoString = EcoString::inline("func");
+        serializer.collect_map(
+            std::iter::once((&FUNC, self.func().name().into_value()))
+                .chain(self.fields()),
+        )
+    }
+}
+
 impl Debug for Content {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let name = self.func.name();
@@ -14,7 +14,7 @@ use crate::doc::{Frame, FrameItem, Meta, Position};
 use crate::eval::{cast, Value};
 use crate::geom::{Point, Transform};
 use crate::model::Label;
-use crate::util::NonZeroExt;
+use crate::util::{hash128, NonZeroExt};
 
 /// Identifies the location of an element in the document.
 ///
@@ -38,6 +38,12 @@ impl Location {
         self.variant = n;
         self
     }
+
+    /// Produce a location that is unique to the `index`-th of multiple
+    /// documents that are merged into one.
+    pub fn in_document(self, index: usize) -> Self {
+        Self { hash: hash128(&(self.hash, index)), ..self }
+    }
 }
 
 impl Debug for Location {
@@ -294,6 +300,14 @@ impl Introspector {
                 }
                 list
             }
+            Selector::Within { selector, start, end } => self
+                .query(selector)
+                .into_iter()
+                .filter(|elem| {
+                    elem.location()
+                        .is_some_and(|loc| (*start..=*end).contains(&self.page(loc)))
+                })
+                .collect(),
         };
 
         self.queries.borrow_mut().insert(hash, output.clone());
@@ -18,7 +18,7 @@ pub use self::label::{Label, Unlabellable};
 pub use self::realize::{
     applicable, realize, Behave, Behaviour, Finalize, Guard, Locatable, Show, Synthesize,
 };
-pub use self::selector::{LocatableSelector, Selector, ShowableSelector};
+pub use self::selector::{LocatableSelector, PageRange, Selector, ShowableSelector};
 pub use self::styles::{
     Fold, Property, Recipe, Resolve, Style, StyleChain, StyleVec, StyleVecBuilder,
     Styles, Transform,
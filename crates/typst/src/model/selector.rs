@@ -1,5 +1,6 @@
 use std::any::{Any, TypeId};
 use std::fmt::{self, Debug, Formatter, Write};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use ecow::{eco_format, EcoString, EcoVec};
@@ -7,7 +8,7 @@ use ecow::{eco_format, EcoString, EcoVec};
 use super::{Content, ElemFunc, Label, Location};
 use crate::diag::{bail, StrResult};
 use crate::eval::{
-    cast, CastInfo, Dict, FromValue, Func, IntoValue, Reflect, Regex, Value,
+    cast, Array, CastInfo, Dict, FromValue, Func, IntoValue, Reflect, Regex, Value,
 };
 use crate::model::Locatable;
 use crate::util::pretty_array_like;
@@ -36,6 +37,9 @@ pub enum Selector {
     Before { selector: Arc<Self>, end: Arc<Self>, inclusive: bool },
     /// Matches all matches of `selector` after `start`.
     After { selector: Arc<Self>, start: Arc<Self>, inclusive: bool },
+    /// Matches all matches of `selector` on the pages from `start` to `end`
+    /// (inclusive).
+    Within { selector: Arc<Self>, start: NonZeroUsize, end: NonZeroUsize },
 }
 
 impl Selector {
@@ -79,6 +83,11 @@ impl Selector {
         }
     }
 
+    /// Transforms this selector into a [`Selector::Within`] selector.
+    pub fn within(self, start: NonZeroUsize, end: NonZeroUsize) -> Self {
+        Self::Within { selector: Arc::new(self), start, end }
+    }
+
     /// Whether the selector matches for the target.
     pub fn matches(&self, target: &Content) -> bool {
         match self {
@@ -99,7 +108,7 @@ impl Selector {
             Self::And(selectors) => selectors.iter().all(move |sel| sel.matches(target)),
             Self::Location(location) => target.location() == Some(*location),
             // Not supported here.
-            Self::Before { .. } | Self::After { .. } => false,
+            Self::Before { .. } | Self::After { .. } | Self::Within { .. } => false,
         }
     }
 }
@@ -147,6 +156,14 @@ impl Debug for Selector {
                 }
                 f.write_char(')')
             }
+            Self::Within { selector, start, end } => {
+                selector.fmt(f)?;
+                if start == end {
+                    write!(f, ".within(page: {start})")
+                } else {
+                    write!(f, ".within(page: ({start}, {end}))")
+                }
+            }
         }
     }
 }
@@ -163,6 +180,27 @@ cast! {
     location: Location => Self::Location(location),
 }
 
+/// A range of pages, given as a single page number or as an array with the
+/// first and last page.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PageRange(pub NonZeroUsize, pub NonZeroUsize);
+
+cast! {
+    PageRange,
+    page: NonZeroUsize => Self(page, page),
+    pages: Array => match pages.as_slice() {
+        [start, end] => {
+            let start: NonZeroUsize = start.clone().cast()?;
+            let end: NonZeroUsize = end.clone().cast()?;
+            if end < start {
+                bail!("page range must not end before it starts");
+            }
+            Self(start, end)
+        }
+        _ => bail!("page range must consist of exactly two page numbers"),
+    },
+}
+
 /// A selector that can be used with `query`.
 ///
 /// Hopefully, this is made obsolete by a more powerful query mechanism in the
@@ -214,6 +252,7 @@ impl FromValue for LocatableSelector {
                         validate(selector)?;
                     }
                 }
+                Selector::Within { selector, .. } => validate(selector)?,
             }
             Ok(())
         }
@@ -278,7 +317,8 @@ impl FromValue for ShowableSelector {
                 | Selector::Location(_)
                 | Selector::Can(_)
                 | Selector::Before { .. }
-                | Selector::After { .. } => {
+                | Selector::After { .. }
+                | Selector::Within { .. } => {
                     bail!("this selector cannot be used with show")
                 }
             }
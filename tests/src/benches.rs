@@ -20,6 +20,7 @@ main!(
     bench_eval,
     bench_typeset,
     bench_compile,
+    bench_recompile,
     bench_render,
 );
 
@@ -87,6 +88,18 @@ fn bench_compile(iai: &mut Iai) {
     iai.run(|| typst::compile(&world, &mut tracer));
 }
 
+fn bench_recompile(iai: &mut Iai) {
+    // Only the last of multiple runs of pages changes, so the others can be
+    // reused from the first compilation.
+    let mut world = BenchWorld::new();
+    world.source = Source::detached([TEXT; 3].join("\n#pagebreak()\n"));
+    let mut tracer = Tracer::default();
+    typst::compile(&world, &mut tracer).unwrap();
+    let end = world.source.len_bytes();
+    world.source.edit(end..end, "Changed.");
+    iai.run(|| typst::compile(&world, &mut tracer));
+}
+
 fn bench_render(iai: &mut Iai) {
     let world = BenchWorld::new();
     let mut tracer = Tracer::default();
@@ -23,6 +23,7 @@ use walkdir::WalkDir;
 use typst::diag::{bail, FileError, FileResult, Severity, StrResult};
 use typst::doc::{Document, Frame, FrameItem, Meta};
 use typst::eval::{eco_format, func, Datetime, Library, NoneValue, Tracer, Value};
+use typst::export::PdfOptions;
 use typst::font::{Font, FontBook};
 use typst::geom::{Abs, Color, RgbaColor, Smart};
 use typst::syntax::{FileId, Source, Span, SyntaxNode};
@@ -523,7 +524,14 @@ fn test_part(
     let mut tracer = Tracer::default();
 
     let (mut frames, diagnostics) = match typst::compile(world, &mut tracer) {
-        Ok(document) => (document.pages, tracer.warnings()),
+        Ok(document) => {
+            let mut warnings = tracer.warnings();
+            if metadata.part_configuration.accessible.unwrap_or(false) {
+                let options = PdfOptions { accessible: true, ..Default::default() };
+                warnings.extend(typst::export::pdf_with_options(&document, &options).1);
+            }
+            (document.pages, warnings)
+        }
         Err(errors) => {
             let mut warnings = tracer.warnings();
             warnings.extend(*errors);
@@ -624,6 +632,7 @@ fn print_user_output(
 struct TestConfiguration {
     compare_ref: Option<bool>,
     validate_hints: Option<bool>,
+    accessible: Option<bool>,
 }
 
 struct TestPartMetadata {
@@ -663,12 +672,14 @@ impl UserOutput {
 fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     let mut compare_ref = None;
     let mut validate_hints = None;
+    let mut accessible = None;
     let mut expectations = HashSet::default();
 
     let lines: Vec<_> = source.text().lines().map(str::trim).collect();
     for (i, line) in lines.iter().enumerate() {
         compare_ref = get_flag_metadata(line, "Ref").or(compare_ref);
         validate_hints = get_flag_metadata(line, "Hints").or(validate_hints);
+        accessible = get_flag_metadata(line, "Accessible").or(accessible);
 
         fn num(s: &mut Scanner) -> usize {
             s.eat_while(char::is_numeric).parse().unwrap()
@@ -708,7 +719,7 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     }
 
     TestPartMetadata {
-        part_configuration: TestConfiguration { compare_ref, validate_hints },
+        part_configuration: TestConfiguration { compare_ref, validate_hints, accessible },
         invariants: expectations,
     }
 }
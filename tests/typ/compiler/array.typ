@@ -166,6 +166,26 @@
 // Test the `fold` method.
 #test(().fold("hi", grid), "hi")
 #test((1, 2, 3, 4).fold(0, (s, x) => s + x), 10)
+#test((1, 2, 3, 4).fold(0, (s, x) => s + x, until: s => s >= 3), 3)
+#test((1, 2).fold(0, (s, x) => s + x, until: s => false), 3)
+
+---
+// Test the `find-map` method.
+#test(().find-map(x => x), none)
+#test((1, 2, 3).find-map(x => if x > 1 { x * 10 }), 20)
+#test((1, 2).find-map(x => none), none)
+
+---
+// Test the `take-while` and `skip-while` methods.
+#test(().take-while(x => true), ())
+#test((1, 2, 5, 1).take-while(x => x < 3), (1, 2))
+#test((1, 2, 5, 1).skip-while(x => x < 3), (5, 1))
+#test((1, 2).take-while(x => x < 3), (1, 2))
+#test((1, 2).skip-while(x => x < 3), ())
+
+---
+// Error: 20-21 expected boolean, found integer
+#(1, 2).take-while(x => x + 1)
 
 ---
 // Error: 20-22 unexpected argument
@@ -254,6 +274,30 @@
 #test(("Hello", "World", "Hi", "There").dedup(key: x => x.len()), ("Hello", "Hi"))
 #test(("Hello", "World", "Hi", "There").dedup(key: x => x.at(0)), ("Hello", "World", "There"))
 
+---
+// Test the `chunks` and `windows` methods.
+#test(().chunks(2), ())
+#test((1, 2, 3, 4, 5).chunks(2), ((1, 2), (3, 4), (5,)))
+#test((1, 2, 3, 4, 5).chunks(2, exact: true), ((1, 2), (3, 4)))
+#test((1, 2, 3).windows(2), ((1, 2), (2, 3)))
+#test((1, 2).windows(3), ())
+
+---
+// Test the `group-by` method.
+#test(().group-by(x => x), ())
+#test(
+  (1, 2, 3, 4, 5).group-by(calc.odd),
+  ((true, (1, 3, 5)), (false, (2, 4))),
+)
+#test(
+  ("Alice", "Bob", "Anna").group-by(name => name.first()),
+  (("A", ("Alice", "Anna")), ("B", ("Bob",))),
+)
+
+---
+// Error: 16-17 number must be positive
+#(1, 2).chunks(0)
+
 ---
 // Error: 32-37 cannot divide by zero
 #(1, 2, 0, 3).sorted(key: x => 5 / x)
@@ -128,6 +128,7 @@
   let f(x) = x + 1
 
   // Error: 8-13 unexpected argument
+  // Error: 15-22 unexpected argument
   f(1, "two", () => x)
 }
 
@@ -99,3 +99,30 @@
   // Error: 3-9 expected dictionary, found none
   object.property = "value"
 }
+
+---
+// Test merging dictionaries.
+#let base = (a: 1, b: (c: 2, d: 3))
+#test(base.merge((a: 5)), (a: 5, b: (c: 2, d: 3)))
+#test(base.merge((b: (c: 4))), (a: 1, b: (c: 4)))
+#test(base.merge((b: (c: 4)), deep: true), (a: 1, b: (c: 4, d: 3)))
+#test(base.merge((b: 4), deep: true), (a: 1, b: 4))
+#test(base.merge((e: 6)).keys(), ("a", "b", "e"))
+#test((:).merge(base), base)
+
+---
+// Test constructing dictionaries from pairs.
+#let dict = (a: 1, b: "two", c: (3,))
+#test(dictionary.from-pairs(dict.pairs()), dict)
+#test(dictionary(dict.pairs()), dict)
+#test(dictionary(dict), dict)
+#test(dictionary.from-pairs(()), (:))
+#test(dictionary.from-pairs((("a", 1), ("a", 2))), (a: 2))
+
+---
+// Error: 23-31 expected pair, found integer
+#dictionary.from-pairs((1, 2))
+
+---
+// Error: 13-27 pair must consist of exactly two items
+#dictionary((("a", 1, 2),))
@@ -37,3 +37,32 @@ Can not be used as @intro
 
 // Error: 1:20-1:26 cannot reference heading without numbering
 Can not be used as @intro
+
+---
+// Error: 2-6 unknown variable: rgbb
+// Hint: 2-6 did you mean `rgb`?
+#rgbb
+
+---
+// Error: 7-15 unexpected argument: fil
+// Hint: 7-15 did you mean `fill`?
+#text(fil: red)[A]
+
+---
+#let greet(name, birthday: false) = name
+
+// Error: 17-30 unexpected argument: birthdy
+// Hint: 17-30 did you mean `birthday`?
+#greet("Typst", birthdy: true)
+
+---
+// Error: 13-17 dictionary does not contain key "widt" and no default value was specified
+// Hint: 13-17 did you mean `width`?
+#(width: 1).widt
+
+---
+// Error: 7-15 unexpected argument: fil
+// Hint: 7-15 did you mean `fill`?
+// Error: 17-26 unexpected argument: sise
+// Hint: 17-26 did you mean `size`?
+#text(fil: red, sise: 2pt)[A]
@@ -66,6 +66,35 @@
 // Error: 2-11 cannot mutate a temporary value
 #(module,).at(0).push()
 
+---
+// Renaming a module import.
+#import "module.typ" as other
+#test(other.b, 1)
+#test(other.item(1, 2), 3)
+
+// Renaming and importing items at the same time.
+#import "module.typ" as mod: c
+#test(mod.c, c)
+
+// Renaming a function scope.
+#import enum as e
+#e(e.item(1)[First])
+
+---
+// Dynamic and conditional imports.
+#let name = "module"
+#import (name + ".typ") as dynamic
+#test(dynamic.d, 3)
+
+#let cond = true
+#import (if cond { "module.typ" } else { "modules/chap1.typ" }) as chosen: b
+#test(chosen.a, none)
+#test(b, 1)
+
+---
+// Error: 24 expected identifier
+#import "module.typ" as
+
 ---
 // Who needs whitespace anyways?
 #import"module.typ":*
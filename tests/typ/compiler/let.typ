@@ -259,3 +259,60 @@ Three
 
 // Error: 15 expected expression
 #let func(x) =
+
+---
+// Ref: false
+// Nested destructuring.
+#let (a, (b, c), ..d) = (1, (2, 3), 4, 5)
+#test((a, b, c, d), (1, 2, 3, (4, 5)))
+
+#let (x: (y, z), w: (v, _)) = (x: (y: 1, z: 2), w: (3, 4))
+#test((y, z, v), (1, 2, 3))
+
+#let ((p, q),) = ((p: 1, q: 2),)
+#test((p, q), (1, 2))
+
+---
+// Ref: false
+// Destructuring with defaults.
+#let (a, b = 2, c = a + 1) = (1,)
+#test((a, b, c), (1, 2, 2))
+
+#let (a, ..rest, b = 5) = (1,)
+#test((a, rest, b), (1, (), 5))
+
+#let (a, b = 0, ..rest) = (1,)
+#test((a, b, rest), (1, 0, ()))
+
+#let (title, author = "Anonymous", size: s = 11pt) = (title: "Report")
+#test((title, author, s), ("Report", "Anonymous", 11pt))
+
+#let (title, author = "Anonymous", ..other) = (title: "A", author: "B", year: 2023)
+#test((title, author, other), ("A", "B", (year: 2023)))
+
+---
+// Ref: false
+// Defaults and nested patterns in parameters.
+#let f((x, y = 0)) = x + y
+#test(f((1,)), 1)
+#test(f((1, 2)), 3)
+
+#let offset = 10
+#let g((size: s = offset, pos: (x, y))) = s + x + y
+#test(g((pos: (1, 2))), 13)
+
+---
+// Error: 14-15 not enough elements to destructure
+#let (a, (b, c)) = (1, (2,))
+
+---
+// Error: 10-16 cannot destructure integer
+#let (a, (b, c)) = (1, 2)
+
+---
+// Error: 7-8 destructuring key not found in dictionary
+#let (x: (a, b)) = (y: 1)
+
+---
+// Error: 11-12 at most one binding per identifier is allowed
+#let (a, (a, b)) = (1, (2, 3))
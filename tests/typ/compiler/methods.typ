@@ -54,6 +54,20 @@
 #test([a].fields(), (text: "a"))
 #test([a *b*].fields(),  (children: ([a], [ ], strong[b])))
 
+---
+// Test content introspection methods.
+#test([a *b*].children(), ([a], [ ], strong[b]))
+#test([a].children(), ([a],))
+#test([a *b* _c_].text(), "a b c")
+#test(heading[Intro].text(), "Intro")
+#let body = [
+  - One
+  - Two *three*
+]
+#test(body.query(list.item).len(), 2)
+#test(body.query(strong).map(it => it.body), ([three],))
+#test(body.query(heading), ())
+
 ---
 // Test length unit conversions.
 #test((500.934pt).pt(), 500.934)
@@ -75,6 +89,21 @@
 #test(5em.abs.cm(), 0.0)
 #test((5em + 6in).abs.inches(), 6.0)
 
+---
+// Test resolving em units with styles.
+#set text(10pt)
+#style(styles => {
+  test((2em + 3pt).pt(styles), 23.0)
+  test((1em).mm(styles), (10pt).mm())
+  test((5pt).ems(styles), 0.5)
+  test((1.5em + 5pt).ems(styles), 2.0)
+})
+#text(20pt, style(styles => test((1em).pt(styles), 20.0)))
+
+---
+// Error: 11-13 missing argument: styles
+#(1em).ems()
+
 ---
 // Error: 2-21 cannot convert a length with non-zero em units (-6pt + 10.5em) to pt
 // Hint: 2-21 use 'length.abs.pt()' instead to ignore its em component
@@ -287,3 +287,33 @@
 #let inc2 = inc.with(y: 2)
 #test(inc2(2), 4)
 #test(inc2(2, y: 4), 6)
+
+---
+// Test `compose` method.
+#let double(x) = 2 * x
+#let inc(x) = x + 1
+#test(double.compose(inc)(3), 8)
+#test(inc.compose(double)(3), 7)
+#test(str.compose(double, calc.abs)(-3), "6")
+#test(double.compose()(4), 8)
+#test(range(3).map(inc.compose(double)), (1, 3, 5))
+
+---
+// Test `curry` method.
+#let add3(x, y, z) = x + y + z
+#let curried = add3.curry()
+#test(curried(1)(2)(3), 6)
+#test(curried(1, 2)(3), 6)
+#test(curried(1)(2, 3), 6)
+#test(curried(1, 2, 3), 6)
+#test(calc.pow.curry()(2)(10), 1024)
+#test(add3.with(1).curry()(2)(3), 6)
+
+#let sum(..nums) = nums.pos().sum()
+#test(sum.curry(3)(1)(2)(3), 6)
+
+---
+#let sum(..nums) = nums.pos().sum()
+
+// Error: 2-13 cannot determine the number of parameters of this function
+#sum.curry()
@@ -211,6 +211,53 @@
 #test("a123c".split(regex("\d")), ("a", "", "", "c"))
 #test("a123c".split(regex("\d+")), ("a", "c"))
 
+---
+// Test the `pad-start` and `pad-end` methods.
+#test("7".pad-start(3, fill: "0"), "007")
+#test("abc".pad-end(5), "abc  ")
+#test("abcdef".pad-start(3), "abcdef")
+#test("é".pad-end(3, fill: "."), "é..")
+#test("🏳️‍🌈".pad-start(2, fill: "🏳️‍🌈"), "🏳️‍🌈🏳️‍🌈")
+#test("".pad-start(2, fill: "→"), "→→")
+
+---
+// Error: 2-32 fill must be exactly one grapheme cluster
+#"abc".pad-start(5, fill: "ab")
+
+---
+// Test the `normalize` method.
+#test("\u{e9}" == "e\u{301}", false)
+#test("e\u{301}".normalize(), "\u{e9}")
+#test("\u{e9}".normalize("nfd"), "e\u{301}")
+#test("\u{fb01}".normalize("nfkc"), "fi")
+#test("\u{fb01}".normalize("nfc"), "\u{fb01}")
+
+---
+// Error: 18-23 expected "nfc", "nfd", "nfkc", or "nfkd"
+#"abc".normalize("nfx")
+
+---
+// Test the `sort-key` method.
+#let words = ("Zebra", "élan", "apple", "Öl", "ost")
+#test(words.sorted(), ("Zebra", "apple", "ost", "Öl", "élan"))
+#test(
+  words.sorted(key: w => w.sort-key()),
+  ("apple", "élan", "Öl", "ost", "Zebra"),
+)
+#test(
+  words.sorted(key: w => w.sort-key(lang: "sv")),
+  ("apple", "élan", "ost", "Zebra", "Öl"),
+)
+#test(
+  ("ñu", "nube", "oca").sorted(key: w => w.sort-key(lang: "es")),
+  ("nube", "ñu", "oca"),
+)
+#test(("e\u{301}", "es").sorted(key: w => w.sort-key()), ("e\u{301}", "es"))
+#test(
+  ("Strasse", "Straßburg", "Str").sorted(key: w => w.sort-key()),
+  ("Str", "Straßburg", "Strasse"),
+)
+
 ---
 // Error: 2-2:1 unclosed string
 #"hello\"
@@ -18,11 +18,11 @@
 #test(calc.round(calc.pi, digits: 2), 3.14)
 
 ---
-// Error: 6-10 expected boolean, integer, float, or string, found length
+// Error: 6-10 expected boolean, integer, float, decimal, or string, found length
 #int(10pt)
 
 ---
-// Error: 8-13 expected boolean, integer, float, ratio, or string, found function
+// Error: 8-13 expected boolean, integer, float, ratio, decimal, or string, found function
 #float(float)
 
 ---
@@ -33,6 +33,27 @@
 // Error: 8-15 invalid float: 1.2.3
 #float("1.2.3")
 
+---
+// Test parsing integers in other bases.
+#test(int("ff", base: 16), 255)
+#test(int("-7F", base: 16), -127)
+#test(int("1010", base: 2), 10)
+#test(int("z", base: 36), 35)
+#test(int("42", base: 10), 42)
+#test(str(int("c0ffee", base: 16), base: 16), "c0ffee")
+
+---
+// Error: 6-10 invalid integer: 12
+#int("12", base: 2)
+
+---
+// Error: 17-18 base must be between 2 and 36
+#int("1", base: 1)
+
+---
+// Error: 15-17 base is only supported for strings
+#int(5, base: 16)
+
 ---
 // Test the `abs` function.
 #test(calc.abs(-3), 3)
@@ -44,7 +65,7 @@
 #test(calc.abs(-25%), 25%)
 
 ---
-// Error: 11-22 expected integer, float, length, angle, ratio, or fraction, found string
+// Error: 11-22 expected integer, float, length, angle, ratio, fraction, or complex, found string
 #calc.abs("no number")
 
 ---
@@ -164,6 +185,22 @@
 #test(calc.binom(5, 6), 0)
 #test(calc.binom(6, 2), 15)
 
+---
+// Test the bitwise functions.
+#test(calc.bit-and(0b1100, 0b1010), 0b1000)
+#test(calc.bit-and(0xff, 0x0f, 0x3c), 0x0c)
+#test(calc.bit-or(0b1100, 0b1010), 0b1110)
+#test(calc.bit-xor(0b1100, 0b1010), 0b0110)
+#test(calc.bit-not(0), -1)
+#test(calc.bit-not(-6), 5)
+#test(calc.bit-lshift(1, 4), 16)
+#test(calc.bit-rshift(256, 4), 16)
+#test(calc.bit-rshift(-16, 2), -4)
+
+---
+// Error: 21-23 shift amount must be between 0 and 63
+#calc.bit-lshift(1, 64)
+
 ---
 // Test the `gcd` function.
 #test(calc.gcd(112, 77), 7)
@@ -227,3 +264,43 @@
 ---
 // Error: 18-19 number must not be zero
 #range(10, step: 0)
+
+---
+// Test complex numbers.
+#let z = calc.complex(3, 4)
+#test(z.re(), 3.0)
+#test(z.im(), 4.0)
+#test(z.abs(), 5.0)
+#test(calc.abs(z), 5.0)
+#test(z.conj(), calc.complex(3, -4))
+#test(z + 1, calc.complex(4, 4))
+#test(z - calc.i, calc.complex(3, 3))
+#test(z * z.conj(), calc.complex(25))
+#test(calc.i * calc.i, calc.complex(-1))
+#test(z / calc.complex(0, 2), calc.complex(2, -1.5))
+#test(-z, calc.complex(-3, -4))
+#test(z.display(), "3 + 4i")
+#test(calc.complex(1, -2).display(), "1 - 2i")
+#test((1 / z).display(digits: 2), "0.12 - 0.16i")
+#test(calc.polar(2, 90deg).display(digits: 1), "0.0 + 2.0i")
+#test(calc.complex(0, 2).display(digits: 0, polar: true), "2∠90°")
+#test(calc.round(calc.complex(1, 1).arg().deg(), digits: 6), 45.0)
+
+---
+// Test polynomial roots.
+#let approx(roots) = roots.map(z => (
+  calc.round(z.re(), digits: 6),
+  calc.round(z.im(), digits: 6),
+))
+#test(approx(calc.roots(2, -4)), ((2.0, 0.0),))
+#test(approx(calc.roots(1, -3, 2)), ((1.0, 0.0), (2.0, 0.0)))
+#test(approx(calc.roots(0, 1, 0, 1)), ((0.0, -1.0), (0.0, 1.0)))
+#test(approx(calc.roots(1, -6, 11, -6)), ((1.0, 0.0), (2.0, 0.0), (3.0, 0.0)))
+
+---
+// Error: 3-13 cannot divide by zero
+#(calc.i / 0)
+
+---
+// Error: 12-15 polynomial must have a degree of at least one
+#calc.roots(5)
@@ -7,12 +7,26 @@
 
 // Alpha channel.
 #test(rgb(255, 0, 0, 50%), rgb("ff000080"))
+#test(luma(50%, 50%).to-rgb(), rgb(128, 128, 128, 50%))
+#test(cmyk(0%, 0%, 0%, 0%, 50%).to-rgb(), rgb(255, 255, 255, 50%))
+#test(rgb("#ff000080").to-cmyk(), cmyk(0%, 100%, 100%, 0%, 50%))
+#test(rgb("#ff000080").lighten(10%).hsl().at(3), rgb("#ff000080").hsl().at(3))
+#test(repr(luma(40, 50%)), "luma(40, 50.2%)")
+#test(repr(cmyk(10%, 20%, 30%, 40%, 50%)), "cmyk(10.2%, 20.0%, 30.2%, 40.0%, 50.2%)")
 
 // Test color modification methods.
 #test(rgb(25, 35, 45).lighten(10%), rgb(48, 57, 66))
 #test(rgb(40, 30, 20).darken(10%), rgb(36, 27, 18))
 #test(rgb("#133337").negate(), rgb(236, 204, 200))
 #test(white.lighten(100%), white)
+#test(color.hsl(0deg, 50%, 50%).saturate(100%), rgb("#ff0000"))
+#test(rgb("#ff0000").desaturate(100%), rgb(128, 128, 128))
+#test(rgb("#ff0000").saturate(50%), rgb("#ff0000"))
+#test(luma(40%).saturate(50%), luma(40%))
+#test(rgb("#ff0000").mix(rgb("#00ff00")), color.mix(rgb("#ff0000"), rgb("#00ff00")))
+#test(rgb("#ff0000").mix(rgb("#00ff00"), space: "srgb"), rgb("#808000"))
+#test(rgb("#aaff00").mix(rgb("#aa00ff"), 75%, space: "srgb"), rgb("#aa40bf"))
+#test(red.mix(green, 0%), red)
 
 // Color mixing, in Oklab space by default.
 #test(color.mix(rgb("#ff0000"), rgb("#00ff00")), rgb("#d0a800"))
@@ -34,6 +48,22 @@
 #test(color.mix((rgb("#aaff00"), 50%), (rgb("#aa00ff"), 50%), space: "srgb"), rgb("#aa8080"))
 #test(color.mix((rgb("#aaff00"), 75%), (rgb("#aa00ff"), 25%), space: "srgb"), rgb("#aabf40"))
 
+// Other color spaces.
+#test(color.hsl(0deg, 100%, 50%), rgb("#ff0000"))
+#test(color.hsl(120deg, 100%, 25%), rgb(0, 128, 0))
+#test(color.hsl(-240deg, 100%, 25%, 50%), rgb(0, 128, 0, 50%))
+#test(color.hsv(240deg, 50%, 100%), rgb(128, 128, 255))
+#test(color.oklab(0%, 0.0, 0.0), rgb("#000000"))
+#test(color.oklab(100%, 0.0, 0.0), rgb("#ffffff"))
+#test(rgb("#ff0000").hsl(), (0deg, 100%, 50%, 100%))
+#test(rgb("#ff0000").hsv(), (0deg, 100%, 100%, 100%))
+
+// Conversions between color kinds.
+#test(rgb("#ff0000").to-cmyk(), cmyk(0%, 100%, 100%, 0%))
+#test(luma(50%).to-rgb(), rgb(128, 128, 128))
+#test(white.to-luma(), luma(255))
+#test(cmyk(0%, 0%, 0%, 100%).to-luma(), luma(0))
+
 ---
 // Test gray color conversion.
 // Ref: true
@@ -48,6 +78,10 @@
 // Error: 6-11 color string contains non-hexadecimal letters
 #rgb("lol")
 
+---
+// Error: 18-22 ratio must be between 0% and 100%
+#color.hsl(0deg, 120%, 50%)
+
 ---
 // Error: 5-7 missing argument: red component
 #rgb()
@@ -103,7 +137,7 @@
 #test(str(10 / 3).len() > 10, true)
 
 ---
-// Error: 6-8 expected integer, float, label, or string, found content
+// Error: 6-8 expected integer, float, decimal, label, bytes, or string, found content
 #str([])
 
 ---
@@ -114,6 +148,28 @@
 // Error: 18-19 base is only supported for integers
 #str(1.23, base: 2)
 
+---
+// Test number formatting.
+#test(str(3.14159, digits: 2), "3.14")
+#test(str(2.5, digits: 0), "2")
+#test(str(12, digits: 3), "12.000")
+#test(str(-1234567, thousands: ","), "-1,234,567")
+#test(str(123, thousands: ","), "123")
+#test(str(1234.5, thousands: " ", decimal: ","), "1 234,5")
+#test(str(1234567.891, digits: 2, thousands: ".", decimal: ","), "1.234.567,89")
+#test(str(1500, scientific: true), "1.5e3")
+#test(str(1500, digits: 2, scientific: true), "1.50e3")
+#test(str(0.000123, scientific: true), "1.23e-4")
+#test(str(123456.0, digits: 2, scientific: true, decimal: ","), "1,23e5")
+
+---
+// Error: 6-11 number formatting is only supported for numbers
+#str("abc", digits: 2)
+
+---
+// Error: 17-18 number formatting is only supported in base 10
+#str(255, base: 2, thousands: " ")
+
 ---
 // Test the unicode function.
 #test(str.from-unicode(97), "a")
@@ -187,11 +243,64 @@
 #test(e.minute(), none)
 #test(e.second(), none)
 
+// Test localized displaying of dates.
+#test(
+  datetime(year: 2023, month: 3, day: 29)
+    .display("[weekday], [day]. [month repr:long] [year]", lang: "de"),
+  "Mittwoch, 29. März 2023",
+)
+#test(
+  datetime(year: 2023, month: 3, day: 29)
+    .display("[weekday repr:short] [month repr:short]", lang: "fr"),
+  "mer mar",
+)
+#test(
+  datetime(year: 2023, month: 3, day: 29).display("[month repr:long]", lang: "en"),
+  "March",
+)
+#test(
+  datetime(year: 2023, month: 1, day: 15).display("[month repr:long] [year]", lang: "pl"),
+  "styczeń 2023",
+)
+#test(
+  datetime(year: 2023, month: 1, day: 15)
+    .display("[day] [month repr:long] [year]", lang: "pl"),
+  "15 stycznia 2023",
+)
+
+// Test displaying dates with custom names.
+#let names = (
+  months: ("januaro", "februaro", "marto", "aprilo", "majo", "junio", "julio",
+    "aŭgusto", "septembro", "oktobro", "novembro", "decembro"),
+  weekdays: ("lundo", "mardo", "merkredo", "ĵaŭdo", "vendredo", "sabato", "dimanĉo"),
+)
+#test(
+  datetime(year: 2023, month: 3, day: 29)
+    .display("[weekday], [day]. [month repr:long]", lang: "de", names: names),
+  "merkredo, 29. marto",
+)
+
+// Test parsing of ISO strings.
+#test(datetime("2023-02-01"), datetime(year: 2023, month: 2, day: 1))
+#test(datetime("15:38"), datetime(hour: 15, minute: 38, second: 0))
+#test(
+  datetime("2023-02-01T15:38:57Z"),
+  datetime(year: 2023, month: 2, day: 1, hour: 15, minute: 38, second: 57),
+)
+#test(
+  datetime("2023-02-01 15:38:57.123"),
+  datetime(year: 2023, month: 2, day: 1, hour: 15, minute: 38, second: 57),
+)
+
 // Test today
 #test(datetime.today().display(), "1970-01-01")
 #test(datetime.today(offset: auto).display(), "1970-01-01")
 #test(datetime.today(offset: 2).display(), "1970-01-01")
 
+---
+// Error: 57-83 expected 12 month names
+#datetime(year: 2023, month: 3, day: 29).display(names: (months: (), weekdays: ()))
+
 ---
 // Error: 10-12 at least one of date or time must be fully specified
 #datetime()
@@ -204,6 +313,18 @@
 // Error: 10-41 date is invalid
 #datetime(year: 2000, month: 2, day: 30)
 
+---
+// Error: 10-24 invalid date: "2000-02-30"
+#datetime("2000-02-30")
+
+---
+// Error: 10-19 invalid time: "25:00"
+#datetime("25:00")
+
+---
+// Error: 10-34 cannot combine an ISO string with individual components
+#datetime("2000-01-01", hour: 12)
+
 ---
 // Error: 26-35 missing closing bracket for bracket at index 0
 #datetime.today().display("[year")
@@ -223,3 +344,109 @@
 ---
 // Error: 26-36 failed to format datetime in the requested format
 #datetime.today().display("[hour]")
+
+---
+// Test durations and datetime arithmetic.
+#let start = datetime(year: 2023, month: 8, day: 1)
+#let end = datetime(year: 2023, month: 8, day: 15)
+#test(end - start, duration(weeks: 2))
+#test((end - start).days(), 14)
+#test(start + duration(days: 5), datetime(year: 2023, month: 8, day: 6))
+#test(start - duration(days: 1), datetime(year: 2023, month: 7, day: 31))
+#test(duration(days: 5) + start, datetime(year: 2023, month: 8, day: 6))
+#test(start < end, true)
+#test(
+  datetime(hour: 23, minute: 0, second: 0) + duration(hours: 2),
+  datetime(hour: 1, minute: 0, second: 0),
+)
+#test(
+  datetime(year: 2023, month: 1, day: 1, hour: 12, minute: 0, second: 0)
+    - datetime(year: 2022, month: 12, day: 31, hour: 11, minute: 30, second: 0),
+  duration(days: 1, minutes: 30),
+)
+
+---
+// Test duration operations and display.
+#test(repr(duration()), "duration(seconds: 0)")
+#test(repr(duration(days: 9, minutes: 1)), "duration(weeks: 1, days: 2, minutes: 1)")
+#test(duration(minutes: 90).hours(), 1.5)
+#test(duration(hours: 36).weeks(), 36 / 168)
+#test((duration(hours: 1) * 2.5).minutes(), 150)
+#test(2 * duration(minutes: 1), duration(seconds: 120))
+#test(duration(hours: 1) / 4, duration(minutes: 15))
+#test(duration(hours: 3) / duration(minutes: 30), 6.0)
+#test(duration(hours: 1) - duration(hours: 2), -duration(hours: 1))
+#test(duration(days: 1) > duration(hours: 23), true)
+#test(duration(hours: 26, minutes: 3).display(), "26:03:00")
+#test(duration(hours: 26, minutes: 3).display("[days]d [hours]h [minutes]min"), "1d 2h 3min")
+#test(duration(seconds: -61).display(), "-0:01:01")
+
+---
+// Error: 3-83 cannot subtract a time from a date
+#(datetime(year: 2023, month: 1, day: 1) - datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 3-34 cannot divide by zero
+#(duration(hours: 1) / duration())
+
+---
+// Error: 3-62 cannot compare duration and datetime
+#(duration(days: 1) < datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 27-36 invalid component name 'day'
+#duration(days: 1).display("[day]")
+
+---
+// Test bytes conversions.
+#test(bytes("Hi"), bytes((72, 105)))
+#test(str(bytes("Typst 😃")), "Typst 😃")
+#test(repr(bytes((1, 2, 3))), "bytes(3)")
+
+---
+// Error: 8-14 number must be between 0 and 255
+#bytes((256,))
+
+---
+// Error: 6-19 bytes are not valid utf-8
+#str(bytes((255,)))
+
+---
+// Test decimals.
+#test(decimal("0.1") + decimal("0.2"), decimal("0.3"))
+#test(decimal("0.1") + decimal("0.2") == 0.3, false)
+#test(decimal("19.99") * 3, decimal("59.97"))
+#test(decimal("2.50") - 1, decimal("1.5"))
+#test(decimal(3) == 3, true)
+#test(decimal("10") / 4, decimal("2.5"))
+#test(decimal(0.1), decimal("0.1"))
+#test(-decimal("1.5"), decimal("-1.5"))
+#test(decimal("1.5") < 2, true)
+#test(decimal("1.25") > decimal("1.2"), true)
+#test(repr(decimal("1.20")), "decimal(\"1.20\")")
+#test(str(decimal("1.20")), "1.20")
+#test(int(decimal("-2.7")), -2)
+#test(float(decimal("2.5")), 2.5)
+
+---
+// Test rounding decimals.
+#test(str(decimal("2.345").round(2)), "2.34")
+#test(str(decimal("2.345").round(2, mode: "half-up")), "2.35")
+#test(str(decimal("2.341").round(2, mode: "up")), "2.35")
+#test(str(decimal("-2.349").round(2, mode: "down")), "-2.34")
+#test(str(decimal("-2.341").round(1, mode: "floor")), "-2.4")
+#test(str(decimal("2").rescale(2)), "2.00")
+#test(str(decimal("1234.5"), digits: 2, thousands: ","), "1,234.50")
+#test(decimal("-3.5").abs(), decimal("3.5"))
+
+---
+// Error: 10-17 invalid decimal: 1.2.3
+#decimal("1.2.3")
+
+---
+// Error: 3-21 cannot add decimal and float
+#(decimal("1") + 1.5)
+
+---
+// Error: 3-19 cannot divide by zero
+#(decimal("1") / 0)
@@ -0,0 +1,15 @@
+// Test hash functions.
+// Ref: false
+
+---
+#test(crypto.sha256(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+#test(crypto.sha256("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+#test(crypto.md5("abc"), "900150983cd24fb0d6963f7d28e17f72")
+#test(crypto.md5(bytes("Typst")), crypto.md5("Typst"))
+#test(crypto.crc32(""), 0)
+#test(crypto.crc32("abc"), 891568578)
+#test(crypto.crc32(bytes((97, 98, 99))), 891568578)
+
+---
+// Error: 16-18 expected string or bytes, found integer
+#crypto.sha256(42)
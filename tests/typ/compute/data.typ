@@ -30,6 +30,34 @@
 // Error: 6-22 failed to parse csv file: found 3 instead of 2 fields in line 3
 #csv("/files/bad.csv")
 
+---
+// Test reading CSV data with a header.
+#let data = csv("/files/zoo.csv", header: true)
+#test(data.len(), 3)
+#test(data.at(0).Name, "Debby")
+#test(data.at(2), (Name: "Sleepy", Species: "Dolphin", Weight: "150kg", Length: "180cm"))
+
+---
+// Test converting CSV columns.
+#let data = csv(
+  "/files/planets.csv",
+  header: true,
+  types: (moons: "integer", radius: "float", habitable: "boolean"),
+)
+#test(data.at(1), (name: "Earth", moons: 1, radius: 6371.0, habitable: true))
+#test(data.at(2).habitable, none)
+#let rows = csv("/files/planets.csv", header: true, types: ("string", "integer"))
+#test(rows.map(row => row.moons), (0, 1, 2))
+#test(rows.at(0).radius, "2439.7")
+
+---
+// Error: 6-26 failed to parse csv file: cannot convert "radius" to float in line 1
+#csv("/files/planets.csv", types: ("string", "string", "float"))
+
+---
+// Error: 6-26 column types can only be given by name if the file has a header
+#csv("/files/planets.csv", types: (moons: "integer"))
+
 ---
 // Test reading JSON data.
 #let data = json("/files/zoo.json")
@@ -111,3 +139,49 @@
 ---
 // Error: 6-22 failed to parse xml file: found closing tag 'data' instead of 'hello' in line 3
 #xml("/files/bad.xml")
+
+---
+// Test selecting XML elements.
+#let data = xml("/files/example.xml")
+#let titles = xml.select(data, "news/article/title")
+#test(titles.map(t => t.children.first()), ("2022 Budget approved", "Tigers win the World Series"))
+#test(xml.select(data, "news/*/content/p").len(), 5)
+#test(xml.select(titles.first(), "nothing"), ())
+#test(xml.select(xml("/files/data.xml"), "data/hello/@name"), ("hi",))
+
+---
+// Error: 12-52 attribute selector must be the last segment of the path
+#xml.select(xml("/files/data.xml"), "data/@name/x")
+
+---
+// Test encoding data.
+#test(json.encode((a: 1, b: (true, none)), pretty: false), "{\"a\":1,\"b\":[true,null]}")
+#test(json.encode((a: 1)), "{\n  \"a\": 1\n}")
+#test(json.encode(datetime(year: 2023, month: 5, day: 17)), "\"2023-05-17\"")
+#test(yaml.encode((name: "Debby", weight: 1900)), "name: Debby\nweight: 1900\n")
+#test(toml.encode((title: "Zoo", count: 3)), "count = 3\ntitle = \"Zoo\"\n")
+#test(csv.encode((("a", "b"), (1, none))), "a,b\n1,\n")
+#test(csv.encode(((name: "a", n: 1), (name: "b", n: 2)), delimiter: ";"), "name;n\na;1\nb;2\n")
+
+---
+// Error: 14-17 cannot encode length as json
+#json.encode(1pt)
+
+---
+// Error: 14-23 cannot encode none as toml
+#toml.encode((a: none))
+
+---
+// Test loading and calling plugins.
+#let p = plugin("/files/hello.wasm")
+#test(p.hello(), bytes("Hello from wasm!"))
+#test(str(p.echo(bytes("Typst"))), "Typst")
+#test(str(p.echo(bytes((240, 159, 152, 131)))), "😃")
+
+---
+// Error: 2-36 plugin function takes 1 argument, but 0 were given
+#plugin("/files/hello.wasm").echo()
+
+---
+// Error: 2-36 type plugin has no method `nope`
+#plugin("/files/hello.wasm").nope()
@@ -0,0 +1,22 @@
+// Test Base64 and hexadecimal encoding.
+// Ref: false
+
+---
+#test(base64.encode("Typst"), "VHlwc3Q=")
+#test(base64.encode(bytes((251, 255))), "+/8=")
+#test(base64.encode(bytes((251, 255)), url-safe: true), "-_8=")
+#test(str(base64.decode("VHlwc3Q=")), "Typst")
+#test(str(base64.decode("VHlwc3Q")), "Typst")
+#test(base64.decode("-_8", url-safe: true), bytes((251, 255)))
+#test(hex.encode("Typst"), "5479707374")
+#test(hex.encode(bytes((0, 171, 255))), "00abff")
+#test(hex.decode("00ABff"), bytes((0, 171, 255)))
+#test(str(hex.decode(hex.encode("😃"))), "😃")
+
+---
+// Error: 12-19 hexadecimal string must have an even number of digits
+#hex.decode("abc")
+
+---
+// Error: 12-18 invalid hexadecimal digit: 'x'
+#hex.decode("xy")
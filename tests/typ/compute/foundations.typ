@@ -6,6 +6,34 @@
 #test(type(ltr), "direction")
 #test(type(10 / 3), "float")
 
+---
+// Test type predicates.
+#test(type.is(1, "integer"), true)
+#test(type.is(1.5, "integer", "float"), true)
+#test(type.is("a", "content"), false)
+#test(type.is([= Title], heading), true)
+#test(type.is([= Title], "content"), true)
+#test(type.is([Text], heading, strong), false)
+#test(type.is(heading, "function"), true)
+
+---
+// Error: 13-15 unexpected argument
+#type.is(1, 12)
+
+---
+// Test fields.
+#test(fields([= Title]).level, 1)
+#test(fields(heading).contains("numbering"), true)
+#test(fields(heading).contains("body"), true)
+
+---
+// Error: 9-10 expected content or element function, found integer
+#fields(1)
+
+---
+// Error: 9-13 expected content or element function, found function
+#fields(repr)
+
 ---
 #test(repr(ltr), "ltr")
 #test(repr((1, 2, false, )), "(1, 2, false)")
@@ -25,6 +53,20 @@
 // Error: 7-24 panicked with: "this is wrong"
 #panic("this is wrong")
 
+---
+// Test warnings.
+// Warning: 6-28 this is deprecated
+#warn("this is deprecated")
+
+---
+// Warnings don't stop evaluation.
+#let x = {
+  // Warning: 7-14 one
+  warn("one")
+  1
+}
+#test(x, 1)
+
 ---
 // Test failing assertions.
 // Error: 8-16 assertion failed
@@ -77,6 +119,14 @@
 #test(eval("1 + 2"), 3)
 #test(eval("1 + x", scope: (x: 3)), 4)
 #test(eval("let x = x + 1; x + 1", scope: (x: 1)), 3)
+#test(eval("str(len)", scope: (str: upper, len: "ab")), "AB")
+#test(eval("(a, b) => a + b", scope: (a: 1))(2, 3), 5)
+
+---
+// The evaluated code only sees the provided scope, not local bindings.
+#let secret = 1
+// Error: 7-15 unknown variable: secret
+#eval("secret")
 
 ---
 // Test evaluation in other modes.
@@ -130,3 +180,47 @@ _No relative giraffe!_
 ---
 // Error: 7-12 expected semicolon or line break
 #eval("1 2")
+
+---
+// Test catch.
+#test(catch(() => 1 + 2), (ok: true, value: 3))
+#test(catch(() => 1 + "a").error, "cannot add integer and string")
+#test(catch(() => 1 + "a").errors, ((message: "cannot add integer and string", hints: ()),))
+#test(catch(() => eval("1 2 3")).errors.len(), 2)
+#test(catch(() => text(names: (figure: ""))[]).errors.first().hints.len(), 1)
+#test(catch(() => panic("oh no")).error, "panicked with: \"oh no\"")
+#test(catch(() => json("does-not-exist.json")).ok, false)
+
+#let (value: v = "fallback") = catch(() => int("x"))
+#test(v, "fallback")
+
+---
+// Error: 8-9 expected function, found integer
+#catch(1)
+
+---
+// Test match.
+#let kind(value) = match(
+  value,
+  ((x, y)) => "pair",
+  ((name, age = none)) => "person",
+  ((head, ..)) => "list",
+  _ => "other",
+)
+
+#test(kind((1, 2)), "pair")
+#test(kind((name: "Jane")), "person")
+#test(kind((name: "Jane", age: 32)), "person")
+#test(kind((1, 2, 3)), "list")
+#test(kind(()), "other")
+#test(kind(5), "other")
+#test(match((a: (b: 1)), ((a: (c,))) => 1, ((a: (b,))) => b), 1)
+
+---
+// Error: 7-23 no case matches integer
+#match(1, ((x,)) => x)
+
+---
+// Test system inputs. The test runner doesn't provide any.
+#test(type(sys.inputs), "dictionary")
+#test(sys.inputs.at("variant", default: "draft"), "draft")
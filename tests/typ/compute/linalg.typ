@@ -0,0 +1,52 @@
+// Test linear algebra functions.
+// Ref: false
+
+---
+#let round(m) = m.map(row => row.map(x => calc.round(x, digits: 6)))
+#let a = ((1, 2), (3, 4))
+#test(linalg.identity(2), ((1, 0), (0, 1)))
+#test(linalg.transpose(((1, 2, 3), (4, 5, 6))), ((1, 4), (2, 5), (3, 6)))
+#test(linalg.dot((1, 2, 3), (4, 5, 6)), 32.0)
+#test(linalg.mul(a, a), ((7, 10), (15, 22)))
+#test(linalg.mul(a, (1, 1)), (3, 7))
+#test(linalg.mul(a, linalg.identity(2)), a)
+#test(calc.round(linalg.det(a), digits: 6), -2.0)
+#test(linalg.det(((1, 2), (2, 4))), 0.0)
+#let k = 700000
+#test(linalg.det(((0.1 * k, 0.7 * k), (0.3 * k, 2.1 * k))), 0.0)
+#test(linalg.inv(((calc.pow(2.0, -60), 0), (0, calc.pow(2.0, -60)))).at(0).at(0), calc.pow(2.0, 60))
+#test(round(linalg.inv(((4, 7), (2, 6)))), ((0.6, -0.7), (-0.2, 0.4)))
+#test(round(linalg.mul(a, linalg.inv(a))), ((1, 0), (0, 1)))
+#test(linalg.solve(((2, 1), (1, -1)), (5, 1)).map(x => calc.round(x, digits: 6)), (2, 1))
+
+---
+// Test eigenvalues.
+#test(linalg.eigenvalues(((2, 0), (0, 3))).map(x => calc.round(x, digits: 6)), (2, 3))
+#test(linalg.eigenvalues(((2, 1), (1, 2))).map(x => calc.round(x, digits: 6)), (1, 3))
+#let rotation = linalg.eigenvalues(((0, -1), (1, 0)))
+#test(rotation.map(z => type(z)), ("complex", "complex"))
+#test(rotation.map(z => calc.round(z.im(), digits: 6)), (-1, 1))
+
+---
+// Test that results can be displayed as matrices.
+$ #math.mat(..linalg.mul(((1, 2), (3, 4)), ((0, 1), (1, 0)))) $
+
+---
+// Error: 13-35 expected square matrix, found 2×3 matrix
+#linalg.det(((1, 2, 3), (4, 5, 6)))
+
+---
+// Error: 13-29 matrix is singular
+#linalg.inv(((1, 2), (2, 4)))
+
+---
+// Error: 12-34 cannot multiply a 1×2 matrix with a 3×1 vector
+#linalg.mul(((1, 2),), (1, 2, 3))
+
+---
+// Error: 13-27 matrix rows must all have the same length
+#linalg.det(((1, 2), (3,)))
+
+---
+// Error: 13-21 expected number, found string
+#linalg.dot(("a", 1), (1, 2))
@@ -0,0 +1,46 @@
+// Test seeded pseudo-random numbers.
+// Ref: false
+
+---
+// Test that generators with the same seed agree.
+#let a = random.new(seed: 42)
+#let b = random.new(seed: 42)
+#let (xs, ys) = ((), ())
+#for _ in range(10) {
+  xs.push(a.int(0, 1000))
+  ys.push(b.int(0, 1000))
+}
+#test(xs, ys)
+#test(a.float(), b.float())
+
+---
+// Test the ranges of generated numbers.
+#let rng = random.new(seed: 7)
+#for _ in range(100) {
+  let n = rng.int(-3, 3)
+  assert(n >= -3 and n < 3)
+  let f = rng.float()
+  assert(f >= 0.0 and f < 1.0)
+}
+#test(rng.int(5, 6), 5)
+
+---
+// Test shuffling and choosing.
+#let rng = random.new(seed: 1)
+#let items = (1, 2, 3, 4, 5)
+#test(rng.shuffle(items).sorted(), items)
+#test(rng.choice(items) in items, true)
+#test(type(rng), "rng")
+
+---
+// Test that generators in collections advance while temporaries don't.
+#let rngs = (random.new(seed: 3),)
+#let first = rngs.at(0).int(0, 1000000)
+#test(rngs.at(0).int(0, 1000000) != first, true)
+#test(random.new(seed: 3).int(0, 1000000), first)
+#test(random.new(seed: 3).int(0, 1000000), first)
+
+---
+// Test that the method names don't make calls on other values mutating.
+// Error: 2-22 type dictionary has no method `int`
+#(int: x => x).int(1)
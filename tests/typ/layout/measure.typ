@@ -0,0 +1,34 @@
+// Test measuring content.
+// Ref: false
+
+---
+// Measuring with explicit styles.
+#style(styles => {
+  let small = measure([Hello], styles)
+  let narrow = measure(lorem(20), styles, width: 3cm)
+  test(small.width < 3cm, true)
+  test(narrow.width <= 3cm, true)
+  test(narrow.height > small.height, true)
+})
+
+---
+// Measuring with the styles of the call site.
+#let body = [Hello]
+#let widths = state("widths", ())
+#let record = measure(body, size => widths.update(w => w + (size.width,)))
+
+#record
+#set text(20pt)
+#record
+
+// The widths are only recorded after the first layout iteration.
+#locate(loc => {
+  let widths = widths.final(loc)
+  if widths.len() == 2 {
+    test(widths.first() < widths.last(), true)
+  }
+})
+
+---
+// Error: 16-17 expected styles or function, found integer
+#measure([Hi], 1)
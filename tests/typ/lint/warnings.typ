@@ -0,0 +1,38 @@
+// Test warnings and their suppression.
+// Ref: false
+
+---
+// Warning: 17-30 unknown font family: nonexistent
+#set text(font: "Nonexistent")
+
+---
+// Warning: 17-43 unknown font family: nonexistent
+#set text(font: ("PT Sans", "Nonexistent"))
+
+---
+#suppress-warnings("unknown-font")[
+  #set text(font: "Nonexistent")
+]
+
+---
+#suppress-warnings[
+  #warn("hidden")
+  **
+]
+
+---
+// Warning: 36-38 no text within stars
+// Hint: 36-38 using multiple consecutive stars (e.g. **) has no additional effect
+#suppress-warnings("unknown-font")[**]
+
+---
+// Warning: 2-23 passing a boolean to `indent` is deprecated
+// Hint: 2-23 use `auto` instead of `true` and `none` instead of `false`
+#outline(indent: true)
+= Heading
+
+---
+#suppress-warnings("deprecated")[
+  #outline(indent: true)
+  = Heading
+]
@@ -0,0 +1,50 @@
+// Test retrieving counter values at labels, selectors, and the end.
+// Ref: false
+
+---
+#set page(height: 80pt, width: 120pt)
+#set heading(numbering: "1.")
+
+= A
+= B <b>
+#pagebreak()
+= C
+== D <d>
+
+#locate(loc => {
+  test(counter(heading).at(<b>), (2,))
+  test(counter(heading).at(<d>), (3, 1))
+  test(counter(heading).at(heading.where(level: 2)), (3, 1))
+  test(counter(heading).final(), (3, 1))
+  test(counter(heading).final(loc), (3, 1))
+  test(counter(page).at(<b>), (1,))
+  test(counter(page).final(), (2,))
+})
+
+---
+// State's final value doesn't need a location either.
+#let s = state("final", 0)
+#s.update(1)
+#locate(loc => {
+  test(s.final(), 2)
+  test(s.final(loc), 2)
+})
+#s.update(2)
+
+---
+= A
+#locate(loc => {
+  // Error: 23-32 label does not exist in the document
+  counter(heading).at(<missing>)
+})
+
+---
+= A
+#locate(loc => {
+  // Error: 23-29 selector does not match any element
+  counter(heading).at(figure)
+})
+
+---
+// Error: 22-24 expected location, function, label, or selector, found integer
+#counter(heading).at(12)
@@ -0,0 +1,18 @@
+// Test attaching files to the PDF.
+// Ref: false
+
+---
+#pdf.embed-file("/files/data.csv", description: "Raw data", mime-type: "text/csv")
+#locate(loc => {
+  let embeds = query(pdf.embed-file, loc)
+  test(embeds.first().path, "/files/data.csv")
+  test(embeds.first().description, "Raw data")
+})
+
+---
+// Error: 17-37 file not found (searched at files/missing.csv)
+#pdf.embed-file("/files/missing.csv")
+
+---
+// Error: 16-18 missing argument: path to file
+#pdf.embed-file()
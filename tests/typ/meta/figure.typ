@@ -100,3 +100,20 @@ We can clearly see that @fig-cylinder and
 #show figure: set block(breakable: true)
 
 #figure(table[a][b][c][d][e], caption: [A table])
+
+---
+// Test the alternative description of figures.
+// Ref: false
+#test(figure(alt: "A blue square", square(fill: blue)).alt, "A blue square")
+#test(figure(square()).has("alt"), false)
+
+---
+// Test that images without an alternative description are reported.
+// Accessible: true
+// Ref: false
+#figure(alt: "A tiger", image("/files/tiger.jpg", width: 10pt))
+#image("/files/rhino.png", width: 10pt, alt: "A rhino")
+
+// Warning: 2-40 image has no alternative description
+// Hint: 2-40 add an `alt` text to the image or its figure
+#image("/files/tiger.jpg", width: 10pt)
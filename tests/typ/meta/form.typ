@@ -0,0 +1,12 @@
+// Test PDF form fields.
+
+---
+Name: #pdf.text-field("name", value: "Jane Doe", width: 60pt) \
+#pdf.checkbox("terms", checked: true) I accept the terms. \
+#pdf.text-field("comments", width: 100%, height: 3em, multiline: true)
+
+Signed: #pdf.signature("signature", width: 100%, height: 1cm)
+
+---
+// Error: 16-18 missing argument: name
+#pdf.text-field()
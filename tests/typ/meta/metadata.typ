@@ -0,0 +1,15 @@
+// Test metadata retrieval.
+// Ref: false
+
+---
+#metadata("Hello") <greeting>
+#metadata((a: 1, b: (2, 3))) <data>
+#locate(loc => {
+  test(query(<greeting>, loc).first().value, "Hello")
+  test(query(<data>, loc).first().value.b.last(), 3)
+  test(query(metadata, loc).len(), 2)
+})
+
+---
+// Error: 10-12 missing argument: value
+#metadata()
@@ -5,7 +5,11 @@
 #set page(width: 200pt)
 #set heading(numbering: "1.a.")
 #outline()
+// Warning: 2-24 passing a boolean to `indent` is deprecated
+// Hint: 2-24 use `auto` instead of `true` and `none` instead of `false`
 #outline(indent: false)
+// Warning: 2-23 passing a boolean to `indent` is deprecated
+// Hint: 2-23 use `auto` instead of `true` and `none` instead of `false`
 #outline(indent: true)
 #outline(indent: none)
 #outline(indent: auto)
@@ -31,7 +35,11 @@
 // Without heading numbering
 #set page(width: 200pt)
 #outline()
+// Warning: 2-24 passing a boolean to `indent` is deprecated
+// Hint: 2-24 use `auto` instead of `true` and `none` instead of `false`
 #outline(indent: false)
+// Warning: 2-23 passing a boolean to `indent` is deprecated
+// Hint: 2-23 use `auto` instead of `true` and `none` instead of `false`
 #outline(indent: true)
 #outline(indent: none)
 #outline(indent: auto)
@@ -0,0 +1,26 @@
+// Test filtering queries by page.
+// Ref: false
+
+---
+#set page(height: 80pt, width: 120pt)
+
+= A
+#pagebreak()
+= B
+= C <c>
+#pagebreak()
+= D
+#pagebreak()
+= E
+
+#locate(loc => {
+  let bodies(sel) = query(sel, loc).map(it => it.body)
+  test(bodies(selector(heading).within(page: 2)), ([B], [C]))
+  test(bodies(selector(heading).within(page: (2, 3))), ([B], [C], [D]))
+  test(bodies(selector(heading).within(page: (3, 10))), ([D], [E]))
+  test(bodies(selector(heading).within(page: 5)), ())
+  test(
+    bodies(selector(heading).after(<c>).within(page: (1, 3))),
+    ([C], [D]),
+  )
+})
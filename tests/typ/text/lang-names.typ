@@ -0,0 +1,90 @@
+// Test overriding the generated names.
+
+---
+#set text(names: (table: "Tab.", heading: "Abschnitt"))
+#show figure: it => { test(it.supplement.text, "Tab."); it }
+#show heading: it => { test(it.supplement.text, "Abschnitt"); it }
+
+#set heading(numbering: "1.")
+= Introduction <intro>
+#figure(table[A], caption: [Data]) <data>
+See @intro and @data.
+
+---
+// Names for a language only apply to that language and take precedence over
+// general ones.
+#set text(names: (
+  image: "Picture",
+  eo: (image: "Figuro", table: "Tabelo"),
+  de-CH: (table: "Tabelle (CH)"),
+))
+
+#let check(kind, name) = {
+  show figure: it => { test(it.supplement.text, name); it }
+  figure(kind, caption: [A caption])
+}
+
+#check(image("/files/tiger.jpg", width: 1cm), "Picture")
+#check(table[A], "Table")
+
+#set text(lang: "eo")
+#check(image("/files/tiger.jpg", width: 1cm), "Figuro")
+#check(table[A], "Tabelo")
+
+#set text(lang: "de", region: "ch")
+#check(image("/files/tiger.jpg", width: 1cm), "Picture")
+#check(table[A], "Tabelle (CH)")
+
+---
+// Later names are merged with earlier ones.
+#set text(names: (image: "Picture", raw: "Code"))
+#set text(names: (image: "Illustration"))
+#show figure.where(kind: image): it => {
+  test(it.supplement.text, "Illustration")
+  it
+}
+#show figure.where(kind: raw): it => { test(it.supplement.text, "Code"); it }
+
+#figure(image("/files/tiger.jpg", width: 1cm), caption: [A tiger])
+#figure(```rust fn main() {}```, caption: [A program])
+
+---
+// A language can have month and weekday names that are also understood by
+// the display method of datetimes.
+#let eo = (
+  image: "Figuro",
+  months: ("januaro", "februaro", "marto", "aprilo", "majo", "junio", "julio",
+    "aŭgusto", "septembro", "oktobro", "novembro", "decembro"),
+  weekdays: ("lundo", "mardo", "merkredo", "ĵaŭdo", "vendredo", "sabato", "dimanĉo"),
+)
+
+#set text(lang: "eo", names: (eo: eo))
+#show figure: it => { test(it.supplement.text, "Figuro"); it }
+#figure(image("/files/tiger.jpg", width: 1cm), caption: [Tigro])
+#test(
+  datetime(year: 2023, month: 3, day: 29).display("[weekday]", names: eo),
+  "merkredo",
+)
+
+---
+// Error: 18-28 expected string, found integer
+#set text(names: (table: 1))
+
+---
+// Error: 18-39 expected two letter region code (ISO 3166-1 alpha-2)
+#set text(names: (de-xyz: (table: "")))
+
+---
+// Error: 18-30 cannot override the name of `figure`
+// Hint: 18-30 names can be overridden for `image`, `table`, `raw`, `equation`, `heading`, `outline`, and `bibliography`
+#set text(names: (figure: ""))
+
+---
+// Error: 18-41 cannot override the name of `month`
+// Hint: 18-41 names can be overridden for `image`, `table`, `raw`, `equation`, `heading`, `outline`, and `bibliography`
+// Hint: 18-41 a language can also have `months` and `weekdays`
+#set text(names: (eo: (month: "Monato")))
+
+---
+// Error: 18-36 missing key: "weekdays"
+#set text(names: (eo: (months: ())))
@@ -0,0 +1,70 @@
+// Test long lines in raw blocks.
+
+---
+// Words that don't fit extend past the margin by default.
+#set page(width: 120pt)
+
+```rust
+let a_very_long_identifier = 1;
+```
+
+---
+// Long lines are wrapped with a marker and a hanging indent.
+#set page(width: 120pt)
+#set raw(overflow: "wrap")
+
+```rust
+fn main() {
+    println!("This line is far too long");
+
+    let short = 1;
+}
+```
+
+---
+// The marker can be customized.
+#set page(width: 120pt)
+#set raw(overflow: "wrap", marker: text(gray)[→])
+
+```
+a very long line of plain text without any highlighting
+```
+
+---
+// Wrapped raw blocks break across pages.
+#set page(width: 120pt, height: 60pt)
+#set raw(overflow: "wrap")
+
+#raw(block: true, range(10).map(i => "line " + str(i)).join("\n"))
+
+---
+// The whole block is shrunk until the longest line fits.
+#set page(width: 120pt)
+#set raw(overflow: "shrink")
+
+```typ
+#let message = "This line is far too long"
+```
+
+---
+// Words that don't fit into a line on their own are broken anywhere.
+#set page(width: 120pt)
+#set raw(overflow: "wrap")
+
+```rust
+let a_very_long_identifier = 1;
+```
+
+---
+// A shrunk body that already fits is aligned in a block that fills the width.
+#set page(width: 120pt)
+#set block(width: 100%, fill: luma(230))
+#set raw(overflow: "shrink", align: center)
+
+```
+short
+```
+
+---
+// Error: 20-26 expected "visible", "wrap", or "shrink"
+#set raw(overflow: "clip")
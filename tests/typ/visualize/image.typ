@@ -60,3 +60,7 @@ A #box(image("/files/tiger.jpg", height: 1cm, width: 80%)) B
 ---
 // Error: 2-25 failed to parse svg: found closing tag 'g' instead of 'style' in line 4
 #image("/files/bad.svg")
+
+---
+// Error: 2-25 failed to decode image
+#image("/files/bad.png")
@@ -0,0 +1,25 @@
+// Test the built-in color palettes.
+
+---
+#let swatches(colors) = stack(
+  dir: ltr,
+  ..colors.map(c => square(size: 10pt, fill: c)),
+)
+
+#swatches(palette.category10)
+#swatches(palette.okabe-ito)
+#swatches(palette.viridis)
+#swatches(palette.blues)
+
+---
+// Palettes are arrays of colors.
+// Ref: false
+#test(palette.category10.len(), 10)
+#test(palette.category10.at(3), rgb("#d62728"))
+#test(palette.okabe-ito.first(), black)
+#test(palette.viridis.last(), rgb("#fde725"))
+#test(palette.blues.all(c => type(c) == "color"), true)
+
+---
+// Error: 10-17 module `palette` does not contain `rainbow`
+#palette.rainbow
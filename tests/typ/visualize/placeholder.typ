@@ -0,0 +1,30 @@
+// Test placeholder content.
+
+---
+#placeholder.image(width: 3cm, height: 1cm)
+#placeholder.image(width: 2cm, height: 1cm, seed: 1)
+#placeholder.image(width: 100%, height: 1cm, fill: red)
+
+---
+// Placeholder images are rectangles with the given size.
+// Ref: false
+#let img = placeholder.image(width: 3cm, height: 2cm)
+#test(img.func(), rect)
+#test(img.width, 3cm)
+#test(img.height, 2cm)
+#test(placeholder.image(fill: red).fill, red)
+#test(placeholder.image(seed: 1).fill, placeholder.image(seed: 1).fill)
+
+---
+// Generated values only depend on the seed.
+// Ref: false
+#test(type(placeholder.name()), "string")
+#test(placeholder.name(), placeholder.name(seed: 0))
+#test(placeholder.name(seed: 5), placeholder.name(seed: 5))
+#test(type(placeholder.date()), "datetime")
+#test(placeholder.date(seed: 2), placeholder.date(seed: 2))
+#test(range(20).all(s => placeholder.date(seed: s).year() >= 1970), true)
+
+---
+// Error: 26-29 expected color or none, found integer
+#placeholder.image(fill: 123)
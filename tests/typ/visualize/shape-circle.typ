@@ -55,4 +55,5 @@ Expanded by height.
 ---
 // Radius wins over width and height.
 // Error: 23-34 unexpected argument: width
+// Error: 36-49 unexpected argument: height
 #circle(radius: 10pt, width: 50pt, height: 100pt, fill: eastern)
@@ -36,4 +36,5 @@
 ---
 // Size wins over width and height.
 // Error: 09-20 unexpected argument: width
+// Error: 22-34 unexpected argument: height
 #square(width: 10cm, height: 20cm, size: 1cm, fill: rgb("eb5278"))